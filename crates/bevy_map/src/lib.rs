@@ -42,9 +42,9 @@
 //! - [`runtime`] - Map loading and rendering (requires `runtime` feature)
 
 // =============================================================================
-// Re-export derive macro at top level for ergonomics
+// Re-export derive macros at top level for ergonomics
 // =============================================================================
-pub use bevy_map_derive::MapEntity;
+pub use bevy_map_derive::{MapDataType, MapEntity, MapProperty};
 
 // =============================================================================
 // Core module - fundamental data structures
@@ -64,9 +64,10 @@ pub mod core {
 
 // Core type re-exports at crate root
 pub use bevy_map_core::{
-    CollisionData, CollisionShape, EditorProject, EntityInstance, Layer, LayerData, LayerType,
-    Level, MapProject, MapProjectBuilder, OneWayDirection, PhysicsBody, TileProperties, Tileset,
-    TilesetImage, Value, OCCUPIED_CELL,
+    CollisionData, CollisionShape, DataInstance, DataStore, EditorProject, EntityInstance, Layer,
+    LayerData, LayerType, Level, MapProject, MapProjectBuilder, OneWayDirection, PhysicsBody,
+    TileProperties, Tileset, TilesetImage, TilesetKind, Value, WorldConfig, WorldProject,
+    OCCUPIED_CELL,
 };
 
 // =============================================================================
@@ -102,14 +103,16 @@ pub use bevy_map_animation::{
 /// - [`DialogueTree`] - Complete dialogue definition
 /// - [`DialogueNode`] - Individual dialogue nodes
 /// - [`DialogueRunner`] - Runtime dialogue state
+/// - [`DialogueVariables`] - Variables read and written by dialogue expressions
 /// - [`DialoguePlugin`] - Bevy plugin for dialogue
 pub mod dialogue {
     pub use bevy_map_dialogue::*;
 }
 
 pub use bevy_map_dialogue::{
-    DialogueChoice, DialogueChoiceEvent, DialogueEndEvent, DialogueHandle, DialogueNode,
-    DialogueNodeType, DialoguePlugin, DialogueRunner, DialogueTree, StartDialogueEvent,
+    DialogueActionEvent, DialogueChoice, DialogueChoiceEvent, DialogueEndEvent, DialogueEventExt,
+    DialogueEventType, DialogueHandle, DialogueNode, DialogueNodeType, DialoguePlugin,
+    DialogueRunner, DialogueTree, DialogueValue, DialogueVariables, StartDialogueEvent,
 };
 
 // =============================================================================
@@ -163,11 +166,13 @@ pub mod runtime {
 
 #[cfg(feature = "runtime")]
 pub use bevy_map_runtime::{
-    attach_dialogues, complete_sprite_loads, spawn_map_project, spawn_sprite_components, Dialogue,
-    EntityProperties, EntityRegistry, MapCollider, MapCollisionPlugin, MapEntityExt,
-    MapEntityMarker, MapEntityType, MapHandle, MapLoadError, MapProjectLoader, MapRoot,
-    MapRuntimePlugin, MapSpawnedEvent, SpawnMapEvent, SpawnMapProjectEvent, SpriteSlot,
-    TilesetTextures,
+    attach_dialogues, complete_sprite_loads, spawn_map_project, spawn_sprite_components, DataRef,
+    Dialogue, EntityProperties, EntityRegistry, LevelTransitionPlugin, LevelTransitions,
+    LevelTraveler, MapCollider, MapCollisionPlugin, MapData, MapDataType, MapDeltaPlugin,
+    MapDeltas, MapEdit, MapEntityExt, MapEntityMarker, MapEntityType, MapFlag, MapFlags, MapHandle,
+    MapLoadError, MapProjectLoader, MapProperty, MapRoot, MapRuntimePlugin, MapSpawnedEvent,
+    SpawnMapEvent, SpawnMapProjectEvent, SpriteSlot, TileChangedEvent, TilesetTextures,
+    WorldCommandsExt, WorldLevel, WorldProjectLoader, WorldStreaming, WorldStreamingPlugin,
 };
 
 // =============================================================================
@@ -181,13 +186,13 @@ pub use bevy_map_runtime::{
 /// use bevy_map::prelude::*;
 /// ```
 pub mod prelude {
-    // Derive macro
-    pub use crate::MapEntity;
+    // Derive macros (MapProperty and MapDataType are also traits when `runtime` is enabled)
+    pub use crate::{MapDataType, MapEntity, MapProperty};
 
     // Core types
     pub use crate::{
         CollisionData, CollisionShape, EntityInstance, Layer, LayerData, Level, MapProject,
-        Tileset, Value,
+        Tileset, Value, WorldProject,
     };
 
     // Animation
@@ -195,8 +200,9 @@ pub mod prelude {
 
     // Dialogue
     pub use crate::{
-        DialogueChoice, DialogueChoiceEvent, DialogueEndEvent, DialogueHandle, DialogueNode,
-        DialoguePlugin, DialogueRunner, DialogueTree, StartDialogueEvent,
+        DialogueActionEvent, DialogueChoice, DialogueChoiceEvent, DialogueEndEvent,
+        DialogueEventExt, DialogueEventType, DialogueHandle, DialogueNode, DialoguePlugin,
+        DialogueRunner, DialogueTree, DialogueValue, DialogueVariables, StartDialogueEvent,
     };
 
     // Autotile
@@ -205,7 +211,8 @@ pub mod prelude {
     // Runtime (if enabled)
     #[cfg(feature = "runtime")]
     pub use crate::{
-        spawn_map_project, EntityRegistry, MapEntityExt, MapHandle, MapRoot, MapRuntimePlugin,
-        SpawnMapEvent, SpawnMapProjectEvent, TilesetTextures,
+        spawn_map_project, DataRef, EntityRegistry, MapData, MapEdit, MapEntityExt, MapFlags,
+        MapHandle, MapRoot, MapRuntimePlugin, SpawnMapEvent, SpawnMapProjectEvent,
+        TileChangedEvent, TilesetTextures, WorldCommandsExt, WorldLevel,
     };
 }
@@ -0,0 +1,193 @@
+//! Round-trips of `#[derive(MapEntity)]` components through entity properties
+
+use bevy::asset::uuid::Uuid;
+use bevy::prelude::*;
+use bevy_map::prelude::*;
+use bevy_map::{DataRef, EntityInstance, MapEntityType, MapFlags, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(MapProperty, Debug, Clone, Copy, Default, PartialEq)]
+enum Faction {
+    #[default]
+    Friendly,
+    #[map_prop(name = "hostile")]
+    Hostile,
+}
+
+#[derive(MapProperty, Debug, Clone, Copy, PartialEq)]
+#[map_prop(flags)]
+enum Ability {
+    Fly,
+    Swim,
+    #[map_prop(value = 8)]
+    Climb,
+}
+
+#[derive(MapProperty, Debug, Clone, Default, PartialEq)]
+struct Stats {
+    strength: i32,
+    #[map_prop(default = 1.0)]
+    speed: f32,
+}
+
+#[derive(MapDataType, Debug)]
+#[map_data(type_name = "Item")]
+struct Item {
+    #[allow(dead_code)]
+    name: String,
+}
+
+/// A custom type read with `FromStr` and written with `Display`
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Version(u32, u32);
+
+impl FromStr for Version {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        let (major, minor) = s.split_once('.').ok_or(())?;
+        Ok(Version(
+            major.parse().map_err(|_| ())?,
+            minor.parse().map_err(|_| ())?,
+        ))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.0, self.1)
+    }
+}
+
+#[derive(Component, MapEntity, Debug)]
+#[map_entity(type_name = "Creature")]
+struct Creature {
+    #[map_prop]
+    faction: Faction,
+    #[map_prop]
+    abilities: MapFlags<Ability>,
+    #[map_prop]
+    stats: Stats,
+    #[map_prop]
+    path: Vec<Vec2>,
+    #[map_prop(name = "nickname")]
+    name: Option<String>,
+    #[map_prop]
+    home: Vec2,
+    #[map_prop]
+    tint: Color,
+    #[map_prop]
+    loot: DataRef<Item>,
+    #[map_prop(parse)]
+    version: Version,
+}
+
+fn point(x: f64, y: f64) -> Value {
+    Value::Object(HashMap::from([
+        ("x".to_string(), Value::Float(x)),
+        ("y".to_string(), Value::Float(y)),
+    ]))
+}
+
+fn creature_properties(loot: Uuid) -> HashMap<String, Value> {
+    HashMap::from([
+        ("faction".to_string(), Value::String("hostile".to_string())),
+        (
+            "abilities".to_string(),
+            Value::Array(vec![
+                Value::String("Swim".to_string()),
+                Value::String("Climb".to_string()),
+            ]),
+        ),
+        (
+            "stats".to_string(),
+            Value::Object(HashMap::from([
+                ("strength".to_string(), Value::Int(7)),
+                ("speed".to_string(), Value::Float(2.5)),
+            ])),
+        ),
+        (
+            "path".to_string(),
+            Value::Array(vec![point(0.0, 0.0), point(16.0, -8.0)]),
+        ),
+        ("nickname".to_string(), Value::String("Bob".to_string())),
+        ("home".to_string(), point(32.0, 48.0)),
+        ("tint".to_string(), Value::String("#ff8000".to_string())),
+        ("loot".to_string(), Value::String(loot.to_string())),
+        ("version".to_string(), Value::String("1.2".to_string())),
+    ])
+}
+
+#[test]
+fn test_round_trip_every_field_kind() {
+    let loot = Uuid::new_v4();
+    let mut instance = EntityInstance::new("Creature".to_string(), [0.0, 0.0]);
+    instance.properties = creature_properties(loot);
+
+    let creature = Creature::from_instance(&instance);
+    assert_eq!(creature.faction, Faction::Hostile);
+    assert!(creature.abilities.contains(Ability::Swim));
+    assert!(creature.abilities.contains(Ability::Climb));
+    assert!(!creature.abilities.contains(Ability::Fly));
+    assert_eq!(
+        creature.stats,
+        Stats {
+            strength: 7,
+            speed: 2.5
+        }
+    );
+    assert_eq!(creature.path, vec![Vec2::ZERO, Vec2::new(16.0, -8.0)]);
+    assert_eq!(creature.name.as_deref(), Some("Bob"));
+    assert_eq!(creature.home, Vec2::new(32.0, 48.0));
+    assert_eq!(creature.tint, Color::srgb_u8(255, 128, 0));
+    assert_eq!(creature.loot, DataRef::new(loot));
+    assert_eq!(creature.version, Version(1, 2));
+
+    assert_eq!(creature.to_properties(), creature_properties(loot));
+}
+
+#[test]
+fn test_round_trip_defaults() {
+    let mut instance = EntityInstance::new("Creature".to_string(), [0.0, 0.0]);
+    instance
+        .properties
+        .insert("stats".to_string(), Value::Object(HashMap::new()));
+    let creature = Creature::from_instance(&instance);
+    assert_eq!(creature.faction, Faction::Friendly);
+    assert_eq!(creature.abilities, MapFlags::default());
+    assert_eq!(creature.stats.speed, 1.0);
+    assert_eq!(creature.name, None);
+    assert!(creature.loot.is_none());
+
+    // Colors may come back in another color space, so compare what gets written
+    let mut reloaded = instance.clone();
+    reloaded.properties = creature.to_properties();
+    assert_eq!(
+        Creature::from_instance(&reloaded).to_properties(),
+        reloaded.properties
+    );
+}
+
+/// Only spawned by game code, never placed in the editor
+#[derive(Component, MapEntity)]
+#[map_entity(type_name = "Projectile", placeable = false)]
+struct Projectile {
+    #[map_prop(default = 1.0)]
+    speed: f32,
+}
+
+#[test]
+fn test_type_def_placeable() {
+    let mut schema = bevy_map::schema::Schema::default();
+    assert!(Creature::type_def(&mut schema).placeable);
+
+    let projectile = Projectile::type_def(&mut schema);
+    assert!(!projectile.placeable);
+    assert_eq!(projectile.properties.len(), 1);
+    assert_eq!(
+        Projectile::from_instance(&EntityInstance::new("Projectile".to_string(), [0.0, 0.0])).speed,
+        1.0
+    );
+}
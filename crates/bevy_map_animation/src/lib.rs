@@ -516,11 +516,11 @@ impl SpriteData {
 
     /// Update grid dimensions from image size
     pub fn update_from_image_size(&mut self, image_width: u32, image_height: u32) {
-        if self.frame_width > 0 {
-            self.columns = image_width / self.frame_width;
+        if let Some(columns) = image_width.checked_div(self.frame_width) {
+            self.columns = columns;
         }
-        if self.frame_height > 0 {
-            self.rows = image_height / self.frame_height;
+        if let Some(rows) = image_height.checked_div(self.frame_height) {
+            self.rows = rows;
         }
     }
 }
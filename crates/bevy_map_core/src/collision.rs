@@ -2,10 +2,11 @@
 //!
 //! This module provides the core collision types used by the editor and runtime:
 //! - `CollisionShape` - Shape types (None, Full, Rectangle, Circle, Polygon)
-//! - `CollisionData` - Full collision configuration including layers and one-way
+//! - `CollisionData` - Full collision configuration including layers, one-way and material
 //! - `PhysicsBody` - Body type (Static, Dynamic, Kinematic)
 //! - `OneWayDirection` - One-way platform direction
 
+use crate::polygon::{validate_polygon, PolygonIssue};
 use serde::{Deserialize, Serialize};
 
 /// Collision shape types supported by the editor
@@ -34,7 +35,10 @@ pub enum CollisionShape {
         #[serde(default = "default_radius")]
         radius: f32,
     },
-    /// Polygon collider (convex hull)
+    /// Polygon collider
+    ///
+    /// Must be a simple polygon (no self-intersections). Concave outlines are
+    /// split into convex parts at runtime (see [`crate::decompose_polygon`]).
     Polygon {
         /// Vertices in local coordinates (0-1 normalized)
         points: Vec<[f32; 2]>,
@@ -76,6 +80,17 @@ impl CollisionShape {
         CollisionShape::Polygon { points }
     }
 
+    /// Check that the shape can be turned into a collider
+    ///
+    /// Only polygons can be invalid: they need at least three points, an area,
+    /// and no self-intersecting edges.
+    pub fn validate(&self) -> Result<(), PolygonIssue> {
+        match self {
+            CollisionShape::Polygon { points } => validate_polygon(points),
+            _ => Ok(()),
+        }
+    }
+
     /// Get the display name of this shape type
     pub fn name(&self) -> &'static str {
         match self {
@@ -130,6 +145,20 @@ impl OneWayDirection {
         !matches!(self, OneWayDirection::None)
     }
 
+    /// Outward normal `(x, y)` of the solid side (Y-up), or `None` if not one-way.
+    ///
+    /// Bodies approaching from this side collide; bodies approaching from the
+    /// opposite side pass through.
+    pub fn solid_normal(&self) -> Option<(f32, f32)> {
+        match self {
+            OneWayDirection::None => None,
+            OneWayDirection::Top => Some((0.0, 1.0)),
+            OneWayDirection::Bottom => Some((0.0, -1.0)),
+            OneWayDirection::Left => Some((-1.0, 0.0)),
+            OneWayDirection::Right => Some((1.0, 0.0)),
+        }
+    }
+
     /// Get the display name of this direction
     pub fn name(&self) -> &'static str {
         match self {
@@ -143,7 +172,7 @@ impl OneWayDirection {
 }
 
 /// Collision data for a tile or entity
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CollisionData {
     /// The collision shape
     #[serde(default)]
@@ -160,12 +189,34 @@ pub struct CollisionData {
     /// Collision mask (which layers to collide with)
     #[serde(default = "default_mask")]
     pub mask: u32,
+    /// Friction coefficient (0 = ice, 1 = very grippy)
+    #[serde(default = "default_friction")]
+    pub friction: f32,
+    /// Restitution (bounciness, 0 = no bounce, 1 = perfectly elastic)
+    #[serde(default)]
+    pub restitution: f32,
+    /// Sensors detect overlaps without blocking movement (e.g. spikes, triggers)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_sensor: bool,
 }
 
 fn default_mask() -> u32 {
     0xFFFFFFFF
 }
 
+/// Default friction, matching the physics engine's default
+pub const DEFAULT_FRICTION: f32 = 0.5;
+
+fn default_friction() -> f32 {
+    DEFAULT_FRICTION
+}
+
+impl Default for CollisionData {
+    fn default() -> Self {
+        Self::new(CollisionShape::None)
+    }
+}
+
 impl CollisionData {
     /// Create new collision data with a shape
     pub fn new(shape: CollisionShape) -> Self {
@@ -175,6 +226,9 @@ impl CollisionData {
             one_way: OneWayDirection::None,
             layer: 0,
             mask: default_mask(),
+            friction: DEFAULT_FRICTION,
+            restitution: 0.0,
+            is_sensor: false,
         }
     }
 
@@ -228,6 +282,24 @@ impl CollisionData {
         self
     }
 
+    /// Set the friction coefficient
+    pub fn with_friction(mut self, friction: f32) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Set the restitution (bounciness)
+    pub fn with_restitution(mut self, restitution: f32) -> Self {
+        self.restitution = restitution;
+        self
+    }
+
+    /// Make this a sensor that detects overlaps without blocking movement
+    pub fn with_sensor(mut self, is_sensor: bool) -> Self {
+        self.is_sensor = is_sensor;
+        self
+    }
+
     /// Check if this collision data is effectively empty (no collision)
     pub fn is_empty(&self) -> bool {
         !self.has_collision()
@@ -238,6 +310,15 @@ impl CollisionData {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_one_way_solid_normal() {
+        assert_eq!(OneWayDirection::None.solid_normal(), None);
+        assert_eq!(OneWayDirection::Top.solid_normal(), Some((0.0, 1.0)));
+        assert_eq!(OneWayDirection::Bottom.solid_normal(), Some((0.0, -1.0)));
+        assert_eq!(OneWayDirection::Left.solid_normal(), Some((-1.0, 0.0)));
+        assert_eq!(OneWayDirection::Right.solid_normal(), Some((1.0, 0.0)));
+    }
+
     #[test]
     fn test_collision_shape_default() {
         let shape = CollisionShape::default();
@@ -254,6 +335,29 @@ mod tests {
         assert_eq!(data.mask, 0xFFFFFFFF);
     }
 
+    #[test]
+    fn test_collision_data_material_defaults() {
+        let data = CollisionData::default();
+        assert_eq!(data.friction, DEFAULT_FRICTION);
+        assert_eq!(data.restitution, 0.0);
+        assert!(!data.is_sensor);
+
+        // Older files without material fields keep the previous behavior
+        let parsed: CollisionData = serde_json::from_str(r#"{"shape":{"type":"Full"}}"#).unwrap();
+        assert_eq!(parsed.friction, DEFAULT_FRICTION);
+        assert_eq!(parsed.mask, 0xFFFFFFFF);
+
+        let spikes = CollisionData::full()
+            .with_friction(0.0)
+            .with_restitution(0.8)
+            .with_sensor(true);
+        let json = serde_json::to_string(&spikes).unwrap();
+        let parsed: CollisionData = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.friction, 0.0);
+        assert_eq!(parsed.restitution, 0.8);
+        assert!(parsed.is_sensor);
+    }
+
     #[test]
     fn test_one_way_direction() {
         assert!(!OneWayDirection::None.is_one_way());
@@ -263,6 +367,18 @@ mod tests {
         assert!(OneWayDirection::Right.is_one_way());
     }
 
+    #[test]
+    fn test_collision_shape_validate() {
+        assert!(CollisionShape::Full.validate().is_ok());
+        let slope = CollisionShape::polygon(vec![[0.0, 1.0], [1.0, 0.0], [1.0, 1.0]]);
+        assert!(slope.validate().is_ok());
+        let bowtie = CollisionShape::polygon(vec![[0.0, 0.0], [1.0, 1.0], [1.0, 0.0], [0.0, 1.0]]);
+        assert!(matches!(
+            bowtie.validate(),
+            Err(PolygonIssue::SelfIntersecting { .. })
+        ));
+    }
+
     #[test]
     fn test_collision_shape_serialization() {
         let shape = CollisionShape::Rectangle {
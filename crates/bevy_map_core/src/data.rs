@@ -0,0 +1,154 @@
+//! Data instances - non-placeable objects like items, enemies and loot tables
+//!
+//! Data types are schema types that aren't placed in levels. Their instances are
+//! authored in the editor's data view and shipped with the project, so entities
+//! can reference them with `Ref` properties.
+
+use crate::Value;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A data instance (non-placeable thing like an Item, Quest, etc.)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataInstance {
+    /// Unique identifier, stored by `Ref` properties that point at this instance
+    pub id: Uuid,
+    /// Type name (e.g., "Item", "Quest")
+    pub type_name: String,
+    /// Property values
+    #[serde(default)]
+    pub properties: HashMap<String, Value>,
+}
+
+impl DataInstance {
+    /// Create a new data instance with a random ID
+    pub fn new(type_name: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            type_name,
+            properties: HashMap::new(),
+        }
+    }
+
+    /// Get a property value
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.properties.get(key)
+    }
+
+    /// Get a string property
+    pub fn get_string(&self, key: &str) -> Option<&str> {
+        self.properties.get(key).and_then(|v| v.as_string())
+    }
+
+    /// The instance's `name` property, used to display it in the editor
+    pub fn name(&self) -> Option<&str> {
+        self.get_string("name")
+    }
+}
+
+/// Stores all data_type instances (non-placeable things like Items, Quests)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DataStore {
+    /// Key: type name (e.g., "Item", "Quest")
+    /// Value: list of instances of that type
+    pub instances: HashMap<String, Vec<DataInstance>>,
+}
+
+impl DataStore {
+    pub fn add(&mut self, instance: DataInstance) {
+        self.instances
+            .entry(instance.type_name.clone())
+            .or_default()
+            .push(instance);
+    }
+
+    pub fn remove(&mut self, id: Uuid) -> Option<DataInstance> {
+        for instances in self.instances.values_mut() {
+            if let Some(pos) = instances.iter().position(|i| i.id == id) {
+                return Some(instances.remove(pos));
+            }
+        }
+        None
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&DataInstance> {
+        for instances in self.instances.values() {
+            if let Some(instance) = instances.iter().find(|i| i.id == id) {
+                return Some(instance);
+            }
+        }
+        None
+    }
+
+    pub fn get_mut(&mut self, id: Uuid) -> Option<&mut DataInstance> {
+        for instances in self.instances.values_mut() {
+            if let Some(instance) = instances.iter_mut().find(|i| i.id == id) {
+                return Some(instance);
+            }
+        }
+        None
+    }
+
+    pub fn get_by_type(&self, type_name: &str) -> &[DataInstance] {
+        self.instances
+            .get(type_name)
+            .map(|v| v.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Find an instance of a type by its `name` property
+    pub fn find_by_name(&self, type_name: &str, name: &str) -> Option<&DataInstance> {
+        self.get_by_type(type_name)
+            .iter()
+            .find(|i| i.name() == Some(name))
+    }
+
+    pub fn all_instances(&self) -> impl Iterator<Item = &DataInstance> {
+        self.instances.values().flatten()
+    }
+
+    /// Total number of instances across all types
+    pub fn len(&self) -> usize {
+        self.instances.values().map(Vec::len).sum()
+    }
+
+    /// Check if the store has no instances
+    pub fn is_empty(&self) -> bool {
+        self.instances.values().all(Vec::is_empty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_store_lookups() {
+        let mut store = DataStore::default();
+        assert!(store.is_empty());
+
+        let mut sword = DataInstance::new("Item".to_string());
+        sword
+            .properties
+            .insert("name".to_string(), Value::String("Sword".to_string()));
+        let sword_id = sword.id;
+        store.add(sword);
+        store.add(DataInstance::new("Enemy".to_string()));
+
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get(sword_id).unwrap().name(), Some("Sword"));
+        assert_eq!(store.get_by_type("Item").len(), 1);
+        assert!(store.get_by_type("Quest").is_empty());
+        assert_eq!(store.find_by_name("Item", "Sword").unwrap().id, sword_id);
+        assert!(store.find_by_name("Enemy", "Sword").is_none());
+
+        // Round trips through the project file format
+        let json = serde_json::to_string(&store).unwrap();
+        let loaded: DataStore = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.get(sword_id).unwrap().type_name, "Item");
+
+        assert!(store.remove(sword_id).is_some());
+        assert!(store.get(sword_id).is_none());
+    }
+}
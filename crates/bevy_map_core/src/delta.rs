@@ -0,0 +1,371 @@
+//! Save-game deltas: runtime changes to a level relative to its original data
+//!
+//! A [`MapDelta`] records what happened to one level during play (changed
+//! tiles, spawned, removed and modified entity instances) without copying the
+//! level. Applying it to the original [`MapProject`] recreates the modified
+//! level. Every changed cell remembers its original tile, so a delta applied
+//! to a base map that was edited since reports [`DeltaConflict`]s instead of
+//! silently overwriting the new data.
+
+use crate::{EntityInstance, MapProject, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use uuid::Uuid;
+
+/// A changed cell of a tile layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TileDelta {
+    pub x: u32,
+    pub y: u32,
+    /// Tile in the original level
+    pub original: Option<u32>,
+    /// Tile after the change
+    pub tile: Option<u32>,
+}
+
+/// Changes to an entity instance of the original level
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntityDelta {
+    /// New position, if the entity moved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<[f32; 2]>,
+    /// Properties that were added or changed
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub properties: HashMap<String, Value>,
+    /// Properties that were removed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_properties: Vec<String>,
+}
+
+/// Runtime changes to one level
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MapDelta {
+    /// Changed cells, keyed by tile layer name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tiles: HashMap<String, Vec<TileDelta>>,
+    /// Entity instances added during play
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spawned: Vec<EntityInstance>,
+    /// IDs of original entity instances that were removed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub despawned: Vec<Uuid>,
+    /// Changes to original entity instances, keyed by instance ID
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub modified: HashMap<Uuid, EntityDelta>,
+}
+
+/// A part of a delta that doesn't match the level it is applied to
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeltaConflict {
+    /// The level has no tile layer with this name; its changes are skipped
+    MissingLayer(String),
+    /// A changed cell is outside the level and is skipped
+    OutOfBounds { layer: String, x: u32, y: u32 },
+    /// The level's tile differs from the original the change was made to.
+    /// The delta's tile is applied anyway.
+    TileChanged {
+        layer: String,
+        x: u32,
+        y: u32,
+        expected: Option<u32>,
+        found: Option<u32>,
+    },
+    /// A removed or modified entity instance is not in the level
+    MissingEntity(Uuid),
+    /// A spawned entity instance has the ID of an instance already in the level
+    /// and is skipped
+    DuplicateEntity(Uuid),
+}
+
+impl fmt::Display for DeltaConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeltaConflict::MissingLayer(layer) => write!(f, "tile layer '{}' not found", layer),
+            DeltaConflict::OutOfBounds { layer, x, y } => {
+                write!(
+                    f,
+                    "cell ({}, {}) of layer '{}' is outside the level",
+                    x, y, layer
+                )
+            }
+            DeltaConflict::TileChanged {
+                layer,
+                x,
+                y,
+                expected,
+                found,
+            } => write!(
+                f,
+                "cell ({}, {}) of layer '{}' was {:?} but the change was made to {:?}",
+                x, y, layer, found, expected
+            ),
+            DeltaConflict::MissingEntity(id) => write!(f, "entity {} not found", id),
+            DeltaConflict::DuplicateEntity(id) => write!(f, "entity {} already exists", id),
+        }
+    }
+}
+
+impl MapDelta {
+    /// Check if the delta contains no changes
+    pub fn is_empty(&self) -> bool {
+        self.tiles.values().all(Vec::is_empty)
+            && self.spawned.is_empty()
+            && self.despawned.is_empty()
+            && self.modified.is_empty()
+    }
+
+    /// Record a tile change; `old` is the tile before this change
+    ///
+    /// Cells changed back to their original tile are dropped from the delta.
+    pub fn record_tile(&mut self, layer: &str, x: u32, y: u32, old: Option<u32>, new: Option<u32>) {
+        let cells = self.tiles.entry(layer.to_string()).or_default();
+        match cells.iter().position(|cell| cell.x == x && cell.y == y) {
+            Some(pos) if cells[pos].original == new => {
+                cells.remove(pos);
+            }
+            Some(pos) => cells[pos].tile = new,
+            None if old != new => cells.push(TileDelta {
+                x,
+                y,
+                original: old,
+                tile: new,
+            }),
+            None => {}
+        }
+        if cells.is_empty() {
+            self.tiles.remove(layer);
+        }
+    }
+
+    /// Record an entity instance added during play
+    pub fn record_spawn(&mut self, instance: EntityInstance) {
+        self.spawned.retain(|spawned| spawned.id != instance.id);
+        self.spawned.push(instance);
+    }
+
+    /// Record the removal of an entity instance
+    pub fn record_despawn(&mut self, id: Uuid) {
+        let spawned = self.spawned.len();
+        self.spawned.retain(|instance| instance.id != id);
+        if self.spawned.len() == spawned && !self.despawned.contains(&id) {
+            self.modified.remove(&id);
+            self.despawned.push(id);
+        }
+    }
+
+    /// Record an entity instance moving to a new position
+    pub fn record_move(&mut self, id: Uuid, position: [f32; 2]) {
+        if let Some(instance) = self.spawned_mut(id) {
+            instance.position = position;
+        } else {
+            self.modified.entry(id).or_default().position = Some(position);
+        }
+    }
+
+    /// Record a property of an entity instance being set
+    pub fn record_property(&mut self, id: Uuid, key: &str, value: Value) {
+        if let Some(instance) = self.spawned_mut(id) {
+            instance.properties.insert(key.to_string(), value);
+        } else {
+            let delta = self.modified.entry(id).or_default();
+            delta.removed_properties.retain(|removed| removed != key);
+            delta.properties.insert(key.to_string(), value);
+        }
+    }
+
+    /// Record a property of an entity instance being removed
+    pub fn record_property_removed(&mut self, id: Uuid, key: &str) {
+        if let Some(instance) = self.spawned_mut(id) {
+            instance.properties.remove(key);
+        } else {
+            let delta = self.modified.entry(id).or_default();
+            delta.properties.remove(key);
+            if !delta
+                .removed_properties
+                .iter()
+                .any(|removed| removed == key)
+            {
+                delta.removed_properties.push(key.to_string());
+            }
+        }
+    }
+
+    fn spawned_mut(&mut self, id: Uuid) -> Option<&mut EntityInstance> {
+        self.spawned.iter_mut().find(|instance| instance.id == id)
+    }
+
+    /// Apply the delta to an original level
+    ///
+    /// Conflicting tile changes are still applied, so the player's changes
+    /// win over edits made to the base map. Returns every conflict found.
+    pub fn apply(&self, project: &mut MapProject) -> Vec<DeltaConflict> {
+        let mut conflicts = Vec::new();
+        let level = &mut project.level;
+
+        for (layer_name, cells) in &self.tiles {
+            let layer_index = level
+                .layer_index_by_name(layer_name)
+                .filter(|index| level.layers[*index].tileset_id().is_some());
+            let Some(layer_index) = layer_index else {
+                conflicts.push(DeltaConflict::MissingLayer(layer_name.clone()));
+                continue;
+            };
+
+            for cell in cells {
+                if cell.x >= level.width || cell.y >= level.height {
+                    conflicts.push(DeltaConflict::OutOfBounds {
+                        layer: layer_name.clone(),
+                        x: cell.x,
+                        y: cell.y,
+                    });
+                    continue;
+                }
+                let found = level.get_tile(layer_index, cell.x, cell.y);
+                if found != cell.original {
+                    conflicts.push(DeltaConflict::TileChanged {
+                        layer: layer_name.clone(),
+                        x: cell.x,
+                        y: cell.y,
+                        expected: cell.original,
+                        found,
+                    });
+                }
+                level.set_tile(layer_index, cell.x, cell.y, cell.tile);
+            }
+
+            // Multi-cell tiles may have been placed, moved or removed
+            let tileset = level.layers[layer_index]
+                .tileset_id()
+                .and_then(|id| project.tilesets.get(&id));
+            level.rebuild_occupied_cells(layer_index, |tile| {
+                tileset
+                    .map(|tileset| tileset.get_tile_grid_size(crate::tile_index(tile)))
+                    .unwrap_or((1, 1))
+            });
+        }
+
+        for id in &self.despawned {
+            if level.remove_entity(*id).is_none() {
+                conflicts.push(DeltaConflict::MissingEntity(*id));
+            }
+        }
+
+        for (id, delta) in &self.modified {
+            let Some(instance) = level.get_entity_mut(*id) else {
+                conflicts.push(DeltaConflict::MissingEntity(*id));
+                continue;
+            };
+            if let Some(position) = delta.position {
+                instance.position = position;
+            }
+            for key in &delta.removed_properties {
+                instance.properties.remove(key);
+            }
+            instance.properties.extend(
+                delta
+                    .properties
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone())),
+            );
+        }
+
+        for instance in &self.spawned {
+            if level.get_entity(instance.id).is_some() {
+                conflicts.push(DeltaConflict::DuplicateEntity(instance.id));
+                continue;
+            }
+            level.add_entity(instance.clone());
+        }
+
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Layer, LayerData, Level, TileProperties, Tileset, OCCUPIED_CELL};
+
+    fn test_project() -> MapProject {
+        let mut tileset = Tileset::new("Tiles".to_string(), "tiles.png".to_string(), 16, 4, 1);
+        tileset.set_tile_properties(1, TileProperties::default().with_grid_size(2, 1));
+        let mut level = Level::new("Test".to_string(), 4, 2);
+        level.add_layer(Layer::new_tile_layer(
+            "Ground".to_string(),
+            tileset.id,
+            4,
+            2,
+        ));
+        level.set_tile(0, 0, 0, Some(0));
+        level.add_entity(EntityInstance::new("Chest".to_string(), [8.0, 8.0]));
+        level.add_entity(EntityInstance::new("Door".to_string(), [24.0, 8.0]));
+        MapProject::new(level, vec![tileset])
+    }
+
+    #[test]
+    fn test_record_and_apply() {
+        let mut project = test_project();
+        let chest = project.level.entities[0].id;
+        let door = project.level.entities[1].id;
+
+        let mut delta = MapDelta::default();
+        delta.record_tile("Ground", 0, 0, Some(0), None);
+        delta.record_tile("Ground", 2, 1, None, Some(1));
+        delta.record_tile("Ground", 3, 1, None, Some(OCCUPIED_CELL));
+        // Changing a cell back drops it from the delta
+        delta.record_tile("Ground", 1, 1, None, Some(2));
+        delta.record_tile("Ground", 1, 1, Some(2), None);
+        assert_eq!(delta.tiles["Ground"].len(), 3);
+
+        delta.record_property(chest, "opened", Value::Bool(true));
+        delta.record_despawn(door);
+        let coin = EntityInstance::new("Coin".to_string(), [40.0, 8.0]);
+        delta.record_spawn(coin.clone());
+        delta.record_move(coin.id, [48.0, 8.0]);
+
+        let json = serde_json::to_string(&delta).unwrap();
+        let delta: MapDelta = serde_json::from_str(&json).unwrap();
+
+        assert!(delta.apply(&mut project).is_empty());
+        let level = &project.level;
+        assert_eq!(level.get_tile(0, 0, 0), None);
+        assert_eq!(level.get_tile(0, 2, 1), Some(1));
+        if let LayerData::Tiles { occupied_cells, .. } = &level.layers[0].data {
+            assert_eq!(occupied_cells.get(&7), Some(&6));
+        }
+        assert_eq!(
+            level.get_entity(chest).unwrap().properties.get("opened"),
+            Some(&Value::Bool(true))
+        );
+        assert!(level.get_entity(door).is_none());
+        assert_eq!(level.get_entity(coin.id).unwrap().position, [48.0, 8.0]);
+    }
+
+    #[test]
+    fn test_conflicts_after_base_changes() {
+        let mut project = test_project();
+        let door = project.level.entities[1].id;
+        let mut delta = MapDelta::default();
+        delta.record_tile("Ground", 0, 0, Some(0), Some(3));
+        delta.record_tile("Walls", 0, 0, None, Some(3));
+        delta.record_despawn(door);
+
+        // The base map was edited after the delta was recorded
+        project.level.set_tile(0, 0, 0, Some(2));
+        project.level.remove_entity(door);
+
+        let conflicts = delta.apply(&mut project);
+        assert_eq!(conflicts.len(), 3);
+        assert!(conflicts.contains(&DeltaConflict::MissingLayer("Walls".to_string())));
+        assert!(conflicts.contains(&DeltaConflict::TileChanged {
+            layer: "Ground".to_string(),
+            x: 0,
+            y: 0,
+            expected: Some(0),
+            found: Some(2),
+        }));
+        assert!(conflicts.contains(&DeltaConflict::MissingEntity(door)));
+        assert_eq!(project.level.get_tile(0, 0, 0), Some(3));
+    }
+}
@@ -107,6 +107,11 @@ pub enum ColliderConfig {
         /// Radius in pixels
         radius: f32,
     },
+    /// Polygon collider, concave outlines are split into convex parts
+    Polygon {
+        /// Vertices in pixels relative to the entity origin (Y-up)
+        points: Vec<[f32; 2]>,
+    },
 }
 
 impl Default for ColliderConfig {
@@ -125,6 +130,7 @@ impl ColliderConfig {
             ColliderConfig::Box { .. } => "Box",
             ColliderConfig::Capsule { .. } => "Capsule",
             ColliderConfig::Circle { .. } => "Circle",
+            ColliderConfig::Polygon { .. } => "Polygon",
         }
     }
 
@@ -134,6 +140,7 @@ impl ColliderConfig {
             ColliderConfig::Box { .. } => "box",
             ColliderConfig::Capsule { .. } => "capsule",
             ColliderConfig::Circle { .. } => "circle",
+            ColliderConfig::Polygon { .. } => "polygon",
         }
     }
 
@@ -151,6 +158,11 @@ impl ColliderConfig {
     pub fn new_circle(radius: f32) -> Self {
         ColliderConfig::Circle { radius }
     }
+
+    /// Create a new polygon collider from vertices in pixels (Y-up)
+    pub fn new_polygon(points: Vec<[f32; 2]>) -> Self {
+        ColliderConfig::Polygon { points }
+    }
 }
 
 /// Physics component configuration for an entity type
@@ -465,5 +477,12 @@ mod tests {
 
         let circle = ColliderConfig::new_circle(8.0);
         assert_eq!(circle.display_name(), "Circle");
+
+        let polygon = ColliderConfig::new_polygon(vec![[-8.0, -8.0], [8.0, -8.0], [0.0, 8.0]]);
+        assert_eq!(polygon.display_name(), "Polygon");
+        let json = serde_json::to_string(&polygon).unwrap();
+        assert!(json.contains("\"type\":\"polygon\""));
+        let parsed: ColliderConfig = serde_json::from_str(&json).unwrap();
+        assert!(matches!(parsed, ColliderConfig::Polygon { points } if points.len() == 3));
     }
 }
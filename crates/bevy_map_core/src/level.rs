@@ -1,9 +1,61 @@
 //! Level/map containing tiles and entities
 
-use crate::{EntityInstance, Layer, LayerData};
+use crate::{EntityInstance, Layer, LayerData, OCCUPIED_CELL};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// A change to one cell of a tile layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileChange {
+    pub x: u32,
+    pub y: u32,
+    /// Tile before the change
+    pub old: Option<u32>,
+    /// Tile after the change
+    pub new: Option<u32>,
+}
+
+/// Tile changes combined per cell, in the order cells were first changed
+///
+/// A combined change keeps the first old tile and the last new tile; cells
+/// that end up unchanged are dropped by [`TileChangeSet::into_changes`].
+#[derive(Debug, Clone, Default)]
+pub struct TileChangeSet {
+    changes: Vec<TileChange>,
+    /// Position of each cell's change in `changes`
+    index: HashMap<(u32, u32), usize>,
+}
+
+impl TileChangeSet {
+    /// Create an empty change set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the combined changes, without cells that ended up unchanged
+    pub fn into_changes(self) -> Vec<TileChange> {
+        self.changes
+            .into_iter()
+            .filter(|change| change.old != change.new)
+            .collect()
+    }
+}
+
+impl Extend<TileChange> for TileChangeSet {
+    fn extend<I: IntoIterator<Item = TileChange>>(&mut self, changes: I) {
+        for change in changes {
+            match self.index.get(&(change.x, change.y)) {
+                Some(&pos) => self.changes[pos].new = change.new,
+                None => {
+                    self.index.insert((change.x, change.y), self.changes.len());
+                    self.changes.push(change);
+                }
+            }
+        }
+    }
+}
+
 /// A level/map containing tiles and entities
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Level {
@@ -123,6 +175,167 @@ impl Level {
         }
     }
 
+    /// Place a tile whose base cell is at (x, y), covering `footprint` cells
+    ///
+    /// Multi-cell tiles store the tile in the base cell and `OCCUPIED_CELL`
+    /// in the other cells, which extend to +x and +y. Tiles overlapping the
+    /// footprint are erased entirely. Nothing is placed if the footprint
+    /// doesn't fit in the level.
+    pub fn place_tile(
+        &mut self,
+        layer_index: usize,
+        x: u32,
+        y: u32,
+        tile: u32,
+        footprint: (u32, u32),
+    ) -> Vec<TileChange> {
+        let (grid_width, grid_height) = (footprint.0.max(1), footprint.1.max(1));
+        if x + grid_width > self.width || y + grid_height > self.height {
+            return Vec::new();
+        }
+        if !matches!(
+            self.layers.get(layer_index).map(|l| &l.data),
+            Some(LayerData::Tiles { .. })
+        ) {
+            return Vec::new();
+        }
+
+        let mut changes = TileChangeSet::new();
+        for dy in 0..grid_height {
+            for dx in 0..grid_width {
+                changes.extend(self.erase_tile(layer_index, x + dx, y + dy));
+            }
+        }
+
+        let width = self.width;
+        let base_idx = (y * width + x) as usize;
+        let mut placed = Vec::new();
+        if let LayerData::Tiles {
+            tiles,
+            occupied_cells,
+            ..
+        } = &mut self.layers[layer_index].data
+        {
+            for dy in 0..grid_height {
+                for dx in 0..grid_width {
+                    let (cx, cy) = (x + dx, y + dy);
+                    let idx = (cy * width + cx) as usize;
+                    let Some(cell) = tiles.get_mut(idx) else {
+                        continue;
+                    };
+                    let new = if idx == base_idx {
+                        tile
+                    } else {
+                        occupied_cells.insert(idx, base_idx);
+                        OCCUPIED_CELL
+                    };
+                    placed.push(TileChange {
+                        x: cx,
+                        y: cy,
+                        old: *cell,
+                        new: Some(new),
+                    });
+                    *cell = Some(new);
+                }
+            }
+        }
+        changes.extend(placed);
+        changes.into_changes()
+    }
+
+    /// Erase the tile covering (x, y), including every cell of a multi-cell tile
+    pub fn erase_tile(&mut self, layer_index: usize, x: u32, y: u32) -> Vec<TileChange> {
+        let mut changes = Vec::new();
+        if x >= self.width || y >= self.height {
+            return changes;
+        }
+        let width = self.width;
+        let Some(LayerData::Tiles {
+            tiles,
+            occupied_cells,
+            ..
+        }) = self.layers.get_mut(layer_index).map(|l| &mut l.data)
+        else {
+            return changes;
+        };
+
+        let idx = (y * width + x) as usize;
+        let base_idx = occupied_cells.get(&idx).copied().unwrap_or(idx);
+        let mut cells: Vec<usize> = occupied_cells
+            .iter()
+            .filter(|(_, base)| **base == base_idx)
+            .map(|(cell, _)| *cell)
+            .collect();
+        cells.push(base_idx);
+        cells.sort_unstable();
+
+        for cell in cells {
+            occupied_cells.remove(&cell);
+            let Some(tile) = tiles.get_mut(cell) else {
+                continue;
+            };
+            if let Some(old) = tile.take() {
+                changes.push(TileChange {
+                    x: cell as u32 % width,
+                    y: cell as u32 / width,
+                    old: Some(old),
+                    new: None,
+                });
+            }
+        }
+        changes
+    }
+
+    /// Recompute which cells of a layer are covered by multi-cell tiles
+    ///
+    /// `grid_size` returns the footprint of a tile, e.g. from
+    /// `Tileset::get_tile_grid_size`. Only cells holding `OCCUPIED_CELL` are
+    /// linked to a base cell.
+    pub fn rebuild_occupied_cells(
+        &mut self,
+        layer_index: usize,
+        grid_size: impl Fn(u32) -> (u32, u32),
+    ) {
+        let (width, height) = (self.width, self.height);
+        let Some(LayerData::Tiles {
+            tiles,
+            occupied_cells,
+            ..
+        }) = self.layers.get_mut(layer_index).map(|l| &mut l.data)
+        else {
+            return;
+        };
+
+        occupied_cells.clear();
+        for (base_idx, tile) in tiles.iter().enumerate() {
+            let Some(tile) = *tile else {
+                continue;
+            };
+            if tile == OCCUPIED_CELL {
+                continue;
+            }
+            let (grid_width, grid_height) = grid_size(tile);
+            let (x, y) = (base_idx as u32 % width, base_idx as u32 / width);
+            for dy in 0..grid_height {
+                for dx in 0..grid_width {
+                    let (cx, cy) = (x + dx, y + dy);
+                    if (dx, dy) == (0, 0) || cx >= width || cy >= height {
+                        continue;
+                    }
+                    let idx = (cy * width + cx) as usize;
+                    if tiles.get(idx) == Some(&Some(OCCUPIED_CELL)) {
+                        occupied_cells.insert(idx, base_idx);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get the index of the first layer with the given name
+    pub fn layer_index_by_name(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|layer| layer.name == name)
+    }
+
     /// Remove a layer by index
     pub fn remove_layer(&mut self, index: usize) -> Option<Layer> {
         if index < self.layers.len() {
@@ -234,6 +447,59 @@ mod tests {
         assert_eq!(level.get_tile(0, 5, 5), None);
     }
 
+    #[test]
+    fn test_multi_cell_placement() {
+        let mut level = Level::new("Test".to_string(), 4, 4);
+        level.add_layer(Layer::new_tile_layer(
+            "Ground".to_string(),
+            Uuid::new_v4(),
+            4,
+            4,
+        ));
+        assert_eq!(level.layer_index_by_name("Ground"), Some(0));
+        level.set_tile(0, 2, 1, Some(7));
+
+        // A 2x2 tile at (1, 0) overwrites the tile at (2, 1)
+        let changes = level.place_tile(0, 1, 0, 5, (2, 2));
+        assert_eq!(changes.len(), 4);
+        assert!(changes.contains(&TileChange {
+            x: 2,
+            y: 1,
+            old: Some(7),
+            new: Some(OCCUPIED_CELL),
+        }));
+        assert_eq!(level.get_tile(0, 1, 0), Some(5));
+        assert!(level.place_tile(0, 3, 3, 5, (2, 2)).is_empty());
+
+        // Placing over an occupied cell removes the whole multi-cell tile
+        let changes = level.place_tile(0, 2, 0, 3, (1, 1));
+        assert_eq!(changes.len(), 4);
+        assert_eq!(level.get_tile(0, 1, 0), None);
+        assert_eq!(level.get_tile(0, 2, 1), None);
+        assert_eq!(level.get_tile(0, 2, 0), Some(3));
+
+        level.place_tile(0, 0, 2, 5, (2, 2));
+        assert_eq!(level.erase_tile(0, 1, 3).len(), 4);
+        if let LayerData::Tiles { occupied_cells, .. } = &level.layers[0].data {
+            assert!(occupied_cells.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_tile_change_set() {
+        let change = |x, y, old, new| TileChange { x, y, old, new };
+        let mut changes = TileChangeSet::new();
+        changes.extend([change(3, 0, None, Some(1)), change(1, 2, Some(4), None)]);
+        changes.extend([change(0, 0, None, Some(2)), change(3, 0, Some(1), Some(5))]);
+        // Reverting a cell drops it from the result
+        changes.extend([change(1, 2, None, Some(4))]);
+
+        assert_eq!(
+            changes.into_changes(),
+            vec![change(3, 0, None, Some(5)), change(0, 0, None, Some(2))]
+        );
+    }
+
     #[test]
     fn test_entity_operations() {
         let mut level = Level::new("Test".to_string(), 10, 10);
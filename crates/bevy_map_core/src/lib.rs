@@ -10,16 +10,24 @@
 //! - `EntityTypeConfig` - Type-level component configurations (physics, input, sprite)
 
 mod collision;
+mod data;
+mod delta;
 mod entity;
 mod entity_type_config;
 mod layer;
 mod level;
+mod navigation;
+mod polygon;
 mod project;
 mod tileset;
 mod value;
 mod world;
 
-pub use collision::{CollisionData, CollisionShape, OneWayDirection, PhysicsBody};
+pub use collision::{
+    CollisionData, CollisionShape, OneWayDirection, PhysicsBody, DEFAULT_FRICTION,
+};
+pub use data::{DataInstance, DataStore};
+pub use delta::{DeltaConflict, EntityDelta, MapDelta, TileDelta};
 pub use entity::EntityInstance;
 pub use entity_type_config::{
     ColliderConfig, EntityTypeConfig, InputConfig, InputProfile, PhysicsBodyType, PhysicsConfig,
@@ -29,8 +37,18 @@ pub use layer::{
     tile_flip_x, tile_flip_y, tile_index, tile_with_flips, toggle_flip_x, toggle_flip_y, Layer,
     LayerData, LayerType, OCCUPIED_CELL, TILE_FLIP_MASK, TILE_FLIP_X, TILE_FLIP_Y, TILE_INDEX_MASK,
 };
-pub use level::Level;
-pub use project::{EditorProject, MapProject, MapProjectBuilder};
-pub use tileset::{TileProperties, Tileset, TilesetImage};
+pub use level::{Level, TileChange, TileChangeSet};
+pub use navigation::{
+    blocks_movement, DiagonalMode, FlowField, NavGrid, NavTile, NAV_COST_PROPERTY,
+};
+pub use polygon::{
+    decompose_polygon, is_convex_polygon, polygon_self_intersections, polygon_signed_area,
+    validate_polygon, PolygonIssue,
+};
+pub use project::{EditorProject, MapProject, MapProjectBuilder, WorldProject};
+pub use tileset::{
+    animation_frame_index, TileAnimation, TileGridLayout, TileProperties, TileRef, Tileset,
+    TilesetImage, TilesetKind, DEFAULT_TILE_ANIMATION_FPS,
+};
 pub use value::Value;
 pub use world::{ConnectionDirection, LevelConnection, WorldConfig, WorldLayout};
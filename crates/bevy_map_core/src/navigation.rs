@@ -0,0 +1,624 @@
+//! Navigation grids and pathfinding over tile maps
+//!
+//! A [`NavGrid`] stores the movement cost of every cell of a level. Cells with
+//! solid tile collision are blocked; other cells cost `1.0` unless a tile sets
+//! the [`NAV_COST_PROPERTY`] custom property. Grids answer A* path queries and
+//! build [`FlowField`]s for steering many agents towards the same goal.
+//!
+//! Coordinates are `(x, y)` cell positions with `y = 0` at the bottom row,
+//! matching [`Level::get_tile`](crate::Level::get_tile).
+
+use crate::{CollisionData, Level, Tileset};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use uuid::Uuid;
+
+/// Custom tile property holding a movement cost multiplier
+///
+/// Numbers greater than zero set the cost of walking onto the tile (e.g. `3.0`
+/// for mud). Zero or negative numbers block the cell.
+pub const NAV_COST_PROPERTY: &str = "nav_cost";
+
+/// How diagonal moves are allowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiagonalMode {
+    /// Only orthogonal moves
+    Never,
+    /// Diagonal moves only when both orthogonal neighbours are walkable
+    #[default]
+    NoCornerCutting,
+    /// Diagonal moves when at least one orthogonal neighbour is walkable
+    AllowCornerCutting,
+    /// Diagonal moves even between two blocked cells
+    Always,
+}
+
+impl DiagonalMode {
+    /// Get the display name of this mode
+    pub fn name(&self) -> &'static str {
+        match self {
+            DiagonalMode::Never => "Never",
+            DiagonalMode::NoCornerCutting => "No Corner Cutting",
+            DiagonalMode::AllowCornerCutting => "Allow Corner Cutting",
+            DiagonalMode::Always => "Always",
+        }
+    }
+
+    /// All modes, for UI dropdowns
+    pub fn all() -> &'static [DiagonalMode] {
+        &[
+            DiagonalMode::Never,
+            DiagonalMode::NoCornerCutting,
+            DiagonalMode::AllowCornerCutting,
+            DiagonalMode::Always,
+        ]
+    }
+}
+
+/// Navigation contribution of a single tile
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct NavTile {
+    /// The tile blocks movement
+    pub solid: bool,
+    /// Cost from the tile's [`NAV_COST_PROPERTY`], if set
+    pub cost: Option<f32>,
+}
+
+impl NavTile {
+    /// Navigation data of a tile (flip flags in `tile` are ignored)
+    pub fn from_tile(tileset: &Tileset, tile: u32) -> Self {
+        let Some(props) = tileset.get_tile_properties(crate::tile_index(tile)) else {
+            return Self::default();
+        };
+        let cost = props
+            .get_custom(NAV_COST_PROPERTY)
+            .and_then(|value| value.as_f64())
+            .map(|cost| cost as f32);
+        Self {
+            solid: blocks_movement(&props.collision) || cost.is_some_and(|cost| cost <= 0.0),
+            cost,
+        }
+    }
+
+    /// Combine the tiles stacked in a cell into its movement cost
+    ///
+    /// Any solid tile blocks the cell; otherwise the highest cost wins.
+    pub fn combine(tiles: impl IntoIterator<Item = NavTile>) -> Option<f32> {
+        let mut cost: Option<f32> = None;
+        for tile in tiles {
+            if tile.solid {
+                return None;
+            }
+            if let Some(tile_cost) = tile.cost {
+                cost = Some(cost.map_or(tile_cost, |c| c.max(tile_cost)));
+            }
+        }
+        Some(cost.unwrap_or(1.0))
+    }
+}
+
+/// Check if collision data blocks grid movement
+///
+/// Sensors and one-way platforms can be walked through; any other collision
+/// shape blocks the whole cell.
+pub fn blocks_movement(collision: &CollisionData) -> bool {
+    collision.has_collision() && !collision.is_sensor && !collision.is_one_way()
+}
+
+/// Walkability and movement cost of every cell of a level
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavGrid {
+    width: u32,
+    height: u32,
+    /// Cost of entering each cell, `None` if blocked
+    costs: Vec<Option<f32>>,
+    /// How diagonal moves are allowed
+    pub diagonal: DiagonalMode,
+}
+
+impl NavGrid {
+    /// Create a grid where every cell is walkable with cost `1.0`
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            costs: vec![Some(1.0); (width * height) as usize],
+            diagonal: DiagonalMode::default(),
+        }
+    }
+
+    /// Build a grid from the tile layers of a level
+    pub fn from_level<'a>(level: &Level, tileset: impl Fn(Uuid) -> Option<&'a Tileset>) -> Self {
+        let mut grid = Self::new(level.width, level.height);
+        let layers: Vec<_> = level
+            .layers
+            .iter()
+            .filter_map(|layer| match &layer.data {
+                crate::LayerData::Tiles {
+                    tileset_id, tiles, ..
+                } => Some((tileset(*tileset_id)?, tiles)),
+                _ => None,
+            })
+            .collect();
+
+        for (idx, cost) in grid.costs.iter_mut().enumerate() {
+            *cost = NavTile::combine(layers.iter().filter_map(|(tileset, tiles)| {
+                let tile = (*tiles.get(idx)?)?;
+                Some(NavTile::from_tile(tileset, tile))
+            }));
+        }
+        grid
+    }
+
+    /// Grid width in cells
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Grid height in cells
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Check if a cell is inside the grid
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height
+    }
+
+    /// Cost of entering a cell, `None` if blocked or outside the grid
+    pub fn cost(&self, x: u32, y: u32) -> Option<f32> {
+        self.index(x, y).and_then(|idx| self.costs[idx])
+    }
+
+    /// Check if a cell can be walked on
+    pub fn is_walkable(&self, x: u32, y: u32) -> bool {
+        self.cost(x, y).is_some()
+    }
+
+    /// Set the cost of a cell (`None` blocks it)
+    pub fn set_cost(&mut self, x: u32, y: u32, cost: Option<f32>) {
+        if let Some(idx) = self.index(x, y) {
+            self.costs[idx] = cost;
+        }
+    }
+
+    /// Walkable neighbours of a cell with the distance to each (1 or √2)
+    pub fn neighbors(&self, x: u32, y: u32) -> impl Iterator<Item = ((u32, u32), f32)> + '_ {
+        const DIRECTIONS: [(i32, i32); 8] = [
+            (1, 0),
+            (-1, 0),
+            (0, 1),
+            (0, -1),
+            (1, 1),
+            (-1, 1),
+            (1, -1),
+            (-1, -1),
+        ];
+        let (x, y) = (x as i32, y as i32);
+        DIRECTIONS.iter().filter_map(move |&(dx, dy)| {
+            let (nx, ny) = (x + dx, y + dy);
+            if !self.contains(nx, ny) || !self.is_walkable(nx as u32, ny as u32) {
+                return None;
+            }
+            if dx == 0 || dy == 0 {
+                return Some(((nx as u32, ny as u32), 1.0));
+            }
+            let side_a = self.is_walkable(nx as u32, y as u32);
+            let side_b = self.is_walkable(x as u32, ny as u32);
+            let allowed = match self.diagonal {
+                DiagonalMode::Never => false,
+                DiagonalMode::NoCornerCutting => side_a && side_b,
+                DiagonalMode::AllowCornerCutting => side_a || side_b,
+                DiagonalMode::Always => true,
+            };
+            allowed.then_some(((nx as u32, ny as u32), std::f32::consts::SQRT_2))
+        })
+    }
+
+    /// Find the cheapest path between two cells with A*
+    ///
+    /// The path includes both `start` and `goal`. Returns `None` if either cell
+    /// is blocked or the goal can't be reached.
+    pub fn find_path(&self, start: (u32, u32), goal: (u32, u32)) -> Option<Vec<(u32, u32)>> {
+        let start_idx = self.index(start.0, start.1)?;
+        let goal_idx = self.index(goal.0, goal.1)?;
+        if !self.is_walkable(start.0, start.1) || !self.is_walkable(goal.0, goal.1) {
+            return None;
+        }
+
+        // Scale the heuristic by the cheapest cell so it never overestimates
+        let min_cost = self
+            .costs
+            .iter()
+            .flatten()
+            .copied()
+            .fold(f32::INFINITY, f32::min);
+        let heuristic = |(x, y): (u32, u32)| {
+            let dx = x.abs_diff(goal.0) as f32;
+            let dy = y.abs_diff(goal.1) as f32;
+            let distance = if self.diagonal == DiagonalMode::Never {
+                dx + dy
+            } else {
+                dx.max(dy) + (std::f32::consts::SQRT_2 - 1.0) * dx.min(dy)
+            };
+            distance * min_cost
+        };
+
+        let mut best = vec![f32::INFINITY; self.costs.len()];
+        let mut came_from = vec![usize::MAX; self.costs.len()];
+        let mut open = BinaryHeap::new();
+        best[start_idx] = 0.0;
+        open.push(Candidate {
+            priority: heuristic(start),
+            cost: 0.0,
+            index: start_idx,
+        });
+
+        while let Some(Candidate { cost, index, .. }) = open.pop() {
+            if index == goal_idx {
+                let mut path = vec![goal];
+                let mut current = goal_idx;
+                while current != start_idx {
+                    current = came_from[current];
+                    path.push(self.position(current));
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if cost > best[index] {
+                continue;
+            }
+            let (x, y) = self.position(index);
+            for (neighbor, distance) in self.neighbors(x, y) {
+                let neighbor_idx = (neighbor.1 * self.width + neighbor.0) as usize;
+                let step = self.costs[neighbor_idx].unwrap_or(f32::INFINITY) * distance;
+                let next = cost + step;
+                if next < best[neighbor_idx] {
+                    best[neighbor_idx] = next;
+                    came_from[neighbor_idx] = index;
+                    open.push(Candidate {
+                        priority: next + heuristic(neighbor),
+                        cost: next,
+                        index: neighbor_idx,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Build a flow field leading every reachable cell to the nearest goal
+    pub fn flow_field(&self, goals: impl IntoIterator<Item = (u32, u32)>) -> FlowField {
+        let mut distances = vec![f32::INFINITY; self.costs.len()];
+        let mut open = BinaryHeap::new();
+        for (x, y) in goals {
+            if let Some(idx) = self.index(x, y).filter(|_| self.is_walkable(x, y)) {
+                distances[idx] = 0.0;
+                open.push(Candidate {
+                    priority: 0.0,
+                    cost: 0.0,
+                    index: idx,
+                });
+            }
+        }
+
+        // Dijkstra outwards from the goals. Moving from `neighbor` into `cell`
+        // costs the cost of `cell`, as in `find_path`.
+        while let Some(Candidate { cost, index, .. }) = open.pop() {
+            if cost > distances[index] {
+                continue;
+            }
+            let (x, y) = self.position(index);
+            let cell_cost = self.costs[index].unwrap_or(f32::INFINITY);
+            for (neighbor, distance) in self.neighbors(x, y) {
+                let neighbor_idx = (neighbor.1 * self.width + neighbor.0) as usize;
+                let next = cost + cell_cost * distance;
+                if next < distances[neighbor_idx] {
+                    distances[neighbor_idx] = next;
+                    open.push(Candidate {
+                        priority: next,
+                        cost: next,
+                        index: neighbor_idx,
+                    });
+                }
+            }
+        }
+
+        let directions = (0..self.costs.len())
+            .map(|idx| {
+                if distances[idx] == 0.0 || !distances[idx].is_finite() {
+                    return None;
+                }
+                let (x, y) = self.position(idx);
+                let cell_cost = |(nx, ny): (u32, u32)| self.costs[(ny * self.width + nx) as usize];
+                self.neighbors(x, y)
+                    .map(|(neighbor, distance)| {
+                        let total = distances[(neighbor.1 * self.width + neighbor.0) as usize]
+                            + cell_cost(neighbor).unwrap_or(f32::INFINITY) * distance;
+                        (neighbor, total)
+                    })
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|((nx, ny), _)| {
+                        ((nx as i32 - x as i32) as i8, (ny as i32 - y as i32) as i8)
+                    })
+            })
+            .collect();
+
+        FlowField {
+            width: self.width,
+            height: self.height,
+            distances,
+            directions,
+        }
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| (y * self.width + x) as usize)
+    }
+
+    fn position(&self, index: usize) -> (u32, u32) {
+        (index as u32 % self.width, index as u32 / self.width)
+    }
+}
+
+/// Distances and steering directions towards a set of goal cells
+///
+/// Built with [`NavGrid::flow_field`]. Rebuild it when the grid changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowField {
+    width: u32,
+    height: u32,
+    distances: Vec<f32>,
+    directions: Vec<Option<(i8, i8)>>,
+}
+
+impl FlowField {
+    /// Path cost from a cell to the nearest goal, `None` if unreachable
+    pub fn distance(&self, x: u32, y: u32) -> Option<f32> {
+        let idx = self.index(x, y)?;
+        Some(self.distances[idx]).filter(|d| d.is_finite())
+    }
+
+    /// Step `(dx, dy)` to take from a cell, `None` at goals and unreachable cells
+    pub fn direction(&self, x: u32, y: u32) -> Option<(i32, i32)> {
+        let (dx, dy) = self.directions[self.index(x, y)?]?;
+        Some((dx as i32, dy as i32))
+    }
+
+    /// Next cell to move to from a cell
+    pub fn next_cell(&self, x: u32, y: u32) -> Option<(u32, u32)> {
+        let (dx, dy) = self.direction(x, y)?;
+        Some(((x as i32 + dx) as u32, (y as i32 + dy) as u32))
+    }
+
+    /// Check if a cell is one of the goals
+    pub fn is_goal(&self, x: u32, y: u32) -> bool {
+        self.distance(x, y) == Some(0.0)
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| (y * self.width + x) as usize)
+    }
+}
+
+/// Open-set entry ordered by lowest priority first
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    priority: f32,
+    cost: f32,
+    index: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so `BinaryHeap` pops the lowest priority; prefer deeper nodes on ties
+        other
+            .priority
+            .total_cmp(&self.priority)
+            .then_with(|| self.cost.total_cmp(&other.cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Layer, TileProperties};
+
+    /// Build a grid from rows drawn top to bottom: `#` blocked, `~` cost 5, `.` open
+    fn grid(rows: &[&str]) -> NavGrid {
+        let height = rows.len() as u32;
+        let width = rows[0].len() as u32;
+        let mut grid = NavGrid::new(width, height);
+        for (row, line) in rows.iter().enumerate() {
+            let y = height - 1 - row as u32;
+            for (x, c) in line.chars().enumerate() {
+                match c {
+                    '#' => grid.set_cost(x as u32, y, None),
+                    '~' => grid.set_cost(x as u32, y, Some(5.0)),
+                    _ => {}
+                }
+            }
+        }
+        grid
+    }
+
+    fn path_cost(grid: &NavGrid, path: &[(u32, u32)]) -> f32 {
+        path.windows(2)
+            .map(|step| {
+                let (a, b) = (step[0], step[1]);
+                let diagonal = a.0 != b.0 && a.1 != b.1;
+                let distance = if diagonal {
+                    std::f32::consts::SQRT_2
+                } else {
+                    1.0
+                };
+                grid.cost(b.0, b.1).unwrap() * distance
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_path_around_wall() {
+        let mut grid = grid(&[
+            ".....", //
+            ".###.", //
+            ".....",
+        ]);
+        grid.diagonal = DiagonalMode::Never;
+        let path = grid.find_path((0, 1), (4, 1)).unwrap();
+        assert_eq!(path.first(), Some(&(0, 1)));
+        assert_eq!(path.last(), Some(&(4, 1)));
+        assert_eq!(path.len(), 7);
+        assert!(path.iter().all(|&(x, y)| grid.is_walkable(x, y)));
+    }
+
+    #[test]
+    fn test_corner_cutting_rules() {
+        // Two blocks touching at a corner: (1,0) and (0,1)
+        let mut grid = grid(&[
+            "#.", //
+            ".#",
+        ]);
+        grid.diagonal = DiagonalMode::NoCornerCutting;
+        assert!(grid.find_path((0, 0), (1, 1)).is_none());
+        grid.diagonal = DiagonalMode::AllowCornerCutting;
+        assert!(grid.find_path((0, 0), (1, 1)).is_none());
+        grid.diagonal = DiagonalMode::Always;
+        assert_eq!(grid.find_path((0, 0), (1, 1)), Some(vec![(0, 0), (1, 1)]));
+
+        // A single block next to the diagonal
+        let mut grid = grid_with_block();
+        grid.diagonal = DiagonalMode::NoCornerCutting;
+        assert_eq!(grid.find_path((0, 0), (1, 1)).unwrap().len(), 3);
+        grid.diagonal = DiagonalMode::AllowCornerCutting;
+        assert_eq!(grid.find_path((0, 0), (1, 1)), Some(vec![(0, 0), (1, 1)]));
+    }
+
+    fn grid_with_block() -> NavGrid {
+        grid(&[
+            "..", //
+            ".#",
+        ])
+    }
+
+    #[test]
+    fn test_path_avoids_expensive_cells() {
+        let mut grid = grid(&[
+            ".....", //
+            ".~~~.", //
+            ".....",
+        ]);
+        grid.diagonal = DiagonalMode::Never;
+        let path = grid.find_path((0, 1), (4, 1)).unwrap();
+        assert!(path.iter().all(|&(x, y)| grid.cost(x, y) == Some(1.0)));
+        assert_eq!(path_cost(&grid, &path), 6.0);
+    }
+
+    #[test]
+    fn test_unreachable_goal() {
+        let grid = grid(&[
+            "..#..", //
+            "..#..",
+        ]);
+        assert!(grid.find_path((0, 0), (4, 0)).is_none());
+        assert!(grid.find_path((0, 0), (2, 0)).is_none());
+        assert!(grid.flow_field([(4, 0)]).distance(0, 0).is_none());
+    }
+
+    #[test]
+    fn test_flow_field_matches_a_star() {
+        let grid = grid(&[
+            "......", //
+            ".##~#.", //
+            "......", //
+            "#.##..",
+        ]);
+        let field = grid.flow_field([(5, 0)]);
+        assert!(field.is_goal(5, 0));
+        assert_eq!(field.direction(5, 0), None);
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                let Some(path) = grid.find_path((x, y), (5, 0)) else {
+                    assert!(field.distance(x, y).is_none());
+                    continue;
+                };
+                let expected = path_cost(&grid, &path);
+                assert!((field.distance(x, y).unwrap() - expected).abs() < 1e-4);
+
+                // Following the field reaches the goal with the same cost
+                let mut cell = (x, y);
+                let mut followed = vec![cell];
+                while let Some(next) = field.next_cell(cell.0, cell.1) {
+                    cell = next;
+                    followed.push(cell);
+                }
+                assert_eq!(cell, (5, 0));
+                assert!((path_cost(&grid, &followed) - expected).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_grid_from_level() {
+        let mut tileset = Tileset::new("Tiles".to_string(), "tiles.png".to_string(), 16, 4, 1);
+        tileset.set_tile_full_collision(0, true);
+        tileset.set_tile_collision(1, CollisionData::full().with_sensor(true));
+        tileset.set_tile_properties(
+            2,
+            TileProperties::new().with_custom(NAV_COST_PROPERTY.to_string(), 4.0.into()),
+        );
+        tileset.set_tile_collision(
+            3,
+            CollisionData::full().with_one_way(crate::OneWayDirection::Top),
+        );
+
+        let mut level = Level::new("Test".to_string(), 5, 1);
+        level.add_layer(Layer::new_tile_layer(
+            "Ground".to_string(),
+            tileset.id,
+            5,
+            1,
+        ));
+        for x in 0..4 {
+            level.set_tile(0, x, 0, Some(x));
+        }
+
+        let grid = NavGrid::from_level(&level, |id| (id == tileset.id).then_some(&tileset));
+        assert_eq!(grid.cost(0, 0), None);
+        assert_eq!(grid.cost(1, 0), Some(1.0));
+        assert_eq!(grid.cost(2, 0), Some(4.0));
+        assert_eq!(grid.cost(3, 0), Some(1.0));
+        assert_eq!(grid.cost(4, 0), Some(1.0));
+    }
+
+    #[test]
+    fn test_combine_nav_tiles() {
+        let open = NavTile::default();
+        let mud = NavTile {
+            solid: false,
+            cost: Some(3.0),
+        };
+        let wall = NavTile {
+            solid: true,
+            cost: None,
+        };
+        assert_eq!(NavTile::combine([]), Some(1.0));
+        assert_eq!(NavTile::combine([open, mud]), Some(3.0));
+        assert_eq!(NavTile::combine([mud, wall]), None);
+    }
+}
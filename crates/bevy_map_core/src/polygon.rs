@@ -0,0 +1,368 @@
+//! Polygon validation and convex decomposition
+//!
+//! Collision polygons are authored as simple (possibly concave) outlines. Physics
+//! engines only accept convex shapes, so concave outlines are split into convex
+//! parts with ear clipping followed by Hertel-Mehlhorn merging of the triangles.
+//!
+//! All functions work in any coordinate system and accept either winding order.
+
+use std::fmt;
+
+/// Tolerance used for area and orientation tests
+const EPSILON: f32 = 1e-6;
+
+/// Problem found while validating a polygon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolygonIssue {
+    /// Fewer than three vertices
+    TooFewPoints,
+    /// All vertices are (nearly) collinear, so the polygon has no area
+    ZeroArea,
+    /// Two non-adjacent edges cross. Edge `i` runs from vertex `i` to vertex `i + 1`.
+    SelfIntersecting {
+        first_edge: usize,
+        second_edge: usize,
+    },
+}
+
+impl fmt::Display for PolygonIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolygonIssue::TooFewPoints => write!(f, "polygon needs at least 3 points"),
+            PolygonIssue::ZeroArea => write!(f, "polygon has no area"),
+            PolygonIssue::SelfIntersecting {
+                first_edge,
+                second_edge,
+            } => write!(
+                f,
+                "polygon edges {} and {} intersect",
+                first_edge, second_edge
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PolygonIssue {}
+
+/// Signed area of a polygon (positive for counter-clockwise winding in Y-up space)
+pub fn polygon_signed_area(points: &[[f32; 2]]) -> f32 {
+    let n = points.len();
+    let mut area = 0.0;
+    for i in 0..n {
+        let a = points[i];
+        let b = points[(i + 1) % n];
+        area += a[0] * b[1] - b[0] * a[1];
+    }
+    area * 0.5
+}
+
+/// Check if a polygon is convex (collinear vertices are allowed)
+pub fn is_convex_polygon(points: &[[f32; 2]]) -> bool {
+    let n = points.len();
+    if n < 3 {
+        return false;
+    }
+    let sign = polygon_signed_area(points).signum();
+    (0..n).all(|i| {
+        let turn = cross(points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+        turn * sign >= -EPSILON
+    })
+}
+
+/// Find all pairs of non-adjacent edges that intersect
+///
+/// Edge `i` runs from vertex `i` to vertex `(i + 1) % len`. Pairs are returned
+/// with the smaller edge index first.
+pub fn polygon_self_intersections(points: &[[f32; 2]]) -> Vec<(usize, usize)> {
+    let n = points.len();
+    let mut pairs = Vec::new();
+    if n < 4 {
+        return pairs;
+    }
+    for i in 0..n {
+        for j in (i + 2)..n {
+            // The first and last edges share vertex 0
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+            if segments_intersect(
+                points[i],
+                points[(i + 1) % n],
+                points[j],
+                points[(j + 1) % n],
+            ) {
+                pairs.push((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+/// Check that a polygon is simple and has an area
+pub fn validate_polygon(points: &[[f32; 2]]) -> Result<(), PolygonIssue> {
+    if points.len() < 3 {
+        return Err(PolygonIssue::TooFewPoints);
+    }
+    if let Some(&(first_edge, second_edge)) = polygon_self_intersections(points).first() {
+        return Err(PolygonIssue::SelfIntersecting {
+            first_edge,
+            second_edge,
+        });
+    }
+    if polygon_signed_area(points).abs() <= EPSILON {
+        return Err(PolygonIssue::ZeroArea);
+    }
+    Ok(())
+}
+
+/// Split a simple polygon into convex parts
+///
+/// Convex polygons are returned unchanged as a single part. Concave polygons are
+/// triangulated and the triangles merged back into as few convex parts as the
+/// greedy Hertel-Mehlhorn pass finds. Parts use counter-clockwise winding.
+///
+/// Returns an empty list for polygons that fail [`validate_polygon`].
+pub fn decompose_polygon(points: &[[f32; 2]]) -> Vec<Vec<[f32; 2]>> {
+    if validate_polygon(points).is_err() {
+        return Vec::new();
+    }
+    if is_convex_polygon(points) {
+        return vec![points.to_vec()];
+    }
+
+    // Work on a counter-clockwise copy so ears are the left turns
+    let mut ccw = points.to_vec();
+    if polygon_signed_area(&ccw) < 0.0 {
+        ccw.reverse();
+    }
+
+    let mut parts = triangulate(&ccw);
+    merge_convex_parts(&ccw, &mut parts);
+
+    parts
+        .into_iter()
+        .map(|part| part.into_iter().map(|i| ccw[i]).collect())
+        .collect()
+}
+
+/// Ear-clip a counter-clockwise polygon into triangles of vertex indices
+fn triangulate(points: &[[f32; 2]]) -> Vec<Vec<usize>> {
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    let mut triangles = Vec::with_capacity(points.len() - 2);
+
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let ear = (0..n)
+            .find(|&i| is_ear(points, &remaining, i))
+            // Numerical trouble: clip the sharpest left turn so we always terminate
+            .unwrap_or_else(|| {
+                (0..n)
+                    .max_by(|&a, &b| {
+                        let turn = |i: usize| {
+                            cross(
+                                points[remaining[(i + n - 1) % n]],
+                                points[remaining[i]],
+                                points[remaining[(i + 1) % n]],
+                            )
+                        };
+                        turn(a).total_cmp(&turn(b))
+                    })
+                    .unwrap_or(0)
+            });
+
+        let prev = remaining[(ear + n - 1) % n];
+        let next = remaining[(ear + 1) % n];
+        triangles.push(vec![prev, remaining[ear], next]);
+        remaining.remove(ear);
+    }
+    triangles.push(remaining);
+    triangles
+}
+
+/// Check if `remaining[i]` is an ear of the remaining polygon
+fn is_ear(points: &[[f32; 2]], remaining: &[usize], i: usize) -> bool {
+    let n = remaining.len();
+    let prev = remaining[(i + n - 1) % n];
+    let curr = remaining[i];
+    let next = remaining[(i + 1) % n];
+    let (a, b, c) = (points[prev], points[curr], points[next]);
+
+    if cross(a, b, c) <= EPSILON {
+        return false;
+    }
+    remaining.iter().all(|&other| {
+        other == prev
+            || other == curr
+            || other == next
+            || points[other] == a
+            || points[other] == b
+            || points[other] == c
+            || !point_in_triangle(points[other], a, b, c)
+    })
+}
+
+/// Greedily merge neighbouring parts across shared edges while the result stays convex
+fn merge_convex_parts(points: &[[f32; 2]], parts: &mut Vec<Vec<usize>>) {
+    let mut merged_any = true;
+    while merged_any {
+        merged_any = false;
+        'search: for a in 0..parts.len() {
+            for b in (a + 1)..parts.len() {
+                if let Some(merged) = merge_parts(&parts[a], &parts[b]) {
+                    let vertices: Vec<[f32; 2]> = merged.iter().map(|&i| points[i]).collect();
+                    if is_convex_polygon(&vertices) {
+                        parts[a] = merged;
+                        parts.remove(b);
+                        merged_any = true;
+                        break 'search;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Join two counter-clockwise parts that share an edge, if they do
+fn merge_parts(first: &[usize], second: &[usize]) -> Option<Vec<usize>> {
+    let (n, m) = (first.len(), second.len());
+    for i in 0..n {
+        let (from, to) = (first[i], first[(i + 1) % n]);
+        // Shared edges run in opposite directions in the two parts
+        let Some(j) = (0..m).find(|&j| second[j] == to && second[(j + 1) % m] == from) else {
+            continue;
+        };
+        // Walk `first` from `to` round to `from`, then the rest of `second`
+        let mut merged: Vec<usize> = (1..=n).map(|k| first[(i + k) % n]).collect();
+        merged.extend((2..m).map(|k| second[(j + k) % m]));
+        return Some(merged);
+    }
+    None
+}
+
+/// Z component of `(b - a) x (c - b)`; positive for a left turn
+fn cross(a: [f32; 2], b: [f32; 2], c: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (c[1] - b[1]) - (b[1] - a[1]) * (c[0] - b[0])
+}
+
+/// Orientation of `c` relative to the line through `a` and `b`
+fn orient(a: [f32; 2], b: [f32; 2], c: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Check if `p` lies inside or on the edges of the counter-clockwise triangle `abc`
+fn point_in_triangle(p: [f32; 2], a: [f32; 2], b: [f32; 2], c: [f32; 2]) -> bool {
+    orient(a, b, p) >= -EPSILON && orient(b, c, p) >= -EPSILON && orient(c, a, p) >= -EPSILON
+}
+
+/// Check if `p` lies within the bounding box of segment `ab`
+fn on_segment(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> bool {
+    p[0] >= a[0].min(b[0]) - EPSILON
+        && p[0] <= a[0].max(b[0]) + EPSILON
+        && p[1] >= a[1].min(b[1]) - EPSILON
+        && p[1] <= a[1].max(b[1]) + EPSILON
+}
+
+/// Check if segments `p1p2` and `q1q2` touch or cross
+fn segments_intersect(p1: [f32; 2], p2: [f32; 2], q1: [f32; 2], q2: [f32; 2]) -> bool {
+    let d1 = orient(q1, q2, p1);
+    let d2 = orient(q1, q2, p2);
+    let d3 = orient(p1, p2, q1);
+    let d4 = orient(p1, p2, q2);
+
+    if ((d1 > EPSILON && d2 < -EPSILON) || (d1 < -EPSILON && d2 > EPSILON))
+        && ((d3 > EPSILON && d4 < -EPSILON) || (d3 < -EPSILON && d4 > EPSILON))
+    {
+        return true;
+    }
+
+    (d1.abs() <= EPSILON && on_segment(q1, q2, p1))
+        || (d2.abs() <= EPSILON && on_segment(q1, q2, p2))
+        || (d3.abs() <= EPSILON && on_segment(p1, p2, q1))
+        || (d4.abs() <= EPSILON && on_segment(p1, p2, q2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn total_area(parts: &[Vec<[f32; 2]>]) -> f32 {
+        parts.iter().map(|p| polygon_signed_area(p).abs()).sum()
+    }
+
+    #[test]
+    fn test_convex_polygon_is_single_part() {
+        let square = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        assert!(is_convex_polygon(&square));
+        assert_eq!(validate_polygon(&square), Ok(()));
+        assert_eq!(decompose_polygon(&square), vec![square.to_vec()]);
+    }
+
+    #[test]
+    fn test_l_shape_decomposes_into_convex_parts() {
+        // Y-down editor coordinates, clockwise on screen
+        let l_shape = [
+            [0.0, 0.0],
+            [0.5, 0.0],
+            [0.5, 0.5],
+            [1.0, 0.5],
+            [1.0, 1.0],
+            [0.0, 1.0],
+        ];
+        assert!(!is_convex_polygon(&l_shape));
+        assert_eq!(validate_polygon(&l_shape), Ok(()));
+
+        let parts = decompose_polygon(&l_shape);
+        assert_eq!(parts.len(), 2);
+        assert!(parts.iter().all(|p| is_convex_polygon(p)));
+        assert!((total_area(&parts) - 0.75).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_comb_decomposition_preserves_area() {
+        let comb = [
+            [0.0, 0.0],
+            [4.0, 0.0],
+            [4.0, 3.0],
+            [3.0, 3.0],
+            [3.0, 1.0],
+            [2.5, 1.0],
+            [2.5, 3.0],
+            [1.5, 3.0],
+            [1.5, 1.0],
+            [1.0, 1.0],
+            [1.0, 3.0],
+            [0.0, 3.0],
+        ];
+        let parts = decompose_polygon(&comb);
+        assert!(parts.len() >= 4);
+        assert!(parts.iter().all(|p| is_convex_polygon(p)));
+        let expected = polygon_signed_area(&comb).abs();
+        assert!((total_area(&parts) - expected).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_self_intersection_detected() {
+        let bowtie = [[0.0, 0.0], [1.0, 1.0], [1.0, 0.0], [0.0, 1.0]];
+        assert_eq!(polygon_self_intersections(&bowtie), vec![(0, 2)]);
+        assert_eq!(
+            validate_polygon(&bowtie),
+            Err(PolygonIssue::SelfIntersecting {
+                first_edge: 0,
+                second_edge: 2
+            })
+        );
+        assert!(decompose_polygon(&bowtie).is_empty());
+    }
+
+    #[test]
+    fn test_degenerate_polygons() {
+        assert_eq!(
+            validate_polygon(&[[0.0, 0.0], [1.0, 0.0]]),
+            Err(PolygonIssue::TooFewPoints)
+        );
+        assert_eq!(
+            validate_polygon(&[[0.0, 0.0], [0.5, 0.0], [1.0, 0.0]]),
+            Err(PolygonIssue::ZeroArea)
+        );
+    }
+}
@@ -4,11 +4,14 @@
 //! data and all tileset information needed to render it, so developers don't have
 //! to manually map tileset IDs to textures.
 //!
-//! Two formats are supported:
+//! Three formats are supported:
 //! - `MapProject`: Simple format with HashMap collections (for hand-crafted JSON)
 //! - `EditorProject`: Full editor format with array collections (exported by the editor)
+//! - `WorldProject`: Every level of a project plus the world layout and connections
 
-use crate::{EntityTypeConfig, Level, Tileset};
+use crate::{
+    ConnectionDirection, DataStore, EntityTypeConfig, Level, LevelConnection, Tileset, WorldConfig,
+};
 use bevy_map_animation::SpriteData;
 use bevy_map_dialogue::DialogueTree;
 use serde::{Deserialize, Serialize};
@@ -29,9 +32,9 @@ pub struct EditorProject {
     /// Tilesets as array
     #[serde(default)]
     pub tilesets: Vec<Tileset>,
-    /// Data section (optional, for editor use)
+    /// Data instances (items, enemies, loot tables...) authored in the editor
     #[serde(default)]
-    pub data: Option<serde_json::Value>,
+    pub data: DataStore,
     /// Levels as array (editor supports multiple levels)
     #[serde(default)]
     pub levels: Vec<Level>,
@@ -47,6 +50,9 @@ pub struct EditorProject {
     /// Entity type component configurations (physics, input, sprite per type)
     #[serde(default)]
     pub entity_type_configs: HashMap<String, EntityTypeConfig>,
+    /// World layout and level connections
+    #[serde(default)]
+    pub world_config: WorldConfig,
 }
 
 impl EditorProject {
@@ -102,9 +108,43 @@ impl EditorProject {
             sprite_sheets,
             dialogues,
             entity_type_configs: self.entity_type_configs.clone(),
+            autotile_config: self.autotile_config.clone(),
+            schema: self.schema.clone(),
+            data: self.data.clone(),
         })
     }
 
+    /// Convert to WorldProject (keeps every level and the world layout)
+    pub fn to_world_project(&self) -> WorldProject {
+        let tile_size = self
+            .tilesets
+            .first()
+            .map(|t| t.tile_dimensions())
+            .unwrap_or((32, 32));
+
+        WorldProject {
+            version: self.version,
+            levels: self.levels.clone(),
+            tilesets: self.tilesets.iter().map(|t| (t.id, t.clone())).collect(),
+            sprite_sheets: self
+                .sprite_sheets
+                .iter()
+                .map(|s| (s.id, s.clone()))
+                .collect(),
+            dialogues: self
+                .dialogues
+                .iter()
+                .map(|d| (d.id.to_string(), d.clone()))
+                .collect(),
+            entity_type_configs: self.entity_type_configs.clone(),
+            world: self.world_config.clone(),
+            autotile_config: self.autotile_config.clone(),
+            schema: self.schema.clone(),
+            data: self.data.clone(),
+            tile_size,
+        }
+    }
+
     /// Get entity type config by type name
     pub fn get_entity_type_config(&self, type_name: &str) -> Option<&EntityTypeConfig> {
         self.entity_type_configs.get(type_name)
@@ -142,6 +182,15 @@ pub struct MapProject {
     /// Entity type component configurations (physics, input, sprite per type)
     #[serde(default)]
     pub entity_type_configs: HashMap<String, EntityTypeConfig>,
+    /// Autotile configuration, used for painting terrain at runtime
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub autotile_config: Option<serde_json::Value>,
+    /// Schema the map was saved with, used to check registered entity types
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<serde_json::Value>,
+    /// Data instances that entities can reference with `Ref` properties
+    #[serde(default, skip_serializing_if = "DataStore::is_empty")]
+    pub data: DataStore,
 }
 
 impl MapProject {
@@ -155,6 +204,9 @@ impl MapProject {
             sprite_sheets: HashMap::new(),
             dialogues: HashMap::new(),
             entity_type_configs: HashMap::new(),
+            autotile_config: None,
+            schema: None,
+            data: DataStore::default(),
         }
     }
 
@@ -173,6 +225,9 @@ impl MapProject {
             sprite_sheets: sprite_sheet_map,
             dialogues: HashMap::new(),
             entity_type_configs: HashMap::new(),
+            autotile_config: None,
+            schema: None,
+            data: DataStore::default(),
         }
     }
 
@@ -279,6 +334,254 @@ impl MapProject {
     }
 }
 
+/// Every level of a project together with the shared assets and world layout
+///
+/// Created from an [`EditorProject`] with [`EditorProject::to_world_project`].
+/// Level positions (`world_x`/`world_y`) are in world pixels with Y pointing
+/// down, as shown in the editor's world view. [`WorldProject::level_origin`]
+/// converts them to Bevy's Y-up space.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(bevy::asset::Asset, bevy::reflect::TypePath))]
+pub struct WorldProject {
+    /// Format version for future compatibility
+    pub version: u32,
+    /// All levels of the world
+    pub levels: Vec<Level>,
+    /// Tilesets shared by all levels, keyed by their UUID
+    pub tilesets: HashMap<Uuid, Tileset>,
+    /// Sprite sheets, keyed by their UUID
+    #[serde(default)]
+    pub sprite_sheets: HashMap<Uuid, SpriteData>,
+    /// Dialogue trees, keyed by their ID
+    #[serde(default)]
+    pub dialogues: HashMap<String, DialogueTree>,
+    /// Entity type component configurations (physics, input, sprite per type)
+    #[serde(default)]
+    pub entity_type_configs: HashMap<String, EntityTypeConfig>,
+    /// World layout and level connections
+    #[serde(default)]
+    pub world: WorldConfig,
+    /// Autotile configuration, used for painting terrain at runtime
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub autotile_config: Option<serde_json::Value>,
+    /// Schema the map was saved with, used to check registered entity types
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<serde_json::Value>,
+    /// Data instances shared by all levels
+    #[serde(default, skip_serializing_if = "DataStore::is_empty")]
+    pub data: DataStore,
+    /// Tile size (width, height) used to lay out levels in world space
+    #[serde(default = "default_world_tile_size")]
+    pub tile_size: (u32, u32),
+}
+
+fn default_world_tile_size() -> (u32, u32) {
+    (32, 32)
+}
+
+impl WorldProject {
+    /// Create a new world from its levels, their tilesets and the world layout
+    pub fn new(levels: Vec<Level>, tilesets: Vec<Tileset>, world: WorldConfig) -> Self {
+        let tile_size = tilesets
+            .first()
+            .map(|t| t.tile_dimensions())
+            .unwrap_or_else(default_world_tile_size);
+        Self {
+            version: 1,
+            levels,
+            tilesets: tilesets.into_iter().map(|t| (t.id, t)).collect(),
+            sprite_sheets: HashMap::new(),
+            dialogues: HashMap::new(),
+            entity_type_configs: HashMap::new(),
+            world,
+            autotile_config: None,
+            schema: None,
+            data: DataStore::default(),
+            tile_size,
+        }
+    }
+
+    /// Get a level by ID
+    pub fn get_level(&self, id: Uuid) -> Option<&Level> {
+        self.levels.iter().find(|l| l.id == id)
+    }
+
+    /// Get a level by name
+    pub fn level_by_name(&self, name: &str) -> Option<&Level> {
+        self.levels.iter().find(|l| l.name == name)
+    }
+
+    /// Get all level IDs
+    pub fn level_ids(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.levels.iter().map(|l| l.id)
+    }
+
+    /// Get a tileset by ID
+    pub fn get_tileset(&self, id: Uuid) -> Option<&Tileset> {
+        self.tilesets.get(&id)
+    }
+
+    /// Get connections originating from a level
+    pub fn connections_from(&self, level_id: Uuid) -> Vec<&LevelConnection> {
+        self.world.connections_from(level_id)
+    }
+
+    /// Build a single-level [`MapProject`] for spawning one level
+    pub fn level_project(&self, id: Uuid) -> Option<MapProject> {
+        let level = self.get_level(id)?.clone();
+        Some(MapProject {
+            version: self.version,
+            level,
+            tilesets: self.tilesets.clone(),
+            sprite_sheets: self.sprite_sheets.clone(),
+            dialogues: self.dialogues.clone(),
+            entity_type_configs: self.entity_type_configs.clone(),
+            autotile_config: self.autotile_config.clone(),
+            schema: self.schema.clone(),
+            data: self.data.clone(),
+        })
+    }
+
+    /// Size of a level in world pixels
+    pub fn level_size(&self, level: &Level) -> (f32, f32) {
+        (
+            (level.width * self.tile_size.0) as f32,
+            (level.height * self.tile_size.1) as f32,
+        )
+    }
+
+    /// Rectangle covered by a level in world pixels, as (min_x, min_y, max_x, max_y)
+    pub fn level_rect(&self, level: &Level) -> (f32, f32, f32, f32) {
+        let (width, height) = self.level_size(level);
+        let (x, y) = (level.world_x as f32, level.world_y as f32);
+        (x, y, x + width, y + height)
+    }
+
+    /// Level containing a point in world pixels
+    ///
+    /// If levels overlap, the first one in project order wins.
+    pub fn level_at(&self, x: f32, y: f32) -> Option<&Level> {
+        self.levels.iter().find(|level| {
+            let (min_x, min_y, max_x, max_y) = self.level_rect(level);
+            x >= min_x && x < max_x && y >= min_y && y < max_y
+        })
+    }
+
+    /// Distance in pixels from a world point to the nearest edge of a level
+    ///
+    /// Returns 0 for points inside the level.
+    pub fn distance_to_level(&self, level: &Level, x: f32, y: f32) -> f32 {
+        let (min_x, min_y, max_x, max_y) = self.level_rect(level);
+        let dx = (min_x - x).max(x - max_x).max(0.0);
+        let dy = (min_y - y).max(y - max_y).max(0.0);
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    /// Edge a world point has left a level through
+    ///
+    /// Returns `None` while the point is inside the level.
+    pub fn exit_edge(&self, level: &Level, x: f32, y: f32) -> Option<ConnectionDirection> {
+        let (min_x, min_y, max_x, max_y) = self.level_rect(level);
+        if x < min_x {
+            Some(ConnectionDirection::West)
+        } else if x >= max_x {
+            Some(ConnectionDirection::East)
+        } else if y < min_y {
+            Some(ConnectionDirection::North)
+        } else if y >= max_y {
+            Some(ConnectionDirection::South)
+        } else {
+            None
+        }
+    }
+
+    /// Level and entry edge reached by leaving a level through an edge
+    ///
+    /// Connections work both ways: a connection from A's east edge to B's
+    /// west edge also leads from B's west edge back to A.
+    pub fn exit_through(
+        &self,
+        level_id: Uuid,
+        edge: ConnectionDirection,
+    ) -> Option<(Uuid, ConnectionDirection)> {
+        self.world.connections.iter().find_map(|c| {
+            if c.from_level == level_id && c.from_direction == edge {
+                Some((c.to_level, c.to_direction))
+            } else if c.to_level == level_id && c.to_direction == edge {
+                Some((c.from_level, c.from_direction))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// World point at which a traveler enters a level
+    ///
+    /// The offset along the exit edge is kept on the entry edge (clamped to
+    /// its length), and the point is moved `inset` pixels into the level.
+    pub fn transition_entry(
+        &self,
+        from: &Level,
+        exit_edge: ConnectionDirection,
+        to: &Level,
+        entry_edge: ConnectionDirection,
+        exit_point: (f32, f32),
+        inset: f32,
+    ) -> (f32, f32) {
+        let (from_x, from_y, _, _) = self.level_rect(from);
+        let offset = match exit_edge {
+            ConnectionDirection::East | ConnectionDirection::West => exit_point.1 - from_y,
+            ConnectionDirection::North | ConnectionDirection::South => exit_point.0 - from_x,
+        };
+
+        let (min_x, min_y, max_x, max_y) = self.level_rect(to);
+        let along = |min: f32, max: f32| {
+            let inset = inset.min((max - min) / 2.0);
+            (min + offset).clamp(min + inset, max - inset)
+        };
+        let inset_x = inset.min((max_x - min_x) / 2.0);
+        let inset_y = inset.min((max_y - min_y) / 2.0);
+        match entry_edge {
+            ConnectionDirection::West => (min_x + inset_x, along(min_y, max_y)),
+            ConnectionDirection::East => (max_x - inset_x, along(min_y, max_y)),
+            ConnectionDirection::North => (along(min_x, max_x), min_y + inset_y),
+            ConnectionDirection::South => (along(min_x, max_x), max_y - inset_y),
+        }
+    }
+
+    /// Position of a level's map origin in Bevy's Y-up world space
+    ///
+    /// Spawned maps put the center of tile (0, 0), the bottom-left tile, at
+    /// their origin. This returns where that origin has to go so the level's
+    /// top-left corner lands on (`world_x`, `-world_y`).
+    pub fn level_origin(&self, level: &Level) -> (f32, f32) {
+        let (_, height) = self.level_size(level);
+        let (tile_width, tile_height) = (self.tile_size.0 as f32, self.tile_size.1 as f32);
+        (
+            level.world_x as f32 + tile_width / 2.0,
+            -(level.world_y as f32) - height + tile_height / 2.0,
+        )
+    }
+
+    /// Validate that all tileset references in every level are satisfied
+    pub fn validate(&self) -> Result<(), String> {
+        use crate::LayerData;
+        for level in &self.levels {
+            for (layer_idx, layer) in level.layers.iter().enumerate() {
+                if let LayerData::Tiles { tileset_id, .. } = &layer.data {
+                    if !self.tilesets.contains_key(tileset_id) {
+                        return Err(format!(
+                            "Level '{}' layer {} references missing tileset {}",
+                            level.name, layer_idx, tileset_id
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Builder for creating a MapProject from separate level and tileset sources
 #[derive(Debug, Default)]
 pub struct MapProjectBuilder {
@@ -420,4 +723,91 @@ mod tests {
         assert_eq!(project.level.name, "Test");
         assert_eq!(project.tilesets.len(), 1);
     }
+
+    #[test]
+    fn test_world_project_keeps_all_levels() {
+        let tileset = Tileset::new("Ground".to_string(), "tiles.png".to_string(), 16, 10, 10);
+        let mut start = Level::new_at("Start".to_string(), 10, 5, 0, 0);
+        let cave = Level::new_at("Cave".to_string(), 8, 4, 160, 16);
+        start.add_layer(Layer::new_tile_layer(
+            "Ground".to_string(),
+            tileset.id,
+            10,
+            5,
+        ));
+        let mut world_config = WorldConfig::default();
+        world_config.add_connection(LevelConnection::auto_direction(
+            start.id,
+            crate::ConnectionDirection::East,
+            cave.id,
+        ));
+
+        let world = WorldProject::new(
+            vec![start.clone(), cave.clone()],
+            vec![tileset],
+            world_config,
+        );
+
+        assert_eq!(world.levels.len(), 2);
+        assert_eq!(world.tile_size, (16, 16));
+        assert!(world.validate().is_ok());
+        assert_eq!(world.level_by_name("Cave").unwrap().id, cave.id);
+        assert_eq!(world.connections_from(start.id).len(), 1);
+
+        let project = world.level_project(cave.id).unwrap();
+        assert_eq!(project.level.name, "Cave");
+        assert_eq!(project.tilesets.len(), 1);
+
+        // Cave spans x 160..288 and y 16..80 (Y-down), so its bottom-left
+        // tile center sits at (168, -72) in Y-up space
+        assert_eq!(world.level_size(&cave), (128.0, 64.0));
+        assert_eq!(world.level_origin(&cave), (168.0, -72.0));
+
+        assert_eq!(world.level_at(170.0, 20.0).unwrap().id, cave.id);
+        assert_eq!(world.level_at(10.0, 10.0).unwrap().id, start.id);
+        assert!(world.level_at(300.0, 20.0).is_none());
+        assert_eq!(world.distance_to_level(&cave, 200.0, 40.0), 0.0);
+        assert_eq!(world.distance_to_level(&cave, 291.0, 84.0), 5.0);
+
+        // Leaving Start (0..160 x 0..80) east at y = 40 enters Cave from the
+        // west, 40 pixels down its edge
+        assert_eq!(
+            world.exit_edge(&start, 165.0, 40.0),
+            Some(crate::ConnectionDirection::East)
+        );
+        assert_eq!(world.exit_edge(&start, 80.0, 40.0), None);
+        let (to_level, entry_edge) = world
+            .exit_through(start.id, crate::ConnectionDirection::East)
+            .unwrap();
+        assert_eq!(to_level, cave.id);
+        assert_eq!(entry_edge, crate::ConnectionDirection::West);
+        assert_eq!(
+            world.transition_entry(
+                &start,
+                crate::ConnectionDirection::East,
+                &cave,
+                entry_edge,
+                (165.0, 40.0),
+                8.0
+            ),
+            (168.0, 56.0)
+        );
+        // Connections lead back the other way, and offsets beyond the entry
+        // edge are clamped
+        assert_eq!(
+            world.exit_through(cave.id, crate::ConnectionDirection::West),
+            Some((start.id, crate::ConnectionDirection::East))
+        );
+        assert_eq!(
+            world.transition_entry(
+                &start,
+                crate::ConnectionDirection::East,
+                &cave,
+                entry_edge,
+                (165.0, 79.0),
+                8.0
+            ),
+            (168.0, 72.0)
+        );
+    }
 }
@@ -1,6 +1,7 @@
 //! Tileset configuration with multi-image support
 
 use crate::collision::{CollisionData, CollisionShape, OneWayDirection};
+use crate::Value;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -17,6 +18,9 @@ pub struct TileProperties {
     /// Animation speed in frames per second
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub animation_speed: Option<f32>,
+    /// Per-frame durations in milliseconds. None = every frame uses `animation_speed`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub animation_durations: Option<Vec<u32>>,
     /// Custom user-defined properties
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub custom: HashMap<String, serde_json::Value>,
@@ -34,6 +38,9 @@ pub struct TileProperties {
     pub origin_y: Option<u32>,
 }
 
+/// Default animation speed in frames per second when none is set
+pub const DEFAULT_TILE_ANIMATION_FPS: f32 = 10.0;
+
 /// Default value of 1 for grid dimensions
 fn default_one() -> u32 {
     1
@@ -44,6 +51,16 @@ fn is_one(val: &u32) -> bool {
     *val == 1
 }
 
+/// Check if value equals 0 (for skipping serialization)
+fn is_zero(val: &u32) -> bool {
+    *val == 0
+}
+
+/// Check if an offset is [0, 0] (for skipping serialization)
+fn is_zero_offset(val: &[i32; 2]) -> bool {
+    *val == [0, 0]
+}
+
 /// Deserialize collision data with backward compatibility for old bool format
 fn deserialize_collision<'de, D>(deserializer: D) -> Result<CollisionData, D::Error>
 where
@@ -122,6 +139,44 @@ impl TileProperties {
         self
     }
 
+    /// Set per-frame durations (milliseconds) for this tile's animation
+    pub fn with_animation_durations(mut self, durations: Vec<u32>) -> Self {
+        self.animation_durations = Some(durations);
+        self
+    }
+
+    /// Get the duration of each animation frame in seconds.
+    ///
+    /// Frames without an explicit duration fall back to `1 / animation_speed`.
+    /// Returns an empty list if the tile has no animation frames.
+    pub fn animation_frame_durations(&self) -> Vec<f32> {
+        let Some(frames) = &self.animation_frames else {
+            return Vec::new();
+        };
+        let speed = self
+            .animation_speed
+            .filter(|s| *s > 0.0)
+            .unwrap_or(DEFAULT_TILE_ANIMATION_FPS);
+        let fallback = 1.0 / speed;
+        (0..frames.len())
+            .map(|i| {
+                self.animation_durations
+                    .as_ref()
+                    .and_then(|d| d.get(i))
+                    .filter(|ms| **ms > 0)
+                    .map(|ms| *ms as f32 / 1000.0)
+                    .unwrap_or(fallback)
+            })
+            .collect()
+    }
+
+    /// Get the tile index shown at `elapsed` seconds into a looping animation
+    pub fn animation_frame_at(&self, elapsed: f32) -> Option<u32> {
+        let frames = self.animation_frames.as_ref()?;
+        let durations = self.animation_frame_durations();
+        animation_frame_index(&durations, elapsed).and_then(|i| frames.get(i).copied())
+    }
+
     /// Set a custom property
     pub fn with_custom(mut self, key: String, value: serde_json::Value) -> Self {
         self.custom.insert(key, value);
@@ -137,6 +192,7 @@ impl TileProperties {
     pub fn is_empty(&self) -> bool {
         self.collision.is_empty()
             && self.animation_frames.is_none()
+            && self.animation_durations.is_none()
             && self.custom.is_empty()
             && self.grid_width == 1
             && self.grid_height == 1
@@ -177,6 +233,60 @@ impl TileProperties {
     }
 }
 
+/// Find which frame of a looping animation is active after `elapsed` seconds.
+///
+/// `durations` holds each frame's duration in seconds. Returns None if there
+/// are no frames or the total duration is zero.
+pub fn animation_frame_index(durations: &[f32], elapsed: f32) -> Option<usize> {
+    let total: f32 = durations.iter().sum();
+    if durations.is_empty() || total <= 0.0 {
+        return None;
+    }
+    let mut t = elapsed.rem_euclid(total);
+    for (i, duration) in durations.iter().enumerate() {
+        if t < *duration {
+            return Some(i);
+        }
+        t -= duration;
+    }
+    Some(durations.len() - 1)
+}
+
+/// A tile animation resolved to the tiles of one tileset image
+///
+/// Built with [`Tileset::tile_animation`] for renderers that draw one image
+/// per tilemap.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileAnimation {
+    /// Frame texture indices, local to the image
+    pub frames: Vec<u32>,
+    /// Duration of each frame in seconds
+    pub durations: Vec<f32>,
+}
+
+impl TileAnimation {
+    /// Get the texture index to display after `elapsed` seconds
+    pub fn frame_at(&self, elapsed: f32) -> Option<u32> {
+        animation_frame_index(&self.durations, elapsed).and_then(|i| self.frames.get(i).copied())
+    }
+}
+
+/// How a tileset's images map to tiles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TilesetKind {
+    /// Each image is an atlas sliced into a grid of equally sized tiles
+    #[default]
+    Atlas,
+    /// Each image is a single tile with its own dimensions (props, trees, furniture)
+    Collection,
+}
+
+impl TilesetKind {
+    pub fn is_atlas(&self) -> bool {
+        *self == TilesetKind::Atlas
+    }
+}
+
 /// A single image source within a tileset
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TilesetImage {
@@ -186,6 +296,12 @@ pub struct TilesetImage {
     pub path: String,
     pub columns: u32,
     pub rows: u32,
+    /// Image width in pixels (0 = unknown until the image is loaded)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub width: u32,
+    /// Image height in pixels (0 = unknown until the image is loaded)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub height: u32,
 }
 
 impl TilesetImage {
@@ -197,22 +313,246 @@ impl TilesetImage {
             path,
             columns,
             rows,
+            width: 0,
+            height: 0,
         }
     }
 
+    /// Create an image that holds exactly one tile (for collection tilesets)
+    pub fn single_tile(name: String, path: String, width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            ..Self::new(name, path, 1, 1)
+        }
+    }
+
+    /// Image (width, height) in pixels, if known
+    pub fn pixel_size(&self) -> Option<(u32, u32)> {
+        (self.width > 0 && self.height > 0).then_some((self.width, self.height))
+    }
+
     /// Total number of tiles in this image
     pub fn tile_count(&self) -> u32 {
         self.columns * self.rows
     }
 }
 
+/// Pixel layout of tiles within a tileset image
+///
+/// Tiles start `margin` pixels from the top-left of the image and are
+/// separated by `spacing` pixels. In collection tilesets every image is a
+/// single tile covering the whole image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileGridLayout {
+    pub tile_width: u32,
+    pub tile_height: u32,
+    pub margin: u32,
+    pub spacing: u32,
+    pub collection: bool,
+}
+
+impl TileGridLayout {
+    /// Compute the number of (columns, rows) of tiles in an image of the given size
+    pub fn grid_for_image_size(&self, image_width: u32, image_height: u32) -> (u32, u32) {
+        if self.collection {
+            return (1, 1);
+        }
+        let count = |size: u32, tile: u32| {
+            let usable = size.saturating_sub(2 * self.margin) + self.spacing;
+            usable / (tile.max(1) + self.spacing)
+        };
+        (
+            count(image_width, self.tile_width),
+            count(image_height, self.tile_height),
+        )
+    }
+
+    /// Get the (width, height) in pixels of a single tile of `image`
+    ///
+    /// This is the image's own size for collection tiles (once known).
+    pub fn tile_pixel_size(&self, image: &TilesetImage) -> (u32, u32) {
+        match image.pixel_size() {
+            Some(size) if self.collection => size,
+            _ => (self.tile_width, self.tile_height),
+        }
+    }
+
+    /// Get the pixel rect `[x, y, width, height]` of a tile within its image.
+    ///
+    /// `grid_size` is the tile's size in cells (1x1 for regular tiles); multi-cell
+    /// tiles span the spacing between their cells.
+    pub fn tile_pixel_rect(
+        &self,
+        image: &TilesetImage,
+        local_index: u32,
+        grid_size: (u32, u32),
+    ) -> Option<[u32; 4]> {
+        if image.columns == 0 || local_index >= image.tile_count() {
+            return None;
+        }
+        if self.collection {
+            let (width, height) = image.pixel_size()?;
+            return Some([0, 0, width, height]);
+        }
+        let (grid_width, grid_height) = (grid_size.0.max(1), grid_size.1.max(1));
+        let col = local_index % image.columns;
+        let row = local_index / image.columns;
+        Some([
+            self.margin + col * (self.tile_width + self.spacing),
+            self.margin + row * (self.tile_height + self.spacing),
+            grid_width * self.tile_width + (grid_width - 1) * self.spacing,
+            grid_height * self.tile_height + (grid_height - 1) * self.spacing,
+        ])
+    }
+
+    /// Get the normalized UV rect `[min_x, min_y, max_x, max_y]` of a tile,
+    /// given the pixel size of its image
+    pub fn tile_uv_rect(
+        &self,
+        image: &TilesetImage,
+        local_index: u32,
+        grid_size: (u32, u32),
+        image_width: f32,
+        image_height: f32,
+    ) -> Option<[f32; 4]> {
+        if image_width <= 0.0 || image_height <= 0.0 {
+            return None;
+        }
+        if self.collection {
+            return (image.columns > 0 && local_index < image.tile_count())
+                .then_some([0.0, 0.0, 1.0, 1.0]);
+        }
+        let [x, y, w, h] = self.tile_pixel_rect(image, local_index, grid_size)?;
+        Some([
+            x as f32 / image_width,
+            y as f32 / image_height,
+            (x + w) as f32 / image_width,
+            (y + h) as f32 / image_height,
+        ])
+    }
+
+    /// Size of the atlas bevy_ecs_tilemap expects for `columns` x `rows` tiles
+    ///
+    /// bevy_ecs_tilemap counts an atlas's columns as
+    /// `round((width - spacing) / (tile + spacing))`, which only works when the
+    /// margin equals the spacing and no pixels are left over at the right or
+    /// bottom.
+    pub fn atlas_size(&self, columns: u32, rows: u32) -> (u32, u32) {
+        (
+            self.spacing + columns * (self.tile_width + self.spacing),
+            self.spacing + rows * (self.tile_height + self.spacing),
+        )
+    }
+
+    /// Check if an image of this size must be copied with
+    /// [`TileGridLayout::atlas_pixels`] before bevy_ecs_tilemap can use it
+    pub fn needs_atlas(&self, image_width: u32, image_height: u32) -> bool {
+        if self.collection {
+            return false;
+        }
+        let (columns, rows) = self.grid_for_image_size(image_width, image_height);
+        self.margin != self.spacing || self.atlas_size(columns, rows) != (image_width, image_height)
+    }
+
+    /// Copy an image's tiles into the atlas layout bevy_ecs_tilemap expects
+    ///
+    /// Returns the pixels and the (width, height) from
+    /// [`TileGridLayout::atlas_size`]. The first tile starts `spacing` pixels
+    /// from the top-left; pixels outside the source image are transparent.
+    pub fn atlas_pixels(
+        &self,
+        data: &[u8],
+        image_width: u32,
+        image_height: u32,
+        bytes_per_pixel: usize,
+    ) -> (Vec<u8>, u32, u32) {
+        let (columns, rows) = self.grid_for_image_size(image_width, image_height);
+        let (width, height) = self.atlas_size(columns, rows);
+        let shift = self.margin as i64 - self.spacing as i64;
+        let src_row_bytes = image_width as usize * bytes_per_pixel;
+        let row_bytes = width as usize * bytes_per_pixel;
+        let mut out = vec![0u8; row_bytes * height as usize];
+
+        // Destination columns whose source pixel is inside the image
+        let first_x = (-shift).clamp(0, width as i64);
+        let end_x = (image_width as i64 - shift).clamp(first_x, width as i64);
+        for y in 0..height as i64 {
+            let src_y = y + shift;
+            if src_y < 0 || src_y >= image_height as i64 || first_x == end_x {
+                continue;
+            }
+            let src = src_y as usize * src_row_bytes + (first_x + shift) as usize * bytes_per_pixel;
+            let dst = y as usize * row_bytes + first_x as usize * bytes_per_pixel;
+            let len = (end_x - first_x) as usize * bytes_per_pixel;
+            if src + len <= data.len() {
+                out[dst..dst + len].copy_from_slice(&data[src..src + len]);
+            }
+        }
+        (out, width, height)
+    }
+}
+
+/// A reference to a tile in a tileset, as stored in `tile` properties
+///
+/// Stored as `{"tileset": "<tileset id>", "tile": <virtual tile index>}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct TileRef {
+    pub tileset: Uuid,
+    pub tile: u32,
+}
+
+impl TileRef {
+    pub fn new(tileset: Uuid, tile: u32) -> Self {
+        Self { tileset, tile }
+    }
+
+    /// Read a tile reference from a property value
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let obj = value.as_object()?;
+        Some(Self {
+            tileset: obj.get("tileset")?.as_string()?.parse().ok()?,
+            tile: u32::try_from(obj.get("tile")?.as_int()?).ok()?,
+        })
+    }
+
+    /// Convert to a property value
+    pub fn to_value(&self) -> Value {
+        Value::Object(HashMap::from([
+            (
+                "tileset".to_string(),
+                Value::String(self.tileset.to_string()),
+            ),
+            ("tile".to_string(), Value::Int(self.tile as i64)),
+        ]))
+    }
+}
+
 /// Tileset configuration - can contain multiple images (Godot-style)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tileset {
     pub id: Uuid,
     pub name: String,
-    /// Tile size in pixels (assumes square tiles)
+    /// Whether images are sliced atlases or one image per tile
+    #[serde(default, skip_serializing_if = "TilesetKind::is_atlas")]
+    pub kind: TilesetKind,
+    /// Square tile size in pixels (used when `tile_width`/`tile_height` are unset)
     pub tile_size: u32,
+    /// Tile width in pixels. 0 = use `tile_size`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub tile_width: u32,
+    /// Tile height in pixels. 0 = use `tile_size`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub tile_height: u32,
+    /// Pixels between the image edges and the first row/column of tiles
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub margin: u32,
+    /// Pixels between adjacent tiles in the image
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub spacing: u32,
+    /// Pixel offset [x, y] applied when drawing tiles (x right, y down)
+    #[serde(default, skip_serializing_if = "is_zero_offset")]
+    pub draw_offset: [i32; 2],
     /// Multiple image sources
     #[serde(default)]
     pub images: Vec<TilesetImage>,
@@ -237,7 +577,13 @@ impl Tileset {
         Self {
             id: Uuid::new_v4(),
             name,
+            kind: TilesetKind::Atlas,
             tile_size,
+            tile_width: tile_size,
+            tile_height: tile_size,
+            margin: 0,
+            spacing: 0,
+            draw_offset: [0, 0],
             images: vec![image],
             tile_properties: HashMap::new(),
             path: Some(path),
@@ -251,7 +597,13 @@ impl Tileset {
         Self {
             id: Uuid::new_v4(),
             name,
+            kind: TilesetKind::Atlas,
             tile_size,
+            tile_width: tile_size,
+            tile_height: tile_size,
+            margin: 0,
+            spacing: 0,
+            draw_offset: [0, 0],
             images: Vec::new(),
             tile_properties: HashMap::new(),
             path: None,
@@ -260,11 +612,169 @@ impl Tileset {
         }
     }
 
+    /// Create a new empty collection tileset, where each image is one tile.
+    ///
+    /// `tile_size` is the grid cell size; tile images may be any size and
+    /// cover as many cells as they need.
+    pub fn new_collection(name: String, tile_size: u32) -> Self {
+        Self {
+            kind: TilesetKind::Collection,
+            ..Self::new_empty(name, tile_size)
+        }
+    }
+
+    /// Check if this is a collection tileset (one image per tile)
+    pub fn is_collection(&self) -> bool {
+        self.kind == TilesetKind::Collection
+    }
+
+    /// Set non-square tile dimensions in pixels
+    pub fn with_tile_dimensions(mut self, width: u32, height: u32) -> Self {
+        self.set_tile_dimensions(width, height);
+        self
+    }
+
+    /// Set the image margin and the spacing between tiles in pixels
+    pub fn with_margin_spacing(mut self, margin: u32, spacing: u32) -> Self {
+        self.margin = margin;
+        self.spacing = spacing;
+        self
+    }
+
+    /// Set the pixel offset applied when drawing tiles
+    pub fn with_draw_offset(mut self, x: i32, y: i32) -> Self {
+        self.draw_offset = [x, y];
+        self
+    }
+
+    /// Get the tile (width, height) in pixels
+    pub fn tile_dimensions(&self) -> (u32, u32) {
+        let width = if self.tile_width > 0 {
+            self.tile_width
+        } else {
+            self.tile_size
+        };
+        let height = if self.tile_height > 0 {
+            self.tile_height
+        } else {
+            self.tile_size
+        };
+        (width.max(1), height.max(1))
+    }
+
+    /// Set the tile dimensions in pixels (also updates the legacy `tile_size`)
+    pub fn set_tile_dimensions(&mut self, width: u32, height: u32) {
+        self.tile_width = width.max(1);
+        self.tile_height = height.max(1);
+        self.tile_size = self.tile_width;
+    }
+
+    /// Check if tiles are square
+    pub fn has_square_tiles(&self) -> bool {
+        let (width, height) = self.tile_dimensions();
+        width == height
+    }
+
+    /// Get the pixel layout of tiles in this tileset's images
+    pub fn grid_layout(&self) -> TileGridLayout {
+        let (tile_width, tile_height) = self.tile_dimensions();
+        TileGridLayout {
+            tile_width,
+            tile_height,
+            margin: self.margin,
+            spacing: self.spacing,
+            collection: self.is_collection(),
+        }
+    }
+
+    /// Compute the number of (columns, rows) of tiles in an image of the given size,
+    /// taking margin and spacing into account
+    pub fn grid_for_image_size(&self, image_width: u32, image_height: u32) -> (u32, u32) {
+        self.grid_layout()
+            .grid_for_image_size(image_width, image_height)
+    }
+
+    /// Get the pixel rect `[x, y, width, height]` of a tile within its image.
+    ///
+    /// See [`TileGridLayout::tile_pixel_rect`].
+    pub fn tile_pixel_rect(
+        &self,
+        image: &TilesetImage,
+        local_index: u32,
+        grid_size: (u32, u32),
+    ) -> Option<[u32; 4]> {
+        self.grid_layout()
+            .tile_pixel_rect(image, local_index, grid_size)
+    }
+
+    /// Get the normalized UV rect `[min_x, min_y, max_x, max_y]` of a tile,
+    /// given the pixel size of its image
+    pub fn tile_uv_rect(
+        &self,
+        image: &TilesetImage,
+        local_index: u32,
+        grid_size: (u32, u32),
+        image_width: f32,
+        image_height: f32,
+    ) -> Option<[f32; 4]> {
+        self.grid_layout()
+            .tile_uv_rect(image, local_index, grid_size, image_width, image_height)
+    }
+
     /// Get properties for a tile (returns default if not set)
     pub fn get_tile_properties(&self, tile_index: u32) -> Option<&TileProperties> {
         self.tile_properties.get(&tile_index)
     }
 
+    /// Get a tile's animation with frames local to `image_index`
+    ///
+    /// Frames outside the tileset or in another image are skipped, see
+    /// [`Tileset::invalid_animation_frames`]. Returns None if fewer than two
+    /// frames remain.
+    pub fn tile_animation(&self, tile_index: u32, image_index: usize) -> Option<TileAnimation> {
+        let props = self.get_tile_properties(tile_index)?;
+        if !props.has_animation() {
+            return None;
+        }
+        let (frames, durations): (Vec<u32>, Vec<f32>) = props
+            .animation_frames
+            .as_ref()?
+            .iter()
+            .zip(props.animation_frame_durations())
+            .filter_map(|(frame, duration)| match self.virtual_to_local(*frame) {
+                Some((image, local)) if image == image_index => Some((local, duration)),
+                _ => None,
+            })
+            .unzip();
+        (frames.len() > 1).then_some(TileAnimation { frames, durations })
+    }
+
+    /// Animation frames that can't be played, as sorted (tile, frame) pairs
+    ///
+    /// A frame can't be played if it is outside the tileset or in a different
+    /// image than the animated tile.
+    pub fn invalid_animation_frames(&self) -> Vec<(u32, u32)> {
+        let mut invalid: Vec<(u32, u32)> = self
+            .tile_properties
+            .iter()
+            .filter(|(_, props)| props.has_animation())
+            .flat_map(|(tile, props)| {
+                let tile_image = self.virtual_to_local(*tile).map(|(image, _)| image);
+                props
+                    .animation_frames
+                    .iter()
+                    .flatten()
+                    .filter(move |frame| {
+                        let frame_image = self.virtual_to_local(**frame).map(|(image, _)| image);
+                        frame_image.is_none() || frame_image != tile_image
+                    })
+                    .map(move |frame| (*tile, *frame))
+            })
+            .collect();
+        invalid.sort_unstable();
+        invalid
+    }
+
     /// Get mutable properties for a tile, creating default if not exists
     pub fn get_tile_properties_mut(&mut self, tile_index: u32) -> &mut TileProperties {
         self.tile_properties.entry(tile_index).or_default()
@@ -342,6 +852,27 @@ impl Tileset {
         }
     }
 
+    /// Set friction and restitution for a tile collision
+    pub fn set_tile_material(&mut self, tile_index: u32, friction: f32, restitution: f32) {
+        let props = self.get_tile_properties_mut(tile_index);
+        props.collision.friction = friction;
+        props.collision.restitution = restitution;
+        // Clean up if properties are now empty
+        if props.is_empty() {
+            self.tile_properties.remove(&tile_index);
+        }
+    }
+
+    /// Set whether a tile collision is a sensor
+    pub fn set_tile_sensor(&mut self, tile_index: u32, is_sensor: bool) {
+        let props = self.get_tile_properties_mut(tile_index);
+        props.collision.is_sensor = is_sensor;
+        // Clean up if properties are now empty
+        if props.is_empty() {
+            self.tile_properties.remove(&tile_index);
+        }
+    }
+
     /// Migrate legacy single-image format to multi-image format
     pub fn migrate_to_multi_image(&mut self) {
         if self.images.is_empty() {
@@ -361,6 +892,14 @@ impl Tileset {
         id
     }
 
+    /// Add a single-tile image to a collection tileset and return its virtual tile index
+    pub fn add_tile_image(&mut self, name: String, path: String, width: u32, height: u32) -> u32 {
+        let index = self.total_tile_count();
+        self.images
+            .push(TilesetImage::single_tile(name, path, width, height));
+        index
+    }
+
     /// Remove an image by ID
     pub fn remove_image(&mut self, id: Uuid) -> bool {
         if let Some(pos) = self.images.iter().position(|img| img.id == id) {
@@ -470,20 +1009,49 @@ impl Tileset {
     }
 
     /// Get the grid size for a tile (width, height in cells)
-    /// Returns (1, 1) for tiles without multi-cell properties
+    ///
+    /// Returns (1, 1) for tiles without multi-cell properties. Collection tiles
+    /// default to the cells covered by their image.
     pub fn get_tile_grid_size(&self, tile_index: u32) -> (u32, u32) {
-        self.tile_properties
-            .get(&tile_index)
-            .map(|p| p.grid_size())
-            .unwrap_or((1, 1))
+        match self.tile_properties.get(&tile_index) {
+            Some(props) if props.is_multi_cell() => props.grid_size(),
+            _ if self.is_collection() => self.image_footprint(tile_index).unwrap_or((1, 1)),
+            _ => (1, 1),
+        }
     }
 
     /// Check if a tile is multi-cell (spans more than 1x1 grid cells)
     pub fn is_multi_cell_tile(&self, tile_index: u32) -> bool {
-        self.tile_properties
-            .get(&tile_index)
-            .map(|p| p.is_multi_cell())
-            .unwrap_or(false)
+        let (width, height) = self.get_tile_grid_size(tile_index);
+        width > 1 || height > 1
+    }
+
+    /// Get the (width, height) in pixels a tile is drawn at.
+    ///
+    /// Collection tiles use their image's own size; atlas tiles cover their grid cells.
+    pub fn tile_draw_size(&self, tile_index: u32) -> (u32, u32) {
+        if self.is_collection() {
+            if let Some(size) = self
+                .get_tile_image_info(tile_index)
+                .and_then(|(image, _)| image.pixel_size())
+            {
+                return size;
+            }
+        }
+        let (tile_width, tile_height) = self.tile_dimensions();
+        let (grid_width, grid_height) = self.get_tile_grid_size(tile_index);
+        (grid_width * tile_width, grid_height * tile_height)
+    }
+
+    /// Cells covered by a collection tile's image, rounded up
+    fn image_footprint(&self, tile_index: u32) -> Option<(u32, u32)> {
+        let (image, _) = self.get_tile_image_info(tile_index)?;
+        let (width, height) = image.pixel_size()?;
+        let (tile_width, tile_height) = self.tile_dimensions();
+        Some((
+            width.div_ceil(tile_width).max(1),
+            height.div_ceil(tile_height).max(1),
+        ))
     }
 
     /// Set the grid size for a tile
@@ -532,6 +1100,13 @@ mod tests {
         assert_eq!(tileset.virtual_to_local(20), None);
     }
 
+    #[test]
+    fn test_tile_ref_value() {
+        let tile = TileRef::new(Uuid::new_v4(), 7);
+        assert_eq!(TileRef::from_value(&tile.to_value()), Some(tile));
+        assert_eq!(TileRef::from_value(&Value::String("7".to_string())), None);
+    }
+
     #[test]
     fn test_local_to_virtual() {
         let mut tileset = Tileset::new_empty("Test".to_string(), 32);
@@ -543,4 +1118,195 @@ mod tests {
         assert_eq!(tileset.local_to_virtual(1, 0), Some(16));
         assert_eq!(tileset.local_to_virtual(1, 3), Some(19));
     }
+
+    #[test]
+    fn test_tile_dimensions_fallback() {
+        let mut tileset = Tileset::new_empty("Test".to_string(), 32);
+        assert_eq!(tileset.tile_dimensions(), (32, 32));
+
+        // Legacy files without tile_width/tile_height fall back to tile_size
+        tileset.tile_width = 0;
+        tileset.tile_height = 0;
+        assert_eq!(tileset.tile_dimensions(), (32, 32));
+
+        tileset.set_tile_dimensions(16, 24);
+        assert_eq!(tileset.tile_dimensions(), (16, 24));
+        assert!(!tileset.has_square_tiles());
+    }
+
+    #[test]
+    fn test_grid_with_margin_and_spacing() {
+        // 4x2 tiles of 16x24 with 2px margin and 1px spacing:
+        // width = 2 + 4*16 + 3*1 + 2 = 71, height = 2 + 2*24 + 1 + 2 = 53
+        let tileset = Tileset::new_empty("Test".to_string(), 16)
+            .with_tile_dimensions(16, 24)
+            .with_margin_spacing(2, 1);
+        assert_eq!(tileset.grid_for_image_size(71, 53), (4, 2));
+
+        let image = TilesetImage::new("Main".to_string(), "a.png".to_string(), 4, 2);
+        assert_eq!(
+            tileset.tile_pixel_rect(&image, 0, (1, 1)),
+            Some([2, 2, 16, 24])
+        );
+        assert_eq!(
+            tileset.tile_pixel_rect(&image, 5, (1, 1)),
+            Some([2 + 17, 2 + 25, 16, 24])
+        );
+        // Multi-cell tiles include the spacing between their cells
+        assert_eq!(
+            tileset.tile_pixel_rect(&image, 0, (2, 1)),
+            Some([2, 2, 33, 24])
+        );
+        assert_eq!(tileset.tile_pixel_rect(&image, 8, (1, 1)), None);
+    }
+
+    #[test]
+    fn test_atlas_pixels() {
+        // 4x2 grid of 16px tiles with an 8px margin and no spacing: 80x48 pixels,
+        // each tile filled with its index + 1 and the margin left empty
+        let layout = TileGridLayout {
+            tile_width: 16,
+            tile_height: 16,
+            margin: 8,
+            spacing: 0,
+            collection: false,
+        };
+        let (width, height) = (80u32, 48u32);
+        let mut data = vec![0u8; (width * height) as usize];
+        for y in 8..40 {
+            for x in 8..72 {
+                data[(y * width + x) as usize] = ((y - 8) / 16 * 4 + (x - 8) / 16 + 1) as u8;
+            }
+        }
+
+        // bevy_ecs_tilemap would count round(80 / 16) = 5 columns here
+        assert!(layout.needs_atlas(width, height));
+        let (atlas, atlas_width, atlas_height) = layout.atlas_pixels(&data, width, height, 1);
+        assert_eq!((atlas_width, atlas_height), (64, 32));
+        assert_eq!(atlas.len(), 64 * 32);
+        // Tile 5 is the second tile of row 1
+        assert_eq!(atlas[16 * 64 + 16], 6);
+        assert_eq!(atlas[31 * 64 + 31], 6);
+        assert_eq!(atlas[31 * 64 + 63], 8);
+
+        // Matching margin and spacing with no leftover pixels is used as is
+        let exact = TileGridLayout {
+            margin: 1,
+            spacing: 1,
+            ..layout
+        };
+        assert_eq!(exact.atlas_size(4, 2), (69, 35));
+        assert!(!exact.needs_atlas(69, 35));
+        // Leftover pixels at the right are cropped
+        assert!(exact.needs_atlas(80, 35));
+        // Padding adds the missing spacing around the tiles
+        let padded = TileGridLayout {
+            margin: 0,
+            spacing: 2,
+            ..layout
+        };
+        let (atlas, atlas_width, atlas_height) = padded.atlas_pixels(&[7; 34 * 16], 34, 16, 1);
+        assert_eq!((atlas_width, atlas_height), (38, 20));
+        assert_eq!(atlas[2 * 38 + 1], 0);
+        assert_eq!(atlas[2 * 38 + 2], 7);
+    }
+
+    #[test]
+    fn test_animation_frame_durations() {
+        let props = TileProperties::new().with_animation(vec![4, 5, 6], 4.0);
+        assert_eq!(props.animation_frame_durations(), vec![0.25, 0.25, 0.25]);
+
+        let props = props.with_animation_durations(vec![100, 0, 500]);
+        assert_eq!(props.animation_frame_durations(), vec![0.1, 0.25, 0.5]);
+    }
+
+    #[test]
+    fn test_animation_frame_at() {
+        let props = TileProperties::new()
+            .with_animation(vec![4, 5, 6], 10.0)
+            .with_animation_durations(vec![100, 200, 300]);
+
+        assert_eq!(props.animation_frame_at(0.0), Some(4));
+        assert_eq!(props.animation_frame_at(0.15), Some(5));
+        assert_eq!(props.animation_frame_at(0.35), Some(6));
+        // Loops after the total duration (0.6s)
+        assert_eq!(props.animation_frame_at(0.65), Some(4));
+
+        assert_eq!(TileProperties::new().animation_frame_at(1.0), None);
+        assert_eq!(animation_frame_index(&[], 1.0), None);
+    }
+
+    #[test]
+    fn test_tile_animation() {
+        // 4x2 tileset: frame 9 is outside it
+        let mut tileset = Tileset::new("Water".to_string(), "water.png".to_string(), 16, 4, 2);
+        tileset.set_tile_properties(
+            1,
+            TileProperties::new()
+                .with_animation(vec![1, 2, 9, 3], 10.0)
+                .with_animation_durations(vec![100, 100, 100, 200]),
+        );
+
+        let animation = tileset.tile_animation(1, 0).unwrap();
+        assert_eq!(animation.frames, vec![1, 2, 3]);
+        assert_eq!(animation.durations, vec![0.1, 0.1, 0.2]);
+        assert_eq!(animation.frame_at(0.25), Some(3));
+        // No frames in other images
+        assert!(tileset.tile_animation(1, 1).is_none());
+        assert!(tileset.tile_animation(0, 0).is_none());
+
+        assert_eq!(tileset.invalid_animation_frames(), vec![(1, 9)]);
+    }
+
+    #[test]
+    fn test_collection_tileset() {
+        let mut tileset = Tileset::new_collection("Props".to_string(), 16);
+        assert!(tileset.is_collection());
+
+        let tree = tileset.add_tile_image("Tree".to_string(), "tree.png".to_string(), 40, 64);
+        let rock = tileset.add_tile_image("Rock".to_string(), "rock.png".to_string(), 16, 16);
+        assert_eq!((tree, rock), (0, 1));
+        assert_eq!(tileset.total_tile_count(), 2);
+        assert_eq!(tileset.virtual_to_local(1), Some((1, 0)));
+
+        // Footprint follows the image size, rounded up to whole cells
+        assert_eq!(tileset.get_tile_grid_size(tree), (3, 4));
+        assert!(tileset.is_multi_cell_tile(tree));
+        assert!(!tileset.is_multi_cell_tile(rock));
+        assert_eq!(tileset.tile_draw_size(tree), (40, 64));
+
+        // Explicit multi-cell size overrides the footprint
+        tileset.set_tile_grid_size(tree, 2, 2);
+        assert_eq!(tileset.get_tile_grid_size(tree), (2, 2));
+        assert_eq!(tileset.tile_draw_size(tree), (40, 64));
+
+        // Each tile covers its whole image
+        let image = &tileset.images[0];
+        assert_eq!(
+            tileset.tile_pixel_rect(image, 0, (2, 2)),
+            Some([0, 0, 40, 64])
+        );
+        assert_eq!(
+            tileset.tile_uv_rect(image, 0, (1, 1), 40.0, 64.0),
+            Some([0.0, 0.0, 1.0, 1.0])
+        );
+        assert_eq!(tileset.grid_for_image_size(40, 64), (1, 1));
+    }
+
+    #[test]
+    fn test_collection_tileset_serde() {
+        let mut tileset = Tileset::new_collection("Props".to_string(), 16);
+        tileset.add_tile_image("Tree".to_string(), "tree.png".to_string(), 40, 64);
+
+        let json = serde_json::to_string(&tileset).unwrap();
+        let loaded: Tileset = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.kind, TilesetKind::Collection);
+        assert_eq!(loaded.images[0].pixel_size(), Some((40, 64)));
+
+        // Atlas tilesets don't serialize the kind
+        let atlas = Tileset::new("Atlas".to_string(), "a.png".to_string(), 16, 4, 4);
+        let json = serde_json::to_string(&atlas).unwrap();
+        assert!(!json.contains("\"kind\""));
+        assert!(!json.contains("\"width\""));
+    }
 }
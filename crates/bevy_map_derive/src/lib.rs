@@ -1,13 +1,23 @@
 //! Derive macros for bevy_map_editor entity spawning
 //!
 //! This crate provides the `#[derive(MapEntity)]` macro for automatically
-//! implementing entity spawning from map data.
+//! implementing entity spawning from map data, and writing component state
+//! back to entity properties. `#[derive(MapProperty)]` lets enums and
+//! embedded structs be used as property fields, and `#[derive(MapDataType)]`
+//! reads data instances (items, enemies, loot tables) into typed structs.
 //!
 //! # Example
 //!
 //! ```rust,ignore
 //! use bevy::prelude::*;
-//! use bevy_map_derive::MapEntity;
+//! use bevy_map_derive::{MapEntity, MapProperty};
+//!
+//! #[derive(MapProperty, Default)]
+//! pub enum NpcType {
+//!     #[default]
+//!     QuestGiver,
+//!     Vendor,
+//! }
 //!
 //! #[derive(Component, MapEntity)]
 //! #[map_entity(type_name = "NPC")]
@@ -16,6 +26,8 @@
 //!     pub name: String,
 //!     #[map_prop(default = 100)]
 //!     pub health: i32,
+//!     #[map_prop]
+//!     pub npc_type: NpcType,
 //!     #[map_sprite("sprite")]  // Optional: receives sprite handle when loaded
 //!     pub sprite_handle: Option<Handle<Image>>,
 //! }
@@ -25,7 +37,8 @@ use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, ToTokens};
 use syn::{
-    parse_macro_input, Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Ident, Lit, Meta, Type,
+    parse_macro_input, Attribute, Data, DeriveInput, Expr, ExprLit, Fields, GenericArgument, Ident,
+    Lit, Meta, PathArguments, Type,
 };
 
 /// Derive macro for creating map entities that can be spawned from EntityInstance data
@@ -33,14 +46,38 @@ use syn::{
 /// # Container Attributes
 ///
 /// - `#[map_entity(type_name = "TypeName")]` - The entity type name as used in the map editor
+/// - `#[map_entity(placeable = false)]` - Describe the type as not placeable in the editor
+///   schema, e.g. for types only spawned by game code (defaults to `true`)
 ///
 /// # Field Attributes
 ///
 /// - `#[map_prop]` - Mark a field as coming from entity properties
 /// - `#[map_prop(name = "property_name")]` - Use a different property name than the field name
 /// - `#[map_prop(default = value)]` - Default value if property is missing
+/// - `#[map_prop(min = value, max = value)]` - Numeric range, recorded in the schema
+/// - `#[map_prop(parse)]` - Read the field with `FromStr` and write it with `Display`
+/// - `#[map_prop(asset)]` - Describe a `String` field as an asset path in the schema
+/// - `#[map_prop(extensions = "ogg, wav")]` - Asset file extensions accepted in the editor
 /// - `#[map_sprite]` - Mark a field to receive sprite handle injection (field must be `Option<Handle<Image>>`)
 /// - `#[map_sprite("property_name")]` - Use a different property name than the field name
+///
+/// `#[map_prop]` fields are converted with the `MapProperty` trait, which covers
+/// primitives, `Vec<T>`, `Option<T>`, `Vec2` (Point), `Color` (hex strings) and types
+/// deriving `MapProperty`. Unsupported field types fail to compile; custom types that
+/// were read with `FromStr` by earlier versions now need `#[map_prop(parse)]`. Values
+/// that cannot be converted at runtime fall back to the default and log a warning.
+///
+/// A `#[map_prop]` field of type `Handle<DialogueTree>` is filled in after spawning
+/// from the map's dialogue with the ID stored in the property. Any other `Handle<T>`
+/// field is an asset property: the asset at the stored path is loaded with the
+/// `AssetServer` after spawning, and files that fail to load are logged.
+///
+/// Fields marked with `#[map_prop]` are also written back by the generated
+/// `to_properties`. Dialogue handles are not written back; asset handles are
+/// written back as their path.
+///
+/// The generated `type_def` describes the entity type for the editor schema, with a
+/// property for every `#[map_prop]` field including its type, default and range.
 #[proc_macro_derive(MapEntity, attributes(map_entity, map_prop, map_sprite))]
 pub fn derive_map_entity(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -50,11 +87,77 @@ pub fn derive_map_entity(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Derive macro for types usable as `#[map_prop]` fields
+///
+/// - Enums with unit variants convert to and from the variant name, matching schema enums
+/// - Enums marked `#[map_prop(flags)]` implement `MapFlag` instead, and are used as
+///   `MapFlags<T>` fields: bitsets stored as arrays of variant names
+/// - Structs with named fields convert to and from objects, matching embedded types
+///
+/// # Attributes
+///
+/// - `#[map_prop(name = "name")]` - On the type, use a different schema enum or
+///   embedded type name; on a variant or field, use a different name in map data
+/// - `#[map_prop(flags)]` - On an enum, describe it as a flag enum
+/// - `#[map_prop(value = 4)]` - On a variant, its integer value, or its bit mask in a
+///   flag enum (defaults to the index, or `1 << index` for flags)
+/// - `#[map_prop(default = value)]` - On a field, default value if the key is missing
+/// - `#[map_prop(min = value, max = value)]` - On a field, numeric range for the schema
+#[proc_macro_derive(MapProperty, attributes(map_prop))]
+pub fn derive_map_property(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match impl_map_property(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derive macro for reading data instances into typed structs
+///
+/// Data types are the non-placeable schema types (items, enemies, loot tables).
+/// Their instances are loaded into the `MapData` resource, which reads them with
+/// `MapData::get_as` and `MapData::all`. Entity fields of type `DataRef<T>` are
+/// `Ref` properties that resolve to a `T`.
+///
+/// # Container Attributes
+///
+/// - `#[map_data(type_name = "TypeName")]` - The data type name as used in the map
+///   editor (defaults to the struct name)
+///
+/// # Field Attributes
+///
+/// Every field is read with the `MapProperty` trait, like an embedded type.
+///
+/// - `#[map_prop(name = "property_name")]` - Use a different property name than the field name
+/// - `#[map_prop(default = value)]` - Default value if property is missing
+/// - `#[map_prop(parse)]` - Read the field with `FromStr`
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[derive(MapDataType)]
+/// #[map_data(type_name = "Item")]
+/// pub struct Item {
+///     pub name: String,
+///     #[map_prop(default = 1)]
+///     pub value: i32,
+/// }
+/// ```
+#[proc_macro_derive(MapDataType, attributes(map_data, map_prop))]
+pub fn derive_map_data_type(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match impl_map_data_type(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 fn impl_map_entity(input: &DeriveInput) -> syn::Result<TokenStream2> {
     let name = &input.ident;
 
     // Parse container attributes
     let type_name = parse_type_name(&input.attrs)?;
+    let placeable = find_placeable(&input.attrs)?.unwrap_or(true);
 
     // Get fields
     let fields = match &input.data {
@@ -78,6 +181,19 @@ fn impl_map_entity(input: &DeriveInput) -> syn::Result<TokenStream2> {
     // Collect sprite fields: (field_name, property_name)
     let mut sprite_fields: Vec<(Ident, String)> = Vec::new();
 
+    // Collect dialogue handle fields: (field_name, property_name)
+    let mut dialogue_fields: Vec<(Ident, String)> = Vec::new();
+
+    // Collect asset handle fields: (field_name, property_name)
+    let mut asset_fields: Vec<(Ident, String)> = Vec::new();
+
+    // Collect property writes for to_properties
+    let mut property_writes: Vec<TokenStream2> = Vec::new();
+
+    // Collect schema property definitions for type_def
+    let mut property_defs: Vec<TokenStream2> = Vec::new();
+    let mut describes_fields = false;
+
     // Generate field initialization code
     let field_inits: Vec<TokenStream2> = fields
         .iter()
@@ -106,8 +222,53 @@ fn impl_map_entity(input: &DeriveInput) -> syn::Result<TokenStream2> {
                 .find(|attr| attr.path().is_ident("map_prop"));
 
             if let Some(attr) = map_prop_attr {
-                let (prop_name, default_value) = parse_map_prop_attr(attr, field_name)?;
-                generate_field_init(field_name, field_type, &prop_name, default_value)
+                let prop = parse_map_prop_attr(attr, field_name)?;
+
+                if let Some(asset) = handle_asset_name(field_type) {
+                    let prop_name = &prop.name;
+                    if asset == "DialogueTree" {
+                        // Dialogue handles are injected after spawning
+                        property_defs.push(quote! {
+                            bevy_map::schema::PropertyDef::new(#prop_name, bevy_map::schema::PropType::Dialogue)
+                        });
+                        dialogue_fields.push((field_name.clone(), prop.name));
+                    } else {
+                        // Other handles are loaded from the asset path after spawning
+                        let extensions = &prop.extensions;
+                        property_defs.push(quote! {
+                            {
+                                let mut property = bevy_map::schema::PropertyDef::new(
+                                    #prop_name,
+                                    bevy_map::schema::PropType::Asset,
+                                );
+                                property.extensions = vec![#(#extensions.to_string()),*];
+                                property
+                            }
+                        });
+                        property_writes.push(quote! {
+                            if let Some(path) = self.#field_name.path() {
+                                properties.insert(
+                                    #prop_name.to_string(),
+                                    bevy_map::core::Value::String(path.to_string()),
+                                );
+                            }
+                        });
+                        asset_fields.push((field_name.clone(), prop.name));
+                    }
+                    return Ok(quote! {
+                        #field_name: Default::default()
+                    });
+                }
+
+                property_writes.push(generate_field_write(field_name, &prop));
+                property_defs.push(generate_property_def(field_type, &prop));
+                describes_fields |= !prop.parse;
+                Ok(generate_field_init(
+                    field_name,
+                    field_type,
+                    &prop,
+                    quote! { bevy_map::runtime::read_map_property },
+                ))
             } else {
                 // Field without #[map_prop] - use Default::default()
                 Ok(quote! {
@@ -159,6 +320,75 @@ fn impl_map_entity(input: &DeriveInput) -> syn::Result<TokenStream2> {
         }
     };
 
+    // Generate dialogue handle injection, only when the entity has dialogue fields
+    let dialogue_impl = if dialogue_fields.is_empty() {
+        quote! {}
+    } else {
+        let prop_names: Vec<&str> = dialogue_fields.iter().map(|(_, p)| p.as_str()).collect();
+        let match_arms: Vec<TokenStream2> = dialogue_fields
+            .iter()
+            .map(|(field_name, prop_name)| {
+                quote! {
+                    #prop_name => { self.#field_name = handle.clone(); }
+                }
+            })
+            .collect();
+        quote! {
+            fn dialogue_properties() -> &'static [&'static str] {
+                &[#(#prop_names),*]
+            }
+
+            fn inject_dialogue_handle(&mut self, property_name: &str, handle: bevy::prelude::Handle<bevy_map::runtime::DialogueTree>) {
+                match property_name {
+                    #(#match_arms)*
+                    _ => {}
+                }
+            }
+        }
+    };
+
+    // Generate asset handle loading, only when the entity has asset fields
+    let asset_impl = if asset_fields.is_empty() {
+        quote! {}
+    } else {
+        let prop_names: Vec<&str> = asset_fields.iter().map(|(_, p)| p.as_str()).collect();
+        let match_arms: Vec<TokenStream2> = asset_fields
+            .iter()
+            .map(|(field_name, prop_name)| {
+                quote! {
+                    #prop_name => {
+                        self.#field_name = asset_server.load(path.to_string());
+                        Some(self.#field_name.clone().untyped())
+                    }
+                }
+            })
+            .collect();
+        quote! {
+            fn asset_properties() -> &'static [&'static str] {
+                &[#(#prop_names),*]
+            }
+
+            fn load_asset_handle(
+                &mut self,
+                property_name: &str,
+                path: &str,
+                asset_server: &bevy::prelude::AssetServer,
+            ) -> Option<bevy::prelude::UntypedHandle> {
+                match property_name {
+                    #(#match_arms)*
+                    _ => None,
+                }
+            }
+        }
+    };
+
+    // Only fields described through MapProperty need the schema
+    let schema_arg = if describes_fields {
+        quote! { schema }
+    } else {
+        quote! { _schema }
+    };
+
     // Use bevy_map paths if available, otherwise fall back to direct crate paths
     // This allows both `bevy_map` umbrella crate users and direct crate users to work
     let expanded = quote! {
@@ -173,22 +403,364 @@ fn impl_map_entity(input: &DeriveInput) -> syn::Result<TokenStream2> {
                 }
             }
 
+            fn to_properties(&self) -> std::collections::HashMap<String, bevy_map::core::Value> {
+                let mut properties = std::collections::HashMap::new();
+                #(#property_writes)*
+                properties
+            }
+
+            fn type_def(#schema_arg: &mut bevy_map::schema::Schema) -> bevy_map::schema::TypeDef {
+                bevy_map::schema::TypeDef {
+                    placeable: #placeable,
+                    properties: vec![#(#property_defs),*],
+                    ..Default::default()
+                }
+            }
+
             #sprite_properties_impl
 
             #inject_sprite_impl
+
+            #dialogue_impl
+
+            #asset_impl
         }
     };
 
     Ok(expanded)
 }
 
+fn impl_map_property(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    // Enums and embedded types are named in the schema by their Rust name,
+    // unless renamed with #[map_prop(name = "...")]
+    let container = match find_map_prop_attr(&input.attrs) {
+        Some(attr) => parse_map_prop_attr(attr, name)?,
+        None => MapPropAttr::new(name.to_string()),
+    };
+    let schema_name = &container.name;
+
+    let (from_value, to_value, describe) = match &input.data {
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "MapProperty cannot be derived for empty enums",
+                ));
+            }
+
+            let mut idents = Vec::new();
+            let mut names = Vec::new();
+            let mut values = Vec::new();
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "MapProperty can only be derived for enums with unit variants",
+                    ));
+                }
+                let attr = match find_map_prop_attr(&variant.attrs) {
+                    Some(attr) => parse_map_prop_attr(attr, &variant.ident)?,
+                    None => MapPropAttr::new(variant.ident.to_string()),
+                };
+                idents.push(&variant.ident);
+                names.push(attr.name);
+                values.push(attr.value);
+            }
+
+            // Explicit values are recorded in the schema; the rest use the default
+            let schema_values: Vec<TokenStream2> = values
+                .iter()
+                .map(|value| match value {
+                    Some(value) => quote! { Some(#value) },
+                    None => quote! { None },
+                })
+                .collect();
+            let describe_enum = |constructor: TokenStream2| {
+                quote! {
+                    property.prop_type = bevy_map::schema::PropType::Enum;
+                    property.enum_type = Some(#schema_name.to_string());
+                    let mut enum_def = bevy_map::schema::EnumDef::#constructor([#(#names),*]);
+                    for (variant, value) in enum_def.variants.iter_mut().zip([#(#schema_values),*]) {
+                        variant.value = value;
+                    }
+                    schema.enums.insert(#schema_name.to_string(), enum_def);
+                }
+            };
+
+            if container.flags {
+                let bits = values.iter().enumerate().map(|(index, value)| match value {
+                    Some(value) => quote! { #value },
+                    None => {
+                        let index = proc_macro2::Literal::usize_unsuffixed(index);
+                        quote! { 1 << #index }
+                    }
+                });
+                let describe = describe_enum(quote! { flags });
+                return Ok(quote! {
+                    impl bevy_map::runtime::MapFlag for #name {
+                        const ALL: &'static [Self] = &[#(Self::#idents),*];
+
+                        fn bits(self) -> u64 {
+                            match self {
+                                #(Self::#idents => #bits,)*
+                            }
+                        }
+
+                        fn name(self) -> &'static str {
+                            match self {
+                                #(Self::#idents => #names,)*
+                            }
+                        }
+
+                        fn describe_flags(
+                            property: &mut bevy_map::schema::PropertyDef,
+                            schema: &mut bevy_map::schema::Schema,
+                        ) {
+                            #describe
+                        }
+                    }
+
+                    impl std::ops::BitOr for #name {
+                        type Output = bevy_map::runtime::MapFlags<Self>;
+
+                        fn bitor(self, rhs: Self) -> Self::Output {
+                            bevy_map::runtime::MapFlags::from(self) | rhs
+                        }
+                    }
+                });
+            }
+
+            // Integer values match the explicit value, or the variant index
+            let int_values = values.iter().enumerate().map(|(index, value)| match value {
+                Some(value) => quote! { #value },
+                None => proc_macro2::Literal::usize_unsuffixed(index).to_token_stream(),
+            });
+            (
+                quote! {
+                    if let bevy_map::core::Value::Int(int) = value {
+                        #(if *int == #int_values {
+                            return Some(Self::#idents);
+                        })*
+                        return None;
+                    }
+                    match value.as_string()? {
+                        #(#names => Some(Self::#idents),)*
+                        _ => None,
+                    }
+                },
+                quote! {
+                    let name = match self {
+                        #(Self::#idents => #names,)*
+                    };
+                    bevy_map::core::Value::String(name.to_string())
+                },
+                describe_enum(quote! { new }),
+            )
+        }
+        Data::Struct(data) => {
+            let Fields::Named(fields) = &data.fields else {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "MapProperty can only be derived for structs with named fields",
+                ));
+            };
+
+            let mut field_inits = Vec::new();
+            let mut field_writes = Vec::new();
+            let mut property_defs = Vec::new();
+            for field in &fields.named {
+                let field_name = field.ident.as_ref().unwrap();
+                let field_type = &field.ty;
+                let prop = match find_map_prop_attr(&field.attrs) {
+                    Some(attr) => parse_map_prop_attr(attr, field_name)?,
+                    None => MapPropAttr::new(field_name.to_string()),
+                };
+                if prop.parse {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        "#[map_prop(parse)] is only supported on MapEntity fields",
+                    ));
+                }
+
+                let prop_name = &prop.name;
+                let default = default_expr(field_type, prop.default.as_ref());
+                field_inits.push(quote! {
+                    #field_name: match object.get(#prop_name) {
+                        Some(value) if !value.is_null() => {
+                            <#field_type as bevy_map::runtime::MapProperty>::from_value(value)?
+                        }
+                        _ => #default,
+                    }
+                });
+                field_writes.push(quote! {
+                    (
+                        #prop_name.to_string(),
+                        bevy_map::runtime::MapProperty::to_value(&self.#field_name),
+                    )
+                });
+                property_defs.push(generate_property_def(field_type, &prop));
+            }
+
+            (
+                quote! {
+                    let object = value.as_object()?;
+                    Some(Self {
+                        #(#field_inits),*
+                    })
+                },
+                quote! {
+                    bevy_map::core::Value::Object(std::collections::HashMap::from([
+                        #(#field_writes),*
+                    ]))
+                },
+                quote! {
+                    property.prop_type = bevy_map::schema::PropType::Embedded;
+                    property.embedded_type = Some(#schema_name.to_string());
+                    // Insert a placeholder first so recursive types terminate
+                    if !schema.embedded_types.contains_key(#schema_name) {
+                        schema
+                            .embedded_types
+                            .insert(#schema_name.to_string(), Default::default());
+                        let type_def = bevy_map::schema::TypeDef {
+                            properties: vec![#(#property_defs),*],
+                            ..Default::default()
+                        };
+                        schema.embedded_types.insert(#schema_name.to_string(), type_def);
+                    }
+                },
+            )
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "MapProperty can only be derived for enums and structs",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl bevy_map::runtime::MapProperty for #name {
+            fn from_value(value: &bevy_map::core::Value) -> Option<Self> {
+                #from_value
+            }
+
+            fn to_value(&self) -> bevy_map::core::Value {
+                #to_value
+            }
+
+            fn describe(
+                property: &mut bevy_map::schema::PropertyDef,
+                schema: &mut bevy_map::schema::Schema,
+            ) {
+                #describe
+            }
+        }
+    })
+}
+
+fn impl_map_data_type(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let type_name = find_type_name(&input.attrs, "map_data")?.unwrap_or_else(|| name.to_string());
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "MapDataType can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "MapDataType can only be derived for structs",
+            ))
+        }
+    };
+
+    let field_inits = fields
+        .iter()
+        .map(|field| {
+            let field_name = field.ident.as_ref().unwrap();
+            let prop = match find_map_prop_attr(&field.attrs) {
+                Some(attr) => parse_map_prop_attr(attr, field_name)?,
+                None => MapPropAttr::new(field_name.to_string()),
+            };
+            Ok(generate_field_init(
+                field_name,
+                &field.ty,
+                &prop,
+                quote! { bevy_map::runtime::read_data_property },
+            ))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        impl bevy_map::runtime::MapDataType for #name {
+            fn type_name() -> &'static str {
+                #type_name
+            }
+
+            fn from_data(instance: &bevy_map::core::DataInstance) -> Self {
+                Self {
+                    #(#field_inits),*
+                }
+            }
+        }
+    })
+}
+
 fn parse_type_name(attrs: &[Attribute]) -> syn::Result<String> {
+    find_type_name(attrs, "map_entity")?.ok_or_else(|| {
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "MapEntity requires #[map_entity(type_name = \"...\")]",
+        )
+    })
+}
+
+/// Find `placeable = true|false` in `#[map_entity(...)]`
+fn find_placeable(attrs: &[Attribute]) -> syn::Result<Option<bool>> {
     for attr in attrs {
         if attr.path().is_ident("map_entity") {
             let meta = attr.meta.require_list()?;
             let nested: syn::punctuated::Punctuated<Meta, syn::Token![,]> =
                 meta.parse_args_with(syn::punctuated::Punctuated::parse_terminated)?;
 
+            for meta in nested {
+                if let Meta::NameValue(nv) = meta {
+                    if nv.path.is_ident("placeable") {
+                        return match &nv.value {
+                            Expr::Lit(ExprLit {
+                                lit: Lit::Bool(lit_bool),
+                                ..
+                            }) => Ok(Some(lit_bool.value)),
+                            value => Err(syn::Error::new_spanned(
+                                value,
+                                "Expected `placeable = true` or `placeable = false`",
+                            )),
+                        };
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Find `type_name = "..."` in a container attribute like `#[map_entity(...)]`
+fn find_type_name(attrs: &[Attribute], attr_name: &str) -> syn::Result<Option<String>> {
+    for attr in attrs {
+        if attr.path().is_ident(attr_name) {
+            let meta = attr.meta.require_list()?;
+            let nested: syn::punctuated::Punctuated<Meta, syn::Token![,]> =
+                meta.parse_args_with(syn::punctuated::Punctuated::parse_terminated)?;
+
             for meta in nested {
                 if let Meta::NameValue(nv) = meta {
                     if nv.path.is_ident("type_name") {
@@ -197,7 +769,7 @@ fn parse_type_name(attrs: &[Attribute]) -> syn::Result<String> {
                             ..
                         }) = &nv.value
                         {
-                            return Ok(lit_str.value());
+                            return Ok(Some(lit_str.value()));
                         }
                     }
                 }
@@ -205,18 +777,47 @@ fn parse_type_name(attrs: &[Attribute]) -> syn::Result<String> {
         }
     }
 
-    Err(syn::Error::new(
-        proc_macro2::Span::call_site(),
-        "MapEntity requires #[map_entity(type_name = \"...\")]",
-    ))
+    Ok(None)
 }
 
-fn parse_map_prop_attr(
-    attr: &Attribute,
-    field_name: &Ident,
-) -> syn::Result<(String, Option<TokenStream2>)> {
-    let mut prop_name = field_name.to_string();
-    let mut default_value = None;
+/// Parsed #[map_prop] attribute
+struct MapPropAttr {
+    name: String,
+    default: Option<TokenStream2>,
+    min: Option<TokenStream2>,
+    max: Option<TokenStream2>,
+    parse: bool,
+    /// Set by `asset` or `extensions = "..."`
+    asset: bool,
+    extensions: Vec<String>,
+    /// On an enum, store a set of variants
+    flags: bool,
+    /// On an enum variant, its explicit integer value or bit mask
+    value: Option<TokenStream2>,
+}
+
+impl MapPropAttr {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            default: None,
+            min: None,
+            max: None,
+            parse: false,
+            asset: false,
+            extensions: Vec::new(),
+            flags: false,
+            value: None,
+        }
+    }
+}
+
+fn find_map_prop_attr(attrs: &[Attribute]) -> Option<&Attribute> {
+    attrs.iter().find(|attr| attr.path().is_ident("map_prop"))
+}
+
+fn parse_map_prop_attr(attr: &Attribute, field_name: &Ident) -> syn::Result<MapPropAttr> {
+    let mut prop = MapPropAttr::new(field_name.to_string());
 
     // Handle both #[map_prop] and #[map_prop(...)]
     match &attr.meta {
@@ -228,17 +829,61 @@ fn parse_map_prop_attr(
                 list.parse_args_with(syn::punctuated::Punctuated::parse_terminated)?;
 
             for meta in nested {
-                if let Meta::NameValue(nv) = meta {
-                    if nv.path.is_ident("name") {
+                match meta {
+                    Meta::NameValue(nv) if nv.path.is_ident("name") => {
                         if let Expr::Lit(ExprLit {
                             lit: Lit::Str(lit_str),
                             ..
                         }) = &nv.value
                         {
-                            prop_name = lit_str.value();
+                            prop.name = lit_str.value();
                         }
-                    } else if nv.path.is_ident("default") {
-                        default_value = Some(nv.value.to_token_stream());
+                    }
+                    Meta::NameValue(nv) if nv.path.is_ident("default") => {
+                        prop.default = Some(nv.value.to_token_stream());
+                    }
+                    Meta::NameValue(nv) if nv.path.is_ident("min") => {
+                        prop.min = Some(nv.value.to_token_stream());
+                    }
+                    Meta::NameValue(nv) if nv.path.is_ident("max") => {
+                        prop.max = Some(nv.value.to_token_stream());
+                    }
+                    Meta::Path(path) if path.is_ident("parse") => {
+                        prop.parse = true;
+                    }
+                    Meta::Path(path) if path.is_ident("asset") => {
+                        prop.asset = true;
+                    }
+                    Meta::Path(path) if path.is_ident("flags") => {
+                        prop.flags = true;
+                    }
+                    Meta::NameValue(nv) if nv.path.is_ident("value") => {
+                        prop.value = Some(nv.value.to_token_stream());
+                    }
+                    Meta::NameValue(nv) if nv.path.is_ident("extensions") => {
+                        let Expr::Lit(ExprLit {
+                            lit: Lit::Str(lit_str),
+                            ..
+                        }) = &nv.value
+                        else {
+                            return Err(syn::Error::new_spanned(
+                                &nv.value,
+                                "Expected a comma-separated string, e.g. `extensions = \"ogg, wav\"`",
+                            ));
+                        };
+                        prop.asset = true;
+                        prop.extensions = lit_str
+                            .value()
+                            .split(',')
+                            .map(|extension| extension.trim().trim_start_matches('.').to_string())
+                            .filter(|extension| !extension.is_empty())
+                            .collect();
+                    }
+                    other => {
+                        return Err(syn::Error::new_spanned(
+                            other,
+                            "Expected `name = \"...\"`, `default = ...`, `min = ...`, `max = ...`, `parse`, `asset`, `extensions = \"...\"`, `flags` or `value = ...`",
+                        ))
                     }
                 }
             }
@@ -251,7 +896,7 @@ fn parse_map_prop_attr(
         }
     }
 
-    Ok((prop_name, default_value))
+    Ok(prop)
 }
 
 /// Parse #[map_sprite] or #[map_sprite("property_name")] attribute
@@ -279,93 +924,148 @@ fn parse_map_sprite_attr(attr: &Attribute, field_name: &Ident) -> syn::Result<St
     Ok(prop_name)
 }
 
+/// Last path segment name of a type, e.g. `String` for `std::string::String`
+fn type_name_of(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// For `Handle<T>`, the name of the asset type `T`
+fn handle_asset_name(ty: &Type) -> Option<String> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Handle" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(asset) => type_name_of(asset),
+        _ => None,
+    })
+}
+
+/// Expression producing a field's default value
+fn default_expr(field_type: &Type, default_value: Option<&TokenStream2>) -> TokenStream2 {
+    match default_value {
+        // Allow string literal defaults for String fields
+        Some(default) if type_name_of(field_type).as_deref() == Some("String") => {
+            quote! { #default.to_string() }
+        }
+        Some(default) => quote! { #default },
+        None => quote! { Default::default() },
+    }
+}
+
+/// Generate a field initializer reading `instance` with `reader`
+/// (`read_map_property` for entities, `read_data_property` for data instances)
 fn generate_field_init(
     field_name: &Ident,
     field_type: &Type,
-    prop_name: &str,
-    default_value: Option<TokenStream2>,
-) -> syn::Result<TokenStream2> {
-    let type_str = quote!(#field_type).to_string();
-
-    // Generate the appropriate getter based on field type
-    let getter = if type_str.contains("String") {
-        if let Some(default) = default_value {
-            quote! {
-                instance.get_string(#prop_name)
-                    .map(|s| s.to_string())
-                    .unwrap_or_else(|| #default.to_string())
-            }
-        } else {
-            quote! {
-                instance.get_string(#prop_name)
-                    .map(|s| s.to_string())
-                    .unwrap_or_default()
-            }
-        }
-    } else if type_str.contains("i32")
-        || type_str.contains("i64")
-        || type_str.contains("u32")
-        || type_str.contains("u64")
-        || type_str.contains("usize")
-    {
-        // All integer types use get_int and cast to the target type
-        if let Some(default) = default_value {
-            quote! {
-                instance.get_int(#prop_name)
-                    .map(|v| v as #field_type)
-                    .unwrap_or(#default)
-            }
-        } else {
-            quote! {
-                instance.get_int(#prop_name)
-                    .map(|v| v as #field_type)
-                    .unwrap_or_default()
-            }
-        }
-    } else if type_str.contains("f32") || type_str.contains("f64") {
-        if let Some(default) = default_value {
-            quote! {
-                instance.get_float(#prop_name)
-                    .map(|v| v as #field_type)
-                    .unwrap_or(#default)
-            }
-        } else {
-            quote! {
-                instance.get_float(#prop_name)
-                    .map(|v| v as #field_type)
-                    .unwrap_or_default()
-            }
-        }
-    } else if type_str.contains("bool") {
-        if let Some(default) = default_value {
-            quote! {
-                instance.get_bool(#prop_name)
-                    .unwrap_or(#default)
-            }
-        } else {
-            quote! {
-                instance.get_bool(#prop_name)
-                    .unwrap_or_default()
-            }
+    prop: &MapPropAttr,
+    reader: TokenStream2,
+) -> TokenStream2 {
+    let prop_name = &prop.name;
+
+    let unwrap = match &prop.default {
+        Some(default) if type_name_of(field_type).as_deref() == Some("String") => {
+            quote! { .unwrap_or_else(|| #default.to_string()) }
         }
+        Some(default) => quote! { .unwrap_or(#default) },
+        None => quote! { .unwrap_or_default() },
+    };
+
+    if prop.parse {
+        // Custom types parsed from string properties
+        return quote! {
+            #field_name: instance.get_string(#prop_name)
+                .and_then(|s| s.parse().ok())
+                #unwrap
+        };
+    }
+
+    quote! {
+        #field_name: #reader::<#field_type>(instance, #prop_name)
+            #unwrap
+    }
+}
+
+/// Generate the statement writing a field back into the `properties` map
+fn generate_field_write(field_name: &Ident, prop: &MapPropAttr) -> TokenStream2 {
+    let prop_name = &prop.name;
+
+    let value = if prop.parse {
+        // Parsed types are written back with Display
+        quote! { bevy_map::core::Value::String(self.#field_name.to_string()) }
     } else {
-        // For custom types, try FromStr or Default
-        if let Some(default) = default_value {
-            quote! {
-                instance.get_string(#prop_name)
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(#default)
-            }
+        quote! { bevy_map::runtime::MapProperty::to_value(&self.#field_name) }
+    };
+
+    quote! {
+        properties.insert(#prop_name.to_string(), #value);
+    }
+}
+
+/// Generate an expression building the schema `PropertyDef` for a field
+fn generate_property_def(field_type: &Type, prop: &MapPropAttr) -> TokenStream2 {
+    let prop_name = &prop.name;
+
+    let describe = if prop.parse {
+        // Parsed types are stored as strings
+        quote! { property.prop_type = bevy_map::schema::PropType::String; }
+    } else {
+        quote! { <#field_type as bevy_map::runtime::MapProperty>::describe(&mut property, schema); }
+    };
+
+    let default = prop.default.as_ref().map(|default| {
+        let default = default_expr(field_type, Some(default));
+        let value = if prop.parse {
+            quote! { bevy_map::core::Value::String(default.to_string()) }
         } else {
-            quote! {
-                instance.get_string(#prop_name)
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or_default()
-            }
+            quote! { bevy_map::runtime::MapProperty::to_value(&default) }
+        };
+        quote! {
+            let default: #field_type = #default;
+            property.default = Some(#value.to_json());
         }
-    };
+    });
+    let min = prop
+        .min
+        .as_ref()
+        .map(|min| quote! { property.min = Some(#min as f64); });
+    let max = prop
+        .max
+        .as_ref()
+        .map(|max| quote! { property.max = Some(#max as f64); });
+    let asset = prop.asset.then(|| {
+        let extensions = &prop.extensions;
+        quote! {
+            property.prop_type = bevy_map::schema::PropType::Asset;
+            property.extensions = vec![#(#extensions.to_string()),*];
+        }
+    });
 
-    Ok(quote! {
-        #field_name: #getter
-    })
+    quote! {
+        {
+            let mut property = bevy_map::schema::PropertyDef::new(
+                #prop_name,
+                bevy_map::schema::PropType::String,
+            );
+            #describe
+            #default
+            #min
+            #max
+            #asset
+            property
+        }
+    }
 }
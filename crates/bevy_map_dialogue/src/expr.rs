@@ -0,0 +1,823 @@
+//! Expressions for dialogue conditions and actions
+//!
+//! Conditions are expressions that evaluate to true or false:
+//!
+//! ```text
+//! gold >= 10 && !has_sword
+//! quest.stage == 2 || player_name == "Ada"
+//! ```
+//!
+//! Actions are `;`-separated statements that assign variables or call game
+//! events by name:
+//!
+//! ```text
+//! gold -= 10; has_sword = true; give_item("sword", 1)
+//! ```
+//!
+//! Values are numbers, strings (`"..."` or `'...'`) and `true`/`false`.
+//! Expressions support arithmetic (`+ - * / %`, where `+` also joins strings),
+//! comparisons (`== != < <= > >=`), boolean logic (`&& || !` or `and or not`)
+//! and parentheses. Variable names may contain letters, digits, `_` and `.`.
+//! Unset variables read as `0`, and `0`, `""` and `false` count as false.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Value of a dialogue variable or expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DialogueValue {
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl Default for DialogueValue {
+    fn default() -> Self {
+        DialogueValue::Number(0.0)
+    }
+}
+
+impl DialogueValue {
+    /// Whether the value counts as true in a condition
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            DialogueValue::Bool(b) => *b,
+            DialogueValue::Number(n) => *n != 0.0,
+            DialogueValue::String(s) => !s.is_empty(),
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            DialogueValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            DialogueValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            DialogueValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            DialogueValue::Bool(_) => "bool",
+            DialogueValue::Number(_) => "number",
+            DialogueValue::String(_) => "string",
+        }
+    }
+}
+
+/// Whole numbers are shown without a decimal point
+impl fmt::Display for DialogueValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DialogueValue::Bool(b) => write!(f, "{}", b),
+            DialogueValue::Number(n) => write!(f, "{}", n),
+            DialogueValue::String(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<bool> for DialogueValue {
+    fn from(value: bool) -> Self {
+        DialogueValue::Bool(value)
+    }
+}
+
+impl From<f64> for DialogueValue {
+    fn from(value: f64) -> Self {
+        DialogueValue::Number(value)
+    }
+}
+
+impl From<f32> for DialogueValue {
+    fn from(value: f32) -> Self {
+        DialogueValue::Number(value as f64)
+    }
+}
+
+impl From<i32> for DialogueValue {
+    fn from(value: i32) -> Self {
+        DialogueValue::Number(value as f64)
+    }
+}
+
+impl From<i64> for DialogueValue {
+    fn from(value: i64) -> Self {
+        DialogueValue::Number(value as f64)
+    }
+}
+
+impl From<&str> for DialogueValue {
+    fn from(value: &str) -> Self {
+        DialogueValue::String(value.to_string())
+    }
+}
+
+impl From<String> for DialogueValue {
+    fn from(value: String) -> Self {
+        DialogueValue::String(value)
+    }
+}
+
+/// Error from parsing or evaluating an expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExprError {
+    pub message: String,
+    /// Character offset of the error in the source, for syntax errors
+    pub position: Option<usize>,
+}
+
+impl ExprError {
+    fn syntax(message: impl Into<String>, position: usize) -> Self {
+        Self {
+            message: message.into(),
+            position: Some(position),
+        }
+    }
+
+    fn runtime(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            position: None,
+        }
+    }
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some(position) => write!(f, "{} at column {}", self.message, position + 1),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+/// Unary operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Not,
+    Neg,
+}
+
+/// Binary operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinaryOp {
+    /// Binding strength, higher binds tighter
+    fn precedence(self) -> u8 {
+        match self {
+            BinaryOp::Or => 1,
+            BinaryOp::And => 2,
+            BinaryOp::Eq | BinaryOp::Ne => 3,
+            BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => 4,
+            BinaryOp::Add | BinaryOp::Sub => 5,
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => 6,
+        }
+    }
+}
+
+/// A parsed expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(DialogueValue),
+    Variable(String),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Evaluate the expression against a set of variables
+    pub fn evaluate(&self, variables: &DialogueVariables) -> Result<DialogueValue, ExprError> {
+        match self {
+            Expr::Literal(value) => Ok(value.clone()),
+            Expr::Variable(name) => Ok(variables.get(name).cloned().unwrap_or_default()),
+            Expr::Unary(UnaryOp::Not, operand) => Ok(DialogueValue::Bool(
+                !operand.evaluate(variables)?.is_truthy(),
+            )),
+            Expr::Unary(UnaryOp::Neg, operand) => match operand.evaluate(variables)? {
+                DialogueValue::Number(n) => Ok(DialogueValue::Number(-n)),
+                other => Err(ExprError::runtime(format!(
+                    "cannot negate a {}",
+                    other.type_name()
+                ))),
+            },
+            // Logic short-circuits so `has_quest && quest.stage > 1` is safe
+            Expr::Binary(BinaryOp::And, left, right) => Ok(DialogueValue::Bool(
+                left.evaluate(variables)?.is_truthy() && right.evaluate(variables)?.is_truthy(),
+            )),
+            Expr::Binary(BinaryOp::Or, left, right) => Ok(DialogueValue::Bool(
+                left.evaluate(variables)?.is_truthy() || right.evaluate(variables)?.is_truthy(),
+            )),
+            Expr::Binary(op, left, right) => {
+                apply_binary(*op, left.evaluate(variables)?, right.evaluate(variables)?)
+            }
+        }
+    }
+}
+
+fn apply_binary(
+    op: BinaryOp,
+    left: DialogueValue,
+    right: DialogueValue,
+) -> Result<DialogueValue, ExprError> {
+    use DialogueValue::{Bool, Number, String};
+
+    let result = match (op, &left, &right) {
+        (BinaryOp::Eq, _, _) => Bool(left == right),
+        (BinaryOp::Ne, _, _) => Bool(left != right),
+        (BinaryOp::Add, Number(a), Number(b)) => Number(a + b),
+        (BinaryOp::Add, String(_), _) | (BinaryOp::Add, _, String(_)) => {
+            String(format!("{}{}", left, right))
+        }
+        (BinaryOp::Sub, Number(a), Number(b)) => Number(a - b),
+        (BinaryOp::Mul, Number(a), Number(b)) => Number(a * b),
+        (BinaryOp::Div | BinaryOp::Rem, Number(_), Number(b)) if *b == 0.0 => {
+            return Err(ExprError::runtime("division by zero"));
+        }
+        (BinaryOp::Div, Number(a), Number(b)) => Number(a / b),
+        (BinaryOp::Rem, Number(a), Number(b)) => Number(a % b),
+        (BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge, _, _) => {
+            let ordering = match (&left, &right) {
+                (Number(a), Number(b)) => a.partial_cmp(b),
+                (String(a), String(b)) => Some(a.cmp(b)),
+                _ => None,
+            };
+            let Some(ordering) = ordering else {
+                return Err(ExprError::runtime(format!(
+                    "cannot compare a {} with a {}",
+                    left.type_name(),
+                    right.type_name()
+                )));
+            };
+            Bool(match op {
+                BinaryOp::Lt => ordering.is_lt(),
+                BinaryOp::Le => ordering.is_le(),
+                BinaryOp::Gt => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            })
+        }
+        _ => {
+            return Err(ExprError::runtime(format!(
+                "cannot apply {:?} to a {} and a {}",
+                op,
+                left.type_name(),
+                right.type_name()
+            )));
+        }
+    };
+    Ok(result)
+}
+
+/// A statement in an action
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    /// `name = value`, or `name += value` and friends when `op` is set
+    Assign {
+        variable: String,
+        op: Option<BinaryOp>,
+        value: Expr,
+    },
+    /// `name(args...)`, sent to the game as a [`DialogueCall`]
+    Call { name: String, args: Vec<Expr> },
+}
+
+/// A game event called from a dialogue action, with its evaluated arguments
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogueCall {
+    pub name: String,
+    pub args: Vec<DialogueValue>,
+}
+
+/// Parse a condition expression
+pub fn parse_condition(source: &str) -> Result<Expr, ExprError> {
+    let mut parser = Parser::new(source)?;
+    let expr = parser.expression(0)?;
+    parser.expect_end()?;
+    Ok(expr)
+}
+
+/// Parse an action: statements separated by `;`
+pub fn parse_action(source: &str) -> Result<Vec<Statement>, ExprError> {
+    let mut parser = Parser::new(source)?;
+    let mut statements = Vec::new();
+    while !parser.at_end() {
+        if parser.eat(&Token::Semicolon) {
+            continue;
+        }
+        statements.push(parser.statement()?);
+        if !parser.at_end() && !parser.eat(&Token::Semicolon) {
+            return Err(parser.error("expected ';' between statements"));
+        }
+    }
+    Ok(statements)
+}
+
+/// Variables read and written by dialogue conditions and actions
+///
+/// Games can also set variables directly, e.g. to mirror inventory or quest
+/// state before a dialogue starts.
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DialogueVariables {
+    pub values: HashMap<String, DialogueValue>,
+}
+
+impl DialogueVariables {
+    /// Get a variable's value
+    pub fn get(&self, name: &str) -> Option<&DialogueValue> {
+        self.values.get(name)
+    }
+
+    /// Set a variable
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<DialogueValue>) {
+        self.values.insert(name.into(), value.into());
+    }
+
+    /// Remove a variable, so it reads as `0` again
+    pub fn remove(&mut self, name: &str) -> Option<DialogueValue> {
+        self.values.remove(name)
+    }
+
+    /// Evaluate an expression
+    pub fn evaluate(&self, expression: &str) -> Result<DialogueValue, ExprError> {
+        parse_condition(expression)?.evaluate(self)
+    }
+
+    /// Check a condition; empty conditions pass
+    pub fn check(&self, condition: &str) -> Result<bool, ExprError> {
+        if condition.trim().is_empty() {
+            return Ok(true);
+        }
+        Ok(self.evaluate(condition)?.is_truthy())
+    }
+
+    /// Run an action, applying its assignments and returning the events it calls
+    ///
+    /// Nothing runs if the action doesn't parse. A statement that fails to
+    /// evaluate stops the action, keeping the changes made before it.
+    pub fn execute(&mut self, action: &str) -> Result<Vec<DialogueCall>, ExprError> {
+        let mut calls = Vec::new();
+        for statement in parse_action(action)? {
+            match statement {
+                Statement::Assign {
+                    variable,
+                    op,
+                    value,
+                } => {
+                    let mut value = value.evaluate(self)?;
+                    if let Some(op) = op {
+                        let current = self.get(&variable).cloned().unwrap_or_default();
+                        value = apply_binary(op, current, value)?;
+                    }
+                    self.values.insert(variable, value);
+                }
+                Statement::Call { name, args } => {
+                    let args = args
+                        .iter()
+                        .map(|arg| arg.evaluate(self))
+                        .collect::<Result<_, _>>()?;
+                    calls.push(DialogueCall { name, args });
+                }
+            }
+        }
+        Ok(calls)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Bool(bool),
+    Ident(String),
+    LParen,
+    RParen,
+    Comma,
+    Semicolon,
+    Not,
+    Assign,
+    /// Compound assignment like `+=`
+    AssignOp(BinaryOp),
+    Op(BinaryOp),
+}
+
+/// Split source into tokens with their character positions
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ExprError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let next = chars.get(i + 1).copied();
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c.is_ascii_digit() || (c == '.' && next.is_some_and(|n| n.is_ascii_digit())) {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let number = text
+                .parse()
+                .map_err(|_| ExprError::syntax(format!("invalid number '{}'", text), start))?;
+            tokens.push((Token::Number(number), start));
+            continue;
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let token = match word.as_str() {
+                "true" => Token::Bool(true),
+                "false" => Token::Bool(false),
+                "and" => Token::Op(BinaryOp::And),
+                "or" => Token::Op(BinaryOp::Or),
+                "not" => Token::Not,
+                _ => Token::Ident(word),
+            };
+            tokens.push((token, start));
+            continue;
+        }
+
+        if c == '"' || c == '\'' {
+            i += 1;
+            let mut text = String::new();
+            loop {
+                match chars.get(i) {
+                    None => return Err(ExprError::syntax("unterminated string", start)),
+                    Some(&q) if q == c => break,
+                    Some('\\') => {
+                        match chars.get(i + 1) {
+                            Some('n') => text.push('\n'),
+                            Some(&escaped) => text.push(escaped),
+                            None => return Err(ExprError::syntax("unterminated string", start)),
+                        }
+                        i += 2;
+                    }
+                    Some(&other) => {
+                        text.push(other);
+                        i += 1;
+                    }
+                }
+            }
+            i += 1;
+            tokens.push((Token::Str(text), start));
+            continue;
+        }
+
+        // Two-character operators first
+        let pair = match (c, next) {
+            ('&', Some('&')) => Some(Token::Op(BinaryOp::And)),
+            ('|', Some('|')) => Some(Token::Op(BinaryOp::Or)),
+            ('=', Some('=')) => Some(Token::Op(BinaryOp::Eq)),
+            ('!', Some('=')) => Some(Token::Op(BinaryOp::Ne)),
+            ('<', Some('=')) => Some(Token::Op(BinaryOp::Le)),
+            ('>', Some('=')) => Some(Token::Op(BinaryOp::Ge)),
+            ('+', Some('=')) => Some(Token::AssignOp(BinaryOp::Add)),
+            ('-', Some('=')) => Some(Token::AssignOp(BinaryOp::Sub)),
+            ('*', Some('=')) => Some(Token::AssignOp(BinaryOp::Mul)),
+            ('/', Some('=')) => Some(Token::AssignOp(BinaryOp::Div)),
+            _ => None,
+        };
+        if let Some(token) = pair {
+            i += 2;
+            tokens.push((token, start));
+            continue;
+        }
+
+        let token = match c {
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            ';' => Token::Semicolon,
+            '!' => Token::Not,
+            '=' => Token::Assign,
+            '<' => Token::Op(BinaryOp::Lt),
+            '>' => Token::Op(BinaryOp::Gt),
+            '+' => Token::Op(BinaryOp::Add),
+            '-' => Token::Op(BinaryOp::Sub),
+            '*' => Token::Op(BinaryOp::Mul),
+            '/' => Token::Op(BinaryOp::Div),
+            '%' => Token::Op(BinaryOp::Rem),
+            _ => {
+                return Err(ExprError::syntax(
+                    format!("unexpected character '{}'", c),
+                    start,
+                ))
+            }
+        };
+        i += 1;
+        tokens.push((token, start));
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser with precedence climbing for binary operators
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+    /// Position reported for errors at the end of input
+    end: usize,
+}
+
+impl Parser {
+    fn new(source: &str) -> Result<Self, ExprError> {
+        Ok(Self {
+            tokens: tokenize(source)?,
+            index: 0,
+            end: source.chars().count(),
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(token, _)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.index)
+            .map_or(self.end, |(_, position)| *position)
+    }
+
+    fn at_end(&self) -> bool {
+        self.index >= self.tokens.len()
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).map(|(token, _)| token.clone());
+        self.index += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&self, message: &str) -> ExprError {
+        let found = match self.peek() {
+            None => "end of input".to_string(),
+            Some(token) => describe(token),
+        };
+        ExprError::syntax(format!("{}, found {}", message, found), self.position())
+    }
+
+    fn expect_end(&self) -> Result<(), ExprError> {
+        if self.at_end() {
+            Ok(())
+        } else {
+            Err(self.error("expected an operator"))
+        }
+    }
+
+    fn statement(&mut self) -> Result<Statement, ExprError> {
+        let Some(Token::Ident(name)) = self.peek().cloned() else {
+            return Err(self.error("expected an assignment or event call"));
+        };
+        self.index += 1;
+
+        match self.advance() {
+            Some(Token::Assign) => Ok(Statement::Assign {
+                variable: name,
+                op: None,
+                value: self.expression(0)?,
+            }),
+            Some(Token::AssignOp(op)) => Ok(Statement::Assign {
+                variable: name,
+                op: Some(op),
+                value: self.expression(0)?,
+            }),
+            Some(Token::LParen) => {
+                let mut args = Vec::new();
+                if !self.eat(&Token::RParen) {
+                    loop {
+                        args.push(self.expression(0)?);
+                        if self.eat(&Token::RParen) {
+                            break;
+                        }
+                        if !self.eat(&Token::Comma) {
+                            return Err(self.error("expected ',' or ')'"));
+                        }
+                    }
+                }
+                Ok(Statement::Call { name, args })
+            }
+            _ => {
+                self.index -= 1;
+                Err(self.error(&format!("expected '=' or '(' after '{}'", name)))
+            }
+        }
+    }
+
+    /// Parse an expression whose binary operators bind tighter than `min_precedence`
+    fn expression(&mut self, min_precedence: u8) -> Result<Expr, ExprError> {
+        let mut left = self.unary()?;
+        while let Some(Token::Op(op)) = self.peek() {
+            let op = *op;
+            if op.precedence() <= min_precedence {
+                break;
+            }
+            self.index += 1;
+            let right = self.expression(op.precedence())?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ExprError> {
+        if self.eat(&Token::Not) {
+            return Ok(Expr::Unary(UnaryOp::Not, Box::new(self.unary()?)));
+        }
+        if self.eat(&Token::Op(BinaryOp::Sub)) {
+            return Ok(Expr::Unary(UnaryOp::Neg, Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, ExprError> {
+        let expr = match self.peek() {
+            Some(Token::Number(n)) => Expr::Literal(DialogueValue::Number(*n)),
+            Some(Token::Str(s)) => Expr::Literal(DialogueValue::String(s.clone())),
+            Some(Token::Bool(b)) => Expr::Literal(DialogueValue::Bool(*b)),
+            Some(Token::Ident(name)) => {
+                if self.tokens.get(self.index + 1).map(|(token, _)| token) == Some(&Token::LParen) {
+                    return Err(self.error("event calls are only allowed in actions"));
+                }
+                Expr::Variable(name.clone())
+            }
+            Some(Token::LParen) => {
+                self.index += 1;
+                let expr = self.expression(0)?;
+                if !self.eat(&Token::RParen) {
+                    return Err(self.error("expected ')'"));
+                }
+                return Ok(expr);
+            }
+            _ => return Err(self.error("expected a value")),
+        };
+        self.index += 1;
+        Ok(expr)
+    }
+}
+
+/// Describe a token for error messages
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Number(n) => format!("number {}", n),
+        Token::Str(s) => format!("string \"{}\"", s),
+        Token::Bool(b) => format!("'{}'", b),
+        Token::Ident(name) => format!("'{}'", name),
+        Token::LParen => "'('".to_string(),
+        Token::RParen => "')'".to_string(),
+        Token::Comma => "','".to_string(),
+        Token::Semicolon => "';'".to_string(),
+        Token::Not => "'!'".to_string(),
+        Token::Assign => "'='".to_string(),
+        Token::AssignOp(_) | Token::Op(_) => "an operator".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> DialogueVariables {
+        let mut variables = DialogueVariables::default();
+        variables.set("gold", 25);
+        variables.set("has_sword", false);
+        variables.set("player.name", "Ada");
+        variables
+    }
+
+    #[test]
+    fn test_evaluate_conditions() {
+        let variables = vars();
+        let check = |condition: &str| variables.check(condition).unwrap();
+
+        assert!(check("gold >= 10 && !has_sword"));
+        assert!(check("gold > 10 and not has_sword"));
+        assert!(!check("gold < 10 || has_sword"));
+        assert!(check("player.name == \"Ada\""));
+        assert!(check("player.name != 'Bob'"));
+        // Precedence: * before +, + before comparison
+        assert!(check("1 + 2 * 3 == 7"));
+        assert!(check("(1 + 2) * 3 == 9"));
+        assert!(check("-gold + 30 == 5"));
+        assert!(check("gold % 10 == 5"));
+        // Unset variables read as 0
+        assert!(check("missing == 0"));
+        assert!(!check("missing"));
+        assert!(check(""));
+        // Short-circuit skips the failing comparison
+        assert!(!check("has_sword && player.name > 3"));
+        assert_eq!(
+            variables.evaluate("\"Hi \" + player.name + \"!\"").unwrap(),
+            DialogueValue::String("Hi Ada!".to_string())
+        );
+
+        // Type errors and division by zero are evaluation errors
+        assert!(variables.check("player.name > 3").is_err());
+        assert!(variables.check("gold / 0").is_err());
+        assert!(variables.check("-player.name").is_err());
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        let error = parse_condition("gold >= ").unwrap_err();
+        assert_eq!(error.position, Some(8));
+        assert_eq!(
+            error.to_string(),
+            "expected a value, found end of input at column 9"
+        );
+
+        assert!(parse_condition("gold >= 10)").is_err());
+        assert!(parse_condition("(gold").is_err());
+        assert!(parse_condition("\"open").is_err());
+        assert!(parse_condition("gold # 2").is_err());
+        assert!(parse_condition("give_item(1)").is_err());
+        assert!(parse_condition("gold = 2").is_err());
+
+        assert!(parse_action("gold 5").is_err());
+        assert!(parse_action("gold = 5 has_sword = true").is_err());
+        assert!(parse_action("give_item(1,)").is_err());
+        assert!(parse_action("5 = gold").is_err());
+        assert_eq!(parse_action("").unwrap(), Vec::new());
+        assert_eq!(parse_action("a = 1;; b = 2;").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_execute_actions() {
+        let mut variables = vars();
+        let calls = variables
+            .execute("gold -= 10; has_sword = true; give_item(\"sword\", gold / 5); greeted = 1")
+            .unwrap();
+
+        assert_eq!(variables.get("gold"), Some(&DialogueValue::Number(15.0)));
+        assert_eq!(variables.get("has_sword"), Some(&DialogueValue::Bool(true)));
+        assert_eq!(variables.get("greeted"), Some(&DialogueValue::Number(1.0)));
+        assert_eq!(
+            calls,
+            vec![DialogueCall {
+                name: "give_item".to_string(),
+                args: vec![DialogueValue::from("sword"), DialogueValue::from(3)],
+            }]
+        );
+
+        // Compound assignment on an unset variable starts from 0
+        variables.execute("visits += 1").unwrap();
+        assert_eq!(variables.get("visits"), Some(&DialogueValue::Number(1.0)));
+
+        // Parse errors apply nothing
+        assert!(variables.execute("gold = 0; gold +").is_err());
+        assert_eq!(variables.get("gold"), Some(&DialogueValue::Number(15.0)));
+
+        // Values round trip through save files
+        let json = serde_json::to_string(&variables).unwrap();
+        let loaded: DialogueVariables = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.get("player.name"), variables.get("player.name"));
+    }
+}
@@ -6,6 +6,7 @@
 //! - Speaker assignments
 //! - Conditional branching
 //! - Action triggers
+//! - Condition and action expressions over [`DialogueVariables`] (see [`expr`])
 //!
 //! # Usage
 //!
@@ -30,11 +31,22 @@
 //! tree.add_node(choice_node);
 //! ```
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 use uuid::Uuid;
 
+pub mod expr;
+
+pub use expr::{
+    parse_action, parse_condition, DialogueCall, DialogueValue, DialogueVariables, ExprError,
+};
+
+/// Most condition and action nodes followed in a row before the runner assumes a loop
+const MAX_AUTOMATIC_NODES: usize = 100;
+
 /// Type of dialogue node
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
 #[serde(rename_all = "lowercase")]
@@ -44,7 +56,7 @@ pub enum DialogueNodeType {
     Text,
     /// Player chooses from multiple options
     Choice,
-    /// Check a condition and branch
+    /// Check a condition and branch to `next_node` or `else_node`
     Condition,
     /// Execute an action (give item, start quest, etc.)
     Action,
@@ -94,8 +106,14 @@ pub struct DialogueChoice {
     pub text: String,
     /// Node to go to when this choice is selected
     pub next_node: Option<String>,
-    /// Condition required to show this choice (script/expression string)
+    /// Condition expression required to select this choice
     pub condition: Option<String>,
+    /// Action run when this choice is selected
+    #[serde(default)]
+    pub action: Option<String>,
+    /// Show the choice as disabled, instead of hiding it, when its condition fails
+    #[serde(default)]
+    pub show_disabled: bool,
 }
 
 impl DialogueChoice {
@@ -105,6 +123,8 @@ impl DialogueChoice {
             text: text.into(),
             next_node: Some(next_node.into()),
             condition: None,
+            action: None,
+            show_disabled: false,
         }
     }
 
@@ -113,6 +133,29 @@ impl DialogueChoice {
         self.condition = Some(condition.into());
         self
     }
+
+    /// Create a choice with an action
+    pub fn with_action(mut self, action: impl Into<String>) -> Self {
+        self.action = Some(action.into());
+        self
+    }
+
+    /// Check if the choice's condition passes
+    ///
+    /// Conditions that fail to evaluate are logged and count as failed.
+    pub fn is_available(&self, variables: &DialogueVariables) -> bool {
+        condition_passes(variables, self.condition.as_deref())
+    }
+}
+
+/// A choice as presented to the player
+#[derive(Debug, Clone, Copy)]
+pub struct AvailableChoice<'a> {
+    /// Index to send in [`DialogueChoiceEvent`]
+    pub index: usize,
+    pub choice: &'a DialogueChoice,
+    /// False if the choice is shown but can't be selected
+    pub enabled: bool,
 }
 
 fn default_position() -> (f32, f32) {
@@ -136,11 +179,14 @@ pub struct DialogueNode {
     /// Choices available to the player (for choice nodes)
     #[serde(default)]
     pub choices: Vec<DialogueChoice>,
-    /// Next node to go to (for linear flow)
+    /// Next node to go to (for linear flow, or when a condition node's condition passes)
     pub next_node: Option<String>,
-    /// Condition to check before showing this node (script/expression string)
+    /// Node a condition node goes to when its condition fails
+    #[serde(default)]
+    pub else_node: Option<String>,
+    /// Condition expression; other node types are skipped when it fails
     pub condition: Option<String>,
-    /// Action to execute when entering this node (script/expression string)
+    /// Action run when entering this node
     pub action: Option<String>,
     /// Position in the editor (x, y)
     #[serde(default = "default_position")]
@@ -156,6 +202,7 @@ impl Default for DialogueNode {
             text: String::new(),
             choices: Vec::new(),
             next_node: None,
+            else_node: None,
             condition: None,
             action: None,
             position: (0.0, 0.0),
@@ -215,6 +262,12 @@ impl DialogueNode {
         self
     }
 
+    /// Set the node a condition node goes to when its condition fails
+    pub fn with_else(mut self, else_node: impl Into<String>) -> Self {
+        self.else_node = Some(else_node.into());
+        self
+    }
+
     /// Add a choice
     pub fn with_choice(mut self, choice: DialogueChoice) -> Self {
         self.choices.push(choice);
@@ -226,6 +279,22 @@ impl DialogueNode {
         self.position = (x, y);
         self
     }
+
+    /// The choices to show the player, skipping hidden ones
+    pub fn available_choices(&self, variables: &DialogueVariables) -> Vec<AvailableChoice<'_>> {
+        self.choices
+            .iter()
+            .enumerate()
+            .filter_map(|(index, choice)| {
+                let enabled = choice.is_available(variables);
+                (enabled || choice.show_disabled).then_some(AvailableChoice {
+                    index,
+                    choice,
+                    enabled,
+                })
+            })
+            .collect()
+    }
 }
 
 fn default_dialogue_id() -> String {
@@ -264,6 +333,7 @@ impl DialogueTree {
                 text: "Hello!".to_string(),
                 choices: Vec::new(),
                 next_node: None,
+                else_node: None,
                 condition: None,
                 action: None,
                 position: (100.0, 100.0),
@@ -302,6 +372,9 @@ impl DialogueTree {
             if node.next_node.as_deref() == Some(id) {
                 node.next_node = None;
             }
+            if node.else_node.as_deref() == Some(id) {
+                node.else_node = None;
+            }
             node.choices.retain(|c| c.next_node.as_deref() != Some(id));
         }
         if self.start_node == id {
@@ -345,7 +418,7 @@ impl DialogueTree {
         }
 
         for (id, node) in &self.nodes {
-            if let Some(next) = &node.next_node {
+            for next in node.next_node.iter().chain(&node.else_node) {
                 if !self.nodes.contains_key(next) {
                     errors.push(format!(
                         "Node '{}' references non-existent node '{}'",
@@ -353,6 +426,16 @@ impl DialogueTree {
                     ));
                 }
             }
+            if let Some(condition) = &node.condition {
+                if let Err(e) = parse_condition(condition) {
+                    errors.push(format!("Node '{}' condition: {}", id, e));
+                }
+            }
+            if let Some(action) = &node.action {
+                if let Err(e) = parse_action(action) {
+                    errors.push(format!("Node '{}' action: {}", id, e));
+                }
+            }
             for choice in &node.choices {
                 if let Some(next) = &choice.next_node {
                     if !self.nodes.contains_key(next) {
@@ -362,6 +445,22 @@ impl DialogueTree {
                         ));
                     }
                 }
+                if let Some(condition) = &choice.condition {
+                    if let Err(e) = parse_condition(condition) {
+                        errors.push(format!(
+                            "Choice '{}' in node '{}' condition: {}",
+                            choice.text, id, e
+                        ));
+                    }
+                }
+                if let Some(action) = &choice.action {
+                    if let Err(e) = parse_action(action) {
+                        errors.push(format!(
+                            "Choice '{}' in node '{}' action: {}",
+                            choice.text, id, e
+                        ));
+                    }
+                }
             }
         }
 
@@ -407,6 +506,153 @@ pub struct DialogueEndEvent {
     pub speaker_entity: Entity,
 }
 
+/// Message sent for each event called by a dialogue action, e.g. `give_item("sword", 1)`
+///
+/// Sent for every call. Register a [`DialogueEventType`] to also receive
+/// calls as typed observer events.
+#[derive(Message, Debug, Clone)]
+pub struct DialogueActionEvent {
+    /// The entity that owns the dialogue, if any
+    pub speaker_entity: Option<Entity>,
+    /// Called event name
+    pub name: String,
+    /// Evaluated arguments
+    pub args: Vec<DialogueValue>,
+}
+
+/// Trait for game events that dialogue actions can call by name
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use bevy::prelude::*;
+/// use bevy_map_dialogue::{DialogueEventType, DialogueValue};
+///
+/// #[derive(Event, Clone)]
+/// pub struct GiveItem {
+///     pub item: String,
+///     pub count: u32,
+/// }
+///
+/// impl DialogueEventType for GiveItem {
+///     fn event_name() -> &'static str { "give_item" }
+///
+///     fn from_args(args: &[DialogueValue]) -> Option<Self> {
+///         Some(Self {
+///             item: args.first()?.as_str()?.to_string(),
+///             count: args.get(1).and_then(|v| v.as_number()).unwrap_or(1.0) as u32,
+///         })
+///     }
+/// }
+/// ```
+pub trait DialogueEventType: Event + Clone + Send + Sync + 'static
+where
+    for<'a> <Self as Event>::Trigger<'a>: Default,
+{
+    /// The name actions call this event by
+    fn event_name() -> &'static str;
+
+    /// Create an instance from the call arguments.
+    /// Return None if arguments are missing or invalid.
+    fn from_args(args: &[DialogueValue]) -> Option<Self>;
+}
+
+// Internal trait for type-erased event dispatch
+trait DialogueEventDispatcher: Send + Sync {
+    /// Trigger the event, returning false if the arguments don't match
+    fn dispatch(&self, commands: &mut Commands, args: &[DialogueValue]) -> bool;
+}
+
+struct TypedDialogueEventDispatcher<T: DialogueEventType>
+where
+    for<'a> <T as Event>::Trigger<'a>: Default,
+{
+    _marker: PhantomData<T>,
+}
+
+impl<T: DialogueEventType> DialogueEventDispatcher for TypedDialogueEventDispatcher<T>
+where
+    for<'a> <T as Event>::Trigger<'a>: Default,
+{
+    fn dispatch(&self, commands: &mut Commands, args: &[DialogueValue]) -> bool {
+        match T::from_args(args) {
+            Some(event) => {
+                commands.trigger(event);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Registry of typed dialogue events, filled by [`DialogueEventExt`]
+#[derive(Resource, Default)]
+pub struct DialogueEventRegistry {
+    dispatchers: HashMap<String, Box<dyn DialogueEventDispatcher>>,
+}
+
+impl DialogueEventRegistry {
+    /// Register a typed event
+    pub fn register<T: DialogueEventType>(&mut self)
+    where
+        for<'a> <T as Event>::Trigger<'a>: Default,
+    {
+        self.dispatchers.insert(
+            T::event_name().to_string(),
+            Box::new(TypedDialogueEventDispatcher::<T> {
+                _marker: PhantomData,
+            }),
+        );
+    }
+
+    /// Check if an event name is registered
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.dispatchers.contains_key(name)
+    }
+
+    /// Trigger the typed event for a call, if one is registered
+    pub fn dispatch(&self, commands: &mut Commands, call: &DialogueCall) {
+        if let Some(dispatcher) = self.dispatchers.get(&call.name) {
+            if !dispatcher.dispatch(commands, &call.args) {
+                warn!(
+                    "Dialogue event '{}' called with invalid arguments {:?}",
+                    call.name, call.args
+                );
+            }
+        }
+    }
+}
+
+/// Extension trait for registering typed dialogue events
+///
+/// # Example
+///
+/// ```rust,ignore
+/// App::new()
+///     .add_plugins(DialoguePlugin)
+///     .register_dialogue_event::<GiveItem>()
+///     .add_observer(|event: On<GiveItem>| info!("Got {} {}", event.count, event.item));
+/// ```
+pub trait DialogueEventExt {
+    /// Register a typed event that dialogue actions can call
+    fn register_dialogue_event<T: DialogueEventType>(&mut self) -> &mut Self
+    where
+        for<'a> <T as Event>::Trigger<'a>: Default;
+}
+
+impl DialogueEventExt for App {
+    fn register_dialogue_event<T: DialogueEventType>(&mut self) -> &mut Self
+    where
+        for<'a> <T as Event>::Trigger<'a>: Default,
+    {
+        self.init_resource::<DialogueEventRegistry>();
+        self.world_mut()
+            .resource_mut::<DialogueEventRegistry>()
+            .register::<T>();
+        self
+    }
+}
+
 /// Current state of an active dialogue
 #[derive(Resource, Debug, Clone, Default)]
 pub struct DialogueRunner {
@@ -460,17 +706,128 @@ impl Plugin for DialoguePlugin {
             .register_type::<DialogueTree>()
             .register_type::<DialogueHandle>()
             .init_resource::<DialogueRunner>()
+            .init_resource::<DialogueVariables>()
+            .init_resource::<DialogueEventRegistry>()
             .init_resource::<Messages<StartDialogueEvent>>()
             .init_resource::<Messages<DialogueChoiceEvent>>()
             .init_resource::<Messages<DialogueEndEvent>>()
+            .add_message::<DialogueActionEvent>()
             .add_systems(Update, (handle_start_dialogue, handle_dialogue_choice));
     }
 }
 
+/// Check an optional condition, treating evaluation errors as failures
+fn condition_passes(variables: &DialogueVariables, condition: Option<&str>) -> bool {
+    let Some(condition) = condition else {
+        return true;
+    };
+    variables.check(condition).unwrap_or_else(|e| {
+        warn!("Dialogue condition '{}' failed: {}", condition, e);
+        false
+    })
+}
+
+/// Run an optional action, collecting the events it calls
+fn run_action(
+    variables: &mut DialogueVariables,
+    action: Option<&str>,
+    calls: &mut Vec<DialogueCall>,
+) {
+    let Some(action) = action else {
+        return;
+    };
+    match variables.execute(action) {
+        Ok(called) => calls.extend(called),
+        Err(e) => warn!("Dialogue action '{}' failed: {}", action, e),
+    }
+}
+
+/// Move the runner to a node, following condition and action nodes until one
+/// that waits for the player (Text, Choice or End)
+///
+/// Nodes whose condition fails are skipped. The dialogue ends when a link is
+/// missing. Returns the events called by actions along the way.
+fn enter_node(
+    runner: &mut DialogueRunner,
+    tree: &DialogueTree,
+    node_id: Option<&str>,
+    variables: &mut DialogueVariables,
+) -> Vec<DialogueCall> {
+    let mut calls = Vec::new();
+    let mut next = node_id.map(str::to_string);
+
+    for _ in 0..MAX_AUTOMATIC_NODES {
+        let Some(id) = next else {
+            runner.end();
+            return calls;
+        };
+        let Some(node) = tree.get_node(&id) else {
+            warn!("Dialogue '{}' has no node '{}'", tree.name, id);
+            runner.end();
+            return calls;
+        };
+
+        let passed = condition_passes(variables, node.condition.as_deref());
+        next = match node.node_type {
+            DialogueNodeType::Condition if passed => node.next_node.clone(),
+            DialogueNodeType::Condition => node.else_node.clone(),
+            _ if !passed => node.next_node.clone(),
+            DialogueNodeType::Action => {
+                run_action(variables, node.action.as_deref(), &mut calls);
+                node.next_node.clone()
+            }
+            _ => {
+                run_action(variables, node.action.as_deref(), &mut calls);
+                runner.advance_to(id);
+                return calls;
+            }
+        };
+    }
+
+    warn!(
+        "Dialogue '{}' followed {} condition/action nodes in a row, ending it",
+        tree.name, MAX_AUTOMATIC_NODES
+    );
+    runner.end();
+    calls
+}
+
+/// Messages and commands written as dialogues advance
+#[derive(SystemParam)]
+struct DialogueOutput<'w, 's> {
+    commands: Commands<'w, 's>,
+    end_events: MessageWriter<'w, DialogueEndEvent>,
+    action_events: MessageWriter<'w, DialogueActionEvent>,
+    registry: Res<'w, DialogueEventRegistry>,
+}
+
+impl DialogueOutput<'_, '_> {
+    /// Send the events called by actions, and the end event if the dialogue ended
+    fn send(&mut self, runner: &DialogueRunner, speaker: Option<Entity>, calls: Vec<DialogueCall>) {
+        for call in calls {
+            self.registry.dispatch(&mut self.commands, &call);
+            self.action_events.write(DialogueActionEvent {
+                speaker_entity: speaker,
+                name: call.name,
+                args: call.args,
+            });
+        }
+        if !runner.active {
+            if let Some(entity) = speaker {
+                self.end_events.write(DialogueEndEvent {
+                    speaker_entity: entity,
+                });
+            }
+        }
+    }
+}
+
 /// System to handle starting dialogues
 fn handle_start_dialogue(
     mut events: MessageReader<StartDialogueEvent>,
     mut runner: ResMut<DialogueRunner>,
+    mut variables: ResMut<DialogueVariables>,
+    mut output: DialogueOutput,
     dialogues: Res<Assets<DialogueTree>>,
 ) {
     for event in events.read() {
@@ -481,6 +838,9 @@ fn handle_start_dialogue(
                     event.dialogue.clone(),
                     tree.start_node.clone(),
                 );
+                // The start node may be a condition or action node
+                let calls = enter_node(&mut runner, tree, Some(&tree.start_node), &mut variables);
+                output.send(&runner, Some(event.speaker_entity), calls);
             }
         }
     }
@@ -489,8 +849,9 @@ fn handle_start_dialogue(
 /// System to handle dialogue choices
 fn handle_dialogue_choice(
     mut choice_events: MessageReader<DialogueChoiceEvent>,
-    mut end_events: MessageWriter<DialogueEndEvent>,
     mut runner: ResMut<DialogueRunner>,
+    mut variables: ResMut<DialogueVariables>,
+    mut output: DialogueOutput,
     dialogues: Res<Assets<DialogueTree>>,
 ) {
     for event in choice_events.read() {
@@ -498,66 +859,118 @@ fn handle_dialogue_choice(
             continue;
         }
 
-        let Some(handle) = &runner.dialogue_handle else {
-            continue;
-        };
-        let Some(tree) = dialogues.get(handle) else {
-            continue;
-        };
-        let Some(current_id) = &runner.current_node_id else {
+        let Some(tree) = runner
+            .dialogue_handle
+            .as_ref()
+            .and_then(|handle| dialogues.get(handle))
+        else {
             continue;
         };
-        let Some(node) = tree.get_node(current_id) else {
+        let Some(node) = runner
+            .current_node_id
+            .as_ref()
+            .and_then(|id| tree.get_node(id))
+        else {
             continue;
         };
+        let speaker = runner.speaker_entity;
 
         // Handle based on node type
-        match node.node_type {
+        let calls = match node.node_type {
             DialogueNodeType::Text => {
                 // Advance to next node or end
-                if let Some(next) = &node.next_node {
-                    runner.advance_to(next.clone());
-                } else {
-                    let speaker = runner.speaker_entity;
-                    runner.end();
-                    if let Some(entity) = speaker {
-                        end_events.write(DialogueEndEvent {
-                            speaker_entity: entity,
-                        });
-                    }
-                }
+                enter_node(&mut runner, tree, node.next_node.as_deref(), &mut variables)
             }
             DialogueNodeType::Choice => {
-                // Select the choice and advance
-                if let Some(choice) = node.choices.get(event.choice_index) {
-                    if let Some(next) = &choice.next_node {
-                        runner.advance_to(next.clone());
-                    } else {
-                        let speaker = runner.speaker_entity;
-                        runner.end();
-                        if let Some(entity) = speaker {
-                            end_events.write(DialogueEndEvent {
-                                speaker_entity: entity,
-                            });
-                        }
-                    }
-                }
+                // Hidden and disabled choices can't be selected
+                let Some(choice) = node
+                    .choices
+                    .get(event.choice_index)
+                    .filter(|choice| choice.is_available(&variables))
+                else {
+                    continue;
+                };
+                let mut calls = Vec::new();
+                run_action(&mut variables, choice.action.as_deref(), &mut calls);
+                calls.extend(enter_node(
+                    &mut runner,
+                    tree,
+                    choice.next_node.as_deref(),
+                    &mut variables,
+                ));
+                calls
             }
             DialogueNodeType::End => {
-                let speaker = runner.speaker_entity;
                 runner.end();
-                if let Some(entity) = speaker {
-                    end_events.write(DialogueEndEvent {
-                        speaker_entity: entity,
-                    });
-                }
+                Vec::new()
             }
-            _ => {
-                // For condition/action nodes, advance to next
-                if let Some(next) = &node.next_node {
-                    runner.advance_to(next.clone());
-                }
+            DialogueNodeType::Condition | DialogueNodeType::Action => {
+                // Only reached when the runner was moved onto the node directly
+                let id = runner.current_node_id.clone();
+                enter_node(&mut runner, tree, id.as_deref(), &mut variables)
             }
-        }
+        };
+        output.send(&runner, speaker, calls);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_condition_and_action_nodes() {
+        let mut tree = DialogueTree::empty("shop");
+        let rich = tree.add_node(DialogueNode::new_text("Merchant", "Welcome back!"));
+        let poor = tree.add_node(DialogueNode::new_text("Merchant", "Come back with gold."));
+        let pay = tree.add_node(
+            DialogueNode::new_action("gold -= 10; give_item(\"sword\")").with_next(rich.clone()),
+        );
+        let check = tree.add_node(
+            DialogueNode::new_condition("gold >= 10")
+                .with_next(pay.clone())
+                .with_else(poor.clone()),
+        );
+        assert!(tree.validate().is_err(), "no start node yet");
+        tree.set_start_node(check.clone());
+        assert!(tree.validate().is_ok());
+
+        let mut runner = DialogueRunner::default();
+        let mut variables = DialogueVariables::default();
+        variables.set("gold", 15);
+
+        // Passing condition runs the action node and stops at the text node
+        let calls = enter_node(&mut runner, &tree, Some(&check), &mut variables);
+        assert_eq!(runner.current_node_id.as_deref(), Some(rich.as_str()));
+        assert_eq!(variables.get("gold"), Some(&DialogueValue::Number(5.0)));
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "give_item");
+
+        // Failing condition takes the else branch
+        let calls = enter_node(&mut runner, &tree, Some(&check), &mut variables);
+        assert_eq!(runner.current_node_id.as_deref(), Some(poor.as_str()));
+        assert!(calls.is_empty());
+
+        // A missing link ends the dialogue
+        runner.active = true;
+        enter_node(&mut runner, &tree, None, &mut variables);
+        assert!(!runner.is_active());
+
+        // Choices whose condition fails are hidden unless shown disabled
+        let node = DialogueNode::new_choice("Merchant", "Buy?")
+            .with_choice(DialogueChoice::new("Buy", "b").with_condition("gold >= 10"))
+            .with_choice(DialogueChoice {
+                show_disabled: true,
+                ..DialogueChoice::new("Haggle", "h").with_condition("charisma > 5")
+            })
+            .with_choice(DialogueChoice::new("Leave", "l"));
+        let choices = node.available_choices(&variables);
+        assert_eq!(choices.len(), 2);
+        assert_eq!((choices[0].index, choices[0].enabled), (1, false));
+        assert_eq!((choices[1].index, choices[1].enabled), (2, true));
+
+        // Invalid expressions are reported by validation
+        tree.get_node_mut(&check).unwrap().condition = Some("gold >=".to_string());
+        assert!(tree.validate().is_err());
     }
 }
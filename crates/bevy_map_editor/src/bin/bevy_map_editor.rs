@@ -2,6 +2,7 @@
 //!
 //! Install with: cargo install bevy_map_editor
 //! Run with: bevy_map_editor
+//! Pipeline tools: bevy_map_editor help
 
 use bevy::asset::{AssetPlugin, UnapprovedPathMode};
 use bevy::image::{ImageFilterMode, ImageSamplerDescriptor};
@@ -13,6 +14,12 @@ use bevy_map_editor::EditorPlugin;
 use std::path::PathBuf;
 
 fn main() {
+    // Command-line tools run without opening the editor window
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = bevy_map_editor::cli::run(&args) {
+        std::process::exit(code);
+    }
+
     App::new()
         .add_plugins(
             DefaultPlugins
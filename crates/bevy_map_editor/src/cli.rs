@@ -0,0 +1,273 @@
+//! Command-line tools for build pipelines
+//!
+//! The editor binary runs these instead of opening a window when its first
+//! argument is a command name:
+//!
+//! ```bash
+//! bevy_map_editor export-csv game.map.json Item items.csv
+//! bevy_map_editor import-csv game.map.json Item items.csv --dry-run
+//! ```
+
+use bevy_map_schema::{export_csv, format_cell, import_csv, CsvChange, CsvImport};
+use std::path::Path;
+
+use crate::project::Project;
+
+const USAGE: &str = "\
+Usage:
+  bevy_map_editor export-csv <project.map.json> <type> [output.csv]
+  bevy_map_editor import-csv <project.map.json> <type> <input.csv> [--dry-run]";
+
+/// Run a command-line tool, returning its exit code
+///
+/// Returns `None` if `args` (without the program name) don't name a tool, in
+/// which case the editor should start normally.
+pub fn run(args: &[String]) -> Option<i32> {
+    let (command, rest) = args.split_first()?;
+    let result = match command.as_str() {
+        "export-csv" => export(rest),
+        "import-csv" => import(rest),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            return Some(0);
+        }
+        _ => return None,
+    };
+
+    Some(match result {
+        Ok(()) => 0,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            1
+        }
+    })
+}
+
+fn load_project(path: &str) -> Result<Project, String> {
+    Project::load(Path::new(path)).map_err(|e| format!("failed to load '{}': {}", path, e))
+}
+
+fn export(args: &[String]) -> Result<(), String> {
+    let [project_path, type_name, rest @ ..] = args else {
+        return Err(format!("missing arguments\n{}", USAGE));
+    };
+    let project = load_project(project_path)?;
+    let csv = export_csv(
+        &project.schema,
+        type_name,
+        project.data.get_by_type(type_name),
+    )
+    .map_err(|e| e.to_string())?;
+
+    match rest {
+        [] => print!("{}", csv),
+        [output] => std::fs::write(output, csv)
+            .map_err(|e| format!("failed to write '{}': {}", output, e))?,
+        _ => return Err(format!("too many arguments\n{}", USAGE)),
+    }
+    Ok(())
+}
+
+fn import(args: &[String]) -> Result<(), String> {
+    let dry_run = args.iter().any(|arg| arg == "--dry-run");
+    let args: Vec<&String> = args.iter().filter(|arg| *arg != "--dry-run").collect();
+    let [project_path, type_name, input] = args[..] else {
+        return Err(format!(
+            "expected a project, a type and a CSV file\n{}",
+            USAGE
+        ));
+    };
+
+    let mut project = load_project(project_path)?;
+    let csv =
+        std::fs::read_to_string(input).map_err(|e| format!("failed to read '{}': {}", input, e))?;
+    let import = import_csv(
+        &project.schema,
+        type_name,
+        &csv,
+        project.data.get_by_type(type_name),
+    )
+    .map_err(|e| e.to_string())?;
+
+    print_import(&import);
+    if !import.is_valid() {
+        for error in &import.errors {
+            eprintln!("{}", error);
+        }
+        return Err(format!(
+            "{} row(s) failed validation, nothing was imported",
+            import.errors.len()
+        ));
+    }
+
+    if !dry_run && import.changed_rows().next().is_some() {
+        import.apply(&mut project.data);
+        project
+            .save_current()
+            .map_err(|e| format!("failed to save '{}': {}", project_path, e))?;
+    }
+    Ok(())
+}
+
+/// Print the rows an import adds and updates, with their changed values
+fn print_import(import: &CsvImport) {
+    for row in import.changed_rows() {
+        let marker = if row.change == CsvChange::Added {
+            "+"
+        } else {
+            "~"
+        };
+        println!("{} {}", marker, row.label());
+        for change in &row.changes {
+            println!(
+                "    {}: {} -> {}",
+                change.property,
+                change.before.as_ref().map(format_cell).unwrap_or_default(),
+                change.after.as_ref().map(format_cell).unwrap_or_default()
+            );
+        }
+    }
+    println!(
+        "{} added, {} updated, {} unchanged",
+        import.count(CsvChange::Added),
+        import.count(CsvChange::Updated),
+        import.count(CsvChange::Unchanged)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_map_core::{DataInstance, Value};
+    use bevy_map_schema::{PropType, PropertyDef, Schema, TypeDef};
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("bevy_map_cli_{}_{}", uuid::Uuid::new_v4(), name))
+    }
+
+    fn item(name: &str, value: i64) -> DataInstance {
+        let mut instance = DataInstance::new("Item".to_string());
+        instance
+            .properties
+            .insert("name".to_string(), Value::String(name.to_string()));
+        instance
+            .properties
+            .insert("value".to_string(), Value::Int(value));
+        instance
+    }
+
+    /// Save a project with a few items and return its path
+    fn save_item_project() -> PathBuf {
+        let mut schema = Schema::default();
+        schema.data_types.insert(
+            "Item".to_string(),
+            TypeDef {
+                properties: vec![
+                    PropertyDef::new("name", PropType::String),
+                    PropertyDef::new("value", PropType::Int),
+                    PropertyDef::new("notes", PropType::Multiline),
+                ],
+                ..Default::default()
+            },
+        );
+        let mut project = Project::new(schema);
+        let mut sword = item("Sword, \"Long\"", 10);
+        sword.properties.insert(
+            "notes".to_string(),
+            Value::String("Sharp\nHeavy".to_string()),
+        );
+        project.data.add(sword);
+        project.data.add(item("Shield", 5));
+
+        let path = temp_path("items.map.json");
+        project.save(&path).unwrap();
+        path
+    }
+
+    fn run_args(args: &[&str]) -> Option<i32> {
+        run(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_csv_export_import_round_trip() {
+        let project_path = save_item_project();
+        let csv_path = temp_path("items.csv");
+        let project_arg = project_path.to_str().unwrap();
+        let csv_arg = csv_path.to_str().unwrap();
+
+        assert_eq!(
+            run_args(&["export-csv", project_arg, "Item", csv_arg]),
+            Some(0)
+        );
+        let csv = std::fs::read_to_string(&csv_path).unwrap();
+        assert!(csv.starts_with("id,name,value,notes\n"));
+        assert!(csv.contains(r#","Sword, ""Long""",10,"Sharp"#));
+
+        // Importing the export unchanged leaves the project as it was
+        let saved = std::fs::read_to_string(&project_path).unwrap();
+        assert_eq!(
+            run_args(&["import-csv", project_arg, "Item", csv_arg]),
+            Some(0)
+        );
+        assert_eq!(std::fs::read_to_string(&project_path).unwrap(), saved);
+
+        // Update a row and add one with a quoted name
+        let edited = csv.replace(",Shield,5,", ",Shield,7,") + "\"\",\"Bow, Short\",3,\n";
+        std::fs::write(&csv_path, edited).unwrap();
+        assert_eq!(
+            run_args(&["import-csv", project_arg, "Item", csv_arg, "--dry-run"]),
+            Some(0)
+        );
+        assert_eq!(std::fs::read_to_string(&project_path).unwrap(), saved);
+        assert_eq!(
+            run_args(&["import-csv", project_arg, "Item", csv_arg]),
+            Some(0)
+        );
+
+        let project = Project::load(&project_path).unwrap();
+        let value = |name: &str| {
+            project
+                .data
+                .find_by_name("Item", name)
+                .and_then(|item| item.properties.get("value").cloned())
+        };
+        assert_eq!(project.data.get_by_type("Item").len(), 3);
+        assert_eq!(value("Shield"), Some(Value::Int(7)));
+        assert_eq!(value("Bow, Short"), Some(Value::Int(3)));
+        assert_eq!(value("Sword, \"Long\""), Some(Value::Int(10)));
+        let sword = project
+            .data
+            .find_by_name("Item", "Sword, \"Long\"")
+            .unwrap();
+        assert_eq!(
+            sword.properties.get("notes"),
+            Some(&Value::String("Sharp\nHeavy".to_string()))
+        );
+
+        let _ = std::fs::remove_file(project_path);
+        let _ = std::fs::remove_file(csv_path);
+    }
+
+    #[test]
+    fn test_csv_import_rejects_unknown_columns() {
+        let project_path = save_item_project();
+        let csv_path = temp_path("unknown.csv");
+        std::fs::write(&csv_path, "name,weight\nShield,3\n").unwrap();
+        let saved = std::fs::read_to_string(&project_path).unwrap();
+
+        assert_eq!(
+            run_args(&[
+                "import-csv",
+                project_path.to_str().unwrap(),
+                "Item",
+                csv_path.to_str().unwrap(),
+            ]),
+            Some(1)
+        );
+        assert_eq!(std::fs::read_to_string(&project_path).unwrap(), saved);
+
+        let _ = std::fs::remove_file(project_path);
+        let _ = std::fs::remove_file(csv_path);
+    }
+}
@@ -0,0 +1,129 @@
+//! Undoable data instance imports and bulk property edits
+
+use bevy_map_core::Value;
+use bevy_map_schema::CsvImport;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::{Command, InstanceLocation};
+use crate::project::{DataInstance, Project};
+use crate::render::RenderState;
+
+/// Command that applies a previewed CSV import to the project's data instances
+pub struct DataImportCommand {
+    description: String,
+    /// Existing instances whose properties change: (id, before, after)
+    updated: Vec<(Uuid, HashMap<String, Value>, HashMap<String, Value>)>,
+    /// New instances
+    added: Vec<DataInstance>,
+}
+
+impl DataImportCommand {
+    pub fn new(import: &CsvImport) -> Self {
+        let mut updated = Vec::new();
+        let mut added = Vec::new();
+        for row in import.changed_rows() {
+            match &row.before {
+                Some(before) => updated.push((
+                    before.id,
+                    before.properties.clone(),
+                    row.after.properties.clone(),
+                )),
+                None => added.push(row.after.clone()),
+            }
+        }
+
+        Self {
+            description: format!("Import {} CSV", import.type_name),
+            updated,
+            added,
+        }
+    }
+
+    fn set_properties(&self, project: &mut Project, after: bool) {
+        for (id, before_props, after_props) in &self.updated {
+            if let Some(instance) = project.data.get_mut(*id) {
+                instance.properties = if after { after_props } else { before_props }.clone();
+            }
+        }
+    }
+}
+
+impl Command for DataImportCommand {
+    fn execute(&self, project: &mut Project, _render_state: &mut RenderState) {
+        self.set_properties(project, true);
+        for instance in &self.added {
+            project.data.add(instance.clone());
+        }
+    }
+
+    fn undo(&self, project: &mut Project, _render_state: &mut RenderState) {
+        for instance in &self.added {
+            project.data.remove(instance.id);
+        }
+        self.set_properties(project, false);
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+/// Command that sets one property on several entities or data instances
+pub struct SetPropertyCommand {
+    description: String,
+    property: String,
+    value: Value,
+    /// Each instance's previous value, `None` if the property was unset
+    before: Vec<(InstanceLocation, Option<Value>)>,
+}
+
+impl SetPropertyCommand {
+    /// Capture the current values of `property` on the instances that exist
+    pub fn new(
+        project: &Project,
+        targets: impl IntoIterator<Item = InstanceLocation>,
+        property: &str,
+        value: Value,
+    ) -> Self {
+        let before: Vec<_> = targets
+            .into_iter()
+            .filter_map(|location| {
+                let properties = location.properties(project)?;
+                Some((location, properties.get(property).cloned()))
+            })
+            .collect();
+
+        Self {
+            description: format!("Set {} on {} instance(s)", property, before.len()),
+            property: property.to_string(),
+            value,
+            before,
+        }
+    }
+}
+
+impl Command for SetPropertyCommand {
+    fn execute(&self, project: &mut Project, _render_state: &mut RenderState) {
+        for (location, _) in &self.before {
+            if let Some(properties) = location.properties_mut(project) {
+                properties.insert(self.property.clone(), self.value.clone());
+            }
+        }
+    }
+
+    fn undo(&self, project: &mut Project, _render_state: &mut RenderState) {
+        for (location, before) in &self.before {
+            if let Some(properties) = location.properties_mut(project) {
+                match before {
+                    Some(value) => properties.insert(self.property.clone(), value.clone()),
+                    None => properties.remove(&self.property),
+                };
+            }
+        }
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+}
@@ -2,10 +2,16 @@
 
 pub mod clipboard;
 mod command;
+mod data;
+mod schema;
 mod shortcuts;
+mod tileset;
 
 pub use clipboard::TileClipboard;
 pub use command::{
     collect_tiles_in_region, BatchTileCommand, Command, CommandHistory, MoveEntityCommand,
 };
+pub use data::{DataImportCommand, SetPropertyCommand};
+pub use schema::{InstanceLocation, RefactoredInstance, SchemaRefactorCommand};
 pub use shortcuts::handle_keyboard_shortcuts;
+pub use tileset::TilePropertiesCommand;
@@ -0,0 +1,234 @@
+//! Undoable schema refactoring
+
+use bevy_map_core::{EntityInstance, Value};
+use bevy_map_schema::{RefactorFailure, Schema, SchemaRefactor};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::Command;
+use crate::project::{DataInstance, Project};
+use crate::render::RenderState;
+
+/// Where a refactored instance lives in the project
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InstanceLocation {
+    /// An entity placed in a level
+    Entity { level_id: Uuid, entity_id: Uuid },
+    /// A data instance
+    Data { id: Uuid },
+}
+
+impl InstanceLocation {
+    /// The instance's properties, if it still exists
+    pub fn properties(self, project: &Project) -> Option<&HashMap<String, Value>> {
+        match self {
+            InstanceLocation::Entity {
+                level_id,
+                entity_id,
+            } => project
+                .get_level(level_id)
+                .and_then(|level| level.get_entity(entity_id))
+                .map(|entity| &entity.properties),
+            InstanceLocation::Data { id } => {
+                project.data.get(id).map(|instance| &instance.properties)
+            }
+        }
+    }
+
+    /// Mutable access to the instance's properties, if it still exists
+    pub fn properties_mut(self, project: &mut Project) -> Option<&mut HashMap<String, Value>> {
+        match self {
+            InstanceLocation::Entity {
+                level_id,
+                entity_id,
+            } => project
+                .get_level_mut(level_id)
+                .and_then(|level| level.get_entity_mut(entity_id))
+                .map(|entity| &mut entity.properties),
+            InstanceLocation::Data { id } => project
+                .data
+                .get_mut(id)
+                .map(|instance| &mut instance.properties),
+        }
+    }
+}
+
+/// An instance whose properties are changed by a refactor
+pub struct RefactoredInstance {
+    pub location: InstanceLocation,
+    pub type_name: String,
+    /// Display label (the instance's `name` property, or its type and ID)
+    pub label: String,
+    before: HashMap<String, Value>,
+    after: HashMap<String, Value>,
+    /// Values that could not be converted and are removed
+    pub failures: Vec<RefactorFailure>,
+}
+
+/// Command that applies a [`SchemaRefactor`] to the schema and every instance
+///
+/// Created from the current project without modifying it, so the affected
+/// instances can be previewed before the command is executed.
+pub struct SchemaRefactorCommand {
+    description: String,
+    before_schema: Schema,
+    after_schema: Schema,
+    /// Instances whose properties change
+    pub instances: Vec<RefactoredInstance>,
+    /// Placed entities deleted along with their type: (level, index, entity)
+    pub removed_entities: Vec<(Uuid, usize, EntityInstance)>,
+    /// Data instances deleted along with their type
+    pub removed_data: Vec<DataInstance>,
+}
+
+impl SchemaRefactorCommand {
+    /// Preview a refactor against the current project
+    pub fn new(refactor: SchemaRefactor, project: &Project) -> Self {
+        let before_schema = project.schema.clone();
+        let mut after_schema = before_schema.clone();
+        refactor.apply_to_schema(&mut after_schema);
+
+        let mut instances = Vec::new();
+        let mut removed_entities = Vec::new();
+        let mut removed_data = Vec::new();
+
+        for level in &project.levels {
+            for (index, entity) in level.entities.iter().enumerate() {
+                if refactor.deletes_instances_of(&entity.type_name) {
+                    removed_entities.push((level.id, index, entity.clone()));
+                    continue;
+                }
+                let location = InstanceLocation::Entity {
+                    level_id: level.id,
+                    entity_id: entity.id,
+                };
+                instances.extend(refactor_instance(
+                    &refactor,
+                    &before_schema,
+                    location,
+                    &entity.type_name,
+                    &entity.properties,
+                ));
+            }
+        }
+
+        let mut type_names: Vec<&String> = project.data.instances.keys().collect();
+        type_names.sort();
+        for type_name in type_names {
+            for instance in &project.data.instances[type_name] {
+                if refactor.deletes_instances_of(&instance.type_name) {
+                    removed_data.push(instance.clone());
+                    continue;
+                }
+                let location = InstanceLocation::Data { id: instance.id };
+                instances.extend(refactor_instance(
+                    &refactor,
+                    &before_schema,
+                    location,
+                    &instance.type_name,
+                    &instance.properties,
+                ));
+            }
+        }
+
+        Self {
+            description: refactor.description(),
+            before_schema,
+            after_schema,
+            instances,
+            removed_entities,
+            removed_data,
+        }
+    }
+
+    /// Total number of instances changed or deleted
+    pub fn affected_count(&self) -> usize {
+        self.instances.len() + self.removed_entities.len() + self.removed_data.len()
+    }
+
+    /// Number of values that could not be converted
+    pub fn failure_count(&self) -> usize {
+        self.instances.iter().map(|i| i.failures.len()).sum()
+    }
+
+    fn set_properties(&self, project: &mut Project, after: bool) {
+        for instance in &self.instances {
+            let properties = if after {
+                &instance.after
+            } else {
+                &instance.before
+            };
+            if let Some(target) = instance.location.properties_mut(project) {
+                *target = properties.clone();
+            }
+        }
+    }
+}
+
+impl Command for SchemaRefactorCommand {
+    fn execute(&self, project: &mut Project, render_state: &mut RenderState) {
+        project.schema = self.after_schema.clone();
+        self.set_properties(project, true);
+        for (level_id, _, entity) in &self.removed_entities {
+            if let Some(level) = project.get_level_mut(*level_id) {
+                level.remove_entity(entity.id);
+            }
+        }
+        for instance in &self.removed_data {
+            project.data.remove(instance.id);
+        }
+        render_state.needs_rebuild = true;
+    }
+
+    fn undo(&self, project: &mut Project, render_state: &mut RenderState) {
+        project.schema = self.before_schema.clone();
+        // Entities were recorded in order, so reinserting in order restores their indices
+        for (level_id, index, entity) in &self.removed_entities {
+            if let Some(level) = project.get_level_mut(*level_id) {
+                let index = (*index).min(level.entities.len());
+                level.entities.insert(index, entity.clone());
+            }
+        }
+        for instance in &self.removed_data {
+            project.data.add(instance.clone());
+        }
+        self.set_properties(project, false);
+        render_state.needs_rebuild = true;
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+fn refactor_instance(
+    refactor: &SchemaRefactor,
+    schema: &Schema,
+    location: InstanceLocation,
+    type_name: &str,
+    properties: &HashMap<String, Value>,
+) -> Option<RefactoredInstance> {
+    let mut after = properties.clone();
+    let failures = refactor.apply_to_properties(schema, type_name, &mut after);
+    if after == *properties {
+        return None;
+    }
+
+    let id = match location {
+        InstanceLocation::Entity { entity_id, .. } => entity_id,
+        InstanceLocation::Data { id } => id,
+    };
+    let label = match properties.get("name").and_then(Value::as_string) {
+        Some(name) if !name.is_empty() => format!("{} ({})", name, type_name),
+        _ => format!("{} {}", type_name, &id.to_string()[..8]),
+    };
+
+    Some(RefactoredInstance {
+        location,
+        type_name: type_name.to_string(),
+        label,
+        before: properties.clone(),
+        after,
+        failures,
+    })
+}
@@ -0,0 +1,58 @@
+//! Undoable tileset edits
+
+use bevy_map_core::TileProperties;
+use uuid::Uuid;
+
+use super::Command;
+use crate::project::Project;
+use crate::render::RenderState;
+
+/// Command that replaces the properties of one tileset tile
+pub struct TilePropertiesCommand {
+    description: String,
+    tileset_id: Uuid,
+    tile_index: u32,
+    before: TileProperties,
+    after: TileProperties,
+}
+
+impl TilePropertiesCommand {
+    pub fn new(
+        description: impl Into<String>,
+        tileset_id: Uuid,
+        tile_index: u32,
+        before: TileProperties,
+        after: TileProperties,
+    ) -> Self {
+        Self {
+            description: description.into(),
+            tileset_id,
+            tile_index,
+            before,
+            after,
+        }
+    }
+
+    fn set(&self, project: &mut Project, render_state: &mut RenderState, after: bool) {
+        if let Some(tileset) = project.get_tileset_mut(self.tileset_id) {
+            let properties = if after { &self.after } else { &self.before };
+            tileset.set_tile_properties(self.tile_index, properties.clone());
+            // Tile animations are baked into the viewport's tile entities
+            render_state.needs_rebuild = true;
+        }
+    }
+}
+
+impl Command for TilePropertiesCommand {
+    fn execute(&self, project: &mut Project, render_state: &mut RenderState) {
+        self.set(project, render_state, true);
+    }
+
+    fn undo(&self, project: &mut Project, render_state: &mut RenderState) {
+        self.set(project, render_state, false);
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+}
@@ -24,6 +24,7 @@
 //! ```
 
 pub mod bevy_cli;
+pub mod cli;
 pub mod commands;
 pub mod game_runner;
 pub mod preferences;
@@ -53,7 +54,7 @@ use tools::EditorToolsPlugin;
 use ui::{
     AnimationEditorState, DialogueEditorState, EditorTool, EditorUiPlugin, EntityPaintState,
     GameSettingsDialogState, PendingAction, SchemaEditorState, Selection, SpriteSheetEditorState,
-    TerrainPaintState, TilesetEditorState, ToolMode,
+    TableViewState, TerrainPaintState, TilesetEditorState, ToolMode,
 };
 
 /// Error types for asset path handling
@@ -507,6 +508,7 @@ pub struct EditorState {
     pub tool_mode: ToolMode,
     pub show_grid: bool,
     pub show_collisions: bool,
+    pub show_nav_grid: bool,
     pub snap_to_grid: bool,
     pub zoom: f32,
     pub camera_offset: bevy::math::Vec2,
@@ -519,6 +521,9 @@ pub struct EditorState {
     pub show_schema_editor: bool,
     pub schema_editor_state: SchemaEditorState,
     pub error_message: Option<String>,
+    /// CSV import waiting to be confirmed in the preview dialog
+    pub pending_data_import: Option<bevy_map_schema::CsvImport>,
+    pub table_view_state: TableViewState,
 
     // New project dialog state
     pub new_project_name: String,
@@ -542,6 +547,7 @@ pub struct EditorState {
     pub new_tileset_name: String,
     pub new_tileset_path: String,
     pub new_tileset_tile_size: u32,
+    pub new_tileset_kind: bevy_map_core::TilesetKind,
 
     // Add image to tileset dialog state
     pub show_add_tileset_image_dialog: bool,
@@ -704,6 +710,7 @@ impl Default for EditorState {
             tool_mode: ToolMode::Point,
             show_grid: true,
             show_collisions: false,
+            show_nav_grid: false,
             snap_to_grid: true,
             zoom: 1.0,
             camera_offset: bevy::math::Vec2::ZERO,
@@ -715,6 +722,8 @@ impl Default for EditorState {
             show_schema_editor: false,
             schema_editor_state: SchemaEditorState::default(),
             error_message: None,
+            pending_data_import: None,
+            table_view_state: TableViewState::default(),
 
             new_project_name: String::new(),
             new_project_schema_path: None,
@@ -733,6 +742,7 @@ impl Default for EditorState {
             new_tileset_name: "New Tileset".to_string(),
             new_tileset_path: String::new(),
             new_tileset_tile_size: 32,
+            new_tileset_kind: bevy_map_core::TilesetKind::Atlas,
 
             show_add_tileset_image_dialog: false,
             add_image_name: String::new(),
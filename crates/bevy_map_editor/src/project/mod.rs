@@ -9,6 +9,7 @@ pub use file::*;
 use bevy::prelude::Resource;
 use bevy_map_animation::SpriteData;
 use bevy_map_autotile::AutotileConfig;
+pub use bevy_map_core::{DataInstance, DataStore};
 use bevy_map_core::{EntityTypeConfig, Level, Tileset, WorldConfig};
 use bevy_map_dialogue::DialogueTree;
 use bevy_map_schema::Schema;
@@ -531,76 +532,3 @@ impl Project {
         }
     }
 }
-
-/// A data instance (non-placeable thing like an Item, Quest, etc.)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DataInstance {
-    pub id: Uuid,
-    pub type_name: String,
-    pub properties: HashMap<String, bevy_map_core::Value>,
-}
-
-impl DataInstance {
-    pub fn new(type_name: String) -> Self {
-        Self {
-            id: Uuid::new_v4(),
-            type_name,
-            properties: HashMap::new(),
-        }
-    }
-}
-
-/// Stores all data_type instances (non-placeable things like Items, Quests)
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct DataStore {
-    /// Key: type name (e.g., "Item", "Quest")
-    /// Value: list of instances of that type
-    pub instances: HashMap<String, Vec<DataInstance>>,
-}
-
-impl DataStore {
-    pub fn add(&mut self, instance: DataInstance) {
-        self.instances
-            .entry(instance.type_name.clone())
-            .or_default()
-            .push(instance);
-    }
-
-    pub fn remove(&mut self, id: Uuid) -> Option<DataInstance> {
-        for instances in self.instances.values_mut() {
-            if let Some(pos) = instances.iter().position(|i| i.id == id) {
-                return Some(instances.remove(pos));
-            }
-        }
-        None
-    }
-
-    pub fn get(&self, id: Uuid) -> Option<&DataInstance> {
-        for instances in self.instances.values() {
-            if let Some(instance) = instances.iter().find(|i| i.id == id) {
-                return Some(instance);
-            }
-        }
-        None
-    }
-
-    pub fn get_mut(&mut self, id: Uuid) -> Option<&mut DataInstance> {
-        for instances in self.instances.values_mut() {
-            if let Some(instance) = instances.iter_mut().find(|i| i.id == id) {
-                return Some(instance);
-            }
-        }
-        None
-    }
-
-    pub fn get_by_type(&self, type_name: &str) -> &[DataInstance] {
-        self.instances
-            .get(type_name)
-            .map(|v| v.as_slice())
-            .unwrap_or(&[])
-    }
-
-    pub fn all_instances(&self) -> impl Iterator<Item = &DataInstance> {
-        self.instances.values().flatten()
-    }
-}
@@ -6,7 +6,7 @@
 
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
-use bevy_map_core::{LayerData, OCCUPIED_CELL};
+use bevy_map_core::{LayerData, NavGrid, TileGridLayout, OCCUPIED_CELL};
 use std::collections::HashMap;
 use uuid::Uuid;
 
@@ -27,15 +27,19 @@ impl Plugin for MapRenderPlugin {
             .init_resource::<BrushPreviewCache>()
             .init_resource::<EntityRenderState>()
             .init_resource::<CollisionOverlayCache>()
+            .init_resource::<NavOverlayCache>()
+            .add_systems(Update, sync_tileset_layouts.before(sync_level_rendering))
             .add_systems(Update, sync_level_rendering)
             .add_systems(Update, sync_layer_visibility)
             .add_systems(Update, sync_grid_rendering)
             .add_systems(Update, sync_collision_rendering)
+            .add_systems(Update, sync_nav_grid_rendering)
             .add_systems(Update, sync_selection_preview)
             .add_systems(Update, sync_tile_selection_highlights)
             .add_systems(Update, sync_terrain_preview)
             .add_systems(Update, sync_brush_preview)
             .add_systems(Update, sync_entity_rendering)
+            .add_systems(Update, animate_editor_tiles)
             .add_systems(Update, update_camera_from_editor_state);
     }
 }
@@ -58,10 +62,28 @@ pub struct RenderState {
     /// Last known grid visibility state
     pub last_grid_visible: bool,
     /// Last rendered level dimensions for grid
-    pub last_grid_dimensions: Option<(u32, u32, u32)>, // (width, height, tile_size)
+    pub last_grid_dimensions: Option<(u32, u32, u32, u32)>, // (width, height, tile_width, tile_height)
     /// Multi-cell tile sprites: (level_id, layer_index, x, y) -> sprite entity
     /// These are rendered as separate Sprites instead of TileBundle to span multiple cells
     pub multi_cell_sprites: HashMap<(Uuid, usize, u32, u32), Entity>,
+    /// Last known layout of each tileset, used to rebuild when it changes
+    pub tileset_layouts: HashMap<Uuid, TilesetLayout>,
+}
+
+/// Tile size, margin, spacing and draw offset of a tileset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TilesetLayout {
+    pub grid: TileGridLayout,
+    pub draw_offset: [i32; 2],
+}
+
+impl TilesetLayout {
+    pub fn of(tileset: &bevy_map_core::Tileset) -> Self {
+        Self {
+            grid: tileset.grid_layout(),
+            draw_offset: tileset.draw_offset,
+        }
+    }
 }
 
 impl RenderState {
@@ -99,6 +121,10 @@ pub struct SelectionPreview;
 #[derive(Component)]
 pub struct CollisionOverlay;
 
+/// Marker component for navigation grid overlays
+#[derive(Component)]
+pub struct NavOverlay;
+
 /// Marker component for multi-cell tile sprites
 #[derive(Component)]
 pub struct MultiCellTileSprite {
@@ -108,6 +134,21 @@ pub struct MultiCellTileSprite {
     pub y: u32,
 }
 
+/// Component for tiles that play an animation in the viewport
+#[derive(Component, Deref)]
+pub struct AnimatedEditorTile(pub bevy_map_core::TileAnimation);
+
+impl AnimatedEditorTile {
+    /// Build a viewport animation for a tile, keeping only frames from `image_index`
+    fn from_tileset(
+        tileset: &bevy_map_core::Tileset,
+        tile_index: u32,
+        image_index: usize,
+    ) -> Option<Self> {
+        tileset.tile_animation(tile_index, image_index).map(Self)
+    }
+}
+
 /// Cache for collision overlay entities (for efficient updates)
 #[derive(Resource, Default)]
 pub struct CollisionOverlayCache {
@@ -119,11 +160,140 @@ pub struct CollisionOverlayCache {
     pub last_level: Option<Uuid>,
 }
 
+/// Cache for navigation grid overlay entities
+#[derive(Resource, Default)]
+pub struct NavOverlayCache {
+    /// Overlay entities, one per blocked or weighted cell
+    pub entities: Vec<Entity>,
+    /// Grid the overlay was built from (None when hidden)
+    pub grid: Option<NavGrid>,
+    /// Level the overlay was built for
+    pub last_level: Option<Uuid>,
+}
+
+/// System to keep tileset image grids and tilemap atlases in sync with each
+/// tileset's tile size, margin and spacing
+///
+/// bevy_ecs_tilemap assumes an image's margin equals its tile spacing and
+/// counts columns from the image width, so images that don't match get a
+/// re-laid-out copy for tilemaps (see `TileGridLayout::needs_atlas`).
+fn sync_tileset_layouts(
+    mut project: ResMut<Project>,
+    mut cache: ResMut<TilesetTextureCache>,
+    mut images: ResMut<Assets<Image>>,
+    mut render_state: ResMut<RenderState>,
+) {
+    if !project.is_changed() && !cache.is_changed() {
+        return;
+    }
+
+    let mut grid_updates: Vec<(Uuid, usize, u32, u32)> = Vec::new();
+    let mut size_updates: Vec<(Uuid, usize, u32, u32)> = Vec::new();
+    let mut atlas_updates: Vec<(Uuid, Option<TileGridLayout>)> = Vec::new();
+    for tileset in project.tilesets.iter() {
+        let layout = TilesetLayout::of(tileset);
+        if render_state.tileset_layouts.get(&tileset.id) != Some(&layout) {
+            render_state.tileset_layouts.insert(tileset.id, layout);
+            render_state.needs_rebuild = true;
+        }
+
+        let grid_layout = tileset.grid_layout();
+        for (image_index, image) in tileset.images.iter().enumerate() {
+            let Some((_, _, width, height)) = cache.loaded.get(&image.id) else {
+                continue;
+            };
+            let (width, height) = (*width as u32, *height as u32);
+            let (columns, rows) = tileset.grid_for_image_size(width, height);
+            if (columns, rows) != (image.columns, image.rows) {
+                grid_updates.push((tileset.id, image_index, columns, rows));
+            }
+            // Collection tiles take their size (and cell footprint) from the image
+            if tileset.is_collection() && image.pixel_size() != Some((width, height)) {
+                size_updates.push((tileset.id, image_index, width, height));
+            }
+            // Collection images are drawn whole as sprites, so they never need an atlas
+            let needed = grid_layout
+                .needs_atlas(width, height)
+                .then_some(grid_layout);
+            let prepared = cache.atlases.get(&image.id).map(|(_, layout)| *layout);
+            if prepared != needed {
+                atlas_updates.push((image.id, needed));
+            }
+        }
+    }
+
+    for (tileset_id, image_index, columns, rows) in grid_updates {
+        if let Some(tileset) = project.get_tileset_mut(tileset_id) {
+            if let Some(image) = tileset.images.get_mut(image_index) {
+                image.columns = columns;
+                image.rows = rows;
+            }
+            if image_index == 0 {
+                tileset.columns = columns;
+                tileset.rows = rows;
+            }
+        }
+        render_state.needs_rebuild = true;
+    }
+
+    for (tileset_id, image_index, width, height) in size_updates {
+        if let Some(image) = project
+            .get_tileset_mut(tileset_id)
+            .and_then(|tileset| tileset.images.get_mut(image_index))
+        {
+            image.width = width;
+            image.height = height;
+        }
+        render_state.needs_rebuild = true;
+    }
+
+    for (image_id, layout) in atlas_updates {
+        render_state.needs_rebuild = true;
+        let Some(layout) = layout else {
+            cache.atlases.remove(&image_id);
+            continue;
+        };
+        let Some(handle) = cache
+            .loaded
+            .get(&image_id)
+            .map(|(handle, ..)| handle.clone())
+        else {
+            continue;
+        };
+        let Some(image) = images.get(handle.id()) else {
+            continue;
+        };
+        let format = image.texture_descriptor.format;
+        let (Some(data), Some(bytes_per_pixel), false) = (
+            image.data.as_ref(),
+            format.block_copy_size(None),
+            format.is_compressed(),
+        ) else {
+            // Remember the layout so we don't retry every frame
+            cache.atlases.insert(image_id, (handle, layout));
+            warn!("Cannot apply tileset margin/spacing to this texture format");
+            continue;
+        };
+        let (data, width, height) = layout.atlas_pixels(
+            data,
+            image.width(),
+            image.height(),
+            bytes_per_pixel as usize,
+        );
+        let mut atlas = image.clone();
+        atlas.data = Some(data);
+        atlas.texture_descriptor.size.width = width;
+        atlas.texture_descriptor.size.height = height;
+        let atlas_handle = images.add(atlas);
+        cache.atlases.insert(image_id, (atlas_handle, layout));
+    }
+}
+
 /// System to sync level rendering with the project data
 fn sync_level_rendering(
     mut commands: Commands,
     mut render_state: ResMut<RenderState>,
-    editor_state: Res<EditorState>,
+    mut editor_state: ResMut<EditorState>,
     project: Res<Project>,
     tileset_cache: Res<TilesetTextureCache>,
     tilemap_query: Query<Entity, With<EditorTilemap>>,
@@ -131,6 +301,12 @@ fn sync_level_rendering(
 ) {
     let current_level_id = editor_state.selected_level;
 
+    // Tile animations are baked into tile entities, so rebuild when they change
+    if editor_state.tileset_editor_state.animation_changed {
+        editor_state.tileset_editor_state.animation_changed = false;
+        render_state.needs_rebuild = true;
+    }
+
     // Check if we need to switch levels
     if render_state.rendered_level != current_level_id {
         // Despawn all tile entities from storages first (safe - entity may not exist)
@@ -271,14 +447,20 @@ fn spawn_level_tilemaps(
             continue;
         };
 
-        let tile_size = tileset.tile_size;
-        let tile_size_f32 = tile_size as f32;
+        let (tile_width, tile_height) = tileset.tile_dimensions();
+        let tile_size = Vec2::new(tile_width as f32, tile_height as f32);
+        // Draw offset is authored Y-down, Bevy is Y-up
+        let draw_offset = Vec2::new(
+            tileset.draw_offset[0] as f32,
+            -tileset.draw_offset[1] as f32,
+        );
 
         // Group tiles by image (for multi-image tilesets)
         // bevy_ecs_tilemap uses a single texture per tilemap, so we need separate tilemaps per image
         // Also track which tiles are multi-cell (they'll be rendered as Sprites instead)
-        // Tuple: (x, y, local_tile_index, flip_x, flip_y)
-        let mut tiles_by_image: HashMap<usize, Vec<(u32, u32, u32, bool, bool)>> = HashMap::new();
+        // Tuple: (x, y, virtual_tile_index, local_tile_index, flip_x, flip_y)
+        let mut tiles_by_image: HashMap<usize, Vec<(u32, u32, u32, u32, bool, bool)>> =
+            HashMap::new();
         // Tuple: (x, y, virtual_idx, grid_w, grid_h, image_index, flip_x, flip_y)
         let mut multi_cell_tiles: Vec<(u32, u32, u32, u32, u32, usize, bool, bool)> = Vec::new();
 
@@ -299,8 +481,8 @@ fn spawn_level_tilemaps(
                     // Check if this is a multi-cell tile (use base index for tileset lookup)
                     let (grid_width, grid_height) = tileset.get_tile_grid_size(base_tile_index);
 
-                    if grid_width > 1 || grid_height > 1 {
-                        // Multi-cell tile - will be rendered as Sprite
+                    if grid_width > 1 || grid_height > 1 || tileset.is_collection() {
+                        // Multi-cell or collection tile - will be rendered as Sprite
                         if let Some((image_index, _)) = tileset.virtual_to_local(base_tile_index) {
                             multi_cell_tiles.push((
                                 x,
@@ -321,6 +503,7 @@ fn spawn_level_tilemaps(
                             tiles_by_image.entry(image_index).or_default().push((
                                 x,
                                 y,
+                                base_tile_index,
                                 local_tile_index,
                                 flip_x,
                                 flip_y,
@@ -335,8 +518,8 @@ fn spawn_level_tilemaps(
         for (image_index, image_tiles) in tiles_by_image {
             // Get texture handle for this image
             let texture_handle = if let Some(image) = tileset.images.get(image_index) {
-                if let Some((handle, _, _, _)) = tileset_cache.loaded.get(&image.id) {
-                    handle.clone()
+                if let Some(handle) = tileset_cache.tilemap_texture(&image.id) {
+                    handle
                 } else {
                     asset_server.load(crate::to_asset_path(&image.path))
                 }
@@ -352,8 +535,12 @@ fn spawn_level_tilemaps(
             };
 
             let tilemap_tile_size = TilemapTileSize {
-                x: tile_size_f32,
-                y: tile_size_f32,
+                x: tile_size.x,
+                y: tile_size.y,
+            };
+            let spacing = TilemapSpacing {
+                x: tileset.spacing as f32,
+                y: tileset.spacing as f32,
             };
 
             let grid_size: TilemapGridSize = tilemap_tile_size.into();
@@ -361,21 +548,25 @@ fn spawn_level_tilemaps(
             let tilemap_entity = commands.spawn_empty().id();
 
             // Spawn tiles for this image
-            for (x, y, local_tile_index, flip_x, flip_y) in &image_tiles {
+            for (x, y, base_tile_index, local_tile_index, flip_x, flip_y) in &image_tiles {
                 let tile_pos = TilePos { x: *x, y: *y };
-                let tile_entity = commands
-                    .spawn(TileBundle {
-                        position: tile_pos,
-                        tilemap_id: TilemapId(tilemap_entity),
-                        texture_index: TileTextureIndex(*local_tile_index),
-                        flip: TileFlip {
-                            x: *flip_x,
-                            y: *flip_y,
-                            d: false,
-                        },
-                        ..default()
-                    })
-                    .id();
+                let mut tile_commands = commands.spawn(TileBundle {
+                    position: tile_pos,
+                    tilemap_id: TilemapId(tilemap_entity),
+                    texture_index: TileTextureIndex(*local_tile_index),
+                    flip: TileFlip {
+                        x: *flip_x,
+                        y: *flip_y,
+                        d: false,
+                    },
+                    ..default()
+                });
+                if let Some(animation) =
+                    AnimatedEditorTile::from_tileset(tileset, *base_tile_index, image_index)
+                {
+                    tile_commands.insert(animation);
+                }
+                let tile_entity = tile_commands.id();
                 tile_storage.set(&tile_pos, tile_entity);
             }
 
@@ -393,7 +584,8 @@ fn spawn_level_tilemaps(
                     storage: tile_storage.clone(),
                     texture: TilemapTexture::Single(texture_handle),
                     tile_size: tilemap_tile_size,
-                    transform: Transform::from_xyz(0.0, 0.0, layer_z),
+                    spacing,
+                    transform: Transform::from_translation(draw_offset.extend(layer_z)),
                     anchor: TilemapAnchor::BottomLeft,
                     visibility: if layer.visible {
                         Visibility::Inherited
@@ -432,20 +624,24 @@ fn spawn_level_tilemaps(
                     continue;
                 };
 
-            // Calculate local tile position in the tileset image
+            // Source rect in texture coordinates (pixels), accounting for margin and spacing
             let (_, local_tile_index) = tileset.virtual_to_local(virtual_tile_index).unwrap();
-            let tile_col = local_tile_index % image.columns;
-            let tile_row = local_tile_index / image.columns;
-
-            // Source rect in texture coordinates (pixels)
-            // Note: In Bevy textures, Y=0 is at top, but we need to flip for correct sampling
-            let src_x = (tile_col * tile_size) as f32;
-            let src_y = (tile_row * tile_size) as f32;
-            let src_width = (grid_width * tile_size) as f32;
-            let src_height = (grid_height * tile_size) as f32;
-
-            // Create a rect for the source region
-            let rect = bevy::math::Rect::new(src_x, src_y, src_x + src_width, src_y + src_height);
+            let Some([src_x, src_y, src_w, src_h]) =
+                tileset.tile_pixel_rect(image, local_tile_index, (grid_width, grid_height))
+            else {
+                continue;
+            };
+            let rect = bevy::math::Rect::new(
+                src_x as f32,
+                src_y as f32,
+                (src_x + src_w) as f32,
+                (src_y + src_h) as f32,
+            );
+
+            // The sprite covers exactly its grid cells (any spacing in the source is squeezed),
+            // or its own image size for collection tiles
+            let (draw_width, draw_height) = tileset.tile_draw_size(virtual_tile_index);
+            let (src_width, src_height) = (draw_width as f32, draw_height as f32);
 
             // Get origin point (defaults to center if not set)
             let props = tileset
@@ -457,11 +653,10 @@ fn spawn_level_tilemaps(
             // World position: place sprite so origin aligns with grid cell corner
             // For center origin (size/2): sprite center at grid + size/2 (standard behavior)
             // For top-left origin (0): sprite center at grid + 0 (tile shifts left/down)
-            let world_x = x as f32 * tile_size_f32 + origin_x as f32;
-            let world_y = y as f32 * tile_size_f32 + origin_y as f32;
+            let world_x = x as f32 * tile_size.x + origin_x as f32 + draw_offset.x;
+            let world_y = y as f32 * tile_size.y + origin_y as f32 + draw_offset.y;
 
-            // Z-offset slightly above regular tiles in same layer
-            let layer_z = layer_index as f32 * 0.1 + image_index as f32 * 0.01 + 0.001;
+            let layer_z = sprite_tile_z(tileset, layer_index, image_index);
 
             let sprite_entity = commands
                 .spawn((
@@ -497,6 +692,19 @@ fn spawn_level_tilemaps(
     }
 }
 
+/// Z for tiles rendered as sprites, slightly above regular tiles in the same layer.
+///
+/// Collection tilesets have one image per tile, so their sprites share a single
+/// depth instead of being ordered per image.
+fn sprite_tile_z(tileset: &bevy_map_core::Tileset, layer_index: usize, image_index: usize) -> f32 {
+    let image_z = if tileset.is_collection() {
+        0.0
+    } else {
+        image_index as f32 * 0.01
+    };
+    layer_index as f32 * 0.1 + image_z + 0.001
+}
+
 /// Update a single tile in the rendered tilemap
 ///
 /// This function updates both the visual representation and the TileStorage.
@@ -529,8 +737,13 @@ pub fn update_tile(
         return;
     };
 
-    let tile_size = tileset.tile_size;
-    let tile_size_f32 = tile_size as f32;
+    let (tile_width, tile_height) = tileset.tile_dimensions();
+    let tile_size = Vec2::new(tile_width as f32, tile_height as f32);
+    // Draw offset is authored Y-down, Bevy is Y-up
+    let draw_offset = Vec2::new(
+        tileset.draw_offset[0] as f32,
+        -tileset.draw_offset[1] as f32,
+    );
     let tile_pos = TilePos { x, y };
 
     // Skip rendering OCCUPIED_CELL sentinel values (used for multi-cell tiles)
@@ -564,8 +777,8 @@ pub fn update_tile(
         // Check if this is a multi-cell tile
         let (grid_width, grid_height) = tileset.get_tile_grid_size(tile_idx);
 
-        if grid_width > 1 || grid_height > 1 {
-            // Multi-cell tile - render as Sprite
+        if grid_width > 1 || grid_height > 1 || tileset.is_collection() {
+            // Multi-cell or collection tile - render as Sprite
             // Note: Only remove sprite at exact same position (already handled above at lines 507-511)
             // Overlapping tiles are preserved - no cleanup needed here
 
@@ -582,23 +795,19 @@ pub fn update_tile(
 
                 // Get texture handle
                 if let Some(image) = tileset.images.get(image_index) {
-                    if let Some((texture_handle, _, _, _)) = tileset_cache.loaded.get(&image.id) {
-                        // Calculate tile position in tileset image
-                        let tile_col = local_idx % image.columns;
-                        let tile_row = local_idx / image.columns;
-
-                        // Source rect
-                        let src_x = (tile_col * tile_size) as f32;
-                        let src_y = (tile_row * tile_size) as f32;
-                        let src_width = (grid_width * tile_size) as f32;
-                        let src_height = (grid_height * tile_size) as f32;
-
+                    if let (Some((texture_handle, _, _, _)), Some([src_x, src_y, src_w, src_h])) = (
+                        tileset_cache.loaded.get(&image.id),
+                        tileset.tile_pixel_rect(image, local_idx, (grid_width, grid_height)),
+                    ) {
+                        // Source rect in the tileset image
                         let rect = bevy::math::Rect::new(
-                            src_x,
-                            src_y,
-                            src_x + src_width,
-                            src_y + src_height,
+                            src_x as f32,
+                            src_y as f32,
+                            (src_x + src_w) as f32,
+                            (src_y + src_h) as f32,
                         );
+                        let (draw_width, draw_height) = tileset.tile_draw_size(tile_idx);
+                        let (src_width, src_height) = (draw_width as f32, draw_height as f32);
 
                         // Get origin point (defaults to center if not set)
                         let props = tileset
@@ -609,9 +818,9 @@ pub fn update_tile(
                             props.get_origin(src_width as u32, src_height as u32);
 
                         // World position: place sprite so origin aligns with grid cell corner
-                        let world_x = x as f32 * tile_size_f32 + origin_x as f32;
-                        let world_y = y as f32 * tile_size_f32 + origin_y as f32;
-                        let layer_z = layer_index as f32 * 0.1 + image_index as f32 * 0.01 + 0.001;
+                        let world_x = x as f32 * tile_size.x + origin_x as f32 + draw_offset.x;
+                        let world_y = y as f32 * tile_size.y + origin_y as f32 + draw_offset.y;
+                        let layer_z = sprite_tile_z(tileset, layer_index, image_index);
 
                         let sprite_entity = commands
                             .spawn((
@@ -659,12 +868,10 @@ pub fn update_tile(
                 // Create tilemap on-demand if it doesn't exist
                 if !render_state.tile_storages.contains_key(&key) {
                     // Get texture handle from cache
-                    let texture_handle = tileset.images.get(image_index).and_then(|image| {
-                        tileset_cache
-                            .loaded
-                            .get(&image.id)
-                            .map(|(handle, _, _, _)| handle.clone())
-                    });
+                    let texture_handle = tileset
+                        .images
+                        .get(image_index)
+                        .and_then(|image| tileset_cache.tilemap_texture(&image.id));
 
                     if let Some(texture_handle) = texture_handle {
                         let map_size = TilemapSize {
@@ -673,8 +880,12 @@ pub fn update_tile(
                         };
 
                         let tilemap_tile_size = TilemapTileSize {
-                            x: tile_size_f32,
-                            y: tile_size_f32,
+                            x: tile_size.x,
+                            y: tile_size.y,
+                        };
+                        let spacing = TilemapSpacing {
+                            x: tileset.spacing as f32,
+                            y: tileset.spacing as f32,
                         };
 
                         let grid_size: TilemapGridSize = tilemap_tile_size.into();
@@ -692,7 +903,8 @@ pub fn update_tile(
                                 storage: tile_storage.clone(),
                                 texture: TilemapTexture::Single(texture_handle),
                                 tile_size: tilemap_tile_size,
-                                transform: Transform::from_xyz(0.0, 0.0, layer_z),
+                                spacing,
+                                transform: Transform::from_translation(draw_offset.extend(layer_z)),
                                 anchor: TilemapAnchor::BottomLeft,
                                 visibility: if layer_visible {
                                     Visibility::Inherited
@@ -723,19 +935,23 @@ pub fn update_tile(
                     }
 
                     // Spawn new tile with flip applied
-                    let new_tile = commands
-                        .spawn(TileBundle {
-                            position: tile_pos,
-                            tilemap_id: TilemapId(tilemap_entity),
-                            texture_index: TileTextureIndex(local_idx),
-                            flip: TileFlip {
-                                x: flip_x,
-                                y: flip_y,
-                                d: false,
-                            },
-                            ..default()
-                        })
-                        .id();
+                    let mut tile_commands = commands.spawn(TileBundle {
+                        position: tile_pos,
+                        tilemap_id: TilemapId(tilemap_entity),
+                        texture_index: TileTextureIndex(local_idx),
+                        flip: TileFlip {
+                            x: flip_x,
+                            y: flip_y,
+                            d: false,
+                        },
+                        ..default()
+                    });
+                    if let Some(animation) =
+                        AnimatedEditorTile::from_tileset(tileset, tile_idx, image_index)
+                    {
+                        tile_commands.insert(animation);
+                    }
+                    let new_tile = tile_commands.id();
                     storage.set(&tile_pos, new_tile);
                 }
             }
@@ -753,6 +969,21 @@ pub fn update_tile(
     }
 }
 
+/// System to preview animated tiles in the viewport
+fn animate_editor_tiles(
+    time: Res<Time>,
+    mut query: Query<(&AnimatedEditorTile, &mut TileTextureIndex)>,
+) {
+    let elapsed = time.elapsed_secs();
+    for (animation, mut texture_index) in query.iter_mut() {
+        if let Some(frame) = animation.frame_at(elapsed) {
+            if texture_index.0 != frame {
+                texture_index.0 = frame;
+            }
+        }
+    }
+}
+
 /// System to render grid overlay (sprite-based, on top of tilemaps)
 fn sync_grid_rendering(
     mut commands: Commands,
@@ -779,13 +1010,13 @@ fn sync_grid_rendering(
                                 .tilesets
                                 .iter()
                                 .find(|t| t.id == *tileset_id)
-                                .map(|t| t.tile_size)
+                                .map(|t| t.tile_dimensions())
                         } else {
                             None
                         }
                     })
-                    .unwrap_or(32);
-                (level.width, level.height, tile_size)
+                    .unwrap_or((32, 32));
+                (level.width, level.height, tile_size.0, tile_size.1)
             })
     });
 
@@ -809,19 +1040,20 @@ fn sync_grid_rendering(
         return;
     }
 
-    let Some((width, height, tile_size)) = level_info else {
+    let Some((width, height, tile_width, tile_height)) = level_info else {
         return;
     };
 
-    let tile_size_f32 = tile_size as f32;
+    let tile_width = tile_width as f32;
+    let tile_height = tile_height as f32;
     let grid_color = Color::srgba(0.5, 0.5, 0.5, 0.5);
     let line_thickness = 1.0;
-    let grid_width = width as f32 * tile_size_f32;
-    let grid_height = height as f32 * tile_size_f32;
+    let grid_width = width as f32 * tile_width;
+    let grid_height = height as f32 * tile_height;
 
     // Spawn vertical lines
     for x in 0..=width {
-        let world_x = x as f32 * tile_size_f32;
+        let world_x = x as f32 * tile_width;
         let center_y = grid_height / 2.0;
         let entity = commands
             .spawn((
@@ -839,7 +1071,7 @@ fn sync_grid_rendering(
 
     // Spawn horizontal lines
     for y in 0..=height {
-        let world_y = y as f32 * tile_size_f32;
+        let world_y = y as f32 * tile_height;
         let center_x = grid_width / 2.0;
         let entity = commands
             .spawn((
@@ -911,7 +1143,8 @@ fn sync_collision_rendering(
                 continue;
             };
 
-            let tile_size = tileset.tile_size as f32;
+            let (tile_width, tile_height) = tileset.tile_dimensions();
+            let tile_size = Vec2::new(tile_width as f32, tile_height as f32);
 
             // Iterate through tiles
             for y in 0..level.height {
@@ -921,14 +1154,25 @@ fn sync_collision_rendering(
                         // Check if this tile has collision
                         if let Some(props) = tileset.get_tile_properties(tile_index) {
                             if props.collision.has_collision() {
+                                let cell = Vec2::new(x as f32, y as f32) * tile_size;
+                                // Collection tile shapes are normalized to the tile's image,
+                                // which is drawn around its origin like its sprite
+                                let (base, shape_size) = if tileset.is_collection() {
+                                    let (width, height) = tileset.tile_draw_size(tile_index);
+                                    let (origin_x, origin_y) = props.get_origin(width, height);
+                                    let size = Vec2::new(width as f32, height as f32);
+                                    let origin = Vec2::new(origin_x as f32, origin_y as f32);
+                                    (cell + origin - size / 2.0, size)
+                                } else {
+                                    (cell, tile_size)
+                                };
                                 // Spawn collision overlay sprite(s)
                                 spawn_collision_overlay(
                                     &mut commands,
                                     &mut cache,
                                     &props.collision.shape,
-                                    x,
-                                    y,
-                                    tile_size,
+                                    base,
+                                    shape_size,
                                     layer_idx,
                                     collision_color,
                                 );
@@ -941,20 +1185,103 @@ fn sync_collision_rendering(
     }
 }
 
+/// System to render the navigation grid as an overlay
+///
+/// Blocked cells are drawn red, cells with a custom `nav_cost` orange (more
+/// expensive) or green (cheaper). The overlay is only respawned when the grid
+/// built from the level actually changes.
+fn sync_nav_grid_rendering(
+    mut commands: Commands,
+    mut cache: ResMut<NavOverlayCache>,
+    editor_state: Res<EditorState>,
+    project: Res<Project>,
+) {
+    let level = editor_state
+        .selected_level
+        .filter(|_| editor_state.show_nav_grid)
+        .and_then(|id| project.get_level(id));
+
+    let grid = if let Some(level) = level {
+        let level_changed = cache.last_level != Some(level.id);
+        if !project.is_changed() && !level_changed && cache.grid.is_some() {
+            return;
+        }
+        Some(NavGrid::from_level(level, |id| project.get_tileset(id)))
+    } else {
+        None
+    };
+
+    if grid == cache.grid && cache.last_level == level.map(|l| l.id) {
+        return;
+    }
+
+    for entity in cache.entities.drain(..) {
+        let _ = commands.get_entity(entity).map(|mut e| e.despawn());
+    }
+    cache.last_level = level.map(|l| l.id);
+    cache.grid = grid;
+
+    let (Some(level), Some(grid)) = (level, &cache.grid) else {
+        return;
+    };
+
+    // Cells are sized like the first tile layer's tileset, as in the grid overlay
+    let tile_size = level
+        .layers
+        .iter()
+        .find_map(|layer| match &layer.data {
+            LayerData::Tiles { tileset_id, .. } => project.get_tileset(*tileset_id),
+            _ => None,
+        })
+        .map(|tileset| {
+            let (width, height) = tileset.tile_dimensions();
+            Vec2::new(width as f32, height as f32)
+        })
+        .unwrap_or(Vec2::splat(32.0));
+
+    let mut entities = Vec::new();
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            let color = match grid.cost(x, y) {
+                None => Color::srgba(0.9, 0.1, 0.1, 0.35),
+                Some(cost) if cost > 1.0 => {
+                    Color::srgba(1.0, 0.6, 0.0, (0.1 + cost * 0.05).min(0.4))
+                }
+                Some(cost) if cost < 1.0 => Color::srgba(0.1, 0.8, 0.3, 0.25),
+                Some(_) => continue,
+            };
+            let center = (Vec2::new(x as f32, y as f32) + 0.5) * tile_size;
+            entities.push(
+                commands
+                    .spawn((
+                        Sprite {
+                            color,
+                            custom_size: Some(tile_size),
+                            ..default()
+                        },
+                        // Between the grid (100.0) and collision overlays (101.0)
+                        Transform::from_xyz(center.x, center.y, 100.5),
+                        NavOverlay,
+                    ))
+                    .id(),
+            );
+        }
+    }
+    cache.entities = entities;
+}
+
 /// Spawn collision overlay sprite(s) for a single tile
 fn spawn_collision_overlay(
     commands: &mut Commands,
     cache: &mut CollisionOverlayCache,
     shape: &bevy_map_core::CollisionShape,
-    tile_x: u32,
-    tile_y: u32,
-    tile_size: f32,
+    base: Vec2,
+    tile_size: Vec2,
     layer_idx: usize,
     color: Color,
 ) {
-    // Calculate world position (tiles are positioned from bottom-left)
-    let base_x = tile_x as f32 * tile_size;
-    let base_y = tile_y as f32 * tile_size;
+    // `base` is the bottom-left corner of the area the shape is normalized to
+    let (base_x, base_y) = (base.x, base.y);
     let z = 101.0 + layer_idx as f32 * 0.01; // Just above grid (100.0)
 
     match shape {
@@ -965,10 +1292,10 @@ fn spawn_collision_overlay(
                 .spawn((
                     Sprite {
                         color,
-                        custom_size: Some(Vec2::new(tile_size, tile_size)),
+                        custom_size: Some(tile_size),
                         ..default()
                     },
-                    Transform::from_xyz(base_x + tile_size / 2.0, base_y + tile_size / 2.0, z),
+                    Transform::from_xyz(base_x + tile_size.x / 2.0, base_y + tile_size.y / 2.0, z),
                     CollisionOverlay,
                 ))
                 .id();
@@ -977,10 +1304,10 @@ fn spawn_collision_overlay(
         bevy_map_core::CollisionShape::Rectangle { offset, size } => {
             // Rectangle at offset with size (both normalized 0-1)
             // Flip Y: editor uses Y-down (top=0), Bevy uses Y-up (bottom=0)
-            let width = size[0] * tile_size;
-            let height = size[1] * tile_size;
-            let center_x = base_x + (offset[0] + size[0] / 2.0) * tile_size;
-            let center_y = base_y + (1.0 - offset[1] - size[1] / 2.0) * tile_size;
+            let width = size[0] * tile_size.x;
+            let height = size[1] * tile_size.y;
+            let center_x = base_x + (offset[0] + size[0] / 2.0) * tile_size.x;
+            let center_y = base_y + (1.0 - offset[1] - size[1] / 2.0) * tile_size.y;
 
             let entity = commands
                 .spawn((
@@ -999,9 +1326,9 @@ fn spawn_collision_overlay(
             // Circle - approximate with a square sprite for now
             // Could use a circle texture or shader in the future
             // Flip Y: editor uses Y-down (top=0), Bevy uses Y-up (bottom=0)
-            let diameter = radius * 2.0 * tile_size;
-            let center_x = base_x + offset[0] * tile_size;
-            let center_y = base_y + (1.0 - offset[1]) * tile_size;
+            let diameter = radius * 2.0 * tile_size.min_element();
+            let center_x = base_x + offset[0] * tile_size.x;
+            let center_y = base_y + (1.0 - offset[1]) * tile_size.y;
 
             let entity = commands
                 .spawn((
@@ -1023,7 +1350,12 @@ fn spawn_collision_overlay(
             }
 
             let line_thickness = 2.0;
-            let line_color = Color::srgba(0.0, 0.6, 1.0, 0.6); // Slightly more opaque for lines
+            // Self-intersecting polygons can't be decomposed; draw them in red
+            let line_color = if bevy_map_core::polygon_self_intersections(points).is_empty() {
+                Color::srgba(0.0, 0.6, 1.0, 0.6) // Slightly more opaque for lines
+            } else {
+                Color::srgba(0.9, 0.2, 0.2, 0.8)
+            };
 
             for i in 0..points.len() {
                 let p1 = &points[i];
@@ -1031,10 +1363,10 @@ fn spawn_collision_overlay(
 
                 // Convert normalized coords to world coords
                 // Flip Y: editor uses Y-down (top=0), Bevy uses Y-up (bottom=0)
-                let x1 = base_x + p1[0] * tile_size;
-                let y1 = base_y + (1.0 - p1[1]) * tile_size;
-                let x2 = base_x + p2[0] * tile_size;
-                let y2 = base_y + (1.0 - p2[1]) * tile_size;
+                let x1 = base_x + p1[0] * tile_size.x;
+                let y1 = base_y + (1.0 - p1[1]) * tile_size.y;
+                let x2 = base_x + p2[0] * tile_size.x;
+                let y2 = base_y + (1.0 - p2[1]) * tile_size.y;
 
                 // Calculate line center, length, and angle
                 let center_x = (x1 + x2) / 2.0;
@@ -1119,8 +1451,8 @@ fn sync_selection_preview(
     let tile_size = get_tile_size(&editor_state, &project);
 
     // Calculate end tile position
-    let end_x = (current_pos.x / tile_size).floor() as i32;
-    let end_y = (current_pos.y / tile_size).floor() as i32;
+    let end_x = (current_pos.x / tile_size.x).floor() as i32;
+    let end_y = (current_pos.y / tile_size.y).floor() as i32;
 
     // Normalize bounds
     let min_x = start_x.min(end_x);
@@ -1129,10 +1461,10 @@ fn sync_selection_preview(
     let max_y = start_y.max(end_y);
 
     // Calculate world coordinates for the rectangle
-    let world_min_x = min_x as f32 * tile_size;
-    let world_max_x = (max_x + 1) as f32 * tile_size;
-    let world_min_y = min_y as f32 * tile_size;
-    let world_max_y = (max_y + 1) as f32 * tile_size;
+    let world_min_x = min_x as f32 * tile_size.x;
+    let world_max_x = (max_x + 1) as f32 * tile_size.x;
+    let world_min_y = min_y as f32 * tile_size.y;
+    let world_max_y = (max_y + 1) as f32 * tile_size.y;
 
     let width = world_max_x - world_min_x;
     let height = world_max_y - world_min_y;
@@ -1159,7 +1491,7 @@ fn sync_selection_preview(
 }
 
 /// Get the tile size for the current level/layer/tileset (for preview rendering)
-fn get_tile_size(editor_state: &EditorState, project: &Project) -> f32 {
+fn get_tile_size(editor_state: &EditorState, project: &Project) -> Vec2 {
     let level_id = editor_state.selected_level;
     let layer_idx = editor_state.selected_layer;
 
@@ -1181,8 +1513,11 @@ fn get_tile_size(editor_state: &EditorState, project: &Project) -> f32 {
     layer_tileset_id
         .or(editor_state.selected_tileset)
         .and_then(|id| project.get_tileset(id))
-        .map(|t| t.tile_size as f32)
-        .unwrap_or(32.0)
+        .map(|t| {
+            let (width, height) = t.tile_dimensions();
+            Vec2::new(width as f32, height as f32)
+        })
+        .unwrap_or(Vec2::splat(32.0))
 }
 
 /// Resource tracking the current selection highlight state for change detection
@@ -1311,10 +1646,10 @@ fn sync_tile_selection_highlights(
     // If bounds or offset changed, we need to update or recreate the border sprites
     if bounds_changed || offset_changed {
         // Calculate world coordinates for bounding box (apply move offset)
-        let world_min_x = (min_x + offset_x) as f32 * tile_size;
-        let world_max_x = ((max_x + 1) + offset_x) as f32 * tile_size;
-        let world_min_y = (min_y + offset_y) as f32 * tile_size;
-        let world_max_y = ((max_y + 1) + offset_y) as f32 * tile_size;
+        let world_min_x = (min_x + offset_x) as f32 * tile_size.x;
+        let world_max_x = ((max_x + 1) + offset_x) as f32 * tile_size.x;
+        let world_min_y = (min_y + offset_y) as f32 * tile_size.y;
+        let world_max_y = ((max_y + 1) + offset_y) as f32 * tile_size.y;
 
         let width = world_max_x - world_min_x;
         let height = world_max_y - world_min_y;
@@ -1437,7 +1772,6 @@ fn sync_terrain_preview(
     editor_state: Res<EditorState>,
     project: Res<Project>,
     tileset_cache: Res<TilesetTextureCache>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     mut preview_cache: ResMut<TerrainPreviewCache>,
 ) {
     // Build the new tiles map from editor state
@@ -1502,7 +1836,8 @@ fn sync_terrain_preview(
         return;
     };
 
-    let tile_size = tileset.tile_size as f32;
+    let (tile_width, tile_height) = tileset.tile_dimensions();
+    let tile_size = Vec2::new(tile_width as f32, tile_height as f32);
     let preview_tile_color = Color::srgba(1.0, 1.0, 1.0, 0.6);
     let highlight_color = Color::srgba(0.2, 0.5, 1.0, 0.2);
     let border_color = Color::srgba(0.2, 0.5, 1.0, 0.8);
@@ -1517,46 +1852,37 @@ fn sync_terrain_preview(
             }
         }
 
-        let world_x = x as f32 * tile_size + tile_size / 2.0;
-        let world_y = y as f32 * tile_size + tile_size / 2.0;
+        let world_x = x as f32 * tile_size.x + tile_size.x / 2.0;
+        let world_y = y as f32 * tile_size.y + tile_size.y / 2.0;
         let mut entities = Vec::new();
 
         // Spawn tile sprite
         if let Some((image_index, local_tile_index)) = tileset.virtual_to_local(tile_id) {
             if let Some(image) = tileset.images.get(image_index) {
-                if let Some((texture_handle, _, img_width, img_height)) =
-                    tileset_cache.loaded.get(&image.id)
-                {
-                    let columns = (*img_width as u32) / tileset.tile_size;
-                    let rows = (*img_height as u32) / tileset.tile_size;
-
-                    if columns > 0 && rows > 0 {
-                        let layout = TextureAtlasLayout::from_grid(
-                            UVec2::new(tileset.tile_size, tileset.tile_size),
-                            columns,
-                            rows,
-                            None,
-                            None,
-                        );
-                        let atlas_layout_handle = texture_atlas_layouts.add(layout);
-
-                        let entity = commands
-                            .spawn((
-                                Sprite {
-                                    color: preview_tile_color,
-                                    image: texture_handle.clone(),
-                                    texture_atlas: Some(TextureAtlas {
-                                        layout: atlas_layout_handle,
-                                        index: local_tile_index as usize,
-                                    }),
-                                    ..default()
-                                },
-                                Transform::from_xyz(world_x, world_y, 179.0),
-                                TerrainPreviewHighlight,
-                            ))
-                            .id();
-                        entities.push(entity);
-                    }
+                if let (Some((texture_handle, ..)), Some([src_x, src_y, src_w, src_h])) = (
+                    tileset_cache.loaded.get(&image.id),
+                    tileset.tile_pixel_rect(image, local_tile_index, (1, 1)),
+                ) {
+                    let rect = bevy::math::Rect::new(
+                        src_x as f32,
+                        src_y as f32,
+                        (src_x + src_w) as f32,
+                        (src_y + src_h) as f32,
+                    );
+
+                    let entity = commands
+                        .spawn((
+                            Sprite {
+                                color: preview_tile_color,
+                                image: texture_handle.clone(),
+                                rect: Some(rect),
+                                ..default()
+                            },
+                            Transform::from_xyz(world_x, world_y, 179.0),
+                            TerrainPreviewHighlight,
+                        ))
+                        .id();
+                    entities.push(entity);
                 }
             }
         }
@@ -1566,7 +1892,7 @@ fn sync_terrain_preview(
             .spawn((
                 Sprite {
                     color: highlight_color,
-                    custom_size: Some(Vec2::new(tile_size, tile_size)),
+                    custom_size: Some(tile_size),
                     ..default()
                 },
                 Transform::from_xyz(world_x, world_y, 180.0),
@@ -1580,12 +1906,12 @@ fn sync_terrain_preview(
             .spawn((
                 Sprite {
                     color: border_color,
-                    custom_size: Some(Vec2::new(tile_size, border_thickness)),
+                    custom_size: Some(Vec2::new(tile_size.x, border_thickness)),
                     ..default()
                 },
                 Transform::from_xyz(
                     world_x,
-                    world_y + tile_size / 2.0 - border_thickness / 2.0,
+                    world_y + tile_size.y / 2.0 - border_thickness / 2.0,
                     181.0,
                 ),
                 TerrainPreviewHighlight,
@@ -1598,12 +1924,12 @@ fn sync_terrain_preview(
             .spawn((
                 Sprite {
                     color: border_color,
-                    custom_size: Some(Vec2::new(tile_size, border_thickness)),
+                    custom_size: Some(Vec2::new(tile_size.x, border_thickness)),
                     ..default()
                 },
                 Transform::from_xyz(
                     world_x,
-                    world_y - tile_size / 2.0 + border_thickness / 2.0,
+                    world_y - tile_size.y / 2.0 + border_thickness / 2.0,
                     181.0,
                 ),
                 TerrainPreviewHighlight,
@@ -1616,11 +1942,11 @@ fn sync_terrain_preview(
             .spawn((
                 Sprite {
                     color: border_color,
-                    custom_size: Some(Vec2::new(border_thickness, tile_size)),
+                    custom_size: Some(Vec2::new(border_thickness, tile_size.y)),
                     ..default()
                 },
                 Transform::from_xyz(
-                    world_x - tile_size / 2.0 + border_thickness / 2.0,
+                    world_x - tile_size.x / 2.0 + border_thickness / 2.0,
                     world_y,
                     181.0,
                 ),
@@ -1634,11 +1960,11 @@ fn sync_terrain_preview(
             .spawn((
                 Sprite {
                     color: border_color,
-                    custom_size: Some(Vec2::new(border_thickness, tile_size)),
+                    custom_size: Some(Vec2::new(border_thickness, tile_size.y)),
                     ..default()
                 },
                 Transform::from_xyz(
-                    world_x + tile_size / 2.0 - border_thickness / 2.0,
+                    world_x + tile_size.x / 2.0 - border_thickness / 2.0,
                     world_y,
                     181.0,
                 ),
@@ -1662,7 +1988,6 @@ fn sync_brush_preview(
     project: Res<Project>,
     tileset_cache: Res<TilesetTextureCache>,
     mut preview_cache: ResMut<BrushPreviewCache>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
     // Helper to clear preview
     fn clear_preview(commands: &mut Commands, cache: &mut BrushPreviewCache) {
@@ -1711,96 +2036,55 @@ fn sync_brush_preview(
         return;
     };
 
-    let tile_size = tileset.tile_size as f32;
+    let (tile_width, tile_height) = tileset.tile_dimensions();
     let (grid_width, grid_height) = tileset.get_tile_grid_size(tile_id);
     let preview_color = Color::srgba(1.0, 1.0, 1.0, 0.6);
     let border_color = Color::srgba(0.2, 0.8, 0.2, 0.8); // Green for brush
 
     // Calculate world position using origin (consistent with tile placement)
-    let total_width = grid_width as f32 * tile_size;
-    let total_height = grid_height as f32 * tile_size;
+    let (draw_width, draw_height) = tileset.tile_draw_size(tile_id);
+    let total_width = draw_width as f32;
+    let total_height = draw_height as f32;
     let props = tileset
         .get_tile_properties(tile_id)
         .cloned()
         .unwrap_or_default();
     let (origin_x, origin_y) = props.get_origin(total_width as u32, total_height as u32);
-    let world_x = position.0 as f32 * tile_size + origin_x as f32;
-    let world_y = position.1 as f32 * tile_size + origin_y as f32;
+    let world_x = position.0 as f32 * tile_width as f32 + origin_x as f32;
+    let world_y = position.1 as f32 * tile_height as f32 + origin_y as f32;
 
     // Spawn tile sprite (try to use texture, fall back to colored rectangle)
     let mut sprite_created = false;
     if let Some((image_index, local_tile_index)) = tileset.virtual_to_local(tile_id) {
         if let Some(image) = tileset.images.get(image_index) {
-            if let Some((texture_handle, _, img_width, img_height)) =
-                tileset_cache.loaded.get(&image.id)
-            {
-                let columns = (*img_width as u32) / tileset.tile_size;
-                let rows = (*img_height as u32) / tileset.tile_size;
-
-                if columns > 0 && rows > 0 {
-                    if grid_width > 1 || grid_height > 1 {
-                        // Multi-cell tile: use Sprite with rect for the full region
-                        let tile_col = local_tile_index % image.columns;
-                        let tile_row = local_tile_index / image.columns;
-                        let src_x = (tile_col * tileset.tile_size) as f32;
-                        let src_y = (tile_row * tileset.tile_size) as f32;
-                        let src_width = total_width;
-                        let src_height = total_height;
-
-                        let rect = bevy::math::Rect::new(
-                            src_x,
-                            src_y,
-                            src_x + src_width,
-                            src_y + src_height,
-                        );
+            if let (Some((texture_handle, ..)), Some([src_x, src_y, src_w, src_h])) = (
+                tileset_cache.loaded.get(&image.id),
+                tileset.tile_pixel_rect(image, local_tile_index, (grid_width, grid_height)),
+            ) {
+                // Source region in the tileset image, covering all cells of multi-cell tiles
+                let rect = bevy::math::Rect::new(
+                    src_x as f32,
+                    src_y as f32,
+                    (src_x + src_w) as f32,
+                    (src_y + src_h) as f32,
+                );
 
-                        let entity = commands
-                            .spawn((
-                                Sprite {
-                                    color: preview_color,
-                                    image: texture_handle.clone(),
-                                    rect: Some(rect),
-                                    custom_size: Some(Vec2::new(src_width, src_height)),
-                                    ..default()
-                                },
-                                Transform::from_xyz(world_x, world_y, 179.0),
-                                Visibility::Inherited,
-                                BrushPreviewSprite,
-                            ))
-                            .id();
-                        preview_cache.sprite_entity = Some(entity);
-                        sprite_created = true;
-                    } else {
-                        // Single tile: use TextureAtlas
-                        let layout = TextureAtlasLayout::from_grid(
-                            UVec2::new(tileset.tile_size, tileset.tile_size),
-                            columns,
-                            rows,
-                            None,
-                            None,
-                        );
-                        let atlas_layout_handle = texture_atlas_layouts.add(layout);
-
-                        let entity = commands
-                            .spawn((
-                                Sprite {
-                                    color: preview_color,
-                                    image: texture_handle.clone(),
-                                    texture_atlas: Some(TextureAtlas {
-                                        layout: atlas_layout_handle,
-                                        index: local_tile_index as usize,
-                                    }),
-                                    ..default()
-                                },
-                                Transform::from_xyz(world_x, world_y, 179.0),
-                                Visibility::Inherited,
-                                BrushPreviewSprite,
-                            ))
-                            .id();
-                        preview_cache.sprite_entity = Some(entity);
-                        sprite_created = true;
-                    }
-                }
+                let entity = commands
+                    .spawn((
+                        Sprite {
+                            color: preview_color,
+                            image: texture_handle.clone(),
+                            rect: Some(rect),
+                            custom_size: Some(Vec2::new(total_width, total_height)),
+                            ..default()
+                        },
+                        Transform::from_xyz(world_x, world_y, 179.0),
+                        Visibility::Inherited,
+                        BrushPreviewSprite,
+                    ))
+                    .id();
+                preview_cache.sprite_entity = Some(entity);
+                sprite_created = true;
             }
         }
     }
@@ -2008,8 +2292,10 @@ fn sync_entity_rendering(
 
         // Get color and marker size from schema type definition
         let type_def = project.schema.get_type(&entity.type_name);
-        let color = type_def
-            .map(|td| parse_hex_color(&td.color))
+        let color = project
+            .schema
+            .marker_color(&entity.type_name, &entity.properties)
+            .map(parse_hex_color)
             .unwrap_or(Color::srgba(0.4, 0.8, 0.4, 0.8)); // Default green
         let entity_size = type_def.and_then(|td| td.marker_size).unwrap_or(16) as f32;
 
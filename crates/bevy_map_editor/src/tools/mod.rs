@@ -378,8 +378,8 @@ fn handle_viewport_input(
                 // FOURTH: No entity hit - start marquee selection for tiles
                 // Clear entity selection when starting tile selection
                 editor_state.selection = Selection::None;
-                let tile_x = (world_pos.x / tile_size).floor() as i32;
-                let tile_y = (world_pos.y / tile_size).floor() as i32;
+                let tile_x = (world_pos.x / tile_size.x).floor() as i32;
+                let tile_y = (world_pos.y / tile_size.y).floor() as i32;
                 input_state.rect_start_tile = Some((tile_x, tile_y));
                 input_state.is_drawing_rect = true;
                 editor_state.tile_selection.is_selecting = true;
@@ -387,8 +387,8 @@ fn handle_viewport_input(
             }
             // For tools that support modes, start rectangle drawing if in Rectangle mode
             EditorTool::Paint | EditorTool::Erase | EditorTool::Terrain if is_rectangle_mode => {
-                let tile_x = (world_pos.x / tile_size).floor() as i32;
-                let tile_y = (world_pos.y / tile_size).floor() as i32;
+                let tile_x = (world_pos.x / tile_size.x).floor() as i32;
+                let tile_y = (world_pos.y / tile_size.y).floor() as i32;
                 input_state.rect_start_tile = Some((tile_x, tile_y));
                 input_state.is_drawing_rect = true;
             }
@@ -399,8 +399,8 @@ fn handle_viewport_input(
     // Handle rectangle mode release
     if mouse_buttons.just_released(MouseButton::Left) && input_state.is_drawing_rect {
         if let Some((start_x, start_y)) = input_state.rect_start_tile {
-            let end_x = (world_pos.x / tile_size).floor() as i32;
-            let end_y = (world_pos.y / tile_size).floor() as i32;
+            let end_x = (world_pos.x / tile_size.x).floor() as i32;
+            let end_y = (world_pos.y / tile_size.y).floor() as i32;
 
             // Fill based on the current tool
             match editor_state.current_tool {
@@ -496,14 +496,14 @@ fn handle_viewport_input(
                         // Apply snap-to-grid if enabled
                         if editor_state.snap_to_grid {
                             let snap_unit = tile_size / 2.0;
-                            new_pos[0] = (new_pos[0] / snap_unit).round() * snap_unit;
-                            new_pos[1] = (new_pos[1] / snap_unit).round() * snap_unit;
+                            new_pos[0] = (new_pos[0] / snap_unit.x).round() * snap_unit.x;
+                            new_pos[1] = (new_pos[1] / snap_unit.y).round() * snap_unit.y;
                         }
 
                         // Bounds check
                         if let Some(level) = project.get_level_mut(level_id) {
-                            let level_width_px = level.width as f32 * tile_size;
-                            let level_height_px = level.height as f32 * tile_size;
+                            let level_width_px = level.width as f32 * tile_size.x;
+                            let level_height_px = level.height as f32 * tile_size.y;
 
                             // Clamp to level bounds
                             new_pos[0] = new_pos[0].clamp(0.0, level_width_px);
@@ -522,8 +522,8 @@ fn handle_viewport_input(
             // Tile move - update offset (tiles aren't moved until release)
             else if editor_state.tile_move_original.is_some() {
                 // Calculate tile offset from delta
-                let offset_x = (delta.x / tile_size).round() as i32;
-                let offset_y = (delta.y / tile_size).round() as i32;
+                let offset_x = (delta.x / tile_size.x).round() as i32;
+                let offset_y = (delta.y / tile_size.y).round() as i32;
                 editor_state.tile_move_offset = Some((offset_x, offset_y));
             }
         }
@@ -549,15 +549,13 @@ fn handle_viewport_input(
                     .tilesets
                     .iter()
                     .find(|t| t.id == terrain_set.tileset_id)
-                    .map(|t| t.tile_size as f32)
-                    .unwrap_or(32.0);
-
-                let paint_target = bevy_map_autotile::get_paint_target(
-                    world_pos.x,
-                    world_pos.y,
-                    tile_size,
-                    terrain_set.set_type,
-                );
+                    .map(|t| {
+                        let (width, height) = t.tile_dimensions();
+                        Vec2::new(width as f32, height as f32)
+                    })
+                    .unwrap_or(Vec2::splat(32.0));
+
+                let paint_target = terrain_paint_target(world_pos, tile_size, terrain_set.set_type);
 
                 // Recalculate preview if target changed OR full_tile_mode changed
                 let mode_changed = input_state.last_preview_full_tile_mode != full_tile_mode;
@@ -595,8 +593,8 @@ fn handle_viewport_input(
         && !modal_editor_open
     {
         // Simple floor division to get tile under cursor
-        let tile_x = (world_pos.x / tile_size).floor() as i32;
-        let tile_y = (world_pos.y / tile_size).floor() as i32;
+        let tile_x = (world_pos.x / tile_size.x).floor() as i32;
+        let tile_y = (world_pos.y / tile_size.y).floor() as i32;
         editor_state.brush_preview.position = Some((tile_x, tile_y));
         editor_state.brush_preview.active = true;
     } else {
@@ -613,8 +611,8 @@ fn handle_viewport_input(
         match editor_state.current_tool {
             EditorTool::Paint => {
                 // Get current tile position for line brush anchor tracking
-                let current_tile_x = (world_pos.x / tile_size).floor() as i32;
-                let current_tile_y = (world_pos.y / tile_size).floor() as i32;
+                let current_tile_x = (world_pos.x / tile_size.x).floor() as i32;
+                let current_tile_y = (world_pos.y / tile_size.y).floor() as i32;
 
                 // Line brush: Shift+Click draws line from anchor to current position
                 if mouse_buttons.just_pressed(MouseButton::Left)
@@ -628,8 +626,8 @@ fn handle_viewport_input(
                     // Paint each tile along the line
                     for (lx, ly) in line_points {
                         // Convert tile coords back to world position (center of tile)
-                        let world_x = (lx as f32 + 0.5) * tile_size;
-                        let world_y = (ly as f32 + 0.5) * tile_size;
+                        let world_x = (lx as f32 + 0.5) * tile_size.x;
+                        let world_y = (ly as f32 + 0.5) * tile_size.y;
                         paint_tile(
                             &mut commands,
                             &mut editor_state,
@@ -681,8 +679,8 @@ fn handle_viewport_input(
             }
             EditorTool::Erase => {
                 // Get current tile position for line brush anchor tracking
-                let current_tile_x = (world_pos.x / tile_size).floor() as i32;
-                let current_tile_y = (world_pos.y / tile_size).floor() as i32;
+                let current_tile_x = (world_pos.x / tile_size.x).floor() as i32;
+                let current_tile_y = (world_pos.y / tile_size.y).floor() as i32;
 
                 // Line brush for erase: Shift+Click erases line from anchor to current position
                 if mouse_buttons.just_pressed(MouseButton::Left)
@@ -696,8 +694,8 @@ fn handle_viewport_input(
                     // Erase each tile along the line
                     for (lx, ly) in line_points {
                         // Convert tile coords back to world position (center of tile)
-                        let world_x = (lx as f32 + 0.5) * tile_size;
-                        let world_y = (ly as f32 + 0.5) * tile_size;
+                        let world_x = (lx as f32 + 0.5) * tile_size.x;
+                        let world_y = (ly as f32 + 0.5) * tile_size.y;
                         erase_tile(
                             &mut commands,
                             &mut editor_state,
@@ -743,7 +741,7 @@ fn handle_viewport_input(
 }
 
 /// Get the tile size for the current level/layer/tileset
-fn get_tile_size(editor_state: &EditorState, project: &Project) -> f32 {
+fn get_tile_size(editor_state: &EditorState, project: &Project) -> Vec2 {
     let level_id = editor_state.selected_level;
     let layer_idx = editor_state.selected_layer;
 
@@ -763,8 +761,11 @@ fn get_tile_size(editor_state: &EditorState, project: &Project) -> f32 {
     layer_tileset_id
         .or(editor_state.selected_tileset)
         .and_then(|id| project.tilesets.iter().find(|t| t.id == id))
-        .map(|t| t.tile_size as f32)
-        .unwrap_or(32.0)
+        .map(|t| {
+            let (width, height) = t.tile_dimensions();
+            Vec2::new(width as f32, height as f32)
+        })
+        .unwrap_or(Vec2::splat(32.0))
 }
 
 /// System to handle zoom input
@@ -928,14 +929,18 @@ fn is_click_on_selected_entity(
 }
 
 /// Check if click is within current tile selection
-fn is_click_on_tile_selection(world_pos: Vec2, editor_state: &EditorState, tile_size: f32) -> bool {
+fn is_click_on_tile_selection(
+    world_pos: Vec2,
+    editor_state: &EditorState,
+    tile_size: Vec2,
+) -> bool {
     if editor_state.tile_selection.tiles.is_empty() {
         return false;
     }
 
     // Convert world position to tile coordinates
-    let tile_x = (world_pos.x / tile_size).floor() as u32;
-    let tile_y = (world_pos.y / tile_size).floor() as u32;
+    let tile_x = (world_pos.x / tile_size.x).floor() as u32;
+    let tile_y = (world_pos.y / tile_size.y).floor() as u32;
 
     // Check if this tile position is in the selection
     // Selection tiles are stored as (level_id, layer_idx, x, y)
@@ -1186,16 +1191,18 @@ fn paint_tile(
         .tilesets
         .iter()
         .find(|t| t.id == selected_tileset)
-        .map(|t| (t.tile_size as f32, t.get_tile_grid_size(base_tile_index)));
-    let tile_size = tileset_info.map(|(ts, _)| ts).unwrap_or(32.0);
+        .map(|t| (t.tile_dimensions(), t.get_tile_grid_size(base_tile_index)));
+    let tile_size = tileset_info
+        .map(|((width, height), _)| Vec2::new(width as f32, height as f32))
+        .unwrap_or(Vec2::splat(32.0));
     let (grid_width, grid_height) = tileset_info.map(|(_, gs)| gs).unwrap_or((1, 1));
     let is_multi_cell = grid_width > 1 || grid_height > 1;
     let valid_tileset_ids: HashSet<_> = project.tilesets.iter().map(|t| t.id).collect();
 
     // Convert world position to tile coordinates
     // Simple floor division to get tile under cursor
-    let tile_x = (world_pos.x / tile_size).floor() as i32;
-    let tile_y = (world_pos.y / tile_size).floor() as i32;
+    let tile_x = (world_pos.x / tile_size.x).floor() as i32;
+    let tile_y = (world_pos.y / tile_size.y).floor() as i32;
 
     // Don't repaint the same tile
     if editor_state.last_painted_tile == Some((tile_x as u32, tile_y as u32)) {
@@ -1401,8 +1408,8 @@ fn erase_tile(
 
     let tile_size = get_tile_size(editor_state, project);
 
-    let tile_x = (world_pos.x / tile_size).floor() as i32;
-    let tile_y = (world_pos.y / tile_size).floor() as i32;
+    let tile_x = (world_pos.x / tile_size.x).floor() as i32;
+    let tile_y = (world_pos.y / tile_size.y).floor() as i32;
 
     if editor_state.last_painted_tile == Some((tile_x as u32, tile_y as u32)) {
         return;
@@ -1606,8 +1613,8 @@ fn place_entity(editor_state: &mut EditorState, project: &mut Project, world_pos
         // Snap to nearest tile center: round to nearest half-tile unit
         // This snaps to positions 0.5*size, 1.5*size, 2.5*size, etc.
         let snap_unit = tile_size / 2.0;
-        let snapped_x = (world_pos.x / snap_unit).round() * snap_unit;
-        let snapped_y = (world_pos.y / snap_unit).round() * snap_unit;
+        let snapped_x = (world_pos.x / snap_unit.x).round() * snap_unit.x;
+        let snapped_y = (world_pos.y / snap_unit.y).round() * snap_unit.y;
         Vec2::new(snapped_x, snapped_y)
     } else {
         world_pos
@@ -1618,8 +1625,8 @@ fn place_entity(editor_state: &mut EditorState, project: &mut Project, world_pos
         let Some(level) = project.get_level(level_id) else {
             return;
         };
-        let level_width_px = level.width as f32 * tile_size;
-        let level_height_px = level.height as f32 * tile_size;
+        let level_width_px = level.width as f32 * tile_size.x;
+        let level_height_px = level.height as f32 * tile_size.y;
 
         if final_pos.x < 0.0
             || final_pos.y < 0.0
@@ -1635,7 +1642,7 @@ fn place_entity(editor_state: &mut EditorState, project: &mut Project, world_pos
     let mut entity = EntityInstance::new(type_name.clone(), position);
 
     // Initialize properties from schema defaults if the type exists
-    if let Some(type_def) = project.schema.get_type(&type_name) {
+    if let Some(type_def) = project.schema.resolved_type(&type_name) {
         for prop in &type_def.properties {
             if let Some(default_val) = &prop.default {
                 entity.properties.insert(
@@ -1764,8 +1771,8 @@ fn fill_area(
 
     let tile_size = get_tile_size(editor_state, project);
 
-    let start_x = (world_pos.x / tile_size).floor() as i32;
-    let start_y = (world_pos.y / tile_size).floor() as i32;
+    let start_x = (world_pos.x / tile_size.x).floor() as i32;
+    let start_y = (world_pos.y / tile_size.y).floor() as i32;
 
     let Some(level) = project.get_level_mut(level_id) else {
         return;
@@ -1903,20 +1910,20 @@ fn paint_terrain_tile(
 fn get_paint_targets_along_line(
     start: Vec2,
     end: Vec2,
-    tile_size: f32,
+    tile_size: Vec2,
     set_type: bevy_map_autotile::TerrainSetType,
 ) -> Vec<bevy_map_autotile::PaintTarget> {
     let mut targets = Vec::new();
     let dist = start.distance(end);
 
     // Use sub-tile precision to catch all targets
-    let steps = (dist / (tile_size * 0.4)).ceil() as i32;
+    let steps = (dist / (tile_size.min_element() * 0.4)).ceil() as i32;
     let steps = steps.max(1);
 
     for i in 0..=steps {
         let t = i as f32 / steps as f32;
         let pos = start.lerp(end, t);
-        let target = bevy_map_autotile::get_paint_target(pos.x, pos.y, tile_size, set_type);
+        let target = terrain_paint_target(pos, tile_size, set_type);
 
         // Only add if different from last target
         if targets.last() != Some(&target) {
@@ -1927,6 +1934,19 @@ fn get_paint_targets_along_line(
     targets
 }
 
+/// Get the terrain paint target under a world position
+///
+/// The position is normalized to tile units first so non-square tiles pick
+/// the right corner or edge.
+fn terrain_paint_target(
+    world_pos: Vec2,
+    tile_size: Vec2,
+    set_type: bevy_map_autotile::TerrainSetType,
+) -> bevy_map_autotile::PaintTarget {
+    let tile_pos = world_pos / tile_size;
+    bevy_map_autotile::get_paint_target(tile_pos.x, tile_pos.y, 1.0, set_type)
+}
+
 /// Paint using the new Tiled-style terrain set system
 /// Uses line interpolation for continuous drag painting and target-based deduplication
 /// If full_tile_mode is true (Ctrl held), paints all 8 positions of the center tile
@@ -1961,15 +1981,18 @@ fn paint_terrain_set_tile(
         .tilesets
         .iter()
         .find(|t| t.id == selected_tileset)
-        .map(|t| t.tile_size as f32)
-        .unwrap_or(32.0);
+        .map(|t| {
+            let (width, height) = t.tile_dimensions();
+            Vec2::new(width as f32, height as f32)
+        })
+        .unwrap_or(Vec2::splat(32.0));
 
     // Get paint targets based on mode
     let paint_targets = if full_tile_mode {
         // Full-tile mode: paint all 8 positions (4 corners + 4 edges) of the center tile
         // This fills the tile completely and updates all 8 surrounding neighbors
-        let tile_x = (world_pos.x / tile_size).floor() as u32;
-        let tile_y = (world_pos.y / tile_size).floor() as u32;
+        let tile_x = (world_pos.x / tile_size.x).floor() as u32;
+        let tile_y = (world_pos.y / tile_size.y).floor() as u32;
 
         vec![
             // 4 corners of the tile
@@ -2012,12 +2035,7 @@ fn paint_terrain_set_tile(
         get_paint_targets_along_line(last_pos, world_pos, tile_size, set_type)
     } else {
         // Normal mode, first paint: just paint at current position
-        vec![bevy_map_autotile::get_paint_target(
-            world_pos.x,
-            world_pos.y,
-            tile_size,
-            set_type,
-        )]
+        vec![terrain_paint_target(world_pos, tile_size, set_type)]
     };
 
     // Filter out targets already painted this stroke (target-based deduplication)
@@ -2173,11 +2191,14 @@ fn paint_legacy_terrain_tile(
         .tilesets
         .iter()
         .find(|t| t.id == selected_tileset)
-        .map(|t| t.tile_size as f32)
-        .unwrap_or(32.0);
+        .map(|t| {
+            let (width, height) = t.tile_dimensions();
+            Vec2::new(width as f32, height as f32)
+        })
+        .unwrap_or(Vec2::splat(32.0));
 
-    let tile_x = (world_pos.x / tile_size).floor() as i32;
-    let tile_y = (world_pos.y / tile_size).floor() as i32;
+    let tile_x = (world_pos.x / tile_size.x).floor() as i32;
+    let tile_y = (world_pos.y / tile_size.y).floor() as i32;
 
     if editor_state.last_painted_tile == Some((tile_x as u32, tile_y as u32)) {
         return;
@@ -2540,7 +2561,7 @@ fn calculate_terrain_preview(
     editor_state: &mut EditorState,
     project: &Project,
     world_pos: Vec2,
-    tile_size: f32,
+    tile_size: Vec2,
     full_tile_mode: bool,
 ) {
     let Some(level_id) = editor_state.selected_level else {
@@ -2589,8 +2610,8 @@ fn calculate_terrain_preview(
     // Generate paint targets based on mode
     let paint_targets = if full_tile_mode {
         // Full-tile mode: generate all 8 paint targets (4 corners + 4 edges) for the tile
-        let tile_x = (world_pos.x / tile_size).floor() as u32;
-        let tile_y = (world_pos.y / tile_size).floor() as u32;
+        let tile_x = (world_pos.x / tile_size.x).floor() as u32;
+        let tile_y = (world_pos.y / tile_size.y).floor() as u32;
 
         vec![
             // 4 corners of the tile
@@ -2630,9 +2651,8 @@ fn calculate_terrain_preview(
         ]
     } else {
         // Normal mode: single paint target based on cursor position
-        vec![bevy_map_autotile::get_paint_target(
-            world_pos.x,
-            world_pos.y,
+        vec![terrain_paint_target(
+            world_pos,
             tile_size,
             terrain_set.set_type,
         )]
@@ -1,7 +1,9 @@
 //! Asset Browser - File system browser panel for the map editor
 
 use bevy_egui::egui;
-use std::path::PathBuf;
+use bevy_map_core::Value;
+use bevy_map_schema::PropertyDef;
+use std::path::{Path, PathBuf};
 
 /// Filter settings for the asset browser
 #[derive(Debug, Clone)]
@@ -614,3 +616,136 @@ fn format_size(size: u64) -> String {
         format!("{:.1} GB", size as f64 / (1024.0 * 1024.0 * 1024.0))
     }
 }
+
+/// Most files listed by the asset picker
+const MAX_PICKER_FILES: usize = 2000;
+
+/// List files under `root` accepted by an asset property
+///
+/// Returns sorted (path relative to `root`, file type icon) pairs.
+fn list_asset_files(root: &Path, prop_def: &PropertyDef) -> Vec<(String, &'static str)> {
+    let mut files = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(read_dir) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in read_dir.filter_map(|entry| entry.ok()) {
+            let Some(entry) = FileEntry::from_path(entry.path()) else {
+                continue;
+            };
+            // Skip hidden files and folders such as .git
+            if entry.name.starts_with('.') {
+                continue;
+            }
+            if entry.is_dir {
+                dirs.push(entry.path);
+                continue;
+            }
+            let Ok(relative) = entry.path.strip_prefix(root) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if prop_def.accepts_extension(&relative) {
+                files.push((relative, entry.file_type_icon()));
+                if files.len() >= MAX_PICKER_FILES {
+                    files.sort();
+                    return files;
+                }
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Render a picker for an Asset property: the path, with a browser popup of matching files
+///
+/// Paths are relative to the assets folder, as the runtime's `AssetServer` expects.
+pub fn render_asset_picker(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    value: &mut Value,
+    prop_def: &PropertyDef,
+    assets_root: &Path,
+) {
+    let mut path = value.as_string().unwrap_or_default().to_string();
+    let files_id = ui.make_persistent_id((id_salt, "asset_picker_files"));
+    let search_id = ui.make_persistent_id((id_salt, "asset_picker_search"));
+
+    let browse = ui
+        .horizontal(|ui| {
+            let hint = if prop_def.extensions.is_empty() {
+                "path/to/asset".to_string()
+            } else {
+                format!("*.{}", prop_def.extensions.join(", *."))
+            };
+            let edit = ui.add(
+                egui::TextEdit::singleline(&mut path)
+                    .hint_text(hint)
+                    .desired_width(160.0),
+            );
+            if edit.changed() {
+                *value = Value::String(path.clone());
+            }
+            ui.button("...").on_hover_text("Browse assets")
+        })
+        .inner;
+
+    // Rescan the assets folder each time the browser opens
+    if browse.clicked() {
+        let files = list_asset_files(assets_root, prop_def);
+        ui.data_mut(|data| data.insert_temp(files_id, files));
+    }
+
+    if !path.is_empty() {
+        if !prop_def.accepts_extension(&path) {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                format!("Expected a .{} file", prop_def.extensions.join("/.")),
+            );
+        } else if !assets_root.join(&path).is_file() {
+            ui.colored_label(egui::Color32::YELLOW, "File not found in assets folder");
+        }
+    }
+
+    egui::Popup::from_toggle_button_response(&browse)
+        .close_behavior(egui::PopupCloseBehavior::CloseOnClickOutside)
+        .show(|ui| {
+            let files: Vec<(String, &'static str)> =
+                ui.data(|data| data.get_temp(files_id)).unwrap_or_default();
+            let mut search: String = ui.data(|data| data.get_temp(search_id)).unwrap_or_default();
+            ui.horizontal(|ui| {
+                ui.label("Search:");
+                ui.text_edit_singleline(&mut search);
+            });
+            ui.data_mut(|data| data.insert_temp(search_id, search.clone()));
+            ui.separator();
+
+            if files.is_empty() {
+                ui.label(format!("No matching files in {}", assets_root.display()));
+                return;
+            }
+            let search = search.to_lowercase();
+            egui::ScrollArea::vertical()
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    ui.set_min_width(240.0);
+                    if ui.selectable_label(path.is_empty(), "(None)").clicked() {
+                        *value = Value::String(String::new());
+                        ui.close();
+                    }
+                    for (file, icon) in files.iter().filter(|(file, _)| {
+                        search.is_empty() || file.to_lowercase().contains(&search)
+                    }) {
+                        if ui
+                            .selectable_label(*file == path, format!("{} {}", icon, file))
+                            .clicked()
+                        {
+                            *value = Value::String(file.clone());
+                            ui.close();
+                        }
+                    }
+                });
+        });
+}
@@ -30,6 +30,20 @@ pub enum PendingAction {
     CreateGameProject,
     /// Install Bevy CLI
     InstallBevyCli,
+    /// Merge a schema file (e.g. exported from game code) into the project schema
+    ImportSchema,
+    /// Apply the schema refactor confirmed in the schema editor
+    ApplySchemaRefactor,
+    /// Export the data instances of a type to a CSV file
+    ExportDataCsv(String),
+    /// Read a CSV file of a data type and preview the import
+    ImportDataCsv(String),
+    /// Apply the CSV import confirmed in the preview dialog
+    ApplyDataImport,
+    /// Apply the bulk edit made in the table view
+    ApplyBulkEdit,
+    /// Apply the tile property edit made in the tileset editor
+    ApplyTileProperties,
 }
 
 /// Render all dialogs
@@ -45,6 +59,7 @@ pub fn render_dialogs(
     render_copy_file_dialog(ctx, editor_state, project, assets_base_path);
     render_about_dialog(ctx, editor_state);
     render_error_dialog(ctx, editor_state);
+    render_data_import_dialog(ctx, editor_state);
 
     // Handle pending file actions
     if let Some(action) = editor_state.pending_action.take() {
@@ -121,6 +136,80 @@ pub fn render_dialogs(
                     }
                 }
             }
+            PendingAction::ImportSchema => {
+                #[cfg(feature = "native")]
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("Schema", &["json"])
+                        .pick_file()
+                    {
+                        match bevy_map_schema::load_schema(&path) {
+                            Ok(imported) => {
+                                project.schema.merge(&imported);
+                                project.mark_dirty();
+                            }
+                            Err(e) => {
+                                editor_state.error_message =
+                                    Some(format!("Failed to import schema: {}", e));
+                            }
+                        }
+                    }
+                }
+            }
+            PendingAction::ExportDataCsv(type_name) => {
+                #[cfg(feature = "native")]
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("CSV", &["csv"])
+                        .set_file_name(format!("{}.csv", type_name))
+                        .save_file()
+                    {
+                        let result = bevy_map_schema::export_csv(
+                            &project.schema,
+                            &type_name,
+                            project.data.get_by_type(&type_name),
+                        )
+                        .map_err(|e| e.to_string())
+                        .and_then(|csv| std::fs::write(&path, csv).map_err(|e| e.to_string()));
+                        if let Err(e) = result {
+                            editor_state.error_message =
+                                Some(format!("Failed to export {}: {}", type_name, e));
+                        }
+                    }
+                }
+                #[cfg(not(feature = "native"))]
+                let _ = type_name;
+            }
+            PendingAction::ImportDataCsv(type_name) => {
+                #[cfg(feature = "native")]
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("CSV", &["csv"])
+                        .pick_file()
+                    {
+                        let result = std::fs::read_to_string(&path)
+                            .map_err(|e| e.to_string())
+                            .and_then(|csv| {
+                                bevy_map_schema::import_csv(
+                                    &project.schema,
+                                    &type_name,
+                                    &csv,
+                                    project.data.get_by_type(&type_name),
+                                )
+                                .map_err(|e| e.to_string())
+                            });
+                        match result {
+                            Ok(import) => editor_state.pending_data_import = Some(import),
+                            Err(e) => {
+                                editor_state.error_message =
+                                    Some(format!("Failed to import {}: {}", type_name, e));
+                            }
+                        }
+                    }
+                }
+                #[cfg(not(feature = "native"))]
+                let _ = type_name;
+            }
             _ => {
                 // Put other actions back
                 editor_state.pending_action = Some(action);
@@ -214,6 +303,20 @@ fn render_new_tileset_dialog(
                 ui.text_edit_singleline(&mut editor_state.new_tileset_name);
             });
 
+            ui.horizontal(|ui| {
+                ui.label("Kind:");
+                ui.radio_value(
+                    &mut editor_state.new_tileset_kind,
+                    bevy_map_core::TilesetKind::Atlas,
+                    "Atlas",
+                );
+                ui.radio_value(
+                    &mut editor_state.new_tileset_kind,
+                    bevy_map_core::TilesetKind::Collection,
+                    "Image Collection",
+                );
+            });
+
             ui.horizontal(|ui| {
                 ui.label("Tile Size:");
                 ui.add(
@@ -221,8 +324,16 @@ fn render_new_tileset_dialog(
                 );
             });
 
+            if editor_state.new_tileset_kind == bevy_map_core::TilesetKind::Collection {
+                ui.label("Each image is one tile; the tile size sets the placement grid.");
+            }
+
             ui.horizontal(|ui| {
-                ui.label("Image Path:");
+                let label = match editor_state.new_tileset_kind {
+                    bevy_map_core::TilesetKind::Atlas => "Image Path:",
+                    bevy_map_core::TilesetKind::Collection => "First Tile Image:",
+                };
+                ui.label(label);
                 ui.text_edit_singleline(&mut editor_state.new_tileset_path);
                 #[cfg(feature = "native")]
                 if ui.button("Browse...").clicked() {
@@ -278,13 +389,24 @@ fn create_tileset_from_path(
 ) {
     let path_str = path.to_string_lossy().to_string();
 
-    let tileset = bevy_map_core::Tileset::new(
-        editor_state.new_tileset_name.clone(),
-        path_str,
-        editor_state.new_tileset_tile_size,
-        0, // columns - will be determined when texture loads
-        0, // rows
-    );
+    let tileset = match editor_state.new_tileset_kind {
+        bevy_map_core::TilesetKind::Atlas => bevy_map_core::Tileset::new(
+            editor_state.new_tileset_name.clone(),
+            path_str,
+            editor_state.new_tileset_tile_size,
+            0, // columns - will be determined when texture loads
+            0, // rows
+        ),
+        bevy_map_core::TilesetKind::Collection => {
+            let mut tileset = bevy_map_core::Tileset::new_collection(
+                editor_state.new_tileset_name.clone(),
+                editor_state.new_tileset_tile_size,
+            );
+            // Image size is recorded when the texture loads
+            tileset.add_tile_image(image_name_from_path(&path), path_str, 0, 0);
+            tileset
+        }
+    };
     let tileset_id = tileset.id;
     project.add_tileset(tileset);
     editor_state.selected_tileset = Some(tileset_id);
@@ -294,6 +416,14 @@ fn create_tileset_from_path(
     editor_state.new_tileset_name = "New Tileset".to_string();
     editor_state.new_tileset_path = String::new();
     editor_state.new_tileset_tile_size = 32;
+    editor_state.new_tileset_kind = bevy_map_core::TilesetKind::Atlas;
+}
+
+/// Default tile/image name for a file: its stem (e.g. "tree" for "props/tree.png")
+fn image_name_from_path(path: &std::path::Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Image".to_string())
 }
 
 /// Render the copy file confirmation dialog
@@ -388,12 +518,22 @@ fn add_tileset_image_from_path(
 ) {
     if let Some(tileset_id) = editor_state.selected_tileset {
         if let Some(tileset) = project.tilesets.iter_mut().find(|t| t.id == tileset_id) {
-            tileset.add_image(
-                editor_state.add_image_name.clone(),
-                path.to_string_lossy().to_string(),
-                8, // Default columns - will be recalculated when loaded
-                8, // Default rows
-            );
+            if tileset.is_collection() {
+                // Image size is recorded when the texture loads
+                tileset.add_tile_image(
+                    editor_state.add_image_name.clone(),
+                    path.to_string_lossy().to_string(),
+                    0,
+                    0,
+                );
+            } else {
+                tileset.add_image(
+                    editor_state.add_image_name.clone(),
+                    path.to_string_lossy().to_string(),
+                    8, // Default columns - will be recalculated when loaded
+                    8, // Default rows
+                );
+            }
             project.mark_dirty();
         }
     }
@@ -445,6 +585,86 @@ fn render_error_dialog(ctx: &egui::Context, editor_state: &mut EditorState) {
         });
 }
 
+/// Preview a CSV import so it can be confirmed before it is applied
+fn render_data_import_dialog(ctx: &egui::Context, editor_state: &mut EditorState) {
+    use bevy_map_schema::{format_cell, CsvChange};
+
+    let Some(import) = &editor_state.pending_data_import else {
+        return;
+    };
+
+    let mut apply = false;
+    let mut cancel = false;
+
+    egui::Window::new(format!("Import {} CSV", import.type_name))
+        .id(egui::Id::new("data_import_dialog"))
+        .collapsible(false)
+        .resizable(true)
+        .default_width(450.0)
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "{} added, {} updated, {} unchanged. This can be undone.",
+                import.count(CsvChange::Added),
+                import.count(CsvChange::Updated),
+                import.count(CsvChange::Unchanged)
+            ));
+            if !import.is_valid() {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 100, 100),
+                    format!(
+                        "{} row(s) failed validation. Fix the file and import it again.",
+                        import.errors.len()
+                    ),
+                );
+            }
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for error in &import.errors {
+                        ui.colored_label(egui::Color32::from_rgb(255, 100, 100), error.to_string());
+                    }
+                    for row in import.changed_rows() {
+                        let marker = if row.change == CsvChange::Added {
+                            "Add"
+                        } else {
+                            "Update"
+                        };
+                        ui.label(format!("{} {}", marker, row.label()));
+                        for change in &row.changes {
+                            ui.weak(format!(
+                                "    {}: {} → {}",
+                                change.property,
+                                change.before.as_ref().map(format_cell).unwrap_or_default(),
+                                change.after.as_ref().map(format_cell).unwrap_or_default()
+                            ));
+                        }
+                    }
+                });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+                let can_apply = import.is_valid() && import.changed_rows().next().is_some();
+                if ui
+                    .add_enabled(can_apply, egui::Button::new("Apply"))
+                    .clicked()
+                {
+                    apply = true;
+                }
+            });
+        });
+
+    if apply {
+        editor_state.pending_action = Some(PendingAction::ApplyDataImport);
+    } else if cancel {
+        editor_state.pending_data_import = None;
+    }
+}
+
 fn render_add_tileset_image_dialog(
     ctx: &egui::Context,
     editor_state: &mut EditorState,
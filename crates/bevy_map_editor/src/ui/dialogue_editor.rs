@@ -7,7 +7,10 @@
 //! - Node properties panel
 
 use bevy_egui::egui::{self, Color32, CornerRadius, Pos2, Rect, Sense, Stroke, StrokeKind, Vec2};
-use bevy_map_dialogue::{DialogueChoice, DialogueNode, DialogueNodeType, DialogueTree};
+use bevy_map_dialogue::{
+    parse_action, parse_condition, DialogueChoice, DialogueNode, DialogueNodeType, DialogueTree,
+    ExprError,
+};
 use uuid::Uuid;
 
 /// State for the dialogue editor
@@ -26,6 +29,8 @@ pub struct DialogueEditorState {
     /// Node being dragged
     pub dragging_node: Option<String>,
     /// Connection being created: (source_node_id, is_choice, choice_index)
+    ///
+    /// For non-choice connections, index [`ELSE_OUTPUT`] is a condition node's else branch.
     pub creating_connection: Option<(String, bool, usize)>,
     /// Zoom level
     pub zoom: f32,
@@ -92,6 +97,8 @@ const NODE_WIDTH: f32 = 200.0;
 const NODE_HEADER_HEIGHT: f32 = 28.0;
 const NODE_PADDING: f32 = 8.0;
 const CONNECTION_RADIUS: f32 = 6.0;
+/// Output index of a condition node's else branch
+const ELSE_OUTPUT: usize = 1;
 
 /// Render the dialogue editor window
 pub fn render_dialogue_editor(
@@ -231,7 +238,11 @@ pub fn render_dialogue_editor(
                             }
                         }
                     } else if let Some(source) = state.dialogue_tree.get_node_mut(&source_id) {
-                        source.next_node = Some(target_id);
+                        if choice_idx == ELSE_OUTPUT {
+                            source.else_node = Some(target_id);
+                        } else {
+                            source.next_node = Some(target_id);
+                        }
                         result.changed = true;
                     }
                     state.creating_connection = None;
@@ -348,6 +359,15 @@ fn draw_connections(painter: &egui::Painter, canvas_rect: Rect, state: &Dialogue
             }
         }
 
+        // Draw else connection of condition nodes
+        if let Some(else_id) = &node.else_node {
+            if let Some(target) = state.dialogue_tree.get_node(else_id) {
+                let start = node_output_pos(node, canvas_rect, state, false, ELSE_OUTPUT);
+                let end = node_input_pos(target, canvas_rect, state);
+                draw_bezier_connection(painter, start, end, Color32::from_rgb(220, 100, 100));
+            }
+        }
+
         // Draw choice connections
         for (i, choice) in node.choices.iter().enumerate() {
             if let Some(next_id) = &choice.next_node {
@@ -422,6 +442,11 @@ fn node_output_pos(
     if is_choice {
         let y_offset = NODE_HEADER_HEIGHT + NODE_PADDING + (choice_index as f32 + 0.5) * 24.0;
         Pos2::new(node_rect.right(), node_rect.top() + y_offset)
+    } else if choice_index == ELSE_OUTPUT {
+        Pos2::new(
+            node_rect.right(),
+            node_rect.bottom() - NODE_PADDING - CONNECTION_RADIUS,
+        )
     } else {
         Pos2::new(
             node_rect.right(),
@@ -561,6 +586,24 @@ fn draw_nodes(
             Color32::from_rgb(200, 100, 100),
         );
 
+        // Draw else connector for condition nodes
+        let else_pos = (node.node_type == DialogueNodeType::Condition)
+            .then(|| node_output_pos(&node, canvas_rect, state, false, ELSE_OUTPUT));
+        if let Some(else_pos) = else_pos {
+            painter.circle_filled(
+                else_pos,
+                CONNECTION_RADIUS,
+                Color32::from_rgb(220, 100, 100),
+            );
+            painter.text(
+                else_pos - Vec2::new(CONNECTION_RADIUS + 4.0, 0.0),
+                egui::Align2::RIGHT_CENTER,
+                "else",
+                egui::FontId::proportional(10.0),
+                Color32::from_rgb(220, 150, 150),
+            );
+        }
+
         // Draw choice connectors
         for (i, choice) in node.choices.iter().enumerate() {
             let choice_y =
@@ -622,6 +665,18 @@ fn draw_nodes(
             result.start_connection = Some((node_id.clone(), false, 0));
         }
 
+        if let Some(else_pos) = else_pos {
+            let else_rect = Rect::from_center_size(else_pos, Vec2::splat(CONNECTION_RADIUS * 2.5));
+            let else_response = ui.interact(
+                else_rect,
+                egui::Id::new(format!("{}_else", node_id)),
+                Sense::click_and_drag(),
+            );
+            if else_response.drag_started() {
+                result.start_connection = Some((node_id.clone(), false, ELSE_OUTPUT));
+            }
+        }
+
         // Handle connection drop on input
         let input_rect = Rect::from_center_size(input_pos, Vec2::splat(CONNECTION_RADIUS * 2.5));
         let input_response = ui.interact(
@@ -707,44 +762,56 @@ fn render_node_properties(ui: &mut egui::Ui, node: &mut DialogueNode) -> bool {
 
     ui.separator();
 
-    // Condition (optional)
-    ui.collapsing("Condition", |ui| {
-        let mut condition = node.condition.clone().unwrap_or_default();
-        ui.label("Show this node if:");
-        if ui
-            .add(
-                egui::TextEdit::singleline(&mut condition)
-                    .hint_text("e.g. has_quest(\"quest_id\")"),
-            )
-            .changed()
-        {
-            node.condition = if condition.is_empty() {
-                None
+    // Condition (optional, required by condition nodes)
+    let is_condition = node.node_type == DialogueNodeType::Condition;
+    egui::CollapsingHeader::new("Condition")
+        .default_open(is_condition)
+        .show(ui, |ui| {
+            let mut condition = node.condition.clone().unwrap_or_default();
+            ui.label(if is_condition {
+                "Go to next if true, else if false:"
             } else {
-                Some(condition)
-            };
-            changed = true;
-        }
-        ui.small("(Scripting not yet implemented)");
-    });
+                "Show this node if:"
+            });
+            if ui
+                .add(
+                    egui::TextEdit::singleline(&mut condition)
+                        .hint_text("e.g. gold >= 10 && !has_sword"),
+                )
+                .changed()
+            {
+                node.condition = if condition.is_empty() {
+                    None
+                } else {
+                    Some(condition)
+                };
+                changed = true;
+            }
+            show_expression_error(ui, node.condition.as_deref().map(parse_condition));
+        });
 
-    // Action (optional)
-    ui.collapsing("Action", |ui| {
-        let mut action = node.action.clone().unwrap_or_default();
-        ui.label("Execute when entering:");
-        if ui
-            .add(egui::TextEdit::singleline(&mut action).hint_text("e.g. give_item(\"item_id\")"))
-            .changed()
-        {
-            node.action = if action.is_empty() {
-                None
-            } else {
-                Some(action)
-            };
-            changed = true;
-        }
-        ui.small("(Scripting not yet implemented)");
-    });
+    // Action (optional, required by action nodes)
+    egui::CollapsingHeader::new("Action")
+        .default_open(node.node_type == DialogueNodeType::Action)
+        .show(ui, |ui| {
+            let mut action = node.action.clone().unwrap_or_default();
+            ui.label("Execute when entering:");
+            if ui
+                .add(
+                    egui::TextEdit::singleline(&mut action)
+                        .hint_text("e.g. gold -= 10; give_item(\"sword\")"),
+                )
+                .changed()
+            {
+                node.action = if action.is_empty() {
+                    None
+                } else {
+                    Some(action)
+                };
+                changed = true;
+            }
+            show_expression_error(ui, node.action.as_deref().map(parse_action));
+        });
 
     ui.separator();
 
@@ -776,6 +843,32 @@ fn render_node_properties(ui: &mut egui::Ui, node: &mut DialogueNode) -> bool {
                     changed = true;
                 }
             });
+            show_expression_error(ui, choice.condition.as_deref().map(parse_condition));
+            if choice.condition.is_some()
+                && ui
+                    .checkbox(&mut choice.show_disabled, "Show disabled when false")
+                    .changed()
+            {
+                changed = true;
+            }
+
+            // Choice action
+            ui.horizontal(|ui| {
+                ui.label("  Do:");
+                let mut action = choice.action.clone().unwrap_or_default();
+                if ui
+                    .add(egui::TextEdit::singleline(&mut action).desired_width(150.0))
+                    .changed()
+                {
+                    choice.action = if action.is_empty() {
+                        None
+                    } else {
+                        Some(action)
+                    };
+                    changed = true;
+                }
+            });
+            show_expression_error(ui, choice.action.as_deref().map(parse_action));
         }
 
         if let Some(idx) = to_remove {
@@ -786,8 +879,7 @@ fn render_node_properties(ui: &mut egui::Ui, node: &mut DialogueNode) -> bool {
         if ui.button("+ Add Choice").clicked() {
             node.choices.push(DialogueChoice {
                 text: "New choice".to_string(),
-                next_node: None,
-                condition: None,
+                ..Default::default()
             });
             changed = true;
         }
@@ -812,9 +904,31 @@ fn render_node_properties(ui: &mut egui::Ui, node: &mut DialogueNode) -> bool {
         }
     });
 
+    if is_condition {
+        let else_display = node
+            .else_node
+            .as_ref()
+            .map(|n| truncate_str(n, 12))
+            .unwrap_or_else(|| "(none)".to_string());
+        ui.horizontal(|ui| {
+            ui.label(format!("Else: {}", else_display));
+            if node.else_node.is_some() && ui.small_button("x").clicked() {
+                node.else_node = None;
+                changed = true;
+            }
+        });
+    }
+
     changed
 }
 
+/// Show an expression's syntax error below its text field
+fn show_expression_error<T>(ui: &mut egui::Ui, parsed: Option<Result<T, ExprError>>) {
+    if let Some(Err(error)) = parsed {
+        ui.colored_label(Color32::from_rgb(255, 100, 100), error.to_string());
+    }
+}
+
 /// Truncate a string to a maximum length
 fn truncate_str(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
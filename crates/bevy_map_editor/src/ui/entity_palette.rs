@@ -42,40 +42,35 @@ pub fn render_entity_palette(ui: &mut egui::Ui, editor_state: &mut EditorState,
     ui.label("Click to select an entity type, then place on canvas with the Entity tool.");
     ui.separator();
 
-    // List all placeable types
-    for type_name in placeable_types {
-        if let Some(type_def) = project.schema.get_type(type_name) {
-            let selected = editor_state.selected_entity_type.as_deref() == Some(type_name);
-
-            ui.horizontal(|ui| {
-                // Color swatch from type's color field
-                let color = parse_hex_color(&type_def.color);
-                let (rect, _) =
-                    ui.allocate_exact_size(egui::vec2(20.0, 20.0), egui::Sense::hover());
-                ui.painter().rect_filled(rect, 2.0, color);
-
-                // Icon indicator if type has one
-                if type_def.icon.is_some() {
-                    ui.label("img");
-                }
-
-                // Selectable label with type name
-                if ui.selectable_label(selected, type_name).clicked() {
-                    editor_state.selected_entity_type = Some(type_name.to_string());
-                    // Don't automatically switch tools - let users manually select Entity tool
-                }
-            });
-        }
+    // List placeable types grouped under the types they extend
+    let roots: Vec<&str> = project
+        .schema
+        .data_type_names()
+        .into_iter()
+        .filter(|name| {
+            project
+                .schema
+                .base_types(name)
+                .first()
+                .map_or(true, |base| !project.schema.data_types.contains_key(*base))
+        })
+        .collect();
+    for type_name in roots {
+        render_palette_type(ui, editor_state, project, type_name);
     }
 
     ui.separator();
 
     // Show currently selected type info
     if let Some(type_name) = &editor_state.selected_entity_type {
-        if let Some(type_def) = project.schema.get_type(type_name) {
+        if let Some(type_def) = project.schema.resolved_type(type_name) {
             ui.heading("Selected Type");
             ui.label(format!("Name: {}", type_name));
 
+            if let Some(base) = &type_def.extends {
+                ui.label(format!("Extends: {}", base));
+            }
+
             if let Some(icon) = &type_def.icon {
                 ui.label(format!("Icon: {}", icon));
             }
@@ -95,6 +90,66 @@ pub fn render_entity_palette(ui: &mut egui::Ui, editor_state: &mut EditorState,
     }
 }
 
+/// Render a type and the types that extend it
+///
+/// Non-placeable base types are shown as headings so their placeable subtypes
+/// stay grouped. Branches without placeable types are skipped.
+fn render_palette_type(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    project: &Project,
+    type_name: &str,
+) {
+    if !has_placeable(project, type_name) {
+        return;
+    }
+    let Some(type_def) = project.schema.get_type(type_name) else {
+        return;
+    };
+
+    if type_def.placeable {
+        let selected = editor_state.selected_entity_type.as_deref() == Some(type_name);
+
+        ui.horizontal(|ui| {
+            // Color swatch from type's color field
+            let color = parse_hex_color(&type_def.color);
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(20.0, 20.0), egui::Sense::hover());
+            ui.painter().rect_filled(rect, 2.0, color);
+
+            // Icon indicator if type has one
+            if type_def.icon.is_some() {
+                ui.label("img");
+            }
+
+            // Selectable label with type name
+            if ui.selectable_label(selected, type_name).clicked() {
+                editor_state.selected_entity_type = Some(type_name.to_string());
+                // Don't automatically switch tools - let users manually select Entity tool
+            }
+        });
+    } else {
+        ui.weak(type_name);
+    }
+
+    let subtypes = project.schema.subtypes(type_name);
+    if !subtypes.is_empty() {
+        ui.indent(("palette_subtypes", type_name), |ui| {
+            for subtype in subtypes {
+                render_palette_type(ui, editor_state, project, subtype);
+            }
+        });
+    }
+}
+
+/// Whether a type or any type extending it can be placed
+fn has_placeable(project: &Project, type_name: &str) -> bool {
+    project
+        .schema
+        .data_types
+        .iter()
+        .any(|(name, def)| def.placeable && project.schema.is_subtype(name, type_name))
+}
+
 /// Parse a hex color string like "#FF0000" or "FF0000" into egui::Color32
 fn parse_hex_color(color_str: &str) -> egui::Color32 {
     let hex = color_str.trim_start_matches('#');
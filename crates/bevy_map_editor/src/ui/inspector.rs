@@ -2,8 +2,14 @@
 
 use bevy_egui::egui;
 use bevy_map_animation::SpriteData;
+use bevy_map_core::Tileset;
+use bevy_map_schema::{EnumDef, EnumVariant};
+use std::path::Path;
 use uuid::Uuid;
 
+use super::asset_browser::render_asset_picker;
+use super::tileset::render_tile_picker;
+use super::TilesetTextureCache;
 use crate::project::Project;
 use crate::EditorState;
 
@@ -43,10 +49,15 @@ pub struct InspectorResult {
 }
 
 /// Render the property inspector
+///
+/// `tileset_cache` provides tile thumbnails for Tile properties, and Asset
+/// properties browse files under `assets_root`.
 pub fn render_inspector(
     ui: &mut egui::Ui,
     editor_state: &mut EditorState,
     project: &mut Project,
+    tileset_cache: Option<&TilesetTextureCache>,
+    assets_root: &Path,
 ) -> InspectorResult {
     let mut result = InspectorResult::default();
 
@@ -64,7 +75,14 @@ pub fn render_inspector(
             render_layer_inspector(ui, *level_id, *layer_idx, project);
         }
         Selection::Entity(level_id, entity_id) => {
-            if render_entity_inspector(ui, *level_id, *entity_id, project) {
+            if render_entity_inspector(
+                ui,
+                *level_id,
+                *entity_id,
+                project,
+                tileset_cache,
+                assets_root,
+            ) {
                 result.delete_entity = Some((*level_id, *entity_id));
             }
         }
@@ -75,7 +93,14 @@ pub fn render_inspector(
             render_data_type_inspector(ui, type_name, project);
         }
         Selection::DataInstance(instance_id) => {
-            if render_data_instance_inspector(ui, *instance_id, project, &mut result) {
+            if render_data_instance_inspector(
+                ui,
+                *instance_id,
+                project,
+                &mut result,
+                tileset_cache,
+                assets_root,
+            ) {
                 result.delete_data_instance = Some(*instance_id);
             }
         }
@@ -156,11 +181,13 @@ fn render_entity_inspector(
     level_id: Uuid,
     entity_id: Uuid,
     project: &mut Project,
+    tileset_cache: Option<&TilesetTextureCache>,
+    assets_root: &Path,
 ) -> bool {
     let mut should_delete = false;
 
     // Phase 1: Extract read-only schema data before mutable borrow
-    let (type_name, type_def, enums, sprite_sheets, dialogue_options, ref_options) = {
+    let (type_name, type_def, enums, sprite_sheets, tilesets, dialogue_options, ref_options) = {
         let Some(level) = project.get_level(level_id) else {
             ui.label("Level not found");
             return false;
@@ -171,46 +198,24 @@ fn render_entity_inspector(
         };
 
         let type_name = entity.type_name.clone();
-        let type_def = project.schema.get_type(&type_name).cloned();
+        let type_def = project.schema.resolved_type(&type_name);
         let enums = project.schema.enums.clone();
 
         // Collect sprite sheet data (full SpriteData for embedding)
         let sprite_sheets: Vec<SpriteData> = project.sprite_sheets.clone();
 
-        // Collect dialogue options: (id, name)
-        let dialogue_options: Vec<(String, String)> = project
-            .dialogues
-            .iter()
-            .map(|d| (d.id.clone(), d.name.clone()))
-            .collect();
-
-        // Collect ref options per type: HashMap<type_name, Vec<(id, display_name)>>
-        let ref_options: std::collections::HashMap<String, Vec<(String, String)>> = project
-            .data
-            .instances
-            .iter()
-            .map(|(type_name, instances)| {
-                let opts: Vec<(String, String)> = instances
-                    .iter()
-                    .map(|inst| {
-                        let name = inst
-                            .properties
-                            .get("name")
-                            .and_then(|v| v.as_string())
-                            .unwrap_or(&inst.id.to_string())
-                            .to_string();
-                        (inst.id.to_string(), name)
-                    })
-                    .collect();
-                (type_name.clone(), opts)
-            })
-            .collect();
+        // Tilesets for Tile property pickers
+        let tilesets: Vec<Tileset> = project.tilesets.clone();
+
+        let dialogue_options = dialogue_options(project);
+        let ref_options = ref_options(project);
 
         (
             type_name,
             type_def,
             enums,
             sprite_sheets,
+            tilesets,
             dialogue_options,
             ref_options,
         )
@@ -284,6 +289,9 @@ fn render_entity_inspector(
                 &sprite_sheets,
                 &dialogue_options,
                 &ref_options,
+                &tilesets,
+                tileset_cache,
+                assets_root,
             );
         }
     }
@@ -311,17 +319,53 @@ fn render_tileset_inspector(ui: &mut egui::Ui, tileset_id: Uuid, project: &mut P
         ui.text_edit_singleline(&mut tileset.name);
     });
 
+    if tileset.is_collection() {
+        ui.label("Kind: Image Collection (one image per tile)");
+    }
+
+    let (mut tile_width, mut tile_height) = tileset.tile_dimensions();
     ui.horizontal(|ui| {
-        ui.label("Tile Size:");
-        ui.add(egui::DragValue::new(&mut tileset.tile_size).range(1..=256));
+        ui.label(if tileset.is_collection() {
+            "Grid Size:"
+        } else {
+            "Tile Size:"
+        });
+        let width_changed = ui
+            .add(egui::DragValue::new(&mut tile_width).range(1..=256))
+            .changed();
+        ui.label("x");
+        let height_changed = ui
+            .add(egui::DragValue::new(&mut tile_height).range(1..=256))
+            .changed();
+        if width_changed || height_changed {
+            tileset.set_tile_dimensions(tile_width, tile_height);
+        }
     });
 
+    // Collection images are whole tiles, so there is no atlas layout to configure
+    if !tileset.is_collection() {
+        ui.horizontal(|ui| {
+            ui.label("Margin:");
+            ui.add(egui::DragValue::new(&mut tileset.margin).range(0..=64));
+            ui.label("Spacing:");
+            ui.add(egui::DragValue::new(&mut tileset.spacing).range(0..=64));
+        });
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Draw Offset:");
+        ui.add(egui::DragValue::new(&mut tileset.draw_offset[0]).prefix("x: "));
+        ui.add(egui::DragValue::new(&mut tileset.draw_offset[1]).prefix("y: "));
+    })
+    .response
+    .on_hover_text("Pixel offset applied when drawing tiles (y down)");
+
     ui.label(format!("Images: {}", tileset.images.len()));
     ui.label(format!("Total Tiles: {}", tileset.total_tile_count()));
 }
 
 fn render_data_type_inspector(ui: &mut egui::Ui, type_name: &str, project: &mut Project) {
-    let Some(type_def) = project.schema.get_type(type_name) else {
+    let Some(type_def) = project.schema.resolved_type(type_name) else {
         ui.label("Type not found");
         return;
     };
@@ -353,6 +397,13 @@ fn render_data_type_inspector(ui: &mut egui::Ui, type_name: &str, project: &mut
         ui.label(if type_def.placeable { "Yes" } else { "No" });
     });
 
+    if let Some(base) = &type_def.extends {
+        ui.horizontal(|ui| {
+            ui.label("Extends:");
+            ui.label(base);
+        });
+    }
+
     if let Some(icon) = &type_def.icon {
         ui.horizontal(|ui| {
             ui.label("Icon:");
@@ -390,57 +441,37 @@ fn render_data_instance_inspector(
     instance_id: Uuid,
     project: &mut Project,
     result: &mut InspectorResult,
+    tileset_cache: Option<&TilesetTextureCache>,
+    assets_root: &Path,
 ) -> bool {
     let mut should_delete = false;
 
     // Phase 1: Extract read-only schema data before mutable borrow
-    let (type_name, type_def, enums, sprite_sheets, dialogue_options, ref_options) = {
+    let (type_name, type_def, enums, sprite_sheets, tilesets, dialogue_options, ref_options) = {
         let Some(instance) = project.get_data_instance(instance_id) else {
             ui.label("Instance not found");
             return false;
         };
 
         let type_name = instance.type_name.clone();
-        let type_def = project.schema.get_type(&type_name).cloned();
+        let type_def = project.schema.resolved_type(&type_name);
         let enums = project.schema.enums.clone();
 
         // Collect sprite sheet data (full SpriteData for embedding)
         let sprite_sheets: Vec<SpriteData> = project.sprite_sheets.clone();
 
-        // Collect dialogue options: (id, name)
-        let dialogue_options: Vec<(String, String)> = project
-            .dialogues
-            .iter()
-            .map(|d| (d.id.clone(), d.name.clone()))
-            .collect();
-
-        // Collect ref options per type: HashMap<type_name, Vec<(id, display_name)>>
-        let ref_options: std::collections::HashMap<String, Vec<(String, String)>> = project
-            .data
-            .instances
-            .iter()
-            .map(|(type_name, instances)| {
-                let opts: Vec<(String, String)> = instances
-                    .iter()
-                    .map(|inst| {
-                        let name = inst
-                            .properties
-                            .get("name")
-                            .and_then(|v| v.as_string())
-                            .unwrap_or(&inst.id.to_string())
-                            .to_string();
-                        (inst.id.to_string(), name)
-                    })
-                    .collect();
-                (type_name.clone(), opts)
-            })
-            .collect();
+        // Tilesets for Tile property pickers
+        let tilesets: Vec<Tileset> = project.tilesets.clone();
+
+        let dialogue_options = dialogue_options(project);
+        let ref_options = ref_options(project);
 
         (
             type_name,
             type_def,
             enums,
             sprite_sheets,
+            tilesets,
             dialogue_options,
             ref_options,
         )
@@ -492,6 +523,9 @@ fn render_data_instance_inspector(
                 &sprite_sheets,
                 &dialogue_options,
                 &ref_options,
+                &tilesets,
+                tileset_cache,
+                assets_root,
             ) {
                 // Handle inline instance creation for arrays
                 result.create_instance_for_array =
@@ -666,9 +700,46 @@ pub fn get_default_value(prop_def: &bevy_map_schema::PropertyDef) -> bevy_map_co
         PropType::Sprite => Value::Null,
         PropType::Dialogue => Value::Null,
         PropType::Embedded => Value::Null,
+        PropType::Asset => Value::String(String::new()),
+        PropType::Tile => Value::Null,
     }
 }
 
+/// Dialogue options for Dialogue property pickers: (id, name)
+pub(super) fn dialogue_options(project: &Project) -> Vec<(String, String)> {
+    project
+        .dialogues
+        .iter()
+        .map(|d| (d.id.clone(), d.name.clone()))
+        .collect()
+}
+
+/// Data instances per type for Ref property pickers: type_name -> (id, display_name)
+pub(super) fn ref_options(
+    project: &Project,
+) -> std::collections::HashMap<String, Vec<(String, String)>> {
+    project
+        .data
+        .instances
+        .iter()
+        .map(|(type_name, instances)| {
+            let opts: Vec<(String, String)> = instances
+                .iter()
+                .map(|inst| {
+                    let name = inst
+                        .properties
+                        .get("name")
+                        .and_then(|v| v.as_string())
+                        .unwrap_or(&inst.id.to_string())
+                        .to_string();
+                    (inst.id.to_string(), name)
+                })
+                .collect();
+            (type_name.clone(), opts)
+        })
+        .collect()
+}
+
 /// Check if a property should be shown based on its show_if condition
 fn should_show_property(
     prop_def: &bevy_map_schema::PropertyDef,
@@ -693,6 +764,18 @@ fn should_show_property(
     true
 }
 
+/// Label for an enum variant, in the variant's color
+fn enum_variant_text(variant: &EnumVariant) -> egui::RichText {
+    let text = egui::RichText::new(&variant.name);
+    match &variant.color {
+        Some(color) => {
+            let [r, g, b] = parse_hex_color_to_rgb(color);
+            text.color(egui::Rgba::from_rgb(r, g, b))
+        }
+        None => text,
+    }
+}
+
 /// Parse a hex color string to RGB floats
 fn parse_hex_color_to_rgb(hex: &str) -> [f32; 3] {
     let hex = hex.trim_start_matches('#');
@@ -712,15 +795,18 @@ fn parse_hex_color_to_rgb(hex: &str) -> [f32; 3] {
 /// Returns Some(type_name) if user clicks "Create New" for an Array property
 #[allow(clippy::too_many_arguments)]
 #[allow(deprecated)] // PropType::Sprite is deprecated but we still handle it for backwards compat
-fn render_property_value_editor(
+pub(super) fn render_property_value_editor(
     ui: &mut egui::Ui,
     prop_def: &bevy_map_schema::PropertyDef,
     value: &mut bevy_map_core::Value,
     id_salt: &str,
-    enums: &std::collections::HashMap<String, Vec<String>>,
+    enums: &std::collections::HashMap<String, EnumDef>,
     sprite_sheets: &[SpriteData],
     dialogue_options: &[(String, String)],
     ref_options: &std::collections::HashMap<String, Vec<(String, String)>>,
+    tilesets: &[Tileset],
+    tileset_cache: Option<&TilesetTextureCache>,
+    assets_root: &Path,
 ) -> Option<String> {
     use bevy_map_core::Value;
     use bevy_map_schema::PropType;
@@ -770,26 +856,64 @@ fn render_property_value_editor(
         }
 
         PropType::Enum => {
-            if let Some(enum_type) = &prop_def.enum_type {
-                if let Some(enum_values) = enums.get(enum_type) {
-                    let current = value.as_string().unwrap_or(&String::new()).to_string();
-                    egui::ComboBox::from_id_salt(id_salt)
-                        .selected_text(if current.is_empty() {
-                            "(None)"
-                        } else {
-                            &current
-                        })
-                        .show_ui(ui, |ui| {
-                            for enum_val in enum_values {
-                                if ui
-                                    .selectable_label(current == *enum_val, enum_val)
-                                    .clicked()
-                                {
-                                    *value = Value::String(enum_val.clone());
-                                }
-                            }
-                        });
+            let enum_def = prop_def
+                .enum_type
+                .as_ref()
+                .and_then(|enum_type| enums.get(enum_type));
+            if let Some(enum_def) = enum_def.filter(|enum_def| enum_def.flags) {
+                // Flag enums store the set variants as an array of names
+                let set: Vec<String> = value
+                    .as_array()
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(|item| item.as_string().map(str::to_string))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let mut toggled = None;
+                ui.vertical(|ui| {
+                    for variant in &enum_def.variants {
+                        let mut checked = set.contains(&variant.name);
+                        if ui
+                            .checkbox(&mut checked, enum_variant_text(variant))
+                            .changed()
+                        {
+                            toggled = Some(variant.name.clone());
+                        }
+                    }
+                });
+                if let Some(toggled) = toggled {
+                    // Rebuild in schema order
+                    let flags = enum_def
+                        .names()
+                        .filter(|name| (*name == toggled) != set.iter().any(|s| s == name))
+                        .map(|name| Value::String(name.to_string()))
+                        .collect();
+                    *value = Value::Array(flags);
                 }
+            } else if let Some(enum_def) = enum_def {
+                let current = value.as_string().unwrap_or_default().to_string();
+                let selected_text = match enum_def.variant(&current) {
+                    Some(variant) => enum_variant_text(variant),
+                    None if current.is_empty() => egui::RichText::new("(None)"),
+                    None => egui::RichText::new(&current),
+                };
+                egui::ComboBox::from_id_salt(id_salt)
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        for variant in &enum_def.variants {
+                            if ui
+                                .selectable_label(
+                                    current == variant.name,
+                                    enum_variant_text(variant),
+                                )
+                                .clicked()
+                            {
+                                *value = Value::String(variant.name.clone());
+                            }
+                        }
+                    });
             }
         }
 
@@ -936,6 +1060,14 @@ fn render_property_value_editor(
                 });
         }
 
+        PropType::Asset => {
+            render_asset_picker(ui, id_salt, value, prop_def, assets_root);
+        }
+
+        PropType::Tile => {
+            render_tile_picker(ui, id_salt, value, tilesets, tileset_cache);
+        }
+
         PropType::Array => {
             return render_array_editor(ui, prop_def, value, id_salt, ref_options);
         }
@@ -138,6 +138,12 @@ pub fn render_menu_bar(
                 {
                     ui.close();
                 }
+                if ui
+                    .checkbox(&mut editor_state.table_view_state.open, "Table View")
+                    .clicked()
+                {
+                    ui.close();
+                }
                 ui.separator();
                 if ui
                     .checkbox(&mut editor_state.show_grid, "Show Grid")
@@ -151,6 +157,15 @@ pub fn render_menu_bar(
                 {
                     ui.close();
                 }
+                if ui
+                    .checkbox(&mut editor_state.show_nav_grid, "Show Navigation Grid")
+                    .on_hover_text(
+                        "Blocked cells in red, weighted (nav_cost) cells in orange/green",
+                    )
+                    .clicked()
+                {
+                    ui.close();
+                }
                 // Snapping submenu (Tiled-style)
                 ui.menu_button("Snapping", |ui| {
                     if ui
@@ -172,6 +187,14 @@ pub fn render_menu_bar(
                     editor_state.show_new_tileset_dialog = true;
                     ui.close();
                 }
+                if ui
+                    .button("Import Schema...")
+                    .on_hover_text("Merge a schema exported from game code into this project")
+                    .clicked()
+                {
+                    editor_state.pending_action = Some(PendingAction::ImportSchema);
+                    ui.close();
+                }
                 ui.separator();
                 if ui.button("Game Settings...").clicked() {
                     editor_state.pending_action = Some(PendingAction::OpenGameSettings);
@@ -14,6 +14,7 @@ mod new_project_dialog;
 mod schema_editor;
 mod settings_dialog;
 mod spritesheet_editor;
+mod table_view;
 mod terrain;
 mod terrain_palette;
 mod theme;
@@ -35,10 +36,12 @@ pub use schema_editor::{render_schema_editor, SchemaEditorState};
 pub use spritesheet_editor::{
     render_spritesheet_editor, SpriteSheetEditorResult, SpriteSheetEditorState,
 };
+pub use table_view::{render_table_view, TableScope, TableViewResult, TableViewState};
 pub use terrain_palette::{render_terrain_palette, TerrainPaintState};
 pub use theme::EditorTheme;
 pub use tileset::{
-    find_base_tile_for_position, render_tileset_palette, render_tileset_palette_with_cache,
+    find_base_tile_for_position, image_tile_display_size, render_tileset_palette,
+    render_tileset_palette_with_cache, tile_display_size, tile_uv_rect,
 };
 pub use tileset_editor::{render_tileset_editor, TilesetEditorState};
 pub use toolbar::{render_toolbar, EditorTool, ToolMode};
@@ -47,6 +50,7 @@ pub use world_view::{render_new_level_dialog, render_world_view, NewLevelParams,
 
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass, EguiTextureHandle};
+use bevy_map_core::TileGridLayout;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use uuid::Uuid;
@@ -89,6 +93,9 @@ pub struct TilesetTextureCache {
     pub tileset_primary_image: HashMap<Uuid, Uuid>,
     /// Load state for each image (for UI feedback)
     pub load_states: HashMap<Uuid, ImageLoadState>,
+    /// Tilemap atlases for images that don't match bevy_ecs_tilemap's layout:
+    /// image_id -> (atlas handle, layout it was prepared for)
+    pub atlases: HashMap<Uuid, (Handle<Image>, TileGridLayout)>,
 }
 
 impl TilesetTextureCache {
@@ -104,6 +111,16 @@ impl TilesetTextureCache {
             ImageLoadState::Pending
         }
     }
+
+    /// Get the texture to use for a tilemap of this image
+    ///
+    /// Prefers the margin/spacing-adjusted atlas over the raw image.
+    pub fn tilemap_texture(&self, image_id: &Uuid) -> Option<Handle<Image>> {
+        self.atlases
+            .get(image_id)
+            .map(|(handle, _)| handle.clone())
+            .or_else(|| self.loaded.get(image_id).map(|(handle, ..)| handle.clone()))
+    }
 }
 
 /// Main UI plugin
@@ -193,10 +210,9 @@ fn load_tileset_textures(
 
     // Process images directly without collecting into Vec
     // First gather what we need to process (without cloning paths yet)
-    let mut images_to_process: Vec<(uuid::Uuid, usize, uuid::Uuid, u32)> = Vec::new();
+    let mut images_to_process: Vec<(uuid::Uuid, usize, uuid::Uuid)> = Vec::new();
     for tileset in project.tilesets.iter() {
         let tileset_id = tileset.id;
-        let tile_size = tileset.tile_size;
         for (img_idx, image) in tileset.images.iter().enumerate() {
             let img_id = image.id;
             if !cache.loaded.contains_key(&img_id)
@@ -205,12 +221,12 @@ fn load_tileset_textures(
                     Some(ImageLoadState::Failed(_))
                 )
             {
-                images_to_process.push((tileset_id, img_idx, img_id, tile_size));
+                images_to_process.push((tileset_id, img_idx, img_id));
             }
         }
     }
 
-    for (tileset_id, img_idx, image_id, tile_size) in images_to_process {
+    for (tileset_id, img_idx, image_id) in images_to_process {
         // Get the image path only when needed
         let image_path = project
             .tilesets
@@ -252,16 +268,18 @@ fn load_tileset_textures(
                         if let Some(tileset) =
                             project.tilesets.iter_mut().find(|t| t.id == tileset_id)
                         {
+                            let (columns, rows) =
+                                tileset.grid_for_image_size(width as u32, height as u32);
                             if let Some(tileset_image) =
                                 tileset.images.iter_mut().find(|i| i.id == image_id)
                             {
-                                tileset_image.columns = (width as u32) / tile_size.max(1);
-                                tileset_image.rows = (height as u32) / tile_size.max(1);
+                                tileset_image.columns = columns;
+                                tileset_image.rows = rows;
                             }
                             // Also update legacy columns/rows if this is first image
                             if img_idx == 0 {
-                                tileset.columns = (width as u32) / tile_size.max(1);
-                                tileset.rows = (height as u32) / tile_size.max(1);
+                                tileset.columns = columns;
+                                tileset.rows = rows;
                             }
                         }
                     }
@@ -489,8 +507,13 @@ fn render_ui(
                             .id_salt("inspector_scroll")
                             .auto_shrink([false, false])
                             .show(ui, |ui| {
-                                inspector_result =
-                                    render_inspector(ui, &mut editor_state, &mut project);
+                                inspector_result = render_inspector(
+                                    ui,
+                                    &mut editor_state,
+                                    &mut project,
+                                    Some(&tileset_cache),
+                                    assets_base_path.path(),
+                                );
                             });
                     });
 
@@ -589,7 +612,7 @@ fn render_ui(
 
     // Handle create new data instance from tree view
     if let Some(type_name) = tree_view_result.create_data_instance {
-        if let Some(type_def) = project.schema.get_type(&type_name).cloned() {
+        if let Some(type_def) = project.schema.resolved_type(&type_name) {
             let mut instance = DataInstance::new(type_name.clone());
             // Populate with default values from type definition
             for prop_def in &type_def.properties {
@@ -1141,6 +1164,46 @@ fn render_ui(
             });
     }
 
+    // Bottom panel - Table View
+    if editor_state.table_view_state.open {
+        let mut table_result = TableViewResult::default();
+        egui::TopBottomPanel::bottom("table_view")
+            .resizable(true)
+            .default_height(250.0)
+            .min_height(100.0)
+            .show(ctx, |ui| {
+                let current_level = editor_state.selected_level;
+                table_result = render_table_view(
+                    ui,
+                    &mut editor_state.table_view_state,
+                    current_level,
+                    &mut project,
+                    Some(&tileset_cache),
+                    assets_base_path.path(),
+                );
+            });
+
+        if let Some((level_id, entity_id)) = table_result.go_to_entity {
+            if let Some(entity) = project
+                .get_level(level_id)
+                .and_then(|level| level.get_entity(entity_id))
+            {
+                editor_state.camera_offset =
+                    bevy::math::Vec2::new(entity.position[0], entity.position[1]);
+                editor_state.selected_level = Some(level_id);
+                editor_state.selection = Selection::Entity(level_id, entity_id);
+                editor_state.view_mode = crate::EditorViewMode::Level;
+            }
+        }
+        if let Some(instance_id) = table_result.select_data_instance {
+            editor_state.selection = Selection::DataInstance(instance_id);
+        }
+        if let Some(command) = table_result.bulk_edit {
+            editor_state.table_view_state.pending_bulk_edit = Some(command);
+            editor_state.pending_action = Some(PendingAction::ApplyBulkEdit);
+        }
+    }
+
     // Central area - world view or level view
     egui::CentralPanel::default()
         .frame(egui::Frame::NONE)
@@ -1441,6 +1504,31 @@ fn process_edit_actions(
             PendingAction::InstallBevyCli => {
                 handle_install_bevy_cli(&mut editor_state);
             }
+            PendingAction::ApplySchemaRefactor => {
+                if let Some(command) = editor_state.schema_editor_state.pending_refactor.take() {
+                    history.execute(Box::new(command), &mut project, &mut render_state);
+                }
+            }
+            PendingAction::ApplyDataImport => {
+                if let Some(import) = editor_state.pending_data_import.take() {
+                    let command = crate::commands::DataImportCommand::new(&import);
+                    history.execute(Box::new(command), &mut project, &mut render_state);
+                }
+            }
+            PendingAction::ApplyBulkEdit => {
+                if let Some(command) = editor_state.table_view_state.pending_bulk_edit.take() {
+                    history.execute(Box::new(command), &mut project, &mut render_state);
+                }
+            }
+            PendingAction::ApplyTileProperties => {
+                if let Some(command) = editor_state
+                    .tileset_editor_state
+                    .pending_tile_properties
+                    .take()
+                {
+                    history.execute(Box::new(command), &mut project, &mut render_state);
+                }
+            }
             // File operations are handled in dialogs.rs
             _ => {
                 // Put the action back so dialogs.rs can handle it
@@ -10,7 +10,9 @@ use bevy_egui::egui;
 use bevy_map_core::{
     ColliderConfig, InputConfig, InputProfile, PhysicsBodyType, PhysicsConfig, SpriteConfig,
 };
-use bevy_map_schema::{PropType, PropertyDef, TypeDef};
+use bevy_map_schema::{EnumDef, EnumVariant, PropType, PropertyDef, SchemaRefactor, TypeDef};
+
+use crate::commands::SchemaRefactorCommand;
 
 /// State for the schema editor
 #[derive(Default)]
@@ -24,6 +26,8 @@ pub struct SchemaEditorState {
     pub new_enum_value: String,
     pub editing_enum_name: Option<String>,
     pub enum_rename_buffer: String,
+    pub renaming_enum_value: Option<usize>,
+    pub enum_value_rename_buffer: String,
 
     // Data type editing state
     pub selected_type: Option<String>,
@@ -41,6 +45,9 @@ pub struct SchemaEditorState {
 
     // Color picker state
     pub color_picker_buffer: [f32; 3],
+
+    /// Refactor awaiting confirmation, previewing the instances it changes
+    pub pending_refactor: Option<SchemaRefactorCommand>,
 }
 
 /// Subtab within the type editor
@@ -71,6 +78,8 @@ pub struct PropertyEditState {
     pub ref_type: Option<String>,
     pub item_type: Option<String>,
     pub embedded_type: Option<String>,
+    /// Comma-separated asset extensions
+    pub extensions: String,
     pub show_if: String,
 }
 
@@ -86,6 +95,7 @@ impl Default for PropertyEditState {
             ref_type: None,
             item_type: None,
             embedded_type: None,
+            extensions: String::new(),
             show_if: String::new(),
         }
     }
@@ -103,6 +113,7 @@ impl PropertyEditState {
             ref_type: None,
             item_type: None,
             embedded_type: None,
+            extensions: String::new(),
             show_if: String::new(),
         }
     }
@@ -118,6 +129,7 @@ impl PropertyEditState {
             ref_type: prop.ref_type.clone(),
             item_type: prop.item_type.clone(),
             embedded_type: prop.embedded_type.clone(),
+            extensions: prop.extensions.join(", "),
             show_if: prop.show_if.clone().unwrap_or_default(),
         }
     }
@@ -139,6 +151,15 @@ impl PropertyEditState {
             ref_type: self.ref_type.clone(),
             item_type: self.item_type.clone(),
             embedded_type: self.embedded_type.clone(),
+            extensions: if self.prop_type == PropType::Asset {
+                self.extensions
+                    .split(',')
+                    .map(|e| e.trim().trim_start_matches('.').to_string())
+                    .filter(|e| !e.is_empty())
+                    .collect()
+            } else {
+                Vec::new()
+            },
         }
     }
 }
@@ -200,6 +221,93 @@ pub fn render_schema_editor(
     // Property dialogs
     render_add_property_dialog(ctx, editor_state, project);
     render_edit_property_dialog(ctx, editor_state, project);
+
+    render_refactor_preview_dialog(ctx, editor_state);
+}
+
+/// Preview a refactor so it can be confirmed before it is applied
+fn request_refactor(
+    state: &mut SchemaEditorState,
+    project: &crate::project::Project,
+    refactor: SchemaRefactor,
+) {
+    state.pending_refactor = Some(SchemaRefactorCommand::new(refactor, project));
+}
+
+/// Render the confirmation dialog for a pending refactor
+///
+/// Refactors that don't touch any instances are applied without asking.
+fn render_refactor_preview_dialog(ctx: &egui::Context, editor_state: &mut crate::EditorState) {
+    let Some(command) = &editor_state.schema_editor_state.pending_refactor else {
+        return;
+    };
+    if command.affected_count() == 0 {
+        editor_state.pending_action = Some(crate::ui::PendingAction::ApplySchemaRefactor);
+        return;
+    }
+
+    let mut apply = false;
+    let mut cancel = false;
+
+    egui::Window::new("Confirm Schema Change")
+        .id(egui::Id::new("refactor_preview_dialog"))
+        .collapsible(false)
+        .resizable(true)
+        .default_width(450.0)
+        .show(ctx, |ui| {
+            ui.strong(crate::commands::Command::description(command));
+            ui.label(format!(
+                "{} instance(s) will be changed. This can be undone.",
+                command.affected_count()
+            ));
+            if command.failure_count() > 0 {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 180, 80),
+                    format!(
+                        "{} value(s) cannot be converted and will be removed.",
+                        command.failure_count()
+                    ),
+                );
+            }
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for (_, _, entity) in &command.removed_entities {
+                        ui.label(format!("Delete entity {} {}", entity.type_name, entity.id));
+                    }
+                    for instance in &command.removed_data {
+                        ui.label(format!("Delete {} {}", instance.type_name, instance.id));
+                    }
+                    for instance in &command.instances {
+                        ui.label(&instance.label);
+                        for failure in &instance.failures {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(255, 180, 80),
+                                format!("    {}: {}", failure.property, failure.reason),
+                            );
+                        }
+                    }
+                });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+                if ui.button("Apply").clicked() {
+                    apply = true;
+                }
+            });
+        });
+
+    if apply {
+        editor_state.pending_action = Some(crate::ui::PendingAction::ApplySchemaRefactor);
+        editor_state.schema_editor_state.selected_property_idx = None;
+    } else if cancel {
+        editor_state.schema_editor_state.pending_refactor = None;
+    }
 }
 
 /// Render the Enums tab
@@ -222,7 +330,10 @@ fn render_enums_tab(
                 if ui.button("+").clicked() && !state.new_enum_name.is_empty() {
                     let name = state.new_enum_name.clone();
                     if !project.schema.enums.contains_key(&name) {
-                        project.schema.enums.insert(name.clone(), Vec::new());
+                        project
+                            .schema
+                            .enums
+                            .insert(name.clone(), EnumDef::default());
                         state.selected_enum = Some(name);
                         project.mark_dirty();
                     }
@@ -265,6 +376,26 @@ fn render_enums_tab(
     egui::CentralPanel::default().show_inside(ui, |ui| {
         if let Some(enum_name) = &state.selected_enum.clone() {
             ui.heading(format!("Enum: {}", enum_name));
+
+            let current_flags = project
+                .schema
+                .get_enum(enum_name)
+                .is_some_and(|enum_def| enum_def.flags);
+            let mut flags = current_flags;
+            if ui
+                .checkbox(&mut flags, "Flags")
+                .on_hover_text("Values are sets of variants instead of a single variant")
+                .changed()
+            {
+                request_refactor(
+                    state,
+                    project,
+                    SchemaRefactor::SetEnumFlags {
+                        enum_name: enum_name.clone(),
+                        flags,
+                    },
+                );
+            }
             ui.separator();
 
             // Add new value
@@ -272,9 +403,8 @@ fn render_enums_tab(
                 ui.label("New value:");
                 ui.text_edit_singleline(&mut state.new_enum_value);
                 if ui.button("Add").clicked() && !state.new_enum_value.is_empty() {
-                    if let Some(values) = project.schema.enums.get_mut(enum_name) {
-                        if !values.contains(&state.new_enum_value) {
-                            values.push(state.new_enum_value.clone());
+                    if let Some(enum_def) = project.schema.enums.get_mut(enum_name) {
+                        if enum_def.push(state.new_enum_value.clone()) {
                             project.mark_dirty();
                         }
                     }
@@ -284,48 +414,98 @@ fn render_enums_tab(
             ui.separator();
 
             // Values list
-            if let Some(values) = project.schema.enums.get(enum_name).cloned() {
+            if let Some(enum_def) = project.schema.enums.get(enum_name).cloned() {
+                let values = &enum_def.variants;
                 let mut to_delete = None;
                 let mut to_move_up = None;
                 let mut to_move_down = None;
+                let mut to_rename = None;
+                let mut edited = None;
 
                 for (idx, value) in values.iter().enumerate() {
                     ui.horizontal(|ui| {
                         ui.label(format!("{}.", idx + 1));
-                        ui.label(value);
+                        if state.renaming_enum_value == Some(idx) {
+                            let response =
+                                ui.text_edit_singleline(&mut state.enum_value_rename_buffer);
+                            if response.lost_focus() {
+                                if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                                    to_rename = Some(idx);
+                                }
+                                state.renaming_enum_value = None;
+                            }
+                        } else {
+                            ui.label(&value.name);
+                        }
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             if ui.small_button("X").clicked() {
                                 to_delete = Some(idx);
                             }
+                            if ui.small_button("Rename").clicked() {
+                                state.renaming_enum_value = Some(idx);
+                                state.enum_value_rename_buffer = value.name.clone();
+                            }
                             if idx + 1 < values.len() && ui.small_button("v").clicked() {
                                 to_move_down = Some(idx);
                             }
                             if idx > 0 && ui.small_button("^").clicked() {
                                 to_move_up = Some(idx);
                             }
+                            ui.separator();
+                            if let Some(variant) = render_enum_variant_settings(
+                                ui,
+                                value,
+                                enum_def.value_of(&value.name).unwrap_or_default(),
+                            ) {
+                                edited = Some((idx, variant));
+                            }
                         });
                     });
                 }
 
                 // Apply changes
-                if let Some(idx) = to_delete {
-                    if let Some(values) = project.schema.enums.get_mut(enum_name) {
-                        values.remove(idx);
+                if let Some((idx, variant)) = edited {
+                    if let Some(enum_def) = project.schema.enums.get_mut(enum_name) {
+                        enum_def.variants[idx] = variant;
                         project.mark_dirty();
                     }
                 }
+                if let Some(idx) = to_delete {
+                    request_refactor(
+                        state,
+                        project,
+                        SchemaRefactor::DeleteEnumValue {
+                            enum_name: enum_name.clone(),
+                            value: values[idx].name.clone(),
+                        },
+                    );
+                }
+                if let Some(idx) = to_rename {
+                    let new_value = state.enum_value_rename_buffer.trim().to_string();
+                    if !new_value.is_empty() && !enum_def.contains(&new_value) {
+                        request_refactor(
+                            state,
+                            project,
+                            SchemaRefactor::RenameEnumValue {
+                                enum_name: enum_name.clone(),
+                                from: values[idx].name.clone(),
+                                to: new_value,
+                            },
+                        );
+                    }
+                }
                 if let Some(idx) = to_move_up {
-                    if let Some(values) = project.schema.enums.get_mut(enum_name) {
+                    if let Some(enum_def) = project.schema.enums.get_mut(enum_name) {
                         if idx > 0 {
-                            values.swap(idx, idx - 1);
+                            enum_def.variants.swap(idx, idx - 1);
                             project.mark_dirty();
                         }
                     }
                 }
                 if let Some(idx) = to_move_down {
-                    if let Some(values) = project.schema.enums.get_mut(enum_name) {
-                        if idx + 1 < values.len() {
-                            values.swap(idx, idx + 1);
+                    if let Some(enum_def) = project.schema.enums.get_mut(enum_name) {
+                        if idx + 1 < enum_def.variants.len() {
+                            enum_def.variants.swap(idx, idx + 1);
                             project.mark_dirty();
                         }
                     }
@@ -337,6 +517,85 @@ fn render_enums_tab(
     });
 }
 
+/// Color, icon and value controls for an enum variant, laid out right to left
+///
+/// Returns the edited variant if anything changed.
+fn render_enum_variant_settings(
+    ui: &mut egui::Ui,
+    variant: &EnumVariant,
+    value: i64,
+) -> Option<EnumVariant> {
+    let mut edited = variant.clone();
+    let mut changed = false;
+
+    // Explicit value; empty uses the default shown as the hint
+    let mut value_text = variant.value.map(|v| v.to_string()).unwrap_or_default();
+    if ui
+        .add(
+            egui::TextEdit::singleline(&mut value_text)
+                .hint_text(value.to_string())
+                .desired_width(48.0),
+        )
+        .on_hover_text("Integer value (bit mask for flags); empty uses the default")
+        .changed()
+    {
+        let trimmed = value_text.trim();
+        if trimmed.is_empty() {
+            edited.value = None;
+            changed = true;
+        } else if let Ok(parsed) = trimmed.parse() {
+            edited.value = Some(parsed);
+            changed = true;
+        }
+    }
+
+    match &variant.icon {
+        Some(icon) => {
+            if ui.small_button("x").on_hover_text("Clear icon").clicked() {
+                edited.icon = None;
+                changed = true;
+            }
+            let file_name = std::path::Path::new(icon)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| icon.clone());
+            ui.label(file_name).on_hover_text(icon);
+        }
+        None =>
+        {
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui.small_button("Icon...").clicked() {
+                if let Some(path) = open_icon_dialog() {
+                    edited.icon = Some(path);
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    match &variant.color {
+        Some(color) => {
+            if ui.small_button("x").on_hover_text("Clear color").clicked() {
+                edited.color = None;
+                changed = true;
+            }
+            let mut rgb = parse_color_rgb(color);
+            if ui.color_edit_button_rgb(&mut rgb).changed() {
+                edited.color = Some(rgb_to_hex(rgb));
+                changed = true;
+            }
+        }
+        None => {
+            if ui.small_button("Color").clicked() {
+                edited.color = Some("#ffffff".to_string());
+                changed = true;
+            }
+        }
+    }
+
+    changed.then_some(edited)
+}
+
 /// Render the Data Types tab
 fn render_data_types_tab(
     ui: &mut egui::Ui,
@@ -406,11 +665,11 @@ fn render_data_types_tab(
                     }
 
                     if let Some(name) = to_delete {
-                        project.schema.data_types.remove(&name);
-                        if state.selected_type.as_ref() == Some(&name) {
-                            state.selected_type = None;
-                        }
-                        project.mark_dirty();
+                        request_refactor(
+                            state,
+                            project,
+                            SchemaRefactor::DeleteType { type_name: name },
+                        );
                     }
                 });
         });
@@ -441,21 +700,55 @@ fn render_type_editor(
     ui.separator();
 
     // Read current values for display
-    let (current_placeable, current_color, current_icon, current_marker_size) = {
+    let (
+        current_placeable,
+        current_color,
+        current_color_by,
+        current_icon,
+        current_marker_size,
+        current_extends,
+    ) = {
         let type_def = project.schema.data_types.get(type_name).unwrap();
         (
             type_def.placeable,
             type_def.color.clone(),
+            type_def.color_by.clone(),
             type_def.icon.clone(),
             type_def.marker_size,
+            type_def.extends.clone(),
         )
     };
 
+    // Enum properties whose variant colors can color the marker
+    let enum_properties: Vec<String> = project
+        .schema
+        .resolved_type(type_name)
+        .map(|resolved| {
+            resolved
+                .properties
+                .into_iter()
+                .filter(|p| p.prop_type == PropType::Enum)
+                .map(|p| p.name)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Types that can be extended without creating a cycle
+    let base_candidates: Vec<String> = project
+        .schema
+        .data_type_names()
+        .into_iter()
+        .filter(|name| !project.schema.is_subtype(name, type_name))
+        .map(str::to_string)
+        .collect();
+
     // Type settings
     let mut new_placeable = current_placeable;
     let mut new_color = parse_color_rgb(&current_color);
+    let mut new_color_by = current_color_by.clone();
     let mut new_icon = current_icon.clone().unwrap_or_default();
     let mut new_marker_size = current_marker_size.unwrap_or(16) as i32;
+    let mut new_extends = current_extends.clone();
     let mut settings_changed = false;
 
     egui::CollapsingHeader::new("Settings")
@@ -466,6 +759,28 @@ fn render_type_editor(
                 .num_columns(2)
                 .spacing([10.0, 4.0])
                 .show(ui, |ui| {
+                    // Base type
+                    ui.label("Extends:");
+                    egui::ComboBox::from_id_salt(format!("extends_{}", type_name))
+                        .selected_text(new_extends.as_deref().unwrap_or("(none)"))
+                        .show_ui(ui, |ui| {
+                            if ui
+                                .selectable_value(&mut new_extends, None, "(none)")
+                                .changed()
+                            {
+                                settings_changed = true;
+                            }
+                            for base in &base_candidates {
+                                if ui
+                                    .selectable_value(&mut new_extends, Some(base.clone()), base)
+                                    .changed()
+                                {
+                                    settings_changed = true;
+                                }
+                            }
+                        });
+                    ui.end_row();
+
                     // Placeable checkbox
                     ui.label("Placeable:");
                     if ui
@@ -499,6 +814,36 @@ fn render_type_editor(
                     }
                     ui.end_row();
 
+                    // Marker color from an enum property's variant colors
+                    if !enum_properties.is_empty() || new_color_by.is_some() {
+                        ui.label("Color By:");
+                        egui::ComboBox::from_id_salt(format!("color_by_{}", type_name))
+                            .selected_text(new_color_by.as_deref().unwrap_or("(type color)"))
+                            .show_ui(ui, |ui| {
+                                if ui
+                                    .selectable_value(&mut new_color_by, None, "(type color)")
+                                    .changed()
+                                {
+                                    settings_changed = true;
+                                }
+                                for property in &enum_properties {
+                                    if ui
+                                        .selectable_value(
+                                            &mut new_color_by,
+                                            Some(property.clone()),
+                                            property,
+                                        )
+                                        .changed()
+                                    {
+                                        settings_changed = true;
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text("Use the variant color of an enum property for markers");
+                        ui.end_row();
+                    }
+
                     // Icon (optional) - file browser
                     ui.label("Icon:");
                     ui.horizontal(|ui| {
@@ -535,17 +880,14 @@ fn render_type_editor(
     if settings_changed {
         if let Some(type_def) = project.schema.data_types.get_mut(type_name) {
             type_def.placeable = new_placeable;
+            type_def.extends = new_extends;
             type_def.marker_size = if new_placeable {
                 Some(new_marker_size as u32)
             } else {
                 None
             };
-            type_def.color = format!(
-                "#{:02x}{:02x}{:02x}",
-                (new_color[0] * 255.0) as u8,
-                (new_color[1] * 255.0) as u8,
-                (new_color[2] * 255.0) as u8
-            );
+            type_def.color = rgb_to_hex(new_color);
+            type_def.color_by = new_color_by;
             type_def.icon = if new_icon.is_empty() {
                 None
             } else {
@@ -606,6 +948,33 @@ fn render_properties_section(
             state.property_edit_state = PropertyEditState::new();
         }
     });
+
+    // Inherited properties are edited on the base type; redeclaring one here
+    // overrides its default, min and max
+    let base = project
+        .schema
+        .data_types
+        .get(type_name)
+        .and_then(|t| t.extends.clone());
+    if let Some(base_def) = base
+        .as_deref()
+        .and_then(|b| project.schema.resolved_type(b))
+    {
+        let names: Vec<&str> = base_def
+            .properties
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        ui.weak(format!(
+            "Inherited from {}: {}",
+            base.as_deref().unwrap_or_default(),
+            if names.is_empty() {
+                "(none)".to_string()
+            } else {
+                names.join(", ")
+            }
+        ));
+    }
     ui.separator();
 
     // Property list
@@ -661,6 +1030,10 @@ fn render_properties_section(
                                     if let Some(max) = prop.max {
                                         details.push(format!("max: {}", max));
                                     }
+                                    if !prop.extensions.is_empty() {
+                                        details
+                                            .push(format!("files: {}", prop.extensions.join(", ")));
+                                    }
                                     if !details.is_empty() {
                                         ui.label(details.join(", "));
                                     }
@@ -703,11 +1076,14 @@ fn render_properties_section(
 
                 // Apply changes
                 if let Some(idx) = to_delete {
-                    if let Some(type_def) = project.schema.data_types.get_mut(type_name) {
-                        type_def.properties.remove(idx);
-                        state.selected_property_idx = None;
-                        project.mark_dirty();
-                    }
+                    request_refactor(
+                        state,
+                        project,
+                        SchemaRefactor::DeleteProperty {
+                            type_name: type_name.to_string(),
+                            property: type_def.properties[idx].name.clone(),
+                        },
+                    );
                 }
                 if let Some(idx) = to_edit {
                     state.selected_property_idx = Some(idx);
@@ -846,7 +1222,7 @@ fn render_physics_config(
 
                         // Collider Type
                         ui.label("Collider:");
-                        let collider_names = ["Box", "Capsule", "Circle"];
+                        let collider_names = ["Box", "Capsule", "Circle", "Polygon"];
                         egui::ComboBox::from_id_salt(format!("collider_{}", type_name))
                             .selected_text(config.collider.display_name())
                             .show_ui(ui, |ui| {
@@ -859,6 +1235,12 @@ fn render_physics_config(
                                             "Box" => ColliderConfig::new_box(16.0, 16.0),
                                             "Capsule" => ColliderConfig::new_capsule(14.0, 24.0),
                                             "Circle" => ColliderConfig::new_circle(8.0),
+                                            "Polygon" => ColliderConfig::new_polygon(vec![
+                                                [-8.0, -8.0],
+                                                [8.0, -8.0],
+                                                [8.0, 8.0],
+                                                [-8.0, 8.0],
+                                            ]),
                                             _ => ColliderConfig::default(),
                                         };
                                         changed = true;
@@ -935,6 +1317,43 @@ fn render_physics_config(
                                 }
                                 ui.end_row();
                             }
+                            ColliderConfig::Polygon { points } => {
+                                ui.label("Points:");
+                                ui.vertical(|ui| {
+                                    let mut remove_idx = None;
+                                    for (idx, point) in points.iter_mut().enumerate() {
+                                        ui.horizontal(|ui| {
+                                            for value in point.iter_mut() {
+                                                if ui
+                                                    .add(
+                                                        egui::DragValue::new(value)
+                                                            .range(-256.0..=256.0)
+                                                            .suffix(" px"),
+                                                    )
+                                                    .changed()
+                                                {
+                                                    changed = true;
+                                                }
+                                            }
+                                            if ui.small_button("x").clicked() {
+                                                remove_idx = Some(idx);
+                                            }
+                                        });
+                                    }
+                                    if let Some(idx) = remove_idx {
+                                        points.remove(idx);
+                                        changed = true;
+                                    }
+                                    if ui.small_button("+ Add Point").clicked() {
+                                        points.push(points.last().copied().unwrap_or_default());
+                                        changed = true;
+                                    }
+                                    if let Err(issue) = bevy_map_core::validate_polygon(points) {
+                                        ui.colored_label(egui::Color32::RED, issue.to_string());
+                                    }
+                                });
+                                ui.end_row();
+                            }
                         }
 
                         // Gravity Scale
@@ -1315,18 +1734,27 @@ fn render_edit_property_dialog(
         });
 
     if save {
-        if let Some(type_name) = &editor_state.schema_editor_state.selected_type.clone() {
-            if let Some(prop_idx) = editor_state.schema_editor_state.selected_property_idx {
-                if let Some(type_def) = project.schema.data_types.get_mut(type_name) {
-                    if prop_idx < type_def.properties.len() {
-                        let prop = editor_state
-                            .schema_editor_state
-                            .property_edit_state
-                            .to_property();
-                        type_def.properties[prop_idx] = prop;
-                        project.mark_dirty();
-                    }
-                }
+        let state = &mut editor_state.schema_editor_state;
+        if let (Some(type_name), Some(prop_idx)) =
+            (state.selected_type.clone(), state.selected_property_idx)
+        {
+            let property = project
+                .schema
+                .data_types
+                .get(&type_name)
+                .and_then(|type_def| type_def.properties.get(prop_idx))
+                .map(|prop| prop.name.clone());
+            if let Some(property) = property {
+                let def = Box::new(state.property_edit_state.to_property());
+                request_refactor(
+                    state,
+                    project,
+                    SchemaRefactor::EditProperty {
+                        type_name,
+                        property,
+                        def,
+                    },
+                );
             }
         }
     }
@@ -1398,6 +1826,14 @@ fn render_property_form(
                     ui.text_edit_singleline(&mut state.max);
                     ui.end_row();
                 }
+                PropType::Asset => {
+                    ui.label("Extensions:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut state.extensions)
+                            .hint_text("e.g. ogg, wav (empty = any file)"),
+                    );
+                    ui.end_row();
+                }
                 PropType::Enum => {
                     ui.label("Enum Type:");
                     let enum_names: Vec<_> = project.schema.enums.keys().cloned().collect();
@@ -1518,6 +1954,16 @@ fn parse_color_rgb(color_str: &str) -> [f32; 3] {
     ]
 }
 
+/// Format RGB floats as a `#rrggbb` hex string
+fn rgb_to_hex(rgb: [f32; 3]) -> String {
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        (rgb[0] * 255.0) as u8,
+        (rgb[1] * 255.0) as u8,
+        (rgb[2] * 255.0) as u8
+    )
+}
+
 /// Open a file dialog to select an icon image (native only)
 #[cfg(not(target_arch = "wasm32"))]
 fn open_icon_dialog() -> Option<String> {
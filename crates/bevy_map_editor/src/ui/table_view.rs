@@ -0,0 +1,565 @@
+//! Table view - spreadsheet-style editing of every instance of a type
+//!
+//! Lists the placed entities of a placeable type (across all levels or in the
+//! current level) or the data instances of a data type, with one row per
+//! instance and one column per schema property.
+
+use bevy_egui::egui;
+use bevy_map_animation::SpriteData;
+use bevy_map_core::{Tileset, Value};
+use bevy_map_schema::{format_cell, EnumDef, PropType, PropertyDef};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use uuid::Uuid;
+
+use super::inspector::{
+    dialogue_options, get_default_value, ref_options, render_property_value_editor,
+};
+use super::TilesetTextureCache;
+use crate::commands::{InstanceLocation, SetPropertyCommand};
+use crate::project::Project;
+
+/// Sort key for the instance type column
+const TYPE_COLUMN: &str = "$type";
+/// Sort key for the level column
+const LEVEL_COLUMN: &str = "$level";
+/// Longest text shown in a read-only cell before it is truncated
+const MAX_CELL_CHARS: usize = 40;
+
+/// Which levels entity rows come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TableScope {
+    #[default]
+    AllLevels,
+    CurrentLevel,
+}
+
+/// State for the table view panel
+pub struct TableViewState {
+    pub open: bool,
+    /// Type whose instances are listed (subtypes are included)
+    pub type_name: Option<String>,
+    pub scope: TableScope,
+    /// Only rows with a cell containing this text are shown
+    pub filter: String,
+    /// Column to sort by, `None` for project order
+    pub sort_column: Option<String>,
+    pub sort_ascending: bool,
+    /// Rows checked for bulk editing
+    pub selected: HashSet<InstanceLocation>,
+    /// Property and value set on the checked rows by the bulk edit bar
+    pub bulk_property: Option<String>,
+    pub bulk_value: Value,
+    /// Bulk edit waiting to run through the command history
+    pub pending_bulk_edit: Option<SetPropertyCommand>,
+}
+
+impl Default for TableViewState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            type_name: None,
+            scope: TableScope::default(),
+            filter: String::new(),
+            sort_column: None,
+            sort_ascending: true,
+            selected: HashSet::new(),
+            bulk_property: None,
+            bulk_value: Value::Null,
+            pending_bulk_edit: None,
+        }
+    }
+}
+
+impl TableViewState {
+    /// Open the table listing instances of a type
+    pub fn show_type(&mut self, type_name: &str) {
+        self.open = true;
+        if self.type_name.as_deref() != Some(type_name) {
+            self.type_name = Some(type_name.to_string());
+            self.sort_column = None;
+            self.selected.clear();
+            self.bulk_property = None;
+        }
+    }
+
+    fn toggle_sort(&mut self, column: &str) {
+        if self.sort_column.as_deref() == Some(column) {
+            self.sort_ascending = !self.sort_ascending;
+        } else {
+            self.sort_column = Some(column.to_string());
+            self.sort_ascending = true;
+        }
+    }
+}
+
+/// Result from rendering the table view
+#[derive(Default)]
+pub struct TableViewResult {
+    /// Entity to select and center in the viewport: (level_id, entity_id)
+    pub go_to_entity: Option<(Uuid, Uuid)>,
+    /// Data instance to select in the inspector
+    pub select_data_instance: Option<Uuid>,
+    /// Bulk edit to run through the command history
+    pub bulk_edit: Option<SetPropertyCommand>,
+}
+
+/// One instance listed in the table
+struct TableRow {
+    location: InstanceLocation,
+    type_name: String,
+    level_name: Option<String>,
+    properties: HashMap<String, Value>,
+}
+
+impl TableRow {
+    fn id(&self) -> Uuid {
+        match self.location {
+            InstanceLocation::Entity { entity_id, .. } => entity_id,
+            InstanceLocation::Data { id } => id,
+        }
+    }
+}
+
+/// Options shared by every cell editor in a frame
+struct CellContext<'a> {
+    enums: &'a HashMap<String, EnumDef>,
+    sprite_sheets: &'a [SpriteData],
+    dialogue_options: &'a [(String, String)],
+    ref_options: &'a HashMap<String, Vec<(String, String)>>,
+    tilesets: &'a [Tileset],
+    tileset_cache: Option<&'a TilesetTextureCache>,
+    assets_root: &'a Path,
+}
+
+impl CellContext<'_> {
+    /// Whether a property's editor fits in a table cell
+    fn is_inline_editable(&self, prop: &PropertyDef) -> bool {
+        match prop.prop_type {
+            PropType::Array | PropType::Embedded | PropType::Tile => false,
+            PropType::Enum => !prop
+                .enum_type
+                .as_ref()
+                .and_then(|enum_type| self.enums.get(enum_type))
+                .is_some_and(|enum_def| enum_def.flags),
+            _ => true,
+        }
+    }
+
+    /// Render an editor for a value, returning true if it changed
+    fn edit(
+        &self,
+        ui: &mut egui::Ui,
+        prop: &PropertyDef,
+        value: &mut Value,
+        id_salt: &str,
+    ) -> bool {
+        let before = value.clone();
+        if prop.prop_type == PropType::Multiline {
+            // Multiline text is edited on one line to keep rows compact
+            let mut s = value.as_string().unwrap_or_default().to_string();
+            if ui.text_edit_singleline(&mut s).changed() {
+                *value = Value::String(s);
+            }
+        } else {
+            render_property_value_editor(
+                ui,
+                prop,
+                value,
+                id_salt,
+                self.enums,
+                self.sprite_sheets,
+                self.dialogue_options,
+                self.ref_options,
+                self.tilesets,
+                self.tileset_cache,
+                self.assets_root,
+            );
+        }
+        *value != before
+    }
+
+    /// Display text of a value, used for filtering and sorting
+    fn text(&self, prop: &PropertyDef, value: &Value) -> String {
+        let options = match prop.prop_type {
+            PropType::Ref => prop
+                .ref_type
+                .as_ref()
+                .and_then(|ref_type| self.ref_options.get(ref_type))
+                .map(Vec::as_slice),
+            PropType::Dialogue => Some(self.dialogue_options),
+            _ => None,
+        };
+        if let (Some(options), Some(id)) = (options, value.as_string()) {
+            if let Some((_, name)) = options.iter().find(|(option_id, _)| option_id == id) {
+                return name.clone();
+            }
+        }
+        format_cell(value)
+    }
+}
+
+/// Render the table view panel
+pub fn render_table_view(
+    ui: &mut egui::Ui,
+    state: &mut TableViewState,
+    current_level: Option<Uuid>,
+    project: &mut Project,
+    tileset_cache: Option<&TilesetTextureCache>,
+    assets_root: &Path,
+) -> TableViewResult {
+    let mut result = TableViewResult::default();
+
+    let type_def = state
+        .type_name
+        .as_ref()
+        .and_then(|type_name| project.schema.resolved_type(type_name));
+    let placeable = type_def.as_ref().is_some_and(|def| def.placeable);
+
+    ui.horizontal(|ui| {
+        ui.heading("Table");
+        ui.separator();
+        ui.label("Type:");
+        egui::ComboBox::from_id_salt("table_view_type")
+            .selected_text(state.type_name.as_deref().unwrap_or("(Select)"))
+            .show_ui(ui, |ui| {
+                for type_name in project.schema.all_type_names() {
+                    let selected = state.type_name.as_deref() == Some(type_name);
+                    if ui.selectable_label(selected, type_name).clicked() {
+                        state.show_type(type_name);
+                    }
+                }
+            });
+        if placeable {
+            ui.separator();
+            ui.radio_value(&mut state.scope, TableScope::AllLevels, "All Levels");
+            ui.radio_value(&mut state.scope, TableScope::CurrentLevel, "Current Level");
+        }
+        ui.separator();
+        ui.label("Filter:");
+        ui.text_edit_singleline(&mut state.filter);
+        if !state.filter.is_empty() && ui.small_button("x").clicked() {
+            state.filter.clear();
+        }
+    });
+    ui.separator();
+
+    let (Some(type_name), Some(type_def)) = (state.type_name.clone(), type_def) else {
+        ui.label("Select a type to list its instances");
+        return result;
+    };
+    let columns = type_def.properties;
+    let has_subtypes = !project.schema.subtypes(&type_name).is_empty();
+
+    let dialogue_options = dialogue_options(project);
+    let ref_options = ref_options(project);
+    let cells = CellContext {
+        enums: &project.schema.enums,
+        sprite_sheets: &project.sprite_sheets,
+        dialogue_options: &dialogue_options,
+        ref_options: &ref_options,
+        tilesets: &project.tilesets,
+        tileset_cache,
+        assets_root,
+    };
+
+    let mut rows = collect_rows(project, &type_name, placeable, state.scope, current_level);
+    if !state.filter.is_empty() {
+        let needle = state.filter.to_lowercase();
+        rows.retain(|row| {
+            row.level_name
+                .iter()
+                .chain(std::iter::once(&row.type_name))
+                .any(|text| text.to_lowercase().contains(&needle))
+                || columns.iter().any(|prop| {
+                    row.properties.get(&prop.name).is_some_and(|value| {
+                        cells.text(prop, value).to_lowercase().contains(&needle)
+                    })
+                })
+        });
+    }
+    if let Some(column) = &state.sort_column {
+        let prop = columns.iter().find(|prop| prop.name == *column);
+        rows.sort_by(|a, b| {
+            let ordering = match (column.as_str(), prop) {
+                (TYPE_COLUMN, _) => compare_text(&a.type_name, &b.type_name),
+                (LEVEL_COLUMN, _) => compare_text(
+                    a.level_name.as_deref().unwrap_or_default(),
+                    b.level_name.as_deref().unwrap_or_default(),
+                ),
+                (_, Some(prop)) => compare_values(
+                    &cells,
+                    prop,
+                    a.properties.get(&prop.name),
+                    b.properties.get(&prop.name),
+                ),
+                _ => Ordering::Equal,
+            };
+            if state.sort_ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+    }
+    // Rows hidden by the scope or filter can't be bulk edited
+    state
+        .selected
+        .retain(|location| rows.iter().any(|row| row.location == *location));
+
+    // Bulk edit bar
+    ui.horizontal(|ui| {
+        ui.label(format!(
+            "{} row(s), {} selected",
+            rows.len(),
+            state.selected.len()
+        ));
+        if state.selected.is_empty() {
+            return;
+        }
+        ui.separator();
+        ui.label("Set");
+        egui::ComboBox::from_id_salt("table_view_bulk_property")
+            .selected_text(state.bulk_property.as_deref().unwrap_or("(Property)"))
+            .show_ui(ui, |ui| {
+                for prop in columns.iter().filter(|prop| cells.is_inline_editable(prop)) {
+                    let selected = state.bulk_property.as_deref() == Some(prop.name.as_str());
+                    if ui.selectable_label(selected, &prop.name).clicked() && !selected {
+                        state.bulk_property = Some(prop.name.clone());
+                        state.bulk_value = get_default_value(prop);
+                    }
+                }
+            });
+        let bulk_prop = state
+            .bulk_property
+            .as_ref()
+            .and_then(|name| columns.iter().find(|prop| prop.name == *name));
+        if let Some(prop) = bulk_prop {
+            ui.label("to");
+            cells.edit(ui, prop, &mut state.bulk_value, "table_view_bulk_value");
+            if ui
+                .button(format!("Apply to {} row(s)", state.selected.len()))
+                .clicked()
+            {
+                // Keep table order so undo descriptions and results are predictable
+                let targets = rows
+                    .iter()
+                    .map(|row| row.location)
+                    .filter(|location| state.selected.contains(location));
+                result.bulk_edit = Some(SetPropertyCommand::new(
+                    project,
+                    targets,
+                    &prop.name,
+                    state.bulk_value.clone(),
+                ));
+            }
+        }
+        if ui.button("Clear Selection").clicked() {
+            state.selected.clear();
+        }
+    });
+    ui.separator();
+
+    // Inline edits are applied once the grid is drawn: (row, property, value)
+    let mut edits: Vec<(InstanceLocation, String, Value)> = Vec::new();
+
+    egui::ScrollArea::both()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            egui::Grid::new("table_view_grid")
+                .striped(true)
+                .show(ui, |ui| {
+                    // Header
+                    let mut all_selected = !rows.is_empty()
+                        && rows
+                            .iter()
+                            .all(|row| state.selected.contains(&row.location));
+                    if ui
+                        .checkbox(&mut all_selected, "")
+                        .on_hover_text("Select all rows")
+                        .changed()
+                    {
+                        if all_selected {
+                            state.selected.extend(rows.iter().map(|row| row.location));
+                        } else {
+                            state.selected.clear();
+                        }
+                    }
+                    ui.label("");
+                    if has_subtypes {
+                        sort_header(ui, state, TYPE_COLUMN, "Type");
+                    }
+                    if placeable {
+                        sort_header(ui, state, LEVEL_COLUMN, "Level");
+                    }
+                    for prop in &columns {
+                        sort_header(ui, state, &prop.name, &prop.name);
+                    }
+                    ui.end_row();
+
+                    for row in &rows {
+                        let mut selected = state.selected.contains(&row.location);
+                        if ui.checkbox(&mut selected, "").changed() {
+                            if selected {
+                                state.selected.insert(row.location);
+                            } else {
+                                state.selected.remove(&row.location);
+                            }
+                        }
+
+                        match row.location {
+                            InstanceLocation::Entity {
+                                level_id,
+                                entity_id,
+                            } => {
+                                if ui
+                                    .small_button("Go to")
+                                    .on_hover_text("Select and center in the viewport")
+                                    .clicked()
+                                {
+                                    result.go_to_entity = Some((level_id, entity_id));
+                                }
+                            }
+                            InstanceLocation::Data { id } => {
+                                if ui
+                                    .small_button("Select")
+                                    .on_hover_text("Show in the inspector")
+                                    .clicked()
+                                {
+                                    result.select_data_instance = Some(id);
+                                }
+                            }
+                        }
+                        if has_subtypes {
+                            ui.label(&row.type_name);
+                        }
+                        if placeable {
+                            ui.label(row.level_name.as_deref().unwrap_or_default());
+                        }
+
+                        for prop in &columns {
+                            let current = row.properties.get(&prop.name);
+                            if cells.is_inline_editable(prop) {
+                                let mut value =
+                                    current.cloned().unwrap_or_else(|| get_default_value(prop));
+                                let id_salt = format!("table_{}_{}", row.id(), prop.name);
+                                if cells.edit(ui, prop, &mut value, &id_salt) {
+                                    edits.push((row.location, prop.name.clone(), value));
+                                }
+                            } else {
+                                let text = current.map(format_cell).unwrap_or_default();
+                                if text.chars().count() > MAX_CELL_CHARS {
+                                    let short: String = text.chars().take(MAX_CELL_CHARS).collect();
+                                    ui.label(format!("{}...", short)).on_hover_text(text);
+                                } else {
+                                    ui.label(text);
+                                }
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if !edits.is_empty() {
+        for (location, property, value) in edits {
+            if let Some(properties) = location.properties_mut(project) {
+                properties.insert(property, value);
+            }
+        }
+        project.mark_dirty();
+    }
+
+    result
+}
+
+/// Collect the instances of a type (and its subtypes) in project order
+fn collect_rows(
+    project: &Project,
+    type_name: &str,
+    placeable: bool,
+    scope: TableScope,
+    current_level: Option<Uuid>,
+) -> Vec<TableRow> {
+    let schema = &project.schema;
+    if placeable {
+        project
+            .levels
+            .iter()
+            .filter(|level| scope == TableScope::AllLevels || Some(level.id) == current_level)
+            .flat_map(|level| {
+                level
+                    .entities
+                    .iter()
+                    .filter(|entity| schema.is_subtype(&entity.type_name, type_name))
+                    .map(|entity| TableRow {
+                        location: InstanceLocation::Entity {
+                            level_id: level.id,
+                            entity_id: entity.id,
+                        },
+                        type_name: entity.type_name.clone(),
+                        level_name: Some(level.name.clone()),
+                        properties: entity.properties.clone(),
+                    })
+            })
+            .collect()
+    } else {
+        let mut type_names: Vec<&String> = project
+            .data
+            .instances
+            .keys()
+            .filter(|name| schema.is_subtype(name, type_name))
+            .collect();
+        type_names.sort();
+        type_names
+            .into_iter()
+            .flat_map(|name| project.data.get_by_type(name))
+            .map(|instance| TableRow {
+                location: InstanceLocation::Data { id: instance.id },
+                type_name: instance.type_name.clone(),
+                level_name: None,
+                properties: instance.properties.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Clickable column header showing the sort direction
+fn sort_header(ui: &mut egui::Ui, state: &mut TableViewState, column: &str, label: &str) {
+    let text = match &state.sort_column {
+        Some(sorted) if sorted == column && state.sort_ascending => format!("{} ^", label),
+        Some(sorted) if sorted == column => format!("{} v", label),
+        _ => label.to_string(),
+    };
+    if ui
+        .add(egui::Button::new(egui::RichText::new(text).strong()).frame(false))
+        .on_hover_text("Sort by this column")
+        .clicked()
+    {
+        state.toggle_sort(column);
+    }
+}
+
+/// Case-insensitive text ordering
+fn compare_text(a: &str, b: &str) -> Ordering {
+    a.to_lowercase().cmp(&b.to_lowercase())
+}
+
+/// Order cell values, numerically when both are numbers and missing values last
+fn compare_values(
+    cells: &CellContext,
+    prop: &PropertyDef,
+    a: Option<&Value>,
+    b: Option<&Value>,
+) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => match (a.as_float(), b.as_float()) {
+            (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+            _ => compare_text(&cells.text(prop, a), &cells.text(prop, b)),
+        },
+    }
+}
@@ -1,7 +1,7 @@
 //! Tileset palette display
 
 use bevy_egui::egui;
-use bevy_map_core::Tileset;
+use bevy_map_core::{TileGridLayout, TileRef, Tileset, TilesetImage, Value};
 
 use super::{EditorTheme, ImageLoadState, TilesetTextureCache};
 use crate::project::Project;
@@ -43,6 +43,60 @@ pub fn find_base_tile_for_position(
     clicked_idx // Not in any merged region, return original
 }
 
+/// Get the UV rect of a tile in its image, honoring the tileset's margin and spacing.
+///
+/// Falls back to an even split of the image until its pixel size is known.
+pub fn tile_uv_rect(
+    layout: &TileGridLayout,
+    image: &TilesetImage,
+    local_index: u32,
+    grid_size: (u32, u32),
+    cache: Option<&TilesetTextureCache>,
+) -> egui::Rect {
+    let uv = cache
+        .and_then(|cache| cache.loaded.get(&image.id))
+        .and_then(|(_, _, width, height)| {
+            layout.tile_uv_rect(image, local_index, grid_size, *width, *height)
+        });
+    if let Some([min_x, min_y, max_x, max_y]) = uv {
+        return egui::Rect::from_min_max(egui::pos2(min_x, min_y), egui::pos2(max_x, max_y));
+    }
+
+    let columns = image.columns.max(1);
+    let rows = image.rows.max(1);
+    let col = local_index % columns;
+    let row = local_index / columns;
+    egui::Rect::from_min_max(
+        egui::pos2(col as f32 / columns as f32, row as f32 / rows as f32),
+        egui::pos2(
+            (col + grid_size.0.max(1)) as f32 / columns as f32,
+            (row + grid_size.1.max(1)) as f32 / rows as f32,
+        ),
+    )
+}
+
+/// Get the on-screen size of a tile thumbnail whose longest side is `max_side`
+pub fn tile_display_size(layout: &TileGridLayout, max_side: f32) -> egui::Vec2 {
+    fit_display_size(layout.tile_width, layout.tile_height, max_side)
+}
+
+/// Like [`tile_display_size`], but keeps the aspect ratio of collection tile images
+pub fn image_tile_display_size(
+    layout: &TileGridLayout,
+    image: &TilesetImage,
+    max_side: f32,
+) -> egui::Vec2 {
+    let (width, height) = layout.tile_pixel_size(image);
+    fit_display_size(width, height, max_side)
+}
+
+fn fit_display_size(width: u32, height: u32, max_side: f32) -> egui::Vec2 {
+    let width = width.max(1) as f32;
+    let height = height.max(1) as f32;
+    let scale = max_side / width.max(height);
+    egui::vec2(width * scale, height * scale)
+}
+
 pub fn render_tileset_palette(
     ui: &mut egui::Ui,
     editor_state: &mut EditorState,
@@ -109,13 +163,24 @@ pub fn render_tileset_palette_with_cache(
             // Show tileset summary info
             let total_tiles = tileset.total_tile_count();
             let image_count = tileset.images.len();
-            ui.label(format!(
-                "{} tiles across {} image{}, {}px each",
-                total_tiles,
-                image_count,
-                if image_count == 1 { "" } else { "s" },
-                tileset.tile_size
-            ));
+            if tileset.is_collection() {
+                ui.label(format!(
+                    "{} tile image{} on a {}x{}px grid",
+                    image_count,
+                    if image_count == 1 { "" } else { "s" },
+                    tileset.tile_dimensions().0,
+                    tileset.tile_dimensions().1
+                ));
+            } else {
+                ui.label(format!(
+                    "{} tiles across {} image{}, {}x{}px each",
+                    total_tiles,
+                    image_count,
+                    if image_count == 1 { "" } else { "s" },
+                    tileset.tile_dimensions().0,
+                    tileset.tile_dimensions().1
+                ));
+            }
 
             egui::ScrollArea::both()
                 .auto_shrink([false, false])
@@ -123,6 +188,8 @@ pub fn render_tileset_palette_with_cache(
                     if tileset.images.is_empty() {
                         // Fallback for legacy tilesets
                         render_legacy_tileset(ui, editor_state, tileset, tileset_cache);
+                    } else if tileset.is_collection() {
+                        render_collection_tileset(ui, editor_state, tileset, tileset_cache);
                     } else {
                         // Render all images in the tileset
                         render_multi_image_tileset(ui, editor_state, tileset, tileset_cache);
@@ -136,6 +203,109 @@ pub fn render_tileset_palette_with_cache(
     }
 }
 
+/// Select a palette tile, handling Ctrl+click for the random paint set
+fn select_palette_tile(ui: &egui::Ui, editor_state: &mut EditorState, base_tile: u32) {
+    // Check for Ctrl modifier for random paint tile selection
+    let ctrl_held = ui.input(|i| i.modifiers.ctrl);
+    if ctrl_held && editor_state.random_paint {
+        // Toggle tile in random paint set
+        if let Some(pos) = editor_state
+            .random_paint_tiles
+            .iter()
+            .position(|&t| t == base_tile)
+        {
+            editor_state.random_paint_tiles.remove(pos);
+        } else {
+            editor_state.random_paint_tiles.push(base_tile);
+        }
+        // Also select this tile for visual feedback
+        editor_state.selected_tile = Some(base_tile);
+    } else {
+        // Normal click - select tile and clear random set
+        editor_state.selected_tile = Some(base_tile);
+        if !ctrl_held {
+            editor_state.random_paint_tiles.clear();
+            // Add selected tile to random set if random paint is on
+            if editor_state.random_paint {
+                editor_state.random_paint_tiles.push(base_tile);
+            }
+        }
+    }
+}
+
+/// Render a collection tileset as a wrapped grid of tile images, each at its own aspect ratio
+fn render_collection_tileset(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    tileset: &Tileset,
+    tileset_cache: Option<&TilesetTextureCache>,
+) {
+    let layout = tileset.grid_layout();
+
+    ui.horizontal_wrapped(|ui| {
+        for (img_idx, image) in tileset.images.iter().enumerate() {
+            let Some(virtual_index) = tileset.local_to_virtual(img_idx, 0) else {
+                continue;
+            };
+            let display_size = image_tile_display_size(&layout, image, 48.0);
+            let texture_id = tileset_cache
+                .and_then(|cache| cache.loaded.get(&image.id))
+                .map(|(_, tex_id, _, _)| *tex_id);
+
+            let response = match texture_id {
+                Some(tex_id) => ui.add(
+                    egui::Button::image(egui::Image::new(egui::load::SizedTexture::new(
+                        tex_id,
+                        display_size,
+                    )))
+                    .frame(false)
+                    .corner_radius(0.0),
+                ),
+                None => {
+                    let load_state = tileset_cache
+                        .map(|cache| cache.get_load_state(&image.id))
+                        .unwrap_or(ImageLoadState::Pending);
+                    let label = match load_state {
+                        ImageLoadState::Failed(_) => "ERROR",
+                        _ => "...",
+                    };
+                    ui.add_sized(display_size, egui::Button::new(label))
+                }
+            };
+
+            let stroke_color = if editor_state.selected_tile == Some(virtual_index) {
+                Some(EditorTheme::ACCENT_BLUE)
+            } else if editor_state.random_paint_tiles.contains(&virtual_index) {
+                Some(egui::Color32::from_rgb(150, 200, 100))
+            } else {
+                None
+            };
+            if let Some(color) = stroke_color {
+                ui.painter().rect_stroke(
+                    response.rect,
+                    0.0,
+                    egui::Stroke::new(2.0, color),
+                    egui::StrokeKind::Inside,
+                );
+            }
+
+            if response.clicked() {
+                select_palette_tile(ui, editor_state, virtual_index);
+            }
+
+            let (grid_width, grid_height) = tileset.get_tile_grid_size(virtual_index);
+            let hover_text = match image.pixel_size() {
+                Some((width, height)) => format!(
+                    "Tile {} ({}) - {}x{}px, {}x{} cells",
+                    virtual_index, image.name, width, height, grid_width, grid_height
+                ),
+                None => format!("Tile {} ({})", virtual_index, image.name),
+            };
+            response.on_hover_text(hover_text);
+        }
+    });
+}
+
 /// Render tiles from all images in a multi-image tileset
 fn render_multi_image_tileset(
     ui: &mut egui::Ui,
@@ -143,7 +313,8 @@ fn render_multi_image_tileset(
     tileset: &Tileset,
     tileset_cache: Option<&TilesetTextureCache>,
 ) {
-    let display_size = egui::vec2(32.0, 32.0);
+    let layout = tileset.grid_layout();
+    let display_size = tile_display_size(&layout, 32.0);
     let mut virtual_offset = 0u32;
 
     for (img_idx, image) in tileset.images.iter().enumerate() {
@@ -210,9 +381,6 @@ fn render_multi_image_tileset(
                             }
 
                             // Render tiles with texture
-                            let uv_tile_width = 1.0 / image.columns.max(1) as f32;
-                            let uv_tile_height = 1.0 / image.rows.max(1) as f32;
-
                             // Collect tile rects for drawing combined multi-cell borders
                             let mut tile_rects: Vec<(u32, u32, egui::Rect, u32)> = Vec::new();
 
@@ -233,13 +401,12 @@ fn render_multi_image_tileset(
                                             tileset.get_tile_grid_size(virtual_index);
                                         let is_multi_cell = grid_width > 1 || grid_height > 1;
 
-                                        let uv_min = egui::pos2(
-                                            col as f32 * uv_tile_width,
-                                            row as f32 * uv_tile_height,
-                                        );
-                                        let uv_max = egui::pos2(
-                                            (col + 1) as f32 * uv_tile_width,
-                                            (row + 1) as f32 * uv_tile_height,
+                                        let uv = tile_uv_rect(
+                                            &layout,
+                                            image,
+                                            local_index,
+                                            (1, 1),
+                                            tileset_cache,
                                         );
 
                                         let response = ui.add(
@@ -248,7 +415,7 @@ fn render_multi_image_tileset(
                                                     tex_id,
                                                     display_size,
                                                 ))
-                                                .uv(egui::Rect::from_min_max(uv_min, uv_max)),
+                                                .uv(uv),
                                             )
                                             .frame(false) // Remove button padding
                                             .corner_radius(0.0),
@@ -291,34 +458,7 @@ fn render_multi_image_tileset(
                                                 virtual_index,
                                             );
 
-                                            // Check for Ctrl modifier for random paint tile selection
-                                            let ctrl_held = ui.input(|i| i.modifiers.ctrl);
-                                            if ctrl_held && editor_state.random_paint {
-                                                // Toggle tile in random paint set
-                                                if let Some(pos) = editor_state
-                                                    .random_paint_tiles
-                                                    .iter()
-                                                    .position(|&t| t == base_tile)
-                                                {
-                                                    editor_state.random_paint_tiles.remove(pos);
-                                                } else {
-                                                    editor_state.random_paint_tiles.push(base_tile);
-                                                }
-                                                // Also select this tile for visual feedback
-                                                editor_state.selected_tile = Some(base_tile);
-                                            } else {
-                                                // Normal click - select tile and clear random set
-                                                editor_state.selected_tile = Some(base_tile);
-                                                if !ctrl_held {
-                                                    editor_state.random_paint_tiles.clear();
-                                                    // Add selected tile to random set if random paint is on
-                                                    if editor_state.random_paint {
-                                                        editor_state
-                                                            .random_paint_tiles
-                                                            .push(base_tile);
-                                                    }
-                                                }
-                                            }
+                                            select_palette_tile(ui, editor_state, base_tile);
                                         }
 
                                         let hover_text = if is_multi_cell {
@@ -367,20 +507,18 @@ fn render_multi_image_tileset(
 
                                             if let Some(rect) = combined_rect {
                                                 // Draw the merged tile image as overlay (covering gaps)
-                                                let uv_min = egui::pos2(
-                                                    col as f32 * uv_tile_width,
-                                                    row as f32 * uv_tile_height,
-                                                );
-                                                let uv_max = egui::pos2(
-                                                    (col + props.grid_width) as f32 * uv_tile_width,
-                                                    (row + props.grid_height) as f32
-                                                        * uv_tile_height,
+                                                let uv = tile_uv_rect(
+                                                    &layout,
+                                                    image,
+                                                    row * image.columns + col,
+                                                    (props.grid_width, props.grid_height),
+                                                    tileset_cache,
                                                 );
 
                                                 ui.painter().image(
                                                     tex_id,
                                                     rect,
-                                                    egui::Rect::from_min_max(uv_min, uv_max),
+                                                    uv,
                                                     egui::Color32::WHITE,
                                                 );
 
@@ -551,6 +689,126 @@ fn render_tileset_placeholder(
     });
 }
 
+/// Texture, UV rect and display size for a tile thumbnail, once its image has loaded
+fn tile_thumbnail(
+    tileset: &Tileset,
+    tile: u32,
+    tileset_cache: Option<&TilesetTextureCache>,
+    max_side: f32,
+) -> Option<egui::Image<'static>> {
+    let (image, local_index) = tileset.get_tile_image_info(tile)?;
+    let (_, texture_id, _, _) = tileset_cache?.loaded.get(&image.id)?;
+    let layout = tileset.grid_layout();
+    let size = if tileset.is_collection() {
+        image_tile_display_size(&layout, image, max_side)
+    } else {
+        tile_display_size(&layout, max_side)
+    };
+    let uv = tile_uv_rect(&layout, image, local_index, (1, 1), tileset_cache);
+    Some(egui::Image::new(egui::load::SizedTexture::new(*texture_id, size)).uv(uv))
+}
+
+/// Render a picker for a Tile property: the current tile, with a palette popup to change it
+pub fn render_tile_picker(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    value: &mut Value,
+    tilesets: &[Tileset],
+    tileset_cache: Option<&TilesetTextureCache>,
+) {
+    let current = TileRef::from_value(value);
+    let current_tileset =
+        current.and_then(|tile_ref| tilesets.iter().find(|t| t.id == tile_ref.tileset));
+
+    let button = ui
+        .horizontal(|ui| {
+            let button = match (current, current_tileset) {
+                (Some(tile_ref), Some(tileset)) => {
+                    match tile_thumbnail(tileset, tile_ref.tile, tileset_cache, 24.0) {
+                        Some(image) => ui.add(egui::Button::image_and_text(
+                            image,
+                            format!("{} #{}", tileset.name, tile_ref.tile),
+                        )),
+                        None => ui.button(format!("{} #{}", tileset.name, tile_ref.tile)),
+                    }
+                }
+                (Some(tile_ref), None) => {
+                    ui.button(format!("(missing tileset) #{}", tile_ref.tile))
+                }
+                (None, _) => ui.button("(None)"),
+            };
+            if current.is_some() && ui.small_button("x").on_hover_text("Clear").clicked() {
+                *value = Value::Null;
+            }
+            button
+        })
+        .inner;
+
+    // The tileset shown in the popup, remembered while it is open
+    let shown_id = ui.make_persistent_id((id_salt, "tile_picker_tileset"));
+    egui::Popup::from_toggle_button_response(&button)
+        .close_behavior(egui::PopupCloseBehavior::CloseOnClickOutside)
+        .show(|ui| {
+            if tilesets.is_empty() {
+                ui.label("No tilesets in this project");
+                return;
+            }
+
+            let mut shown = ui
+                .data(|data| data.get_temp::<uuid::Uuid>(shown_id))
+                .or(current.map(|tile_ref| tile_ref.tileset))
+                .filter(|id| tilesets.iter().any(|t| t.id == *id))
+                .unwrap_or(tilesets[0].id);
+            ui.horizontal_wrapped(|ui| {
+                for tileset in tilesets {
+                    ui.selectable_value(&mut shown, tileset.id, &tileset.name);
+                }
+            });
+            ui.data_mut(|data| data.insert_temp(shown_id, shown));
+            ui.separator();
+
+            let Some(tileset) = tilesets.iter().find(|t| t.id == shown) else {
+                return;
+            };
+            egui::ScrollArea::vertical()
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    ui.set_max_width(320.0);
+                    ui.horizontal_wrapped(|ui| {
+                        ui.spacing_mut().item_spacing = egui::vec2(1.0, 1.0);
+                        let total_tiles = tileset.total_tile_count();
+                        for tile in 0..total_tiles.min(1024) {
+                            let response = match tile_thumbnail(tileset, tile, tileset_cache, 32.0)
+                            {
+                                Some(image) => ui.add(
+                                    egui::Button::image(image).frame(false).corner_radius(0.0),
+                                ),
+                                None => ui.add(
+                                    egui::Button::new(tile.to_string())
+                                        .min_size(egui::vec2(32.0, 32.0)),
+                                ),
+                            };
+                            if current == Some(TileRef::new(tileset.id, tile)) {
+                                ui.painter().rect_stroke(
+                                    response.rect,
+                                    0.0,
+                                    egui::Stroke::new(2.0, EditorTheme::ACCENT_BLUE),
+                                    egui::StrokeKind::Inside,
+                                );
+                            }
+                            if response.on_hover_text(format!("Tile {}", tile)).clicked() {
+                                *value = TileRef::new(tileset.id, tile).to_value();
+                                ui.close();
+                            }
+                        }
+                        if total_tiles > 1024 {
+                            ui.label(format!("... and {} more", total_tiles - 1024));
+                        }
+                    });
+                });
+        });
+}
+
 /// Open a file dialog to select a tileset image (native only)
 #[cfg(not(target_arch = "wasm32"))]
 #[allow(dead_code)]
@@ -11,9 +11,16 @@
 use bevy_egui::egui::{self, Color32, Pos2, Shape};
 use bevy_map_autotile::terrain::Color as TerrainColor;
 use bevy_map_autotile::TerrainSetType;
+use bevy_map_core::{TileGridLayout, TileProperties};
+use std::collections::HashSet;
 use std::f32::consts::PI;
 
-use super::{find_base_tile_for_position, EditorTheme, TilesetTextureCache};
+use super::PendingAction;
+use super::{
+    find_base_tile_for_position, image_tile_display_size, tile_display_size, tile_uv_rect,
+    EditorTheme, TilesetTextureCache,
+};
+use crate::commands::TilePropertiesCommand;
 use crate::project::Project;
 use crate::EditorState;
 
@@ -33,6 +40,14 @@ pub struct TilesetEditorState {
     pub merge_drag_start: Option<(u32, u32, usize)>,
     /// Active shift+drag selection current position for tile merging (col, row)
     pub merge_drag_current: Option<(u32, u32)>,
+    /// Tile index to append to the animation timeline
+    pub new_animation_frame: u32,
+    /// Set when tile animation data changed so the viewport can rebuild
+    pub animation_changed: bool,
+    /// Tile property edit waiting to be applied through the undo history
+    pub pending_tile_properties: Option<TilePropertiesCommand>,
+    /// Properties of the edited tile before the current value drag started
+    pub tile_drag_start: Option<TileProperties>,
 }
 
 impl Default for TilesetEditorState {
@@ -46,6 +61,10 @@ impl Default for TilesetEditorState {
             collision_editor: CollisionEditorState::default(),
             merge_drag_start: None,
             merge_drag_current: None,
+            new_animation_frame: 0,
+            animation_changed: false,
+            pending_tile_properties: None,
+            tile_drag_start: None,
         }
     }
 }
@@ -740,7 +759,7 @@ fn render_terrain_sets_tab(
         .tilesets
         .iter()
         .find(|t| t.id == tileset_id)
-        .map(|t| (t.tile_size, t.images.clone(), !t.images.is_empty()));
+        .map(|t| (t.grid_layout(), t.images.clone(), !t.images.is_empty()));
 
     // Split into left panel (terrain list) and right panel (tileset preview)
     // Using resizable SidePanel for better UX
@@ -871,7 +890,7 @@ fn render_terrain_sets_tab(
             });
         });
 
-        if let Some((tile_size, images, has_images)) = tileset_data.clone() {
+        if let Some((layout, images, has_images)) = tileset_data.clone() {
             if !has_images {
                 ui.label("No images in tileset");
             } else {
@@ -884,7 +903,7 @@ fn render_terrain_sets_tab(
                             editor_state,
                             project,
                             tileset_id,
-                            tile_size,
+                            layout,
                             &images,
                             cache,
                         );
@@ -911,7 +930,7 @@ fn render_tileset_with_terrain_overlay(
     editor_state: &mut EditorState,
     project: &mut Project,
     tileset_id: uuid::Uuid,
-    tile_size: u32,
+    layout: TileGridLayout,
     images: &[bevy_map_core::TilesetImage],
     cache: Option<&TilesetTextureCache>,
 ) {
@@ -952,9 +971,6 @@ fn render_tileset_with_terrain_overlay(
                 return;
             }
 
-            let uv_tile_width = 1.0 / image.columns.max(1) as f32;
-            let uv_tile_height = 1.0 / image.rows.max(1) as f32;
-
             // Calculate full grid size (with spacing)
             let spacing = 2.0f32;
             let grid_width =
@@ -983,20 +999,11 @@ fn render_tileset_with_terrain_overlay(
 
                     // Draw tile texture
                     if let Some(tex_id) = texture_id {
-                        let uv_min =
-                            egui::pos2(col as f32 * uv_tile_width, row as f32 * uv_tile_height);
-                        let uv_max = egui::pos2(
-                            (col + 1) as f32 * uv_tile_width,
-                            (row + 1) as f32 * uv_tile_height,
-                        );
+                        let uv = tile_uv_rect(&layout, image, local_index, (1, 1), cache);
 
                         // Draw texture using mesh
                         let mut mesh = egui::Mesh::with_texture(tex_id);
-                        mesh.add_rect_with_uv(
-                            rect,
-                            egui::Rect::from_min_max(uv_min, uv_max),
-                            Color32::WHITE,
-                        );
+                        mesh.add_rect_with_uv(rect, uv, Color32::WHITE);
                         ui.painter().add(Shape::mesh(mesh));
                     } else {
                         // Fallback: draw placeholder
@@ -1039,13 +1046,14 @@ fn render_tileset_with_terrain_overlay(
                     if let Some(tileset) = project.tilesets.iter().find(|t| t.id == tileset_id) {
                         if let Some(props) = tileset.get_tile_properties(virtual_index) {
                             if props.origin_x.is_some() || props.origin_y.is_some() {
-                                let tile_pixel_size = tile_size as f32;
-                                let (ox, oy) = props.get_origin(tile_size, tile_size);
+                                let (ox, oy) =
+                                    props.get_origin(layout.tile_width, layout.tile_height);
 
                                 // Scale origin to display size
-                                let scale = tile_display_size / tile_pixel_size;
-                                let origin_screen_x = rect.left() + ox as f32 * scale;
-                                let origin_screen_y = rect.top() + oy as f32 * scale;
+                                let origin_screen_x = rect.left()
+                                    + ox as f32 * tile_display_size / layout.tile_width as f32;
+                                let origin_screen_y = rect.top()
+                                    + oy as f32 * tile_display_size / layout.tile_height as f32;
 
                                 // Draw small red dot
                                 let dot_radius = 2.0 * (tile_display_size / 32.0).max(1.0);
@@ -1209,7 +1217,7 @@ fn render_tile_properties_tab(
         .tilesets
         .iter()
         .find(|t| t.id == tileset_id)
-        .map(|t| (t.tile_size, t.images.clone(), !t.images.is_empty()));
+        .map(|t| (t.grid_layout(), t.images.clone(), !t.images.is_empty()));
 
     // Left panel: Tile selector (resizable)
     egui::SidePanel::left("tile_properties_selector")
@@ -1218,7 +1226,7 @@ fn render_tile_properties_tab(
         .show_inside(ui, |ui| {
             ui.heading("Select Tile");
 
-            if let Some((tile_size, images, has_images)) = tileset_data.clone() {
+            if let Some((layout, images, has_images)) = tileset_data.clone() {
                 if !has_images {
                     ui.label("No images in tileset");
                 } else {
@@ -1230,7 +1238,7 @@ fn render_tile_properties_tab(
                                 editor_state,
                                 project,
                                 tileset_id,
-                                tile_size,
+                                layout,
                                 &images,
                                 cache,
                             );
@@ -1412,11 +1420,11 @@ fn render_tile_properties_tab(
 
                     // Get tileset info for rendering
                     if let Some(tileset) = project.tilesets.iter().find(|t| t.id == tileset_id) {
-                        let tile_size = tileset.tile_size;
+                        let (tile_width, tile_height) = tileset.tile_dimensions();
 
                         // Calculate tile dimensions in pixels
-                        let tile_pixel_width = current_props.grid_width * tile_size;
-                        let tile_pixel_height = current_props.grid_height * tile_size;
+                        let tile_pixel_width = current_props.grid_width * tile_width;
+                        let tile_pixel_height = current_props.grid_height * tile_height;
 
                         // Current origin (default to center)
                         let origin_x = current_props.origin_x.unwrap_or(tile_pixel_width / 2);
@@ -1443,29 +1451,17 @@ fn render_tile_properties_tab(
                                     .map(|(_, tex_id, _, _)| *tex_id)
                                 {
                                     // Calculate UV coordinates for this tile
-                                    let tile_col = local_idx % image.columns;
-                                    let tile_row = local_idx / image.columns;
-                                    let uv_tile_width = 1.0 / image.columns as f32;
-                                    let uv_tile_height = 1.0 / image.rows as f32;
-
-                                    let uv_min = egui::pos2(
-                                        tile_col as f32 * uv_tile_width,
-                                        tile_row as f32 * uv_tile_height,
-                                    );
-                                    let uv_max = egui::pos2(
-                                        (tile_col + current_props.grid_width) as f32
-                                            * uv_tile_width,
-                                        (tile_row + current_props.grid_height) as f32
-                                            * uv_tile_height,
+                                    let uv = tile_uv_rect(
+                                        &tileset.grid_layout(),
+                                        image,
+                                        local_idx,
+                                        current_props.grid_size(),
+                                        cache,
                                     );
 
                                     // Draw tile texture
                                     let mut mesh = egui::Mesh::with_texture(tex_id);
-                                    mesh.add_rect_with_uv(
-                                        rect,
-                                        egui::Rect::from_min_max(uv_min, uv_max),
-                                        Color32::WHITE,
-                                    );
+                                    mesh.add_rect_with_uv(rect, uv, Color32::WHITE);
                                     ui.painter().add(egui::Shape::mesh(mesh));
                                 }
                             }
@@ -1550,49 +1546,56 @@ fn render_tile_properties_tab(
                     let mut enable_anim = has_anim;
 
                     if ui.checkbox(&mut enable_anim, "Enable animation").changed() {
-                        if let Some(tileset) =
-                            project.tilesets.iter_mut().find(|t| t.id == tileset_id)
-                        {
-                            let props = tileset.get_tile_properties_mut(tile_idx);
-                            if enable_anim {
-                                props.animation_frames = Some(vec![tile_idx]);
-                                props.animation_speed = Some(10.0);
-                            } else {
-                                props.animation_frames = None;
-                                props.animation_speed = None;
-                            }
-                            project.mark_dirty();
-                        }
+                        edit_tile_properties(
+                            editor_state,
+                            project,
+                            tileset_id,
+                            tile_idx,
+                            false,
+                            |props| {
+                                if enable_anim {
+                                    props.animation_frames = Some(vec![tile_idx]);
+                                    props.animation_speed =
+                                        Some(bevy_map_core::DEFAULT_TILE_ANIMATION_FPS);
+                                } else {
+                                    props.animation_frames = None;
+                                    props.animation_speed = None;
+                                    props.animation_durations = None;
+                                }
+                            },
+                        );
                     }
 
                     if enable_anim {
-                        // Animation speed
-                        let mut speed = current_props.animation_speed.unwrap_or(10.0);
+                        // Default frame rate (used by frames without an explicit duration)
+                        let mut speed = current_props
+                            .animation_speed
+                            .unwrap_or(bevy_map_core::DEFAULT_TILE_ANIMATION_FPS);
                         ui.horizontal(|ui| {
                             ui.label("Speed (FPS):");
-                            if ui
-                                .add(egui::DragValue::new(&mut speed).range(0.1..=60.0))
-                                .changed()
-                            {
-                                if let Some(tileset) =
-                                    project.tilesets.iter_mut().find(|t| t.id == tileset_id)
-                                {
-                                    let props = tileset.get_tile_properties_mut(tile_idx);
-                                    props.animation_speed = Some(speed);
-                                    project.mark_dirty();
-                                }
+                            let response =
+                                ui.add(egui::DragValue::new(&mut speed).range(0.1..=60.0));
+                            if response.changed() || response.drag_stopped() {
+                                edit_tile_properties(
+                                    editor_state,
+                                    project,
+                                    tileset_id,
+                                    tile_idx,
+                                    response.dragged(),
+                                    |props| props.animation_speed = Some(speed),
+                                );
                             }
                         });
 
-                        // Animation frames display
-                        ui.label("Frames:");
-                        let frames = current_props.animation_frames.clone().unwrap_or_default();
-                        ui.horizontal_wrapped(|ui| {
-                            for frame in &frames {
-                                ui.label(format!("{}", frame));
-                            }
-                        });
-                        ui.small("(Frame editing coming in future update)");
+                        render_animation_timeline(
+                            ui,
+                            editor_state,
+                            project,
+                            tileset_id,
+                            tile_idx,
+                            &current_props,
+                            cache,
+                        );
                     }
 
                     ui.separator();
@@ -1618,17 +1621,250 @@ fn render_tile_properties_tab(
     });
 }
 
+/// Edit operation requested from the animation timeline
+enum TimelineEdit {
+    SetDuration(usize, u32),
+    MoveLeft(usize),
+    MoveRight(usize),
+    Remove(usize),
+    Add(u32),
+}
+
+/// Render the animation timeline: a live preview and one block per frame
+/// whose width follows the frame's duration.
+fn render_animation_timeline(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    project: &mut Project,
+    tileset_id: uuid::Uuid,
+    tile_idx: u32,
+    props: &bevy_map_core::TileProperties,
+    cache: Option<&TilesetTextureCache>,
+) {
+    let Some(tileset) = project.tilesets.iter().find(|t| t.id == tileset_id) else {
+        return;
+    };
+
+    let frames = props.animation_frames.clone().unwrap_or_default();
+    let durations = props.animation_frame_durations();
+    let mut edit = None;
+    let mut dragging = false;
+
+    // Live preview of the animation
+    ui.horizontal(|ui| {
+        ui.label("Preview:");
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(48.0, 48.0), egui::Sense::hover());
+        let elapsed = ui.input(|i| i.time) as f32;
+        if let Some(frame) = props.animation_frame_at(elapsed) {
+            paint_tile_thumbnail(ui.painter(), rect, tileset, frame, cache);
+        }
+        ui.painter().rect_stroke(
+            rect,
+            0.0,
+            egui::Stroke::new(1.0, Color32::from_gray(100)),
+            egui::StrokeKind::Inside,
+        );
+        let total_ms: f32 = durations.iter().sum::<f32>() * 1000.0;
+        ui.label(format!("{} frames, {:.0} ms", frames.len(), total_ms));
+        if frames.len() > 1 {
+            ui.ctx().request_repaint();
+        }
+    });
+
+    // Timeline: one block per frame, width proportional to duration
+    ui.label("Timeline (duration in ms):");
+    egui::ScrollArea::horizontal()
+        .id_salt("tile_animation_timeline")
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                for (i, frame) in frames.iter().enumerate() {
+                    let duration_ms = (durations[i] * 1000.0).round() as u32;
+                    let block_width = (duration_ms as f32 * 0.4).clamp(56.0, 240.0);
+                    ui.allocate_ui(egui::vec2(block_width, 110.0), |ui| {
+                        ui.vertical(|ui| {
+                            let (rect, _) = ui.allocate_exact_size(
+                                egui::vec2(block_width, 32.0),
+                                egui::Sense::hover(),
+                            );
+                            ui.painter()
+                                .rect_filled(rect, 2.0, Color32::from_rgb(50, 70, 100));
+                            let thumb = egui::Rect::from_min_size(
+                                rect.min + egui::vec2(2.0, 2.0),
+                                egui::vec2(28.0, 28.0),
+                            );
+                            paint_tile_thumbnail(ui.painter(), thumb, tileset, *frame, cache);
+                            ui.painter().text(
+                                thumb.right_center() + egui::vec2(4.0, 0.0),
+                                egui::Align2::LEFT_CENTER,
+                                format!("#{}", frame),
+                                egui::FontId::proportional(11.0),
+                                Color32::WHITE,
+                            );
+
+                            let mut ms = duration_ms;
+                            let response = ui.add(
+                                egui::DragValue::new(&mut ms)
+                                    .range(1..=10_000)
+                                    .suffix(" ms"),
+                            );
+                            if response.changed() || response.drag_stopped() {
+                                edit = Some(TimelineEdit::SetDuration(i, ms));
+                                dragging = response.dragged();
+                            }
+                            ui.horizontal(|ui| {
+                                if ui.small_button("<").clicked() && i > 0 {
+                                    edit = Some(TimelineEdit::MoveLeft(i));
+                                }
+                                if ui.small_button(">").clicked() && i + 1 < frames.len() {
+                                    edit = Some(TimelineEdit::MoveRight(i));
+                                }
+                                if ui.small_button("x").clicked() && frames.len() > 1 {
+                                    edit = Some(TimelineEdit::Remove(i));
+                                }
+                            });
+                        });
+                    });
+                }
+            });
+        });
+
+    // Append a frame
+    let tile_count = tileset.total_tile_count();
+    ui.horizontal(|ui| {
+        let new_frame = &mut editor_state.tileset_editor_state.new_animation_frame;
+        ui.label("Add frame:");
+        ui.add(egui::DragValue::new(new_frame).range(0..=tile_count.saturating_sub(1)));
+        if ui.button("Add").clicked() {
+            edit = Some(TimelineEdit::Add(*new_frame));
+        }
+    });
+
+    let Some(edit) = edit else {
+        return;
+    };
+
+    // Materialize durations so they stay attached to their frames when reordering
+    let mut durations_ms: Vec<u32> = durations
+        .iter()
+        .map(|d| (d * 1000.0).round() as u32)
+        .collect();
+
+    edit_tile_properties(
+        editor_state,
+        project,
+        tileset_id,
+        tile_idx,
+        dragging,
+        |props| {
+            let frames = props.animation_frames.get_or_insert_with(Vec::new);
+            match edit {
+                TimelineEdit::SetDuration(i, ms) => durations_ms[i] = ms,
+                TimelineEdit::MoveLeft(i) => {
+                    frames.swap(i, i - 1);
+                    durations_ms.swap(i, i - 1);
+                }
+                TimelineEdit::MoveRight(i) => {
+                    frames.swap(i, i + 1);
+                    durations_ms.swap(i, i + 1);
+                }
+                TimelineEdit::Remove(i) => {
+                    frames.remove(i);
+                    durations_ms.remove(i);
+                }
+                TimelineEdit::Add(frame) => {
+                    frames.push(frame);
+                    durations_ms.push(durations_ms.last().copied().unwrap_or(100));
+                }
+            }
+            props.animation_durations = Some(durations_ms);
+        },
+    );
+}
+
+/// Edit a tile's properties through the undo history
+///
+/// While `dragging`, the edit is applied right away so the value follows the
+/// pointer, and the whole drag is recorded as one command when it ends.
+fn edit_tile_properties(
+    editor_state: &mut EditorState,
+    project: &mut Project,
+    tileset_id: uuid::Uuid,
+    tile_idx: u32,
+    dragging: bool,
+    edit: impl FnOnce(&mut TileProperties),
+) {
+    let Some(tileset) = project.tilesets.iter_mut().find(|t| t.id == tileset_id) else {
+        return;
+    };
+    let current = tileset
+        .get_tile_properties(tile_idx)
+        .cloned()
+        .unwrap_or_default();
+    let mut after = current.clone();
+    edit(&mut after);
+
+    let state = &mut editor_state.tileset_editor_state;
+    if dragging {
+        state.tile_drag_start.get_or_insert(current);
+        tileset.set_tile_properties(tile_idx, after);
+        state.animation_changed = true;
+        return;
+    }
+
+    let before = state.tile_drag_start.take().unwrap_or(current);
+    state.pending_tile_properties = Some(TilePropertiesCommand::new(
+        format!("Edit tile {} properties", tile_idx),
+        tileset_id,
+        tile_idx,
+        before,
+        after,
+    ));
+    editor_state.pending_action = Some(PendingAction::ApplyTileProperties);
+}
+
+/// Paint a single tile from a tileset into `rect`
+fn paint_tile_thumbnail(
+    painter: &egui::Painter,
+    rect: egui::Rect,
+    tileset: &bevy_map_core::Tileset,
+    tile_index: u32,
+    cache: Option<&TilesetTextureCache>,
+) {
+    let Some((image_index, local_index)) = tileset.virtual_to_local(tile_index) else {
+        return;
+    };
+    let Some(image) = tileset.images.get(image_index) else {
+        return;
+    };
+    let Some(tex_id) = cache
+        .and_then(|c| c.loaded.get(&image.id))
+        .map(|(_, tex_id, _, _)| *tex_id)
+    else {
+        painter.rect_filled(rect, 0.0, Color32::from_gray(40));
+        return;
+    };
+    if image.columns == 0 || image.rows == 0 {
+        return;
+    }
+
+    let uv = tile_uv_rect(&tileset.grid_layout(), image, local_index, (1, 1), cache);
+
+    let mut mesh = egui::Mesh::with_texture(tex_id);
+    mesh.add_rect_with_uv(rect, uv, Color32::WHITE);
+    painter.add(Shape::mesh(mesh));
+}
+
 /// Render tile selector grid for the properties tab with shift+drag tile merging
 fn render_tile_selector_for_properties(
     ui: &mut egui::Ui,
     editor_state: &mut EditorState,
     project: &mut Project,
     tileset_id: uuid::Uuid,
-    _tile_size: u32,
+    layout: TileGridLayout,
     images: &[bevy_map_core::TilesetImage],
     cache: Option<&TilesetTextureCache>,
 ) {
-    let display_size = egui::vec2(32.0, 32.0);
+    let display_size = tile_display_size(&layout, 32.0);
     let spacing = 1.0;
     let mut virtual_offset = 0u32;
 
@@ -1655,9 +1891,6 @@ fn render_tile_selector_for_properties(
                 return;
             }
 
-            let uv_tile_width = 1.0 / image.columns.max(1) as f32;
-            let uv_tile_height = 1.0 / image.rows.max(1) as f32;
-
             // Store tile rects for shift+drag interaction
             let mut tile_rects: Vec<(u32, u32, egui::Rect, u32)> = Vec::new();
 
@@ -1674,12 +1907,7 @@ fn render_tile_selector_for_properties(
                             == Some(virtual_index);
 
                         let response = if let Some(tex_id) = texture_id {
-                            let uv_min =
-                                egui::pos2(col as f32 * uv_tile_width, row as f32 * uv_tile_height);
-                            let uv_max = egui::pos2(
-                                (col + 1) as f32 * uv_tile_width,
-                                (row + 1) as f32 * uv_tile_height,
-                            );
+                            let uv = tile_uv_rect(&layout, image, local_index, (1, 1), cache);
 
                             ui.add(
                                 egui::Button::image(
@@ -1687,7 +1915,7 @@ fn render_tile_selector_for_properties(
                                         tex_id,
                                         display_size,
                                     ))
-                                    .uv(egui::Rect::from_min_max(uv_min, uv_max)),
+                                    .uv(uv),
                                 )
                                 .frame(false) // Remove button padding
                                 .corner_radius(0.0),
@@ -1925,9 +2153,9 @@ fn render_collision_tab(
         .tilesets
         .iter()
         .find(|t| t.id == tileset_id)
-        .map(|t| (t.tile_size, t.images.clone()));
+        .map(|t| (t.grid_layout(), t.images.clone()));
 
-    let Some((tile_size, images)) = tileset_data else {
+    let Some((layout, images)) = tileset_data else {
         ui.label("Tileset not found");
         return;
     };
@@ -1966,7 +2194,7 @@ fn render_collision_tab(
                         ui,
                         editor_state,
                         project,
-                        tile_size as f32,
+                        layout,
                         &images,
                         cache,
                     );
@@ -2049,6 +2277,11 @@ fn render_collision_tab(
                     if !collision_state.polygon_points.is_empty() {
                         ui.label(format!("Points: {}", collision_state.polygon_points.len()));
                     }
+                    if !bevy_map_core::polygon_self_intersections(&collision_state.polygon_points)
+                        .is_empty()
+                    {
+                        ui.colored_label(Color32::YELLOW, "Edges cross each other");
+                    }
                 }
             }
 
@@ -2080,7 +2313,7 @@ fn render_collision_tab(
         ui.separator();
 
         // Render the canvas with collision shape
-        render_collision_canvas(ui, editor_state, project, tile_size as f32, &images, cache);
+        render_collision_canvas(ui, editor_state, project, layout, &images, cache);
     });
 }
 
@@ -2089,7 +2322,7 @@ fn render_collision_tile_selector(
     ui: &mut egui::Ui,
     editor_state: &mut EditorState,
     project: &Project,
-    tile_size: f32,
+    layout: TileGridLayout,
     images: &[bevy_map_core::TilesetImage],
     cache: Option<&TilesetTextureCache>,
 ) {
@@ -2097,10 +2330,11 @@ fn render_collision_tile_selector(
     let tileset = project.tilesets.iter().find(|t| t.id == tileset_id);
 
     let zoom = editor_state.tileset_editor_state.collision_editor.grid_zoom;
-    let display_size = egui::vec2(tile_size * zoom, tile_size * zoom);
+    let max_side = layout.tile_width.max(layout.tile_height) as f32 * zoom;
     let mut virtual_offset = 0u32;
 
     for image in images {
+        let display_size = image_tile_display_size(&layout, image, max_side);
         let texture_id = cache
             .and_then(|c| c.loaded.get(&image.id))
             .map(|(_, tex_id, _, _)| *tex_id);
@@ -2111,9 +2345,6 @@ fn render_collision_tile_selector(
                 return;
             }
 
-            let uv_tile_width = 1.0 / image.columns.max(1) as f32;
-            let uv_tile_height = 1.0 / image.rows.max(1) as f32;
-
             for row in 0..image.rows {
                 ui.horizontal(|ui| {
                     ui.spacing_mut().item_spacing = egui::vec2(1.0, 1.0);
@@ -2137,19 +2368,10 @@ fn render_collision_tile_selector(
 
                         // Draw tile texture
                         if let Some(tex_id) = texture_id {
-                            let uv_min =
-                                egui::pos2(col as f32 * uv_tile_width, row as f32 * uv_tile_height);
-                            let uv_max = egui::pos2(
-                                (col + 1) as f32 * uv_tile_width,
-                                (row + 1) as f32 * uv_tile_height,
-                            );
+                            let uv = tile_uv_rect(&layout, image, local_index, (1, 1), cache);
 
                             let mut mesh = egui::Mesh::with_texture(tex_id);
-                            mesh.add_rect_with_uv(
-                                rect,
-                                egui::Rect::from_min_max(uv_min, uv_max),
-                                Color32::WHITE,
-                            );
+                            mesh.add_rect_with_uv(rect, uv, Color32::WHITE);
                             ui.painter().add(Shape::mesh(mesh));
                         } else {
                             ui.painter().rect_filled(rect, 0.0, Color32::from_gray(60));
@@ -2217,7 +2439,7 @@ fn render_collision_canvas(
     ui: &mut egui::Ui,
     editor_state: &mut EditorState,
     project: &mut Project,
-    tile_size: f32,
+    layout: TileGridLayout,
     images: &[bevy_map_core::TilesetImage],
     cache: Option<&TilesetTextureCache>,
 ) {
@@ -2230,7 +2452,6 @@ fn render_collision_canvas(
     };
 
     let zoom = collision_state.preview_zoom;
-    let canvas_size = egui::vec2(tile_size * zoom, tile_size * zoom);
 
     // Find which image and local index this tile belongs to
     let mut virtual_offset = 0u32;
@@ -2250,6 +2471,10 @@ fn render_collision_canvas(
         return;
     };
 
+    // Collection tiles are edited over their whole image
+    let (tile_width, tile_height) = layout.tile_pixel_size(image);
+    let canvas_size = egui::vec2(tile_width as f32 * zoom, tile_height as f32 * zoom);
+
     // Allocate canvas area with click and drag sensing
     let (canvas_rect, canvas_response) =
         ui.allocate_exact_size(canvas_size, egui::Sense::click_and_drag());
@@ -2261,24 +2486,10 @@ fn render_collision_canvas(
 
     if let Some(tex_id) = texture_id {
         if image.columns > 0 && image.rows > 0 {
-            let uv_tile_width = 1.0 / image.columns as f32;
-            let uv_tile_height = 1.0 / image.rows as f32;
-
-            let col = local_index % image.columns;
-            let row = local_index / image.columns;
-
-            let uv_min = egui::pos2(col as f32 * uv_tile_width, row as f32 * uv_tile_height);
-            let uv_max = egui::pos2(
-                (col + 1) as f32 * uv_tile_width,
-                (row + 1) as f32 * uv_tile_height,
-            );
+            let uv = tile_uv_rect(&layout, image, local_index, (1, 1), cache);
 
             let mut mesh = egui::Mesh::with_texture(tex_id);
-            mesh.add_rect_with_uv(
-                canvas_rect,
-                egui::Rect::from_min_max(uv_min, uv_max),
-                Color32::WHITE,
-            );
+            mesh.add_rect_with_uv(canvas_rect, uv, Color32::WHITE);
             ui.painter().add(Shape::mesh(mesh));
         }
     } else {
@@ -2369,21 +2580,57 @@ fn draw_collision_shape_on_canvas(
         }
         bevy_map_core::CollisionShape::Circle { offset, radius } => {
             let center = normalized_to_canvas_point(canvas_rect, offset);
-            let r = radius * canvas_rect.width();
+            let r = radius * canvas_rect.width().min(canvas_rect.height());
             painter.circle(center, r, fill, stroke);
         }
         bevy_map_core::CollisionShape::Polygon { points } => {
             if points.len() >= 3 {
-                let screen_points: Vec<Pos2> = points
-                    .iter()
-                    .map(|p| normalized_to_canvas_point(canvas_rect, p))
-                    .collect();
-                painter.add(Shape::convex_polygon(screen_points, fill, stroke));
+                draw_collision_polygon(painter, canvas_rect, points, fill, stroke);
             }
         }
     }
 }
 
+/// Draw a possibly concave polygon, highlighting self-intersecting edges
+///
+/// egui can only fill convex shapes, so concave outlines are filled part by part
+/// using the same decomposition the runtime uses for colliders.
+fn draw_collision_polygon(
+    painter: &egui::Painter,
+    canvas_rect: egui::Rect,
+    points: &[[f32; 2]],
+    fill: Color32,
+    stroke: egui::Stroke,
+) {
+    let to_screen = |p: &[f32; 2]| normalized_to_canvas_point(canvas_rect, p);
+
+    for part in bevy_map_core::decompose_polygon(points) {
+        let screen_points: Vec<Pos2> = part.iter().map(to_screen).collect();
+        painter.add(Shape::convex_polygon(
+            screen_points,
+            fill,
+            egui::Stroke::NONE,
+        ));
+    }
+
+    let invalid_edges: HashSet<usize> = bevy_map_core::polygon_self_intersections(points)
+        .into_iter()
+        .flat_map(|(a, b)| [a, b])
+        .collect();
+    let invalid_stroke = egui::Stroke::new(stroke.width, Color32::from_rgb(230, 50, 50));
+    for i in 0..points.len() {
+        let edge = [
+            to_screen(&points[i]),
+            to_screen(&points[(i + 1) % points.len()]),
+        ];
+        if invalid_edges.contains(&i) {
+            painter.line_segment(edge, invalid_stroke);
+        } else {
+            painter.line_segment(edge, stroke);
+        }
+    }
+}
+
 /// Draw drag handles for the shape
 fn draw_collision_handles(
     painter: &egui::Painter,
@@ -2413,7 +2660,10 @@ fn draw_collision_handles(
             let center = normalized_to_canvas_point(canvas_rect, offset);
             painter.circle(center, handle_radius, handle_fill, handle_stroke);
             // Edge handle (right side)
-            let edge = Pos2::new(center.x + radius * canvas_rect.width(), center.y);
+            let edge = Pos2::new(
+                center.x + radius * canvas_rect.width().min(canvas_rect.height()),
+                center.y,
+            );
             painter.circle(edge, handle_radius, handle_fill, handle_stroke);
         }
         bevy_map_core::CollisionShape::Polygon { points } => {
@@ -2727,7 +2977,7 @@ fn handle_collision_canvas_input(
                 let radius = (dx * dx + dy * dy).sqrt();
 
                 let center_pos = normalized_to_canvas_point(canvas_rect, center);
-                let r = radius * canvas_rect.width();
+                let r = radius * canvas_rect.width().min(canvas_rect.height());
                 ui.painter()
                     .circle(center_pos, r, preview_fill, preview_stroke);
             }
@@ -2954,6 +3204,10 @@ fn render_collision_properties(
 
     // Shape info
     ui.label(format!("Shape: {}", collision_data.shape.name()));
+    if let Err(issue) = collision_data.shape.validate() {
+        ui.colored_label(Color32::RED, format!("Invalid: {}", issue))
+            .on_hover_text("Self-intersecting polygons fall back to their convex hull at runtime");
+    }
 
     ui.separator();
 
@@ -3002,6 +3256,52 @@ fn render_collision_properties(
         }
     });
 
+    // Physics material
+    let mut friction = collision_data.friction;
+    let mut restitution = collision_data.restitution;
+    let mut material_changed = false;
+    ui.horizontal(|ui| {
+        ui.label("Friction:");
+        material_changed |= ui
+            .add(
+                egui::DragValue::new(&mut friction)
+                    .range(0.0..=2.0)
+                    .speed(0.05),
+            )
+            .on_hover_text("0 = ice, higher values are grippier")
+            .changed();
+    });
+    ui.horizontal(|ui| {
+        ui.label("Restitution:");
+        material_changed |= ui
+            .add(
+                egui::DragValue::new(&mut restitution)
+                    .range(0.0..=1.0)
+                    .speed(0.05),
+            )
+            .on_hover_text("Bounciness: 0 = no bounce, 1 = perfectly elastic")
+            .changed();
+    });
+    if material_changed {
+        if let Some(tileset) = project.tilesets.iter_mut().find(|t| t.id == tileset_id) {
+            tileset.set_tile_material(tile_idx, friction, restitution);
+            project.mark_dirty();
+        }
+    }
+
+    // Sensor
+    let mut is_sensor = collision_data.is_sensor;
+    if ui
+        .checkbox(&mut is_sensor, "Sensor")
+        .on_hover_text("Detect overlaps without blocking movement (spikes, triggers)")
+        .changed()
+    {
+        if let Some(tileset) = project.tilesets.iter_mut().find(|t| t.id == tileset_id) {
+            tileset.set_tile_sensor(tile_idx, is_sensor);
+            project.mark_dirty();
+        }
+    }
+
     ui.separator();
 
     // Action buttons
@@ -3,7 +3,7 @@
 use bevy_egui::egui;
 use uuid::Uuid;
 
-use super::Selection;
+use super::{PendingAction, Selection};
 use crate::project::Project;
 use crate::EditorState;
 use crate::RenamingItem;
@@ -243,7 +243,7 @@ pub fn render_tree_view(
 
                                     // Expandable type header with instances nested inside
                                     let header_text = format!("{} ({})", type_name, total_count);
-                                    egui::CollapsingHeader::new(&header_text)
+                                    let header = egui::CollapsingHeader::new(&header_text)
                                         .id_salt(format!("data_type_{}", type_name))
                                         .default_open(false)
                                         .show(ui, |ui| {
@@ -404,6 +404,25 @@ pub fn render_tree_view(
                                             }
                                         });
 
+                                    // Instances can be edited as spreadsheets
+                                    header.header_response.context_menu(|ui| {
+                                        if ui.button("Open in Table").clicked() {
+                                            editor_state.table_view_state.show_type(type_name);
+                                            ui.close();
+                                        }
+                                        if !type_def.placeable {
+                                            ui.separator();
+                                            if ui.button("Export CSV...").clicked() {
+                                                editor_state.pending_action = Some(PendingAction::ExportDataCsv(type_name.to_string()));
+                                                ui.close();
+                                            }
+                                            if ui.button("Import CSV...").clicked() {
+                                                editor_state.pending_action = Some(PendingAction::ImportDataCsv(type_name.to_string()));
+                                                ui.close();
+                                            }
+                                        }
+                                    });
+
                                     // "+" button to create new instance
                                     if ui.small_button("+").clicked() {
                                         result.create_data_instance = Some(type_name.to_string());
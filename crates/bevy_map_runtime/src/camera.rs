@@ -18,7 +18,7 @@
 
 use bevy::prelude::*;
 
-use crate::MapRoot;
+use crate::{MapRoot, WorldLevel};
 use bevy_map_core::MapProject;
 
 /// Camera bounds configuration component
@@ -54,9 +54,19 @@ impl CameraBounds {
     /// * `height` - Level height in tiles
     /// * `tile_size` - Size of each tile in pixels
     pub fn from_level(width: u32, height: u32, tile_size: f32) -> Self {
+        Self::from_level_tiles(width, height, Vec2::splat(tile_size))
+    }
+
+    /// Create bounds from level dimensions with non-square tiles
+    ///
+    /// # Arguments
+    /// * `width` - Level width in tiles
+    /// * `height` - Level height in tiles
+    /// * `tile_size` - Tile (width, height) in pixels
+    pub fn from_level_tiles(width: u32, height: u32, tile_size: Vec2) -> Self {
         Self {
             min: Vec2::ZERO,
-            max: Vec2::new(width as f32 * tile_size, height as f32 * tile_size),
+            max: Vec2::new(width as f32 * tile_size.x, height as f32 * tile_size.y),
             padding: 0.0,
         }
     }
@@ -111,26 +121,32 @@ pub fn clamp_camera_to_bounds(
 /// System that automatically sets up camera bounds when a map loads
 ///
 /// This system detects when a `MapRoot` component is added and configures
-/// `CameraBounds` on all cameras based on the level dimensions.
+/// `CameraBounds` on all cameras based on the level dimensions. Levels of a
+/// world (`WorldLevel`) are skipped so the camera can move between them.
 pub fn setup_camera_bounds_from_map(
     mut commands: Commands,
-    map_query: Query<&MapRoot, Added<MapRoot>>,
+    map_query: Query<(&MapRoot, Option<&ChildOf>), Added<MapRoot>>,
+    world_level_query: Query<(), With<WorldLevel>>,
     map_assets: Res<Assets<MapProject>>,
     camera_query: Query<Entity, (With<Camera2d>, Without<CameraBounds>)>,
 ) {
-    for map_root in map_query.iter() {
-        let Some(project) = map_assets.get(&map_root.handle) else {
+    for (map_root, child_of) in map_query.iter() {
+        if child_of.is_some_and(|child_of| world_level_query.contains(child_of.parent())) {
+            continue;
+        }
+
+        let Some(project) = map_assets.get(&map_root.project) else {
             continue;
         };
 
         let level = &project.level;
-        let tile_size = map_root.textures.tile_size;
+        let tile_size = map_root.textures.tile_dimensions;
 
         // Add bounds to all cameras that don't have them
         for camera_entity in camera_query.iter() {
             commands
                 .entity(camera_entity)
-                .insert(CameraBounds::from_level(
+                .insert(CameraBounds::from_level_tiles(
                     level.width,
                     level.height,
                     tile_size,
@@ -138,8 +154,8 @@ pub fn setup_camera_bounds_from_map(
 
             info!(
                 "Set camera bounds to {}x{} pixels ({}x{} tiles)",
-                level.width as f32 * tile_size,
-                level.height as f32 * tile_size,
+                level.width as f32 * tile_size.x,
+                level.height as f32 * tile_size.y,
                 level.width,
                 level.height
             );
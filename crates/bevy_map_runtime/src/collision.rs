@@ -7,8 +7,12 @@
 //!
 //! - Automatic collider spawning for tiles with collision shapes
 //! - Support for all collision shapes (Full, Rectangle, Circle, Polygon)
-//! - One-way platform support
+//! - Concave polygons, decomposed into convex parts (see [`polygon_collider`])
+//! - One-way platform support (see [`crate::one_way`])
+//! - Per-tile friction and restitution, and sensor tiles (see [`SensorTile`])
 //! - Collision layers and masks
+//! - Optional merging of solid tiles into larger colliders (see [`MapCollisionSettings`])
+//! - Local collider rebuilds when tiles change (see [`TileColliders`])
 //!
 //! # Usage
 //!
@@ -27,11 +31,18 @@
 //! app.add_plugins(MapCollisionPlugin);
 //! ```
 
+use bevy::math::URect;
 use bevy::prelude::*;
 use bevy_map_core::CollisionData;
 
 #[cfg(feature = "physics")]
-use bevy_map_core::{CollisionShape, OneWayDirection, PhysicsBody};
+use bevy_map_core::{
+    decompose_polygon, validate_polygon, CollisionShape, MapProject, OneWayDirection, PhysicsBody,
+    PolygonIssue, Tileset,
+};
+
+#[cfg(feature = "physics")]
+use std::collections::HashMap;
 
 #[cfg(feature = "physics")]
 use avian2d::prelude::*;
@@ -39,6 +50,11 @@ use avian2d::prelude::*;
 #[cfg(feature = "physics")]
 use bevy_ecs_tilemap::prelude::*;
 
+#[cfg(feature = "physics")]
+pub use crate::one_way::OneWayPlatform;
+#[cfg(feature = "physics")]
+use crate::one_way::{tick_drop_through, OneWayPlatformHooks};
+
 /// Plugin that provides collision spawning systems
 ///
 /// This plugin automatically spawns Avian2D colliders for tiles with
@@ -49,8 +65,20 @@ pub struct MapCollisionPlugin;
 #[cfg(feature = "physics")]
 impl Plugin for MapCollisionPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(avian2d::PhysicsPlugins::default())
-            .add_systems(Update, spawn_tile_colliders);
+        app.add_plugins(
+            avian2d::PhysicsPlugins::default().with_collision_hooks::<OneWayPlatformHooks>(),
+        )
+        .init_resource::<MapCollisionSettings>()
+        .add_message::<SensorTileEnterEvent>()
+        .add_message::<SensorTileExitEvent>()
+        .add_systems(
+            Update,
+            (
+                (spawn_tile_colliders, rebuild_tile_colliders).chain(),
+                tick_drop_through,
+                emit_sensor_tile_events,
+            ),
+        );
     }
 }
 
@@ -61,123 +89,604 @@ pub struct MapCollider {
     pub data: CollisionData,
 }
 
-/// Marker component for one-way platforms
+/// Marks the collider of a sensor tile
+///
+/// Sensor tiles don't block movement; bodies overlapping them produce
+/// [`SensorTileEnterEvent`] and [`SensorTileExitEvent`] messages.
 #[cfg(feature = "physics")]
-#[derive(Component)]
-pub struct OneWayPlatform {
-    /// Direction that allows pass-through
-    pub direction: OneWayDirection,
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SensorTile {
+    /// Map entity the tile belongs to
+    pub map: Entity,
+    /// Index of the tile layer
+    pub layer: usize,
+    /// Grid position of the tile (Y-up, like `TilePos`)
+    pub tile_pos: UVec2,
+    /// Tileset tile index, or `None` for collision set with [`TileColliders::set_collision`]
+    pub tile_index: Option<u32>,
 }
 
-/// System to spawn tile colliders after map load
-///
-/// This system runs when a `MapRoot` component is added and spawns
-/// colliders for all tiles that have collision data.
+/// Message sent when a collider starts overlapping a sensor tile
 #[cfg(feature = "physics")]
-pub fn spawn_tile_colliders(
-    mut commands: Commands,
-    map_query: Query<(Entity, &super::MapRoot), Added<super::MapRoot>>,
-    map_assets: Res<Assets<bevy_map_core::MapProject>>,
-) {
-    for (map_entity, map_root) in map_query.iter() {
-        let Some(project) = map_assets.get(&map_root.handle) else {
-            continue;
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SensorTileEnterEvent {
+    /// The sensor tile that was entered
+    pub tile: SensorTile,
+    /// Collider entity of the sensor tile
+    pub sensor: Entity,
+    /// Entity that entered (its rigid body, or the collider if it has none)
+    pub entity: Entity,
+}
+
+/// Message sent when a collider stops overlapping a sensor tile
+#[cfg(feature = "physics")]
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SensorTileExitEvent {
+    /// The sensor tile that was exited
+    pub tile: SensorTile,
+    /// Collider entity of the sensor tile
+    pub sensor: Entity,
+    /// Entity that exited (its rigid body, or the collider if it has none)
+    pub entity: Entity,
+}
+
+/// Settings for tile collider spawning
+#[cfg(feature = "physics")]
+#[derive(Resource, Debug, Clone, Default)]
+pub struct MapCollisionSettings {
+    /// Merge adjacent `CollisionShape::Full` tiles with the same body type,
+    /// layer, mask and one-way direction into larger rectangle colliders.
+    ///
+    /// Solid terrain then needs a handful of bodies instead of one per tile,
+    /// and characters no longer snag on the seams between tiles.
+    pub merge_full_tiles: bool,
+}
+
+/// Collision data for a single cell of a tile layer
+#[cfg(feature = "physics")]
+#[derive(Debug, Clone)]
+pub struct TileCollisionCell {
+    /// Collision data from the tileset
+    pub data: CollisionData,
+    /// Size of the area the shape is normalized to (the tile, or a collection tile's image)
+    pub shape_size: Vec2,
+    /// Offset of that area's center from the cell center
+    pub frame_offset: Vec2,
+    /// Tileset tile index the collision came from, if set from a tile
+    pub tile_index: Option<u32>,
+}
+
+#[cfg(feature = "physics")]
+impl TileCollisionCell {
+    /// Collision for a tile of `tileset` covering a single cell of `tile_size`
+    pub fn from_tile(tileset: &Tileset, tile_index: u32, tile_size: Vec2) -> Option<Self> {
+        let props = tileset.get_tile_properties(tile_index)?;
+        if !props.collision.has_collision() {
+            return None;
+        }
+        // Collection tile shapes are normalized to the tile's image, which is
+        // drawn around its origin from the cell's bottom-left corner
+        let (shape_size, frame_offset) = if tileset.is_collection() {
+            let (width, height) = tileset.tile_draw_size(tile_index);
+            let (origin_x, origin_y) = props.get_origin(width, height);
+            (
+                Vec2::new(width as f32, height as f32),
+                Vec2::new(origin_x as f32, origin_y as f32) - tile_size / 2.0,
+            )
+        } else {
+            (tile_size, Vec2::ZERO)
         };
+        Some(Self {
+            data: props.collision.clone(),
+            shape_size,
+            frame_offset,
+            tile_index: Some(tile_index),
+        })
+    }
+
+    /// Properties that must match for full-tile colliders to be merged,
+    /// or `None` if this cell can't be merged
+    ///
+    /// Sensors are never merged so their messages can report the tile involved.
+    fn merge_key(&self, tile_size: Vec2) -> Option<TileMergeKey> {
+        let covers_cell = self.frame_offset == Vec2::ZERO && self.shape_size == tile_size;
+        let mergeable =
+            covers_cell && !self.data.is_sensor && matches!(self.data.shape, CollisionShape::Full);
+        mergeable.then_some((
+            self.data.body_type,
+            self.data.one_way,
+            self.data.layer,
+            self.data.mask,
+            self.data.friction,
+            self.data.restitution,
+        ))
+    }
+}
+
+/// Body type, one-way direction, layer, mask, friction and restitution
+#[cfg(feature = "physics")]
+type TileMergeKey = (PhysicsBody, OneWayDirection, u8, u32, f32, f32);
+
+/// Tile collision state of a spawned map
+///
+/// Tracks the collision data of every cell and which collider covers it, so
+/// that changing a tile only rebuilds the colliders around it. Update cells
+/// with [`TileColliders::set_tile`] or [`TileColliders::set_collision`]; the
+/// affected colliders are rebuilt on the next update.
+#[cfg(feature = "physics")]
+#[derive(Component, Debug, Default)]
+pub struct TileColliders {
+    map_size: UVec2,
+    layers: HashMap<usize, TileColliderLayer>,
+}
+
+#[cfg(feature = "physics")]
+#[derive(Debug)]
+struct TileColliderLayer {
+    tile_size: Vec2,
+    cells: Vec<Option<TileCollisionCell>>,
+    /// Collider entity covering each cell
+    owners: Vec<Option<Entity>>,
+    /// Cells covered by each collider (min inclusive, max exclusive)
+    colliders: HashMap<Entity, URect>,
+    /// Cells whose colliders need rebuilding
+    dirty: Vec<usize>,
+}
 
-        let tile_size = map_root.textures.tile_size;
+#[cfg(feature = "physics")]
+impl TileColliders {
+    /// Build the collision state for a level, with every cell marked for building
+    pub fn from_project(project: &MapProject) -> Self {
         let level = &project.level;
+        let mut colliders = Self {
+            map_size: UVec2::new(level.width, level.height),
+            layers: HashMap::new(),
+        };
+        let cell_count = (level.width * level.height) as usize;
 
-        // Build tilemap parameters for coordinate conversion
-        let map_size = TilemapSize {
-            x: level.width,
-            y: level.height,
+        for (layer_index, layer) in level.layers.iter().enumerate() {
+            let bevy_map_core::LayerData::Tiles {
+                tileset_id, tiles, ..
+            } = &layer.data
+            else {
+                continue;
+            };
+            let Some(tileset) = project.get_tileset(*tileset_id) else {
+                continue;
+            };
+
+            // Collision shapes are normalized to the tileset's tile dimensions
+            let (tile_width, tile_height) = tileset.tile_dimensions();
+            let tile_size = Vec2::new(tile_width as f32, tile_height as f32);
+            let cells = (0..cell_count)
+                .map(|idx| {
+                    let tile_index = bevy_map_core::tile_index((*tiles.get(idx)?)?);
+                    TileCollisionCell::from_tile(tileset, tile_index, tile_size)
+                })
+                .collect();
+
+            colliders.layers.insert(
+                layer_index,
+                TileColliderLayer {
+                    tile_size,
+                    cells,
+                    owners: vec![None; cell_count],
+                    colliders: HashMap::new(),
+                    dirty: (0..cell_count).collect(),
+                },
+            );
+        }
+        colliders
+    }
+
+    /// Update a cell from a tile of `tileset` (or clear it with `None`)
+    pub fn set_tile(
+        &mut self,
+        layer_index: usize,
+        x: u32,
+        y: u32,
+        tileset: &Tileset,
+        tile_index: Option<u32>,
+    ) {
+        let Some(tile_size) = self.layers.get(&layer_index).map(|layer| layer.tile_size) else {
+            return;
         };
-        let grid_size = TilemapGridSize {
-            x: tile_size,
-            y: tile_size,
+        let cell = tile_index.and_then(|index| {
+            TileCollisionCell::from_tile(tileset, bevy_map_core::tile_index(index), tile_size)
+        });
+        self.set_cell(layer_index, x, y, cell);
+    }
+
+    /// Set the collision of a cell directly, normalized to the layer's tile size
+    pub fn set_collision(
+        &mut self,
+        layer_index: usize,
+        x: u32,
+        y: u32,
+        collision: Option<CollisionData>,
+    ) {
+        let Some(tile_size) = self.layers.get(&layer_index).map(|layer| layer.tile_size) else {
+            return;
+        };
+        let cell = collision
+            .filter(|data| data.has_collision())
+            .map(|data| TileCollisionCell {
+                data,
+                shape_size: tile_size,
+                frame_offset: Vec2::ZERO,
+                tile_index: None,
+            });
+        self.set_cell(layer_index, x, y, cell);
+    }
+
+    /// Get the collision of a cell
+    pub fn get(&self, layer_index: usize, x: u32, y: u32) -> Option<&TileCollisionCell> {
+        let idx = self.cell_index(x, y)?;
+        self.layers.get(&layer_index)?.cells[idx].as_ref()
+    }
+
+    /// Number of collider entities currently spawned for this map
+    pub fn collider_count(&self) -> usize {
+        self.layers
+            .values()
+            .map(|layer| layer.colliders.len())
+            .sum()
+    }
+
+    /// Mark every cell for rebuilding (e.g. after changing [`MapCollisionSettings`])
+    pub fn mark_all_dirty(&mut self) {
+        for layer in self.layers.values_mut() {
+            layer.dirty = (0..layer.cells.len()).collect();
+        }
+    }
+
+    /// Check if any cells are waiting to be rebuilt
+    pub fn has_dirty(&self) -> bool {
+        self.layers.values().any(|layer| !layer.dirty.is_empty())
+    }
+
+    fn cell_index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.map_size.x && y < self.map_size.y).then(|| (y * self.map_size.x + x) as usize)
+    }
+
+    fn set_cell(&mut self, layer_index: usize, x: u32, y: u32, cell: Option<TileCollisionCell>) {
+        let Some(idx) = self.cell_index(x, y) else {
+            return;
         };
-        let tilemap_tile_size = TilemapTileSize {
-            x: tile_size,
-            y: tile_size,
+        if let Some(layer) = self.layers.get_mut(&layer_index) {
+            layer.cells[idx] = cell;
+            layer.dirty.push(idx);
+        }
+    }
+
+    /// Despawn the colliders touching dirty cells and spawn replacements.
+    ///
+    /// Colliders covering a dirty cell, and every collider connected to them
+    /// through cells that could merge, are rebuilt as a whole. The result is the
+    /// same as rebuilding the whole map, while the work stays local to the changed
+    /// area. Returns the number of colliders spawned.
+    pub fn rebuild_dirty(
+        &mut self,
+        commands: &mut Commands,
+        map_entity: Entity,
+        merge: bool,
+    ) -> usize {
+        let map_size = TilemapSize {
+            x: self.map_size.x,
+            y: self.map_size.y,
         };
-        let map_type = TilemapType::Square;
-        let anchor = TilemapAnchor::default(); // BottomLeft
+        let (width, height) = (self.map_size.x, self.map_size.y);
+        let mut spawned = 0;
 
-        // Iterate through all tile layers
-        let mut total_colliders = 0;
-        for layer in level.layers.iter() {
-            if let bevy_map_core::LayerData::Tiles {
-                tileset_id, tiles, ..
-            } = &layer.data
-            {
-                // Get the tileset to look up collision data
-                let Some(tileset) = project.get_tileset(*tileset_id) else {
+        for (&layer_index, layer) in self.layers.iter_mut() {
+            if layer.dirty.is_empty() {
+                continue;
+            }
+
+            let tile_size = layer.tile_size;
+            let cell_key = |cells: &[Option<TileCollisionCell>], idx: usize| {
+                if !merge {
+                    return None;
+                }
+                cells[idx].as_ref()?.merge_key(tile_size)
+            };
+
+            // Region to rebuild: the dirty cells, every cell of the colliders
+            // covering them, and (transitively) neighbouring cells they could merge with
+            let mut region = vec![false; layer.cells.len()];
+            let mut pending = std::mem::take(&mut layer.dirty);
+            while let Some(idx) = pending.pop() {
+                if region[idx] {
+                    continue;
+                }
+                region[idx] = true;
+                let owner = layer.owners[idx];
+                if let Some((entity, rect)) =
+                    owner.and_then(|entity| Some((entity, layer.colliders.remove(&entity)?)))
+                {
+                    commands.entity(entity).despawn();
+                    for y in rect.min.y..rect.max.y {
+                        for x in rect.min.x..rect.max.x {
+                            let covered = (y * width + x) as usize;
+                            layer.owners[covered] = None;
+                            pending.push(covered);
+                        }
+                    }
+                }
+
+                let Some(key) = cell_key(&layer.cells, idx) else {
                     continue;
                 };
+                let (x, y) = (idx as u32 % width, idx as u32 / width);
+                let neighbours = [
+                    (x > 0).then(|| idx - 1),
+                    (x + 1 < width).then(|| idx + 1),
+                    (y > 0).then(|| idx - width as usize),
+                    (y + 1 < height).then(|| idx + width as usize),
+                ];
+                for neighbour in neighbours.into_iter().flatten() {
+                    if !region[neighbour] && cell_key(&layer.cells, neighbour) == Some(key) {
+                        pending.push(neighbour);
+                    }
+                }
+            }
 
-                // Spawn colliders for each tile with collision
-                for y in 0..level.height {
-                    for x in 0..level.width {
-                        let idx = (y * level.width + x) as usize;
-                        if let Some(&Some(tile_index)) = tiles.get(idx) {
-                            // Check if this tile has collision
-                            if let Some(props) = tileset.get_tile_properties(tile_index) {
-                                if props.collision.has_collision() {
-                                    spawn_collider_for_tile(
-                                        &mut commands,
-                                        map_entity,
-                                        &props.collision,
-                                        x,
-                                        y,
-                                        tile_size,
-                                        &map_size,
-                                        &grid_size,
-                                        &tilemap_tile_size,
-                                        &map_type,
-                                        &anchor,
-                                    );
-                                    total_colliders += 1;
-                                }
-                            }
-                        }
+            // Only mesh within the bounds of the region
+            let mut bounds = URect {
+                min: UVec2::MAX,
+                max: UVec2::ZERO,
+            };
+            for idx in region
+                .iter()
+                .enumerate()
+                .filter(|(_, r)| **r)
+                .map(|(i, _)| i as u32)
+            {
+                let cell = UVec2::new(idx % width, idx / width);
+                bounds.min = bounds.min.min(cell);
+                bounds.max = bounds.max.max(cell + UVec2::ONE);
+            }
+            let merge_key = |x: u32, y: u32| {
+                let idx = ((bounds.min.y + y) * width + bounds.min.x + x) as usize;
+                if !merge || !region[idx] {
+                    return None;
+                }
+                layer.cells[idx].as_ref()?.merge_key(layer.tile_size)
+            };
+            let mut rects: Vec<URect> =
+                merge_cell_rects(bounds.width(), bounds.height(), merge_key)
+                    .into_iter()
+                    .map(|rect| URect::from_corners(rect.min + bounds.min, rect.max + bounds.min))
+                    .collect();
+
+            // Everything else in the region gets its own collider
+            let mut merged = vec![false; layer.cells.len()];
+            for rect in &rects {
+                for y in rect.min.y..rect.max.y {
+                    for x in rect.min.x..rect.max.x {
+                        merged[(y * width + x) as usize] = true;
+                    }
+                }
+            }
+            for (idx, in_region) in region.iter().enumerate() {
+                if *in_region && !merged[idx] && layer.cells[idx].is_some() {
+                    let (x, y) = (idx as u32 % width, idx as u32 / width);
+                    rects.push(URect::new(x, y, x + 1, y + 1));
+                }
+            }
+
+            for rect in rects {
+                let idx = (rect.min.y * width + rect.min.x) as usize;
+                let Some(cell) = &layer.cells[idx] else {
+                    continue;
+                };
+                let Some(entity) = spawn_collider_for_cells(
+                    commands,
+                    map_entity,
+                    layer_index,
+                    cell,
+                    rect,
+                    layer.tile_size,
+                    &map_size,
+                ) else {
+                    continue;
+                };
+                for y in rect.min.y..rect.max.y {
+                    for x in rect.min.x..rect.max.x {
+                        layer.owners[(y * width + x) as usize] = Some(entity);
                     }
                 }
+                layer.colliders.insert(entity, rect);
+                spawned += 1;
             }
         }
+        spawned
+    }
+}
+
+/// Greedily cover cells with rectangles of cells sharing the same key.
+///
+/// Cells where `key` returns `None` are left uncovered. Rows are scanned
+/// bottom to top; each rectangle is grown as wide as possible, then as tall
+/// as its full width allows. Rectangles use min-inclusive, max-exclusive cell
+/// coordinates.
+pub fn merge_cell_rects<K: PartialEq>(
+    width: u32,
+    height: u32,
+    key: impl Fn(u32, u32) -> Option<K>,
+) -> Vec<URect> {
+    let mut covered = vec![false; (width * height) as usize];
+    let mut rects = Vec::new();
+
+    for y in 0..height {
+        for x in 0..width {
+            if covered[(y * width + x) as usize] {
+                continue;
+            }
+            let Some(cell_key) = key(x, y) else {
+                continue;
+            };
+            let matches = |cx: u32, cy: u32, covered: &[bool]| {
+                !covered[(cy * width + cx) as usize] && key(cx, cy).as_ref() == Some(&cell_key)
+            };
+
+            let mut max_x = x + 1;
+            while max_x < width && matches(max_x, y, &covered) {
+                max_x += 1;
+            }
+            let mut max_y = y + 1;
+            while max_y < height && (x..max_x).all(|cx| matches(cx, max_y, &covered)) {
+                max_y += 1;
+            }
+
+            for cy in y..max_y {
+                for cx in x..max_x {
+                    covered[(cy * width + cx) as usize] = true;
+                }
+            }
+            rects.push(URect::new(x, y, max_x, max_y));
+        }
+    }
+    rects
+}
+
+/// System to spawn tile colliders after map load
+///
+/// This system runs when a `MapRoot` component is added and spawns
+/// colliders for all tiles that have collision data, merging full tiles
+/// if enabled in [`MapCollisionSettings`].
+#[cfg(feature = "physics")]
+pub fn spawn_tile_colliders(
+    mut commands: Commands,
+    map_query: Query<(Entity, &super::MapRoot), Added<super::MapRoot>>,
+    map_assets: Res<Assets<MapProject>>,
+    settings: Res<MapCollisionSettings>,
+) {
+    for (map_entity, map_root) in map_query.iter() {
+        let Some(project) = map_assets.get(&map_root.project) else {
+            continue;
+        };
+
+        let mut colliders = TileColliders::from_project(project);
+        let total_colliders =
+            colliders.rebuild_dirty(&mut commands, map_entity, settings.merge_full_tiles);
+        commands.entity(map_entity).insert(colliders);
+
         if total_colliders > 0 {
             info!("Spawned {} tile colliders from map", total_colliders);
         }
     }
 }
 
-/// Spawn a collider entity for a single tile
+/// System that rebuilds colliders around tiles changed through [`TileColliders`]
+///
+/// Also rebuilds every map's colliders when [`MapCollisionSettings`] changes.
+#[cfg(feature = "physics")]
+pub fn rebuild_tile_colliders(
+    mut commands: Commands,
+    settings: Res<MapCollisionSettings>,
+    mut map_query: Query<(Entity, &mut TileColliders)>,
+) {
+    let settings_changed = settings.is_changed() && !settings.is_added();
+    for (map_entity, mut colliders) in map_query.iter_mut() {
+        if settings_changed {
+            colliders.mark_all_dirty();
+        }
+        if colliders.has_dirty() {
+            colliders.rebuild_dirty(&mut commands, map_entity, settings.merge_full_tiles);
+        }
+    }
+}
+
+/// System that turns collision events on sensor tiles into sensor tile messages
+#[cfg(feature = "physics")]
+pub fn emit_sensor_tile_events(
+    mut started: MessageReader<CollisionStart>,
+    mut ended: MessageReader<CollisionEnd>,
+    sensors: Query<&SensorTile>,
+    mut enter_events: MessageWriter<SensorTileEnterEvent>,
+    mut exit_events: MessageWriter<SensorTileExitEvent>,
+) {
+    // Figure out which side of a pair is the sensor tile
+    let resolve =
+        |collider1: Entity, collider2: Entity, body1: Option<Entity>, body2: Option<Entity>| {
+            if let Ok(tile) = sensors.get(collider1) {
+                Some((*tile, collider1, body2.unwrap_or(collider2)))
+            } else {
+                sensors
+                    .get(collider2)
+                    .ok()
+                    .map(|tile| (*tile, collider2, body1.unwrap_or(collider1)))
+            }
+        };
+
+    for event in started.read() {
+        if let Some((tile, sensor, entity)) =
+            resolve(event.collider1, event.collider2, event.body1, event.body2)
+        {
+            enter_events.write(SensorTileEnterEvent {
+                tile,
+                sensor,
+                entity,
+            });
+        }
+    }
+    for event in ended.read() {
+        if let Some((tile, sensor, entity)) =
+            resolve(event.collider1, event.collider2, event.body1, event.body2)
+        {
+            exit_events.write(SensorTileExitEvent {
+                tile,
+                sensor,
+                entity,
+            });
+        }
+    }
+}
+
+/// Spawn a collider covering a rectangle of cells sharing `cell`'s collision
+///
+/// Rectangles larger than one cell are only produced for merged full tiles.
 #[cfg(feature = "physics")]
-fn spawn_collider_for_tile(
+fn spawn_collider_for_cells(
     commands: &mut Commands,
     map_entity: Entity,
-    collision: &CollisionData,
-    tile_x: u32,
-    tile_y: u32,
-    tile_size: f32,
+    layer_index: usize,
+    cell: &TileCollisionCell,
+    rect: URect,
+    tile_size: Vec2,
     map_size: &TilemapSize,
-    grid_size: &TilemapGridSize,
-    tilemap_tile_size: &TilemapTileSize,
-    map_type: &TilemapType,
-    anchor: &TilemapAnchor,
-) {
-    let Some(collider) = shape_to_collider(&collision.shape, tile_size) else {
-        return;
+) -> Option<Entity> {
+    let collision = &cell.data;
+    let cells = rect.size().as_vec2();
+    let collider = if cells == Vec2::ONE {
+        shape_to_collider(&collision.shape, cell.shape_size)?
+    } else {
+        Collider::rectangle(cells.x * tile_size.x, cells.y * tile_size.y)
     };
 
     // Use bevy_ecs_tilemap's coordinate conversion for consistency with tile rendering
-    let tile_pos = TilePos {
-        x: tile_x,
-        y: tile_y,
+    let tilemap_tile_size = TilemapTileSize {
+        x: tile_size.x,
+        y: tile_size.y,
     };
-    let center = tile_pos.center_in_world(map_size, grid_size, tilemap_tile_size, map_type, anchor);
+    let grid_size: TilemapGridSize = tilemap_tile_size.into();
+    let first_cell = TilePos {
+        x: rect.min.x,
+        y: rect.min.y,
+    };
+    let first_center = first_cell.center_in_world(
+        map_size,
+        &grid_size,
+        &tilemap_tile_size,
+        &TilemapType::Square,
+        &TilemapAnchor::default(),
+    );
+    // `shape_size` is the area the shape is normalized to, centered `frame_offset` from the cell
+    let center = first_center + (cells - Vec2::ONE) * tile_size / 2.0 + cell.frame_offset;
 
     // Apply offset from collision shape
-    let (offset_x, offset_y) = get_shape_offset(&collision.shape, tile_size);
+    let (offset_x, offset_y) = get_shape_offset(&collision.shape, cell.shape_size);
 
     // Add collision layers using bitmasks
     // membership: the layer this collider belongs to (1 << layer)
@@ -191,8 +700,8 @@ fn spawn_collider_for_tile(
             body_type_to_rigid_body(collision.body_type),
             collider,
             CollisionLayers::from_bits(membership, collision.mask),
-            // Prevent bouncing on contact
-            Restitution::new(0.0),
+            Friction::new(collision.friction),
+            Restitution::new(collision.restitution),
             MapCollider {
                 data: collision.clone(),
             },
@@ -201,26 +710,46 @@ fn spawn_collider_for_tile(
 
     // Add one-way marker if applicable
     if collision.one_way.is_one_way() {
-        commands.entity(collider_entity).insert(OneWayPlatform {
-            direction: collision.one_way,
-        });
+        commands
+            .entity(collider_entity)
+            .insert(OneWayPlatform::new(collision.one_way));
+    }
+
+    // Sensors only ever cover a single cell (they are never merged)
+    if collision.is_sensor {
+        commands.entity(collider_entity).insert((
+            Sensor,
+            CollisionEventsEnabled,
+            SensorTile {
+                map: map_entity,
+                layer: layer_index,
+                tile_pos: rect.min,
+                tile_index: cell.tile_index,
+            },
+        ));
     }
 
     // Make it a child of the map
     commands.entity(map_entity).add_child(collider_entity);
+    Some(collider_entity)
 }
 
 /// Convert CollisionShape to Avian Collider
+///
+/// Normalized coordinates are scaled by the tile's width and height separately,
+/// so shapes stretch with non-square tiles. Circle radii use the smaller side.
 #[cfg(feature = "physics")]
-fn shape_to_collider(shape: &CollisionShape, tile_size: f32) -> Option<Collider> {
+fn shape_to_collider(shape: &CollisionShape, tile_size: Vec2) -> Option<Collider> {
     match shape {
         CollisionShape::None => None,
-        CollisionShape::Full => Some(Collider::rectangle(tile_size, tile_size)),
+        CollisionShape::Full => Some(Collider::rectangle(tile_size.x, tile_size.y)),
         CollisionShape::Rectangle { size, .. } => Some(Collider::rectangle(
-            size[0] * tile_size,
-            size[1] * tile_size,
+            size[0] * tile_size.x,
+            size[1] * tile_size.y,
         )),
-        CollisionShape::Circle { radius, .. } => Some(Collider::circle(*radius * tile_size)),
+        CollisionShape::Circle { radius, .. } => {
+            Some(Collider::circle(*radius * tile_size.x.min(tile_size.y)))
+        }
         CollisionShape::Polygon { points } => {
             if points.len() < 3 {
                 return None;
@@ -228,10 +757,43 @@ fn shape_to_collider(shape: &CollisionShape, tile_size: f32) -> Option<Collider>
             // Note: Y is flipped because editor uses Y-down (top=0), Bevy uses Y-up (bottom=0)
             let scaled: Vec<Vec2> = points
                 .iter()
-                .map(|p| Vec2::new((p[0] - 0.5) * tile_size, (0.5 - p[1]) * tile_size))
+                .map(|p| Vec2::new((p[0] - 0.5) * tile_size.x, (0.5 - p[1]) * tile_size.y))
                 .collect();
-            Collider::convex_hull(scaled)
+            polygon_collider(&scaled)
+        }
+    }
+}
+
+/// Build a collider from a polygon outline, which may be concave
+///
+/// Convex outlines become a single convex polygon collider; concave ones are
+/// decomposed into convex parts combined into a compound collider. Polygons with
+/// self-intersecting edges cannot be decomposed and fall back to their convex hull.
+#[cfg(feature = "physics")]
+pub fn polygon_collider(points: &[Vec2]) -> Option<Collider> {
+    let outline: Vec<[f32; 2]> = points.iter().map(|p| p.to_array()).collect();
+    match validate_polygon(&outline) {
+        Ok(()) => {}
+        Err(issue @ PolygonIssue::SelfIntersecting { .. }) => {
+            warn!("Invalid collision polygon ({issue}), using its convex hull instead");
+            return Collider::convex_hull(points.to_vec());
         }
+        Err(_) => return None,
+    }
+
+    let mut parts: Vec<Collider> = decompose_polygon(&outline)
+        .into_iter()
+        .filter_map(|part| Collider::convex_hull(part.into_iter().map(Vec2::from).collect()))
+        .collect();
+    match parts.len() {
+        0 => None,
+        1 => parts.pop(),
+        _ => Some(Collider::compound(
+            parts
+                .into_iter()
+                .map(|part| (Vec2::ZERO, 0.0, part))
+                .collect(),
+        )),
     }
 }
 
@@ -241,18 +803,18 @@ fn shape_to_collider(shape: &CollisionShape, tile_size: f32) -> Option<Collider>
 /// We need to convert this to a center offset from the tile center for the collider.
 /// Note: Editor uses Y-down (top=0), but Bevy uses Y-up (bottom=0), so we flip Y.
 #[cfg(feature = "physics")]
-fn get_shape_offset(shape: &CollisionShape, tile_size: f32) -> (f32, f32) {
+fn get_shape_offset(shape: &CollisionShape, tile_size: Vec2) -> (f32, f32) {
     match shape {
         CollisionShape::Rectangle { offset, size } => (
             // X: offset + size/2 = center from tile origin, -0.5 = offset from tile center
-            (offset[0] + size[0] / 2.0 - 0.5) * tile_size,
+            (offset[0] + size[0] / 2.0 - 0.5) * tile_size.x,
             // Y: flip because editor uses Y-down (top=0), Bevy uses Y-up (bottom=0)
-            (0.5 - offset[1] - size[1] / 2.0) * tile_size,
+            (0.5 - offset[1] - size[1] / 2.0) * tile_size.y,
         ),
         CollisionShape::Circle { offset, .. } => (
-            (offset[0] - 0.5) * tile_size,
+            (offset[0] - 0.5) * tile_size.x,
             // Y: flip for same reason
-            (0.5 - offset[1]) * tile_size,
+            (0.5 - offset[1]) * tile_size.y,
         ),
         _ => (0.0, 0.0),
     }
@@ -278,3 +840,267 @@ impl Plugin for MapCollisionPlugin {
         // No-op when physics feature is disabled
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rects_for(rows: &[&str]) -> Vec<URect> {
+        // Rows are listed top to bottom; y = 0 is the bottom row
+        let height = rows.len() as u32;
+        let width = rows[0].len() as u32;
+        merge_cell_rects(width, height, |x, y| {
+            let row = rows[(height - 1 - y) as usize].as_bytes();
+            (row[x as usize] != b'.').then_some(row[x as usize])
+        })
+    }
+
+    #[test]
+    fn test_merge_solid_block() {
+        assert_eq!(rects_for(&["###", "###"]), vec![URect::new(0, 0, 3, 2)]);
+    }
+
+    #[test]
+    fn test_merge_keeps_keys_apart() {
+        let rects = rects_for(&["##AA", "##AA"]);
+        assert_eq!(rects, vec![URect::new(0, 0, 2, 2), URect::new(2, 0, 4, 2)]);
+    }
+
+    #[test]
+    fn test_merge_l_shape() {
+        // Bottom row is wide, the column above only covers the first cell
+        let rects = rects_for(&["#..", "#..", "###"]);
+        assert_eq!(rects, vec![URect::new(0, 0, 3, 1), URect::new(0, 1, 1, 3)]);
+    }
+
+    #[test]
+    fn test_merge_covers_every_cell_once() {
+        let rows = ["#.##.#", "######", "##..##", "#....#"];
+        let rects = rects_for(&rows);
+        let solid: usize = rows
+            .iter()
+            .map(|row| row.bytes().filter(|&b| b == b'#').count())
+            .sum();
+        let area: u32 = rects.iter().map(|rect| rect.width() * rect.height()).sum();
+        assert_eq!(area as usize, solid);
+        for (i, a) in rects.iter().enumerate() {
+            for b in &rects[i + 1..] {
+                assert!(a.intersect(*b).is_empty());
+            }
+        }
+    }
+
+    #[cfg(feature = "physics")]
+    fn solid_ground_project(width: u32, height: u32) -> MapProject {
+        let mut tileset = Tileset::new("Ground".to_string(), "ground.png".to_string(), 16, 2, 1);
+        tileset.set_tile_full_collision(0, true);
+        let mut level = bevy_map_core::Level::new("Test".to_string(), width, height);
+        level.add_layer(bevy_map_core::Layer::new_tile_layer(
+            "Ground".to_string(),
+            tileset.id,
+            width,
+            height,
+        ));
+        for y in 0..height {
+            for x in 0..width {
+                level.set_tile(0, x, y, Some(0));
+            }
+        }
+        MapProject::new(level, vec![tileset])
+    }
+
+    /// Collider rectangles of every layer, sorted
+    #[cfg(feature = "physics")]
+    fn collider_rects(colliders: &TileColliders) -> Vec<(usize, [u32; 4])> {
+        let mut rects: Vec<_> = colliders
+            .layers
+            .iter()
+            .flat_map(|(index, layer)| {
+                layer
+                    .colliders
+                    .values()
+                    .map(move |r| (*index, [r.min.x, r.min.y, r.max.x, r.max.y]))
+            })
+            .collect();
+        rects.sort_unstable();
+        rects
+    }
+
+    /// Rebuild the dirty cells and check the result matches rebuilding everything
+    #[cfg(feature = "physics")]
+    fn assert_rebuild_matches_full(world: &mut World, map: Entity, colliders: &mut TileColliders) {
+        colliders.rebuild_dirty(&mut world.commands(), map, true);
+        world.flush();
+        let local = collider_rects(colliders);
+        colliders.mark_all_dirty();
+        colliders.rebuild_dirty(&mut world.commands(), map, true);
+        world.flush();
+        assert_eq!(local, collider_rects(colliders));
+    }
+
+    #[cfg(feature = "physics")]
+    #[test]
+    fn test_tile_colliders_merge_and_rebuild_locally() {
+        let mut world = World::new();
+        let map = world.spawn_empty().id();
+        let mut colliders = TileColliders::from_project(&solid_ground_project(6, 3));
+
+        let spawned = colliders.rebuild_dirty(&mut world.commands(), map, true);
+        world.flush();
+        assert_eq!(spawned, 1);
+        assert_eq!(colliders.collider_count(), 1);
+        assert!(!colliders.has_dirty());
+
+        // Punching a hole in the middle only rebuilds the collider around it
+        colliders.set_collision(0, 3, 2, None);
+        assert!(colliders.has_dirty());
+        assert_rebuild_matches_full(&mut world, map, &mut colliders);
+        assert!(colliders.get(0, 3, 2).is_none());
+        assert_eq!(colliders.collider_count(), 3);
+        assert_eq!(world.query::<&MapCollider>().iter(&world).count(), 3);
+
+        // Repeated edits re-merge with the colliders next to them instead of
+        // piling up small ones
+        colliders.set_collision(0, 1, 0, None);
+        assert_rebuild_matches_full(&mut world, map, &mut colliders);
+        colliders.set_collision(0, 3, 2, Some(CollisionData::full()));
+        assert_rebuild_matches_full(&mut world, map, &mut colliders);
+        colliders.set_collision(0, 1, 0, Some(CollisionData::full()));
+        assert_rebuild_matches_full(&mut world, map, &mut colliders);
+        assert_eq!(colliders.collider_count(), 1);
+        assert_eq!(world.query::<&MapCollider>().iter(&world).count(), 1);
+
+        // Without merging there's one collider per solid tile
+        colliders.mark_all_dirty();
+        colliders.rebuild_dirty(&mut world.commands(), map, false);
+        world.flush();
+        assert_eq!(colliders.collider_count(), 18);
+        assert_eq!(world.query::<&MapCollider>().iter(&world).count(), 18);
+    }
+
+    #[cfg(feature = "physics")]
+    #[test]
+    fn test_polygon_collider_decomposes_concave_shapes() {
+        let slope = CollisionShape::polygon(vec![[0.0, 1.0], [1.0, 0.0], [1.0, 1.0]]);
+        let collider = shape_to_collider(&slope, Vec2::splat(16.0)).unwrap();
+        assert!(collider.shape().as_compound().is_none());
+
+        let l_shape = CollisionShape::polygon(vec![
+            [0.0, 0.0],
+            [0.5, 0.0],
+            [0.5, 0.5],
+            [1.0, 0.5],
+            [1.0, 1.0],
+            [0.0, 1.0],
+        ]);
+        let collider = shape_to_collider(&l_shape, Vec2::splat(16.0)).unwrap();
+        let compound = collider.shape().as_compound().unwrap();
+        assert_eq!(compound.shapes().len(), 2);
+
+        // Self-intersecting outlines fall back to the convex hull
+        let bowtie = CollisionShape::polygon(vec![[0.0, 0.0], [1.0, 1.0], [1.0, 0.0], [0.0, 1.0]]);
+        let collider = shape_to_collider(&bowtie, Vec2::splat(16.0)).unwrap();
+        assert!(collider.shape().as_compound().is_none());
+    }
+
+    #[cfg(feature = "physics")]
+    #[test]
+    fn test_sensor_and_material_tiles() {
+        let mut project = solid_ground_project(3, 1);
+        let tileset = project.tilesets.values_mut().next().unwrap();
+        tileset.set_tile_collision(1, CollisionData::full().with_sensor(true));
+        tileset.set_tile_material(0, 0.0, 0.5);
+        project.level.set_tile(0, 1, 0, Some(1));
+
+        let mut world = World::new();
+        let map = world.spawn_empty().id();
+        let mut colliders = TileColliders::from_project(&project);
+        colliders.rebuild_dirty(&mut world.commands(), map, true);
+        world.flush();
+
+        // The sensor splits the ground, and is never merged itself
+        assert_eq!(colliders.collider_count(), 3);
+        let sensors: Vec<SensorTile> = world
+            .query_filtered::<&SensorTile, With<Sensor>>()
+            .iter(&world)
+            .copied()
+            .collect();
+        assert_eq!(sensors.len(), 1);
+        assert_eq!(sensors[0].tile_pos, UVec2::new(1, 0));
+        assert_eq!(sensors[0].tile_index, Some(1));
+        assert_eq!(sensors[0].layer, 0);
+
+        let mut materials = world.query_filtered::<(&Friction, &Restitution), Without<Sensor>>();
+        for (friction, restitution) in materials.iter(&world) {
+            assert_eq!(friction.dynamic_coefficient, 0.0);
+            assert_eq!(restitution.coefficient, 0.5);
+        }
+    }
+
+    #[cfg(feature = "physics")]
+    #[test]
+    fn test_sensor_tile_enter_and_exit_events() {
+        #[derive(Resource, Default)]
+        struct Seen(Vec<(bool, UVec2, Option<u32>)>);
+
+        fn record(
+            mut seen: ResMut<Seen>,
+            mut entered: MessageReader<SensorTileEnterEvent>,
+            mut exited: MessageReader<SensorTileExitEvent>,
+        ) {
+            for event in entered.read() {
+                seen.0
+                    .push((true, event.tile.tile_pos, event.tile.tile_index));
+            }
+            for event in exited.read() {
+                seen.0
+                    .push((false, event.tile.tile_pos, event.tile.tile_index));
+            }
+        }
+
+        let mut app = crate::tests::physics_app(PhysicsPlugins::default());
+        app.add_message::<SensorTileEnterEvent>()
+            .add_message::<SensorTileExitEvent>()
+            .init_resource::<Seen>()
+            .add_systems(Update, (emit_sensor_tile_events, record).chain());
+
+        let map = app.world_mut().spawn_empty().id();
+        app.world_mut().spawn((
+            RigidBody::Static,
+            Collider::rectangle(16.0, 16.0),
+            Sensor,
+            CollisionEventsEnabled,
+            SensorTile {
+                map,
+                layer: 0,
+                tile_pos: UVec2::new(4, 2),
+                tile_index: Some(7),
+            },
+        ));
+        let body = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Collider::rectangle(4.0, 4.0),
+                Transform::from_xyz(-30.0, 0.0, 0.0),
+                LinearVelocity(Vec2::new(200.0, 0.0)),
+                GravityScale(0.0),
+            ))
+            .id();
+
+        for _ in 0..40 {
+            app.update();
+        }
+
+        // The body passes straight through the sensor
+        assert!(app.world().get::<Transform>(body).unwrap().translation.x > 30.0);
+        let seen = &app.world().resource::<Seen>().0;
+        assert_eq!(
+            seen,
+            &vec![
+                (true, UVec2::new(4, 2), Some(7)),
+                (false, UVec2::new(4, 2), Some(7))
+            ]
+        );
+    }
+}
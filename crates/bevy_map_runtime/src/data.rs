@@ -0,0 +1,388 @@
+//! Data instances at runtime - items, enemies, loot tables and other data types
+//!
+//! Data instances authored in the editor ship in the exported map. When a map is
+//! spawned they are loaded into the [`MapData`] resource, which looks them up by
+//! ID or by type. Types deriving [`MapDataType`] read instances into typed structs,
+//! and [`DataRef<T>`] fields resolve entity `Ref` properties to the referenced
+//! instance.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use bevy::prelude::*;
+//! use bevy_map::prelude::*;
+//!
+//! #[derive(MapDataType)]
+//! #[map_data(type_name = "Item")]
+//! pub struct Item {
+//!     pub name: String,
+//!     #[map_prop(default = 1)]
+//!     pub value: i32,
+//! }
+//!
+//! #[derive(Component, MapEntity)]
+//! #[map_entity(type_name = "Chest")]
+//! pub struct Chest {
+//!     #[map_prop]
+//!     pub loot: DataRef<Item>,
+//! }
+//!
+//! fn open_chests(chests: Query<&Chest>, map_data: Res<MapData>) {
+//!     for chest in &chests {
+//!         if let Some(item) = chest.loot.get(&map_data) {
+//!             info!("Found {} worth {}", item.name, item.value);
+//!         }
+//!     }
+//! }
+//! ```
+
+use bevy::prelude::*;
+use bevy_map_core::{DataInstance, DataStore, EntityInstance, MapProject, Value};
+use bevy_map_schema::{PropType, PropertyDef, Schema};
+use std::fmt;
+use std::marker::PhantomData;
+use uuid::Uuid;
+
+use crate::MapProperty;
+
+/// A data type that can be read from a [`DataInstance`]
+///
+/// Usually implemented with `#[derive(MapDataType)]`, which reads every field
+/// through [`MapProperty`] like an embedded type.
+pub trait MapDataType: Sized + 'static {
+    /// Returns the type name as used in the map editor
+    fn type_name() -> &'static str;
+
+    /// Creates a value from data instance properties
+    fn from_data(instance: &DataInstance) -> Self;
+}
+
+/// Read a typed property from a data instance
+///
+/// Returns `None` if the property is missing or null. Values that exist but
+/// cannot be converted are logged with the instance ID and property name.
+pub fn read_data_property<T: MapProperty>(instance: &DataInstance, name: &str) -> Option<T> {
+    let value = instance.properties.get(name)?;
+    if value.is_null() {
+        return T::from_value(value);
+    }
+
+    let parsed = T::from_value(value);
+    if parsed.is_none() {
+        warn!(
+            "Data {} ({}): property '{}' value {:?} cannot be converted to {}",
+            instance.id,
+            instance.type_name,
+            name,
+            value,
+            std::any::type_name::<T>()
+        );
+    }
+    parsed
+}
+
+/// Resource storing all data instances from the loaded map
+///
+/// This is automatically populated when a map is loaded via `MapHandle` or
+/// `SpawnMapProjectEvent`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// fn list_items(map_data: Res<MapData>) {
+///     for item in map_data.of_type("Item") {
+///         info!("{:?}", item.name());
+///     }
+/// }
+/// ```
+#[derive(Resource, Default, Debug, Clone)]
+pub struct MapData {
+    /// Data instances grouped by type name
+    pub store: DataStore,
+}
+
+impl MapData {
+    /// Get a data instance by ID
+    pub fn get(&self, id: Uuid) -> Option<&DataInstance> {
+        self.store.get(id)
+    }
+
+    /// Get all instances of a type
+    pub fn of_type(&self, type_name: &str) -> &[DataInstance] {
+        self.store.get_by_type(type_name)
+    }
+
+    /// Find an instance of a type by its `name` property
+    pub fn find(&self, type_name: &str, name: &str) -> Option<&DataInstance> {
+        self.store.find_by_name(type_name, name)
+    }
+
+    /// Read the instance with this ID as `T`
+    ///
+    /// Returns `None` if there is no such instance or its type is not exactly
+    /// `T`'s type. Schema inheritance is not followed: an instance of a type
+    /// that `extends` `T`'s type is not returned.
+    pub fn get_as<T: MapDataType>(&self, id: Uuid) -> Option<T> {
+        self.get(id)
+            .filter(|instance| instance.type_name == T::type_name())
+            .map(T::from_data)
+    }
+
+    /// Read every instance of `T`'s type, with their IDs
+    ///
+    /// Like [`MapData::get_as`], instances of types extending it are not included.
+    pub fn all<T: MapDataType>(&self) -> impl Iterator<Item = (Uuid, T)> + '_ {
+        self.of_type(T::type_name())
+            .iter()
+            .map(|instance| (instance.id, T::from_data(instance)))
+    }
+
+    /// Resolve a `Ref` property value (an instance ID) to the referenced instance
+    pub fn resolve(&self, value: &Value) -> Option<&DataInstance> {
+        let id = Uuid::parse_str(value.as_string()?).ok()?;
+        self.get(id)
+    }
+
+    /// Resolve an entity's `Ref` property to the referenced instance
+    pub fn resolve_property(
+        &self,
+        instance: &EntityInstance,
+        property: &str,
+    ) -> Option<&DataInstance> {
+        self.resolve(instance.properties.get(property)?)
+    }
+
+    /// Check if there are no data instances
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
+
+    /// Load data instances from a MapProject
+    ///
+    /// Instances are merged by ID, so data from every spawned map stays
+    /// available: an instance that is already loaded is replaced by the
+    /// project's copy, and all other instances are kept. Instances removed
+    /// from a hot-reloaded map stay loaded until [`MapData::clear`].
+    pub fn load_from_project(&mut self, project: &MapProject) {
+        for instance in project.data.all_instances() {
+            match self.store.get_mut(instance.id) {
+                Some(existing) if existing.type_name == instance.type_name => {
+                    *existing = instance.clone();
+                }
+                _ => {
+                    self.store.remove(instance.id);
+                    self.store.add(instance.clone());
+                }
+            }
+        }
+        info!(
+            "Loaded {} data instance(s) from map project",
+            project.data.len()
+        );
+    }
+
+    /// Clear all loaded data instances
+    pub fn clear(&mut self) {
+        self.store = DataStore::default();
+    }
+}
+
+/// A typed reference to a data instance, stored as a `Ref` property
+///
+/// Empty references (`null` in the map) have no ID. Resolve the reference with
+/// [`DataRef::get`] once the map is loaded.
+pub struct DataRef<T> {
+    /// ID of the referenced instance
+    pub id: Option<Uuid>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: MapDataType> DataRef<T> {
+    /// A reference to the instance with this ID
+    pub fn new(id: Uuid) -> Self {
+        Self {
+            id: Some(id),
+            marker: PhantomData,
+        }
+    }
+
+    /// An empty reference
+    pub fn none() -> Self {
+        Self {
+            id: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Check if the reference is empty
+    pub fn is_none(&self) -> bool {
+        self.id.is_none()
+    }
+
+    /// The referenced instance, if it exists
+    pub fn instance<'a>(&self, map_data: &'a MapData) -> Option<&'a DataInstance> {
+        map_data.get(self.id?)
+    }
+
+    /// Read the referenced instance as `T`
+    pub fn get(&self, map_data: &MapData) -> Option<T> {
+        map_data.get_as(self.id?)
+    }
+}
+
+impl<T> Clone for DataRef<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for DataRef<T> {}
+
+impl<T> Default for DataRef<T> {
+    fn default() -> Self {
+        Self {
+            id: None,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for DataRef<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for DataRef<T> {}
+
+impl<T> fmt::Debug for DataRef<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DataRef").field(&self.id).finish()
+    }
+}
+
+/// References are stored as the instance ID string, or `null` when empty
+impl<T: MapDataType> MapProperty for DataRef<T> {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => Some(Self::none()),
+            Value::String(id) if id.is_empty() => Some(Self::none()),
+            Value::String(id) => Uuid::parse_str(id).ok().map(Self::new),
+            _ => None,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        self.id
+            .map(|id| Value::String(id.to_string()))
+            .unwrap_or_default()
+    }
+
+    fn describe(property: &mut PropertyDef, _schema: &mut Schema) {
+        property.prop_type = PropType::Ref;
+        property.ref_type = Some(T::type_name().to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_map_core::Level;
+
+    struct Item {
+        name: String,
+        value: i32,
+    }
+
+    impl MapDataType for Item {
+        fn type_name() -> &'static str {
+            "Item"
+        }
+
+        fn from_data(instance: &DataInstance) -> Self {
+            Self {
+                name: read_data_property(instance, "name").unwrap_or_default(),
+                value: read_data_property(instance, "value").unwrap_or(1),
+            }
+        }
+    }
+
+    fn item(name: &str) -> DataInstance {
+        let mut instance = DataInstance::new("Item".to_string());
+        instance
+            .properties
+            .insert("name".to_string(), Value::String(name.to_string()));
+        instance
+    }
+
+    #[test]
+    fn test_map_data_lookups() {
+        let sword = item("Sword");
+        let sword_id = sword.id;
+        let enemy = DataInstance::new("Enemy".to_string());
+        let enemy_id = enemy.id;
+
+        let mut map_data = MapData::default();
+        map_data.store.add(sword);
+        map_data.store.add(item("Shield"));
+        map_data.store.add(enemy);
+
+        assert_eq!(map_data.of_type("Item").len(), 2);
+        assert_eq!(
+            map_data.find("Item", "Shield").unwrap().name(),
+            Some("Shield")
+        );
+
+        let typed = map_data.get_as::<Item>(sword_id).unwrap();
+        assert_eq!(typed.name, "Sword");
+        assert_eq!(typed.value, 1);
+        // Instances of other types are not read as Items
+        assert!(map_data.get_as::<Item>(enemy_id).is_none());
+        assert_eq!(map_data.all::<Item>().count(), 2);
+
+        // Entity Ref properties resolve to the referenced instance
+        let mut chest = EntityInstance::new("Chest".to_string(), [0.0, 0.0]);
+        chest
+            .properties
+            .insert("loot".to_string(), Value::String(sword_id.to_string()));
+        assert_eq!(
+            map_data.resolve_property(&chest, "loot").unwrap().id,
+            sword_id
+        );
+
+        let loot: DataRef<Item> = crate::read_map_property(&chest, "loot").unwrap();
+        assert_eq!(loot.get(&map_data).unwrap().name, "Sword");
+        assert_eq!(loot.to_value(), Value::String(sword_id.to_string()));
+        assert!(DataRef::<Item>::from_value(&Value::Null).unwrap().is_none());
+
+        let mut property = PropertyDef::new("loot", PropType::String);
+        DataRef::<Item>::describe(&mut property, &mut Schema::default());
+        assert_eq!(property.prop_type, PropType::Ref);
+        assert_eq!(property.ref_type.as_deref(), Some("Item"));
+    }
+
+    #[test]
+    fn test_load_merges_projects() {
+        let sword = item("Sword");
+        let sword_id = sword.id;
+        let mut cave = MapProject::new(Level::new("Cave".to_string(), 1, 1), Vec::new());
+        cave.data.add(sword.clone());
+        cave.data.add(item("Shield"));
+        let mut town = MapProject::new(Level::new("Town".to_string(), 1, 1), Vec::new());
+        let mut renamed = sword;
+        renamed
+            .properties
+            .insert("name".to_string(), Value::String("Old Sword".to_string()));
+        town.data.add(renamed);
+        town.data.add(item("Bread"));
+
+        let mut map_data = MapData::default();
+        map_data.load_from_project(&cave);
+        map_data.load_from_project(&town);
+
+        assert_eq!(map_data.of_type("Item").len(), 3);
+        assert!(map_data.find("Item", "Shield").is_some());
+        assert!(map_data.find("Item", "Bread").is_some());
+        assert_eq!(map_data.get(sword_id).unwrap().name(), Some("Old Sword"));
+    }
+}
@@ -0,0 +1,615 @@
+//! Save-game deltas for spawned maps
+//!
+//! [`MapDeltaPlugin`] records how the player changed each level, relative to
+//! its original `MapProject`, in the [`MapDeltas`] resource:
+//!
+//! - Tile changes made through [`MapEdit`](crate::edit::MapEdit) are recorded
+//!   automatically.
+//! - Entity changes are recorded by game code with [`MapDeltas::record_spawn`],
+//!   [`MapDeltas::record_despawn`], [`MapDeltas::record_move`] and
+//!   [`MapDeltas::record_property`].
+//!
+//! Whenever a map is spawned (initially, when streamed in, or after a hot
+//! reload) its delta is re-applied: tiles are updated and changed entities are
+//! respawned through the [`EntityRegistry`]. Parts of a delta that no longer
+//! match the base map are reported as [`MapDeltaConflictEvent`]s.
+//!
+//! The delta is applied to a copy of the `MapProject` owned by the spawned
+//! map, and [`MapRoot::project`] is pointed at that copy while
+//! [`MapRoot::handle`] keeps the source asset. The shared asset is never
+//! changed, so spawning the same map again applies the delta once more to the
+//! original level.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use bevy_map_runtime::delta::{MapDeltaPlugin, MapDeltas};
+//!
+//! app.add_plugins((MapRuntimePlugin, MapDeltaPlugin));
+//!
+//! fn open_chest(mut deltas: ResMut<MapDeltas>, chests: Query<&MapEntityMarker, Added<Opened>>) {
+//!     for chest in chests.iter() {
+//!         deltas.record_property(chest.instance_id, "opened", Value::Bool(true));
+//!     }
+//! }
+//!
+//! fn save(deltas: Res<MapDeltas>) {
+//!     std::fs::write("save.json", deltas.to_json().unwrap()).unwrap();
+//! }
+//!
+//! fn load(mut deltas: ResMut<MapDeltas>) {
+//!     // Load before spawning the saved levels
+//!     deltas.load_json(&std::fs::read_to_string("save.json").unwrap()).unwrap();
+//! }
+//! ```
+
+use crate::edit::{sync_map, TileChangedEvent};
+use crate::{EntityRegistry, MapEntityMarker, MapRoot};
+use bevy::ecs::message::{Message, MessageReader};
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::TilemapSize;
+use bevy_map_core::{
+    DeltaConflict, EntityInstance, Level, MapDelta, MapProject, TileChange, Value,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Version of the save format written by [`MapDeltas::to_json`]
+const SAVE_VERSION: u32 = 1;
+
+/// Plugin that records map changes and re-applies them to spawned maps
+///
+/// Requires `MapRuntimePlugin`.
+pub struct MapDeltaPlugin;
+
+impl Plugin for MapDeltaPlugin {
+    fn build(&self, app: &mut App) {
+        let apply = apply_map_deltas.before(crate::navigation::spawn_map_navigation);
+        #[cfg(feature = "physics")]
+        let apply = apply.before(crate::collision::spawn_tile_colliders);
+
+        app.init_resource::<MapDeltas>()
+            .add_message::<MapDeltaConflictEvent>()
+            .add_systems(Update, (apply, record_tile_deltas));
+    }
+}
+
+/// Emitted when a re-applied delta doesn't match its base map
+#[derive(Message, Debug, Clone)]
+pub struct MapDeltaConflictEvent {
+    /// The [`MapRoot`] entity the delta was applied to
+    pub map: Entity,
+    /// ID of the level
+    pub level_id: Uuid,
+    /// Every conflict found
+    pub conflicts: Vec<DeltaConflict>,
+}
+
+/// Serialized form of [`MapDeltas`]
+#[derive(Serialize, Deserialize)]
+struct SaveDeltas {
+    version: u32,
+    levels: HashMap<Uuid, MapDelta>,
+}
+
+/// Runtime changes of every level, keyed by level ID
+#[derive(Resource, Debug, Default)]
+pub struct MapDeltas {
+    levels: HashMap<Uuid, MapDelta>,
+    /// Level of every known entity instance
+    entity_levels: HashMap<Uuid, Uuid>,
+}
+
+impl MapDeltas {
+    /// Get the delta of a level
+    pub fn get(&self, level_id: Uuid) -> Option<&MapDelta> {
+        self.levels.get(&level_id)
+    }
+
+    /// Get the delta of a level for editing, creating it if needed
+    pub fn level_mut(&mut self, level_id: Uuid) -> &mut MapDelta {
+        self.levels.entry(level_id).or_default()
+    }
+
+    /// Iterate over the deltas of all levels
+    pub fn levels(&self) -> impl Iterator<Item = (Uuid, &MapDelta)> {
+        self.levels.iter().map(|(id, delta)| (*id, delta))
+    }
+
+    /// Level containing an entity instance, once its map has been spawned
+    pub fn level_of(&self, instance_id: Uuid) -> Option<Uuid> {
+        self.entity_levels.get(&instance_id).copied()
+    }
+
+    /// Record an entity instance spawned into a level during play
+    pub fn record_spawn(&mut self, level_id: Uuid, instance: EntityInstance) {
+        self.entity_levels.insert(instance.id, level_id);
+        self.level_mut(level_id).record_spawn(instance);
+    }
+
+    /// Record the removal of an entity instance
+    ///
+    /// Returns false if the instance doesn't belong to a spawned level.
+    pub fn record_despawn(&mut self, instance_id: Uuid) -> bool {
+        self.with_entity_delta(instance_id, |delta| delta.record_despawn(instance_id))
+    }
+
+    /// Record an entity instance moving to a new position
+    pub fn record_move(&mut self, instance_id: Uuid, position: Vec2) -> bool {
+        self.with_entity_delta(instance_id, |delta| {
+            delta.record_move(instance_id, position.into())
+        })
+    }
+
+    /// Record a property of an entity instance being set
+    pub fn record_property(&mut self, instance_id: Uuid, key: &str, value: Value) -> bool {
+        self.with_entity_delta(instance_id, |delta| {
+            delta.record_property(instance_id, key, value)
+        })
+    }
+
+    /// Record a property of an entity instance being removed
+    pub fn record_property_removed(&mut self, instance_id: Uuid, key: &str) -> bool {
+        self.with_entity_delta(instance_id, |delta| {
+            delta.record_property_removed(instance_id, key)
+        })
+    }
+
+    fn with_entity_delta(&mut self, instance_id: Uuid, f: impl FnOnce(&mut MapDelta)) -> bool {
+        let Some(level_id) = self.level_of(instance_id) else {
+            warn!(
+                "Entity instance {} is not part of a spawned map",
+                instance_id
+            );
+            return false;
+        };
+        f(self.level_mut(level_id));
+        true
+    }
+
+    /// Remember which level each entity instance belongs to
+    fn index_level(&mut self, level: &Level) {
+        let spawned = self.levels.get(&level.id).map(|delta| &delta.spawned);
+        for instance in level.entities.iter().chain(spawned.into_iter().flatten()) {
+            self.entity_levels.insert(instance.id, level.id);
+        }
+    }
+
+    /// Forget all changes
+    pub fn clear(&mut self) {
+        self.levels.clear();
+    }
+
+    /// Serialize the deltas of all changed levels to JSON
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        let levels = self
+            .levels
+            .iter()
+            .filter(|(_, delta)| !delta.is_empty())
+            .map(|(id, delta)| (*id, delta.clone()))
+            .collect();
+        serde_json::to_string(&SaveDeltas {
+            version: SAVE_VERSION,
+            levels,
+        })
+    }
+
+    /// Replace all deltas with ones loaded from JSON
+    ///
+    /// Deltas are applied when levels are spawned, so load them before
+    /// spawning (or respawn the affected levels).
+    pub fn load_json(&mut self, json: &str) -> Result<(), serde_json::Error> {
+        let save: SaveDeltas = serde_json::from_str(json)?;
+        self.levels = save.levels;
+        for (level_id, delta) in &self.levels {
+            for instance in &delta.spawned {
+                self.entity_levels.insert(instance.id, *level_id);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// System that records tile changes made through `MapEdit`
+pub fn record_tile_deltas(
+    mut changed: MessageReader<TileChangedEvent>,
+    maps: Query<&MapRoot>,
+    projects: Res<Assets<MapProject>>,
+    mut deltas: ResMut<MapDeltas>,
+) {
+    for event in changed.read() {
+        let Some(project) = maps
+            .get(event.map)
+            .ok()
+            .and_then(|root| projects.get(&root.project))
+        else {
+            continue;
+        };
+        let Some(layer) = project.level.layers.get(event.layer) else {
+            continue;
+        };
+        deltas.level_mut(project.level.id).record_tile(
+            &layer.name,
+            event.position.x,
+            event.position.y,
+            event.old,
+            event.new,
+        );
+    }
+}
+
+/// System that re-applies recorded deltas to newly spawned maps
+pub fn apply_map_deltas(world: &mut World, maps: &mut QueryState<Entity, Added<MapRoot>>) {
+    let spawned: Vec<Entity> = maps.iter(world).collect();
+    for map in spawned {
+        apply_delta_to_map(world, map);
+    }
+}
+
+fn apply_delta_to_map(world: &mut World, map: Entity) {
+    let Some(root) = world.get::<MapRoot>(map) else {
+        return;
+    };
+    let source = root.project.clone();
+    let textures = root.textures.clone();
+    let map_transform = world.get::<Transform>(map).copied().unwrap_or_default();
+
+    let level_id = world.resource_scope(|world, mut deltas: Mut<MapDeltas>| {
+        let project = world.resource::<Assets<MapProject>>().get(&source)?;
+        deltas.index_level(&project.level);
+        let level_id = project.level.id;
+        deltas
+            .get(level_id)
+            .is_some_and(|delta| !delta.is_empty())
+            .then_some(level_id)
+    });
+    let Some(level_id) = level_id else {
+        return;
+    };
+    // The map gets its own copy, so the delta doesn't reach other maps
+    // spawned from the same asset
+    let Some(handle) = crate::own_map_project(world, map) else {
+        return;
+    };
+
+    world.resource_scope(|world, deltas: Mut<MapDeltas>| {
+        world.resource_scope(|world, mut projects: Mut<Assets<MapProject>>| {
+            let Some(project) = projects.get_mut_untracked(&handle) else {
+                return;
+            };
+            let Some(delta) = deltas.get(level_id) else {
+                return;
+            };
+
+            // Tiles before the delta, to find which cells to redraw
+            let mut layer_changes: Vec<(usize, Vec<TileChange>)> = Vec::new();
+            for (layer_name, cells) in &delta.tiles {
+                let Some(layer_index) = project.level.layer_index_by_name(layer_name) else {
+                    continue;
+                };
+                let changes = cells
+                    .iter()
+                    .map(|cell| TileChange {
+                        x: cell.x,
+                        y: cell.y,
+                        old: project.level.get_tile(layer_index, cell.x, cell.y),
+                        new: cell.tile,
+                    })
+                    .collect();
+                layer_changes.push((layer_index, changes));
+            }
+
+            let conflicts = delta.apply(project);
+
+            let map_size = TilemapSize {
+                x: project.level.width,
+                y: project.level.height,
+            };
+            for (layer_index, mut changes) in layer_changes {
+                let tileset = project.level.layers[layer_index]
+                    .tileset_id()
+                    .and_then(|id| project.tilesets.get(&id));
+                let Some(tileset) = tileset else {
+                    continue;
+                };
+                changes.retain(|change| change.old != change.new);
+                sync_map(
+                    world,
+                    map,
+                    layer_index,
+                    tileset,
+                    &textures,
+                    map_size,
+                    &changes,
+                );
+            }
+
+            // Changed entities are respawned from their updated instances
+            let replaced: HashSet<Uuid> = delta
+                .despawned
+                .iter()
+                .chain(delta.modified.keys())
+                .chain(delta.spawned.iter().map(|instance| &instance.id))
+                .copied()
+                .collect();
+            let mut markers = world.query::<(Entity, &MapEntityMarker)>();
+            let despawn: Vec<Entity> = markers
+                .iter(world)
+                .filter(|(_, marker)| replaced.contains(&marker.instance_id))
+                .map(|(entity, _)| entity)
+                .collect();
+            for entity in despawn {
+                world.despawn(entity);
+            }
+
+            let respawn: Vec<&EntityInstance> = project
+                .level
+                .entities
+                .iter()
+                .filter(|instance| replaced.contains(&instance.id))
+                .collect();
+            let schema = crate::entity_registry::embedded_schema(project.schema.as_ref());
+            world.try_resource_scope(|world, registry: Mut<EntityRegistry>| {
+                let mut commands = world.commands();
+                for instance in respawn {
+                    registry.spawn_with_schema(
+                        &mut commands,
+                        instance,
+                        map_transform,
+                        schema.as_ref(),
+                    );
+                }
+            });
+            world.flush();
+
+            if !conflicts.is_empty() {
+                for conflict in &conflicts {
+                    warn!(
+                        "Save delta conflict in '{}': {}",
+                        project.level.name, conflict
+                    );
+                }
+                world.write_message(MapDeltaConflictEvent {
+                    map,
+                    level_id,
+                    conflicts,
+                });
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit::{apply_tile_edit, TileEdit};
+    use crate::{spawn_map_project, TilesetTextures};
+    use bevy::ecs::message::Messages;
+    use bevy_ecs_tilemap::prelude::{TilePos, TileStorage};
+    use bevy_map_core::{Layer, Tileset};
+
+    fn base_project() -> MapProject {
+        let tileset = Tileset::new("Tiles".to_string(), "tiles.png".to_string(), 16, 4, 1);
+        let mut level = Level::new("Cave".to_string(), 4, 2);
+        level.add_layer(Layer::new_tile_layer(
+            "Ground".to_string(),
+            tileset.id,
+            4,
+            2,
+        ));
+        for x in 0..4 {
+            level.set_tile(0, x, 0, Some(0));
+        }
+        level.add_entity(EntityInstance::new("Chest".to_string(), [8.0, 8.0]));
+        level.add_entity(EntityInstance::new("Door".to_string(), [24.0, 8.0]));
+        MapProject::new(level, vec![tileset])
+    }
+
+    fn spawn_map(app: &mut App, handle: &Handle<MapProject>) -> Entity {
+        let world = app.world_mut();
+        let project = world
+            .resource::<Assets<MapProject>>()
+            .get(handle)
+            .unwrap()
+            .clone();
+        let mut textures = TilesetTextures::new();
+        textures.insert(
+            *project.tilesets.keys().next().unwrap(),
+            0,
+            Handle::default(),
+        );
+
+        world.resource_scope(|world, registry: Mut<EntityRegistry>| {
+            let mut commands = world.commands();
+            let map = spawn_map_project(
+                &mut commands,
+                &project,
+                &textures,
+                Transform::default(),
+                Some(&registry),
+            );
+            commands
+                .entity(map)
+                .insert(MapRoot::new(handle.clone(), textures));
+            world.flush();
+            map
+        })
+    }
+
+    fn map_project(app: &App, map: Entity) -> &MapProject {
+        let root = app.world().get::<MapRoot>(map).unwrap();
+        app.world()
+            .resource::<Assets<MapProject>>()
+            .get(&root.project)
+            .unwrap()
+    }
+
+    fn has_tile(app: &mut App, map: Entity, x: u32, y: u32) -> bool {
+        let tilemaps: Vec<Entity> = app.world().get::<Children>(map).unwrap().to_vec();
+        tilemaps.into_iter().any(|tilemap| {
+            app.world()
+                .get::<TileStorage>(tilemap)
+                .is_some_and(|storage| storage.get(&TilePos { x, y }).is_some())
+        })
+    }
+
+    fn instance_ids(app: &mut App) -> HashSet<Uuid> {
+        let mut query = app.world_mut().query::<&MapEntityMarker>();
+        query
+            .iter(app.world())
+            .map(|marker| marker.instance_id)
+            .collect()
+    }
+
+    fn delta_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<MapProject>()
+            .init_resource::<EntityRegistry>()
+            .add_message::<TileChangedEvent>()
+            .add_plugins(MapDeltaPlugin);
+        app
+    }
+
+    #[test]
+    fn test_record_save_and_reapply() {
+        let mut app = delta_app();
+
+        let project = base_project();
+        let chest = project.level.entities[0].id;
+        let door = project.level.entities[1].id;
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<MapProject>>()
+            .add(project.clone());
+        let map = spawn_map(&mut app, &handle);
+        app.update();
+
+        // Dig a tunnel, open the chest and break the door
+        apply_tile_edit(
+            app.world_mut(),
+            map,
+            "Ground",
+            &TileEdit::Fill {
+                min: UVec2::new(1, 0),
+                max: UVec2::new(2, 0),
+                tile: None,
+            },
+        )
+        .unwrap();
+        app.update();
+        let mut deltas = app.world_mut().resource_mut::<MapDeltas>();
+        assert!(deltas.record_property(chest, "opened", Value::Bool(true)));
+        assert!(deltas.record_despawn(door));
+        assert!(!deltas.record_despawn(Uuid::new_v4()));
+        let json = deltas.to_json().unwrap();
+
+        // Load the save into a fresh session whose base map was edited meanwhile
+        app.world_mut().despawn(map);
+        let mut query = app
+            .world_mut()
+            .query_filtered::<Entity, With<MapEntityMarker>>();
+        let entities: Vec<Entity> = query.iter(app.world()).collect();
+        for entity in entities {
+            app.world_mut().despawn(entity);
+        }
+        let mut loaded = MapDeltas::default();
+        loaded.load_json(&json).unwrap();
+        app.world_mut().insert_resource(loaded);
+
+        let mut edited = project;
+        edited.level.set_tile(0, 2, 0, Some(3));
+        app.world_mut()
+            .resource_mut::<Assets<MapProject>>()
+            .insert(handle.id(), edited)
+            .unwrap();
+        let map = spawn_map(&mut app, &handle);
+        app.update();
+
+        let level = &map_project(&app, map).level;
+        assert_eq!(level.get_tile(0, 1, 0), None);
+        assert_eq!(level.get_tile(0, 2, 0), None);
+        assert_eq!(level.get_tile(0, 3, 0), Some(0));
+        assert!(level.get_entity(door).is_none());
+        assert!(!has_tile(&mut app, map, 1, 0));
+        assert!(has_tile(&mut app, map, 3, 0));
+
+        let ids = instance_ids(&mut app);
+        assert!(ids.contains(&chest) && !ids.contains(&door));
+        let mut query = app
+            .world_mut()
+            .query::<(&MapEntityMarker, &crate::EntityProperties)>();
+        let (_, properties) = query
+            .iter(app.world())
+            .find(|(marker, _)| marker.instance_id == chest)
+            .unwrap();
+        assert_eq!(properties.get_bool("opened"), Some(true));
+
+        let conflicts: Vec<_> = app
+            .world_mut()
+            .resource_mut::<Messages<MapDeltaConflictEvent>>()
+            .drain()
+            .collect();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            conflicts[0].conflicts,
+            vec![DeltaConflict::TileChanged {
+                layer: "Ground".to_string(),
+                x: 2,
+                y: 0,
+                expected: Some(0),
+                found: Some(3),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_spawning_same_handle_twice() {
+        let mut app = delta_app();
+        let project = base_project();
+        let door = project.level.entities[1].id;
+        let level_id = project.level.id;
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<MapProject>>()
+            .add(project);
+
+        let mut deltas = app.world_mut().resource_mut::<MapDeltas>();
+        let delta = deltas.level_mut(level_id);
+        delta.record_tile("Ground", 1, 0, Some(0), None);
+        delta.record_despawn(door);
+
+        let first = spawn_map(&mut app, &handle);
+        app.update();
+        let second = spawn_map(&mut app, &handle);
+        app.update();
+
+        for map in [first, second] {
+            let level = &map_project(&app, map).level;
+            assert_eq!(level.get_tile(0, 1, 0), None);
+            assert_eq!(level.get_tile(0, 2, 0), Some(0));
+            assert!(level.get_entity(door).is_none());
+            assert!(!has_tile(&mut app, map, 1, 0));
+
+            let root = app.world().get::<MapRoot>(map).unwrap();
+            assert_eq!(root.handle, handle);
+            assert_ne!(root.project, handle);
+        }
+        let base = &app
+            .world()
+            .resource::<Assets<MapProject>>()
+            .get(&handle)
+            .unwrap()
+            .level;
+        assert_eq!(base.get_tile(0, 1, 0), Some(0));
+        assert!(base.get_entity(door).is_some());
+
+        let conflicts = app
+            .world_mut()
+            .resource_mut::<Messages<MapDeltaConflictEvent>>()
+            .drain()
+            .count();
+        assert_eq!(conflicts, 0);
+    }
+}
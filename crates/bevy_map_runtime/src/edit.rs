@@ -0,0 +1,732 @@
+//! High-level runtime map editing
+//!
+//! [`MapEdit`] changes the tiles of spawned maps by layer name and keeps
+//! everything derived from them in sync: the `MapProject` asset (including
+//! multi-cell occupancy), the rendered tiles, animated tiles, tile colliders
+//! and navigation grids. Every changed cell is reported as a
+//! [`TileChangedEvent`].
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use bevy_map_runtime::edit::{MapEdit, TileChangedEvent};
+//!
+//! fn dig(mut edit: MapEdit, map: Single<Entity, With<MapRoot>>) {
+//!     edit.clear_tile(*map, "Ground", UVec2::new(4, 2));
+//! }
+//!
+//! fn build(mut edit: MapEdit, map: Single<Entity, With<MapRoot>>) {
+//!     edit.fill_tiles(*map, "Walls", UVec2::new(0, 0), UVec2::new(3, 0), Some(12));
+//!     edit.paint_terrain(*map, "Ground", "Grass", "Dirt", UVec2::new(6, 3));
+//! }
+//!
+//! fn on_tile_changed(mut changed: MessageReader<TileChangedEvent>) {
+//!     for event in changed.read() {
+//!         // Drop loot, play a sound...
+//!     }
+//! }
+//! ```
+//!
+//! Edits are applied when commands are flushed. Positions match `TilePos`
+//! (`y = 0` is the bottom row). The first edit gives the map its own copy of
+//! the `MapProject` ([`MapRoot::project`]), so other maps spawned from the
+//! same asset are unaffected and no hot reload is triggered.
+
+use crate::navigation::MapNavigation;
+use crate::{
+    AnimatedMapTile, CollectionTileSprite, MapLayerIndex, MapRoot, TilemapImageIndex,
+    TilesetTextures,
+};
+use bevy::ecs::message::Message;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use bevy_map_autotile::{paint_terrain, AutotileConfig};
+use bevy_map_core::{
+    LayerData, Level, MapProject, TileChange, TileChangeSet, Tileset, OCCUPIED_CELL,
+};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Emitted for every cell changed through [`MapEdit`]
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub struct TileChangedEvent {
+    /// The [`MapRoot`] entity of the edited map
+    pub map: Entity,
+    /// Index of the edited layer
+    pub layer: usize,
+    /// Cell position
+    pub position: UVec2,
+    /// Previous tile (`OCCUPIED_CELL` for cells covered by a multi-cell tile)
+    pub old: Option<u32>,
+    /// New tile
+    pub new: Option<u32>,
+}
+
+/// Errors that can occur when editing a map
+#[derive(Debug, Error)]
+pub enum MapEditError {
+    #[error("Entity {0} is not a spawned map")]
+    MapNotFound(Entity),
+    #[error("Map asset is not loaded")]
+    MapNotLoaded,
+    #[error("Layer '{0}' not found")]
+    LayerNotFound(String),
+    #[error("Layer '{0}' is not a tile layer")]
+    NotTileLayer(String),
+    #[error("Tileset {0} not found")]
+    TilesetNotFound(Uuid),
+    #[error("Position ({0}, {1}) is outside the map")]
+    OutOfBounds(u32, u32),
+    #[error("Map has no autotile configuration")]
+    NoAutotileConfig,
+    #[error("Terrain set '{0}' not found for the layer's tileset")]
+    TerrainSetNotFound(String),
+    #[error("Terrain '{0}' not found")]
+    TerrainNotFound(String),
+}
+
+/// A change to one tile layer
+#[derive(Debug, Clone, PartialEq)]
+pub enum TileEdit {
+    /// Place a tile with its base cell at `position`
+    ///
+    /// Multi-cell tiles cover cells to the right and above; anything they
+    /// overlap is erased.
+    Set { position: UVec2, tile: u32 },
+    /// Erase the tile covering `position` (all cells of a multi-cell tile)
+    Clear { position: UVec2 },
+    /// Fill a rectangle (inclusive, clamped to the map) with a tile, or clear it
+    Fill {
+        min: UVec2,
+        max: UVec2,
+        tile: Option<u32>,
+    },
+    /// Paint a terrain at a tile corner and recompute the surrounding tiles
+    ///
+    /// Corner `(x, y)` is shared by tiles `x - 1..=x` and `y - 1..=y`.
+    PaintTerrain {
+        terrain_set: String,
+        terrain: String,
+        corner: UVec2,
+    },
+}
+
+/// System parameter for editing spawned maps
+///
+/// `map` may be the [`MapRoot`] entity or the entity holding the map's
+/// `MapHandle` (or `WorldLevel`). Failed edits are logged as warnings.
+#[derive(SystemParam)]
+pub struct MapEdit<'w, 's> {
+    commands: Commands<'w, 's>,
+}
+
+impl MapEdit<'_, '_> {
+    /// Place a tile with its base cell at `position`
+    pub fn set_tile(&mut self, map: Entity, layer: impl Into<String>, position: UVec2, tile: u32) {
+        self.apply(map, layer, TileEdit::Set { position, tile });
+    }
+
+    /// Erase the tile covering `position`
+    pub fn clear_tile(&mut self, map: Entity, layer: impl Into<String>, position: UVec2) {
+        self.apply(map, layer, TileEdit::Clear { position });
+    }
+
+    /// Fill the rectangle `min..=max` with a tile, or clear it with `None`
+    pub fn fill_tiles(
+        &mut self,
+        map: Entity,
+        layer: impl Into<String>,
+        min: UVec2,
+        max: UVec2,
+        tile: Option<u32>,
+    ) {
+        self.apply(map, layer, TileEdit::Fill { min, max, tile });
+    }
+
+    /// Paint a terrain of a terrain set at a tile corner
+    pub fn paint_terrain(
+        &mut self,
+        map: Entity,
+        layer: impl Into<String>,
+        terrain_set: impl Into<String>,
+        terrain: impl Into<String>,
+        corner: UVec2,
+    ) {
+        self.apply(
+            map,
+            layer,
+            TileEdit::PaintTerrain {
+                terrain_set: terrain_set.into(),
+                terrain: terrain.into(),
+                corner,
+            },
+        );
+    }
+
+    /// Queue an edit of a layer
+    pub fn apply(&mut self, map: Entity, layer: impl Into<String>, edit: TileEdit) {
+        let layer = layer.into();
+        self.commands.queue(move |world: &mut World| {
+            if let Err(err) = apply_tile_edit(world, map, &layer, &edit) {
+                warn!("Failed to edit layer '{}': {}", layer, err);
+            }
+        });
+    }
+}
+
+/// Apply an edit immediately and return the changed cells
+pub fn apply_tile_edit(
+    world: &mut World,
+    map: Entity,
+    layer: &str,
+    edit: &TileEdit,
+) -> Result<Vec<TileChange>, MapEditError> {
+    let map_root = find_map_root(world, map).ok_or(MapEditError::MapNotFound(map))?;
+    let textures = world
+        .get::<MapRoot>(map_root)
+        .ok_or(MapEditError::MapNotFound(map))?
+        .textures
+        .clone();
+    // The map gets its own copy, so the edit doesn't reach other maps spawned
+    // from the same asset
+    let handle = crate::own_map_project(world, map_root).ok_or(MapEditError::MapNotLoaded)?;
+
+    world.resource_scope(|world, mut projects: Mut<Assets<MapProject>>| {
+        // Untracked, so the change doesn't trigger a hot reload of the map
+        let project = projects
+            .get_mut_untracked(&handle)
+            .ok_or(MapEditError::MapNotLoaded)?;
+        let (layer_index, tileset_id) = find_tile_layer(&project.level, layer)?;
+        let tileset = project
+            .tilesets
+            .get(&tileset_id)
+            .ok_or(MapEditError::TilesetNotFound(tileset_id))?;
+
+        let changes = match edit {
+            TileEdit::Set { position, tile } => {
+                let footprint = tileset.get_tile_grid_size(bevy_map_core::tile_index(*tile));
+                let level = &mut project.level;
+                let end = *position + UVec2::new(footprint.0, footprint.1) - UVec2::ONE;
+                if end.x >= level.width || end.y >= level.height {
+                    return Err(MapEditError::OutOfBounds(position.x, position.y));
+                }
+                level.place_tile(layer_index, position.x, position.y, *tile, footprint)
+            }
+            TileEdit::Clear { position } => {
+                let level = &mut project.level;
+                if position.x >= level.width || position.y >= level.height {
+                    return Err(MapEditError::OutOfBounds(position.x, position.y));
+                }
+                level.erase_tile(layer_index, position.x, position.y)
+            }
+            TileEdit::Fill { min, max, tile } => {
+                let footprint = tile
+                    .map(|tile| tileset.get_tile_grid_size(bevy_map_core::tile_index(tile)))
+                    .unwrap_or((1, 1));
+                fill_tiles(
+                    &mut project.level,
+                    layer_index,
+                    *min,
+                    *max,
+                    *tile,
+                    footprint,
+                )
+            }
+            TileEdit::PaintTerrain {
+                terrain_set,
+                terrain,
+                corner,
+            } => {
+                let config: AutotileConfig = project
+                    .autotile_config
+                    .clone()
+                    .and_then(|value| serde_json::from_value(value).ok())
+                    .ok_or(MapEditError::NoAutotileConfig)?;
+                let set = config
+                    .terrain_sets
+                    .iter()
+                    .find(|set| set.name == *terrain_set && set.tileset_id == tileset_id)
+                    .ok_or_else(|| MapEditError::TerrainSetNotFound(terrain_set.clone()))?;
+                let terrain_index = set
+                    .get_terrain_index(terrain)
+                    .ok_or_else(|| MapEditError::TerrainNotFound(terrain.clone()))?;
+                let level = &mut project.level;
+                if corner.x > level.width || corner.y > level.height {
+                    return Err(MapEditError::OutOfBounds(corner.x, corner.y));
+                }
+
+                let LayerData::Tiles { tiles, .. } = &level.layers[layer_index].data else {
+                    return Err(MapEditError::NotTileLayer(layer.to_string()));
+                };
+                let mut painted = tiles.clone();
+                paint_terrain(
+                    &mut painted,
+                    level.width,
+                    level.height,
+                    corner.x,
+                    corner.y,
+                    set,
+                    terrain_index,
+                );
+
+                let width = level.width;
+                let mut changes = TileChangeSet::new();
+                for (idx, tile) in painted.into_iter().enumerate() {
+                    let (x, y) = (idx as u32 % width, idx as u32 / width);
+                    if level.get_tile(layer_index, x, y) == tile {
+                        continue;
+                    }
+                    changes.extend(match tile {
+                        Some(tile) => level.place_tile(layer_index, x, y, tile, (1, 1)),
+                        None => level.erase_tile(layer_index, x, y),
+                    });
+                }
+                changes.into_changes()
+            }
+        };
+
+        let tileset = project
+            .tilesets
+            .get(&tileset_id)
+            .ok_or(MapEditError::TilesetNotFound(tileset_id))?;
+        let map_size = TilemapSize {
+            x: project.level.width,
+            y: project.level.height,
+        };
+        sync_map(
+            world,
+            map_root,
+            layer_index,
+            tileset,
+            &textures,
+            map_size,
+            &changes,
+        );
+        world.write_message_batch(changes.iter().map(|change| TileChangedEvent {
+            map: map_root,
+            layer: layer_index,
+            position: UVec2::new(change.x, change.y),
+            old: change.old,
+            new: change.new,
+        }));
+        Ok(changes)
+    })
+}
+
+/// The map root entity itself, or the map root spawned as its child
+fn find_map_root(world: &World, entity: Entity) -> Option<Entity> {
+    if world.get::<MapRoot>(entity).is_some() {
+        return Some(entity);
+    }
+    world
+        .get::<Children>(entity)?
+        .iter()
+        .find(|child| world.get::<MapRoot>(*child).is_some())
+}
+
+fn find_tile_layer(level: &Level, name: &str) -> Result<(usize, Uuid), MapEditError> {
+    let layer_index = level
+        .layer_index_by_name(name)
+        .ok_or_else(|| MapEditError::LayerNotFound(name.to_string()))?;
+    level.layers[layer_index]
+        .tileset_id()
+        .map(|tileset_id| (layer_index, tileset_id))
+        .ok_or_else(|| MapEditError::NotTileLayer(name.to_string()))
+}
+
+fn fill_tiles(
+    level: &mut Level,
+    layer_index: usize,
+    min: UVec2,
+    max: UVec2,
+    tile: Option<u32>,
+    footprint: (u32, u32),
+) -> Vec<TileChange> {
+    if level.width == 0 || level.height == 0 {
+        return Vec::new();
+    }
+    let max = max.min(UVec2::new(level.width - 1, level.height - 1));
+    let (step_x, step_y) = footprint;
+
+    let mut changes = TileChangeSet::new();
+    for y in (min.y..=max.y).step_by(step_y as usize) {
+        for x in (min.x..=max.x).step_by(step_x as usize) {
+            changes.extend(match tile {
+                // Multi-cell tiles are only placed where they fit entirely
+                Some(tile) if x + step_x <= level.width && y + step_y <= level.height => {
+                    level.place_tile(layer_index, x, y, tile, footprint)
+                }
+                Some(_) => continue,
+                None => level.erase_tile(layer_index, x, y),
+            });
+        }
+    }
+    changes.into_changes()
+}
+
+/// Update rendered tiles, navigation and colliders of a map after tile changes
+pub(crate) fn sync_map(
+    world: &mut World,
+    map_root: Entity,
+    layer_index: usize,
+    tileset: &Tileset,
+    textures: &TilesetTextures,
+    map_size: TilemapSize,
+    changes: &[TileChange],
+) {
+    // Tilemaps (by image) and collection sprites of the edited layer
+    let mut tilemaps: Vec<(usize, Entity)> = Vec::new();
+    let mut sprites: Vec<(TilePos, Entity)> = Vec::new();
+    if let Some(children) = world.get::<Children>(map_root) {
+        for child in children.iter() {
+            if world.get::<MapLayerIndex>(child).map(|index| index.0) != Some(layer_index) {
+                continue;
+            }
+            if let Some(image) = world.get::<TilemapImageIndex>(child) {
+                tilemaps.push((image.0, child));
+            } else if let Some(sprite) = world.get::<CollectionTileSprite>(child) {
+                sprites.push((sprite.tile_pos, child));
+            }
+        }
+    }
+
+    for change in changes {
+        let tile_pos = TilePos {
+            x: change.x,
+            y: change.y,
+        };
+
+        // Remove whatever is currently drawn in the cell
+        for (_, tilemap) in &tilemaps {
+            let Some(mut storage) = world.get_mut::<TileStorage>(*tilemap) else {
+                continue;
+            };
+            if let Some(tile_entity) = storage.get(&tile_pos) {
+                storage.remove(&tile_pos);
+                world.despawn(tile_entity);
+            }
+        }
+        sprites.retain(|(pos, sprite)| {
+            if *pos == tile_pos {
+                world.despawn(*sprite);
+                false
+            } else {
+                true
+            }
+        });
+
+        let Some(tile) = change.new.filter(|tile| *tile != OCCUPIED_CELL) else {
+            continue;
+        };
+        let Some((image_index, local_tile_index)) = tileset.virtual_to_local(tile) else {
+            continue;
+        };
+        let Some(texture) = textures.get(tileset.id, image_index) else {
+            warn!(
+                "Missing texture for tileset {} image {}",
+                tileset.id, image_index
+            );
+            continue;
+        };
+
+        if tileset.is_collection() {
+            let sprite = world
+                .spawn(crate::collection_tile_sprite_bundle(
+                    tileset,
+                    texture.clone(),
+                    layer_index,
+                    tile_pos,
+                    tile,
+                ))
+                .id();
+            world.entity_mut(map_root).add_child(sprite);
+            sprites.push((tile_pos, sprite));
+            continue;
+        }
+
+        // Images that weren't used when the map was spawned get a new tilemap
+        let tilemap = match tilemaps.iter().find(|(image, _)| *image == image_index) {
+            Some((_, tilemap)) => *tilemap,
+            None => {
+                let tilemap = world
+                    .spawn(crate::layer_tilemap_bundle(
+                        tileset,
+                        texture.clone(),
+                        layer_index,
+                        image_index,
+                        map_size,
+                        TileStorage::empty(map_size),
+                    ))
+                    .id();
+                world.entity_mut(map_root).add_child(tilemap);
+                tilemaps.push((image_index, tilemap));
+                tilemap
+            }
+        };
+
+        let mut tile_entity = world.spawn(TileBundle {
+            position: tile_pos,
+            tilemap_id: TilemapId(tilemap),
+            texture_index: TileTextureIndex(local_tile_index),
+            ..default()
+        });
+        if let Some(animation) = AnimatedMapTile::from_tileset(tileset, tile, image_index) {
+            tile_entity.insert(animation);
+        }
+        let tile_entity = tile_entity.id();
+        if let Some(mut storage) = world.get_mut::<TileStorage>(tilemap) {
+            storage.set(&tile_pos, tile_entity);
+        }
+    }
+
+    // Cells covered by a multi-cell tile don't block on their own
+    let cell_tile = |change: &TileChange| change.new.filter(|tile| *tile != OCCUPIED_CELL);
+
+    if let Some(mut navigation) = world.get_mut::<MapNavigation>(map_root) {
+        for change in changes {
+            navigation.set_tile(layer_index, change.x, change.y, tileset, cell_tile(change));
+        }
+    }
+
+    #[cfg(feature = "physics")]
+    if let Some(mut colliders) = world.get_mut::<crate::collision::TileColliders>(map_root) {
+        for change in changes {
+            colliders.set_tile(layer_index, change.x, change.y, tileset, cell_tile(change));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::asset::AssetEvent;
+    use bevy::ecs::message::Messages;
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy_map_core::{CollisionData, DiagonalMode, Layer, TileProperties};
+
+    fn test_project() -> MapProject {
+        let mut tileset = Tileset::new("Tiles".to_string(), "tiles.png".to_string(), 16, 4, 1);
+        tileset.set_tile_collision(0, CollisionData::full());
+        tileset.set_tile_properties(1, TileProperties::default().with_grid_size(2, 2));
+        let mut level = Level::new("Test".to_string(), 6, 4);
+        level.add_layer(Layer::new_tile_layer(
+            "Ground".to_string(),
+            tileset.id,
+            6,
+            4,
+        ));
+        level.add_layer(Layer::new_object_layer("Objects".to_string()));
+        level.set_tile(0, 0, 0, Some(2));
+        MapProject::new(level, vec![tileset])
+    }
+
+    fn edit_app() -> (App, Handle<MapProject>) {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<MapProject>()
+            .add_message::<TileChangedEvent>();
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<MapProject>>()
+            .add(test_project());
+        (app, handle)
+    }
+
+    fn spawn_test_map(app: &mut App, handle: &Handle<MapProject>) -> Entity {
+        let world = app.world_mut();
+        let project = world
+            .resource::<Assets<MapProject>>()
+            .get(handle)
+            .unwrap()
+            .clone();
+        let tileset_id = *project.tilesets.keys().next().unwrap();
+        let mut textures = TilesetTextures::new();
+        textures.insert(tileset_id, 0, Handle::default());
+        let navigation = MapNavigation::from_project(&project, DiagonalMode::Never);
+
+        let mut commands = world.commands();
+        let map = crate::spawn_map_project(
+            &mut commands,
+            &project,
+            &textures,
+            Transform::default(),
+            None,
+        );
+        commands
+            .entity(map)
+            .insert((MapRoot::new(handle.clone(), textures), navigation));
+        #[cfg(feature = "physics")]
+        commands
+            .entity(map)
+            .insert(crate::collision::TileColliders::from_project(&project));
+        world.flush();
+        map
+    }
+
+    fn map_project(app: &App, map: Entity) -> &MapProject {
+        let root = app.world().get::<MapRoot>(map).unwrap();
+        app.world()
+            .resource::<Assets<MapProject>>()
+            .get(&root.project)
+            .unwrap()
+    }
+
+    fn rendered_tile(app: &App, map: Entity, x: u32, y: u32) -> Option<u32> {
+        let world = app.world();
+        world
+            .get::<Children>(map)
+            .unwrap()
+            .iter()
+            .filter_map(|tilemap| world.get::<TileStorage>(tilemap)?.get(&TilePos { x, y }))
+            .find_map(|tile| world.get::<TileTextureIndex>(tile))
+            .map(|index| index.0)
+    }
+
+    #[test]
+    fn test_edits_keep_map_in_sync() {
+        let (mut app, handle) = edit_app();
+        let map = spawn_test_map(&mut app, &handle);
+        app.update();
+        app.world_mut()
+            .resource_mut::<Messages<AssetEvent<MapProject>>>()
+            .clear();
+
+        app.world_mut()
+            .run_system_once(move |mut edit: MapEdit| {
+                edit.set_tile(map, "Ground", UVec2::new(1, 0), 0);
+                edit.fill_tiles(map, "Ground", UVec2::new(0, 3), UVec2::new(5, 3), Some(3));
+            })
+            .unwrap();
+
+        let project = map_project(&app, map);
+        assert_eq!(project.level.get_tile(0, 1, 0), Some(0));
+        assert_eq!(project.level.get_tile(0, 5, 3), Some(3));
+        assert_eq!(rendered_tile(&app, map, 1, 0), Some(0));
+        assert_eq!(rendered_tile(&app, map, 4, 3), Some(3));
+        let navigation = app.world().get::<MapNavigation>(map).unwrap();
+        assert!(navigation.grid().cost(1, 0).is_none());
+
+        let events: Vec<_> = app
+            .world_mut()
+            .resource_mut::<Messages<TileChangedEvent>>()
+            .drain()
+            .collect();
+        assert_eq!(events.len(), 7);
+        assert_eq!(events[0].position, UVec2::new(1, 0));
+        assert_eq!((events[0].old, events[0].new), (None, Some(0)));
+
+        // A multi-cell tile replaces what it overlaps and erases as a whole
+        let changes = apply_tile_edit(
+            app.world_mut(),
+            map,
+            "Ground",
+            &TileEdit::Set {
+                position: UVec2::new(0, 0),
+                tile: 1,
+            },
+        )
+        .unwrap();
+        assert_eq!(changes.len(), 4);
+        assert_eq!(rendered_tile(&app, map, 1, 0), None);
+        assert!(app
+            .world()
+            .get::<MapNavigation>(map)
+            .unwrap()
+            .grid()
+            .cost(1, 0)
+            .is_some());
+
+        let changes = apply_tile_edit(
+            app.world_mut(),
+            map,
+            "Ground",
+            &TileEdit::Clear {
+                position: UVec2::new(1, 1),
+            },
+        )
+        .unwrap();
+        assert_eq!(changes.len(), 4);
+        let project = map_project(&app, map);
+        assert!(matches!(
+            &project.level.layers[0].data,
+            LayerData::Tiles { occupied_cells, .. } if occupied_cells.is_empty()
+        ));
+        assert_eq!(project.level.get_tile(0, 0, 0), None);
+
+        assert!(matches!(
+            apply_tile_edit(
+                app.world_mut(),
+                map,
+                "Objects",
+                &TileEdit::Clear {
+                    position: UVec2::ZERO
+                }
+            ),
+            Err(MapEditError::NotTileLayer(_))
+        ));
+        assert!(matches!(
+            apply_tile_edit(
+                app.world_mut(),
+                map,
+                "Ground",
+                &TileEdit::Set {
+                    position: UVec2::new(5, 3),
+                    tile: 1
+                }
+            ),
+            Err(MapEditError::OutOfBounds(5, 3))
+        ));
+
+        // Editing never hot-reloads the map
+        app.update();
+        let reloaded = app
+            .world_mut()
+            .resource_mut::<Messages<AssetEvent<MapProject>>>()
+            .drain()
+            .any(|event| matches!(event, AssetEvent::Modified { .. }));
+        assert!(!reloaded);
+    }
+
+    #[test]
+    fn test_edit_leaves_maps_from_same_asset_unchanged() {
+        let (mut app, handle) = edit_app();
+        let edited = spawn_test_map(&mut app, &handle);
+        let other = spawn_test_map(&mut app, &handle);
+        app.update();
+
+        apply_tile_edit(
+            app.world_mut(),
+            edited,
+            "Ground",
+            &TileEdit::Set {
+                position: UVec2::new(1, 0),
+                tile: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(map_project(&app, edited).level.get_tile(0, 1, 0), Some(0));
+        assert_eq!(rendered_tile(&app, edited, 1, 0), Some(0));
+        let root = app.world().get::<MapRoot>(edited).unwrap();
+        assert_eq!(root.handle, handle);
+        assert_ne!(root.project, handle);
+
+        assert_eq!(map_project(&app, other).level.get_tile(0, 1, 0), None);
+        assert_eq!(rendered_tile(&app, other, 1, 0), None);
+        let navigation = app.world().get::<MapNavigation>(other).unwrap();
+        assert!(navigation.grid().cost(1, 0).is_some());
+        #[cfg(feature = "physics")]
+        {
+            let colliders = app
+                .world()
+                .get::<crate::collision::TileColliders>(other)
+                .unwrap();
+            assert!(colliders.get(0, 1, 0).is_none());
+            let colliders = app
+                .world()
+                .get::<crate::collision::TileColliders>(edited)
+                .unwrap();
+            assert!(colliders.get(0, 1, 0).is_some());
+        }
+    }
+}
@@ -157,7 +157,7 @@ fn spawn_entity_input(
     // Try to get the first available map project
     let project = map_root_query
         .iter()
-        .find_map(|root| map_assets.get(&root.handle));
+        .find_map(|root| map_assets.get(&root.project));
 
     let Some(project) = project else {
         return;
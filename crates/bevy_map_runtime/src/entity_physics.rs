@@ -31,6 +31,8 @@ use avian2d::prelude::*;
 use crate::entity_registry::MapEntityMarker;
 use crate::MapRoot;
 
+#[cfg(feature = "physics")]
+use crate::collision::polygon_collider;
 #[cfg(feature = "physics")]
 use bevy_map_core::{ColliderConfig, MapProject, PhysicsBodyType, PhysicsConfig};
 
@@ -93,7 +95,7 @@ fn spawn_entity_physics(
     // Try to get the first available map project
     let project = map_root_query
         .iter()
-        .find_map(|root| map_assets.get(&root.handle));
+        .find_map(|root| map_assets.get(&root.project));
 
     let Some(project) = project else {
         return;
@@ -147,6 +149,14 @@ fn spawn_physics_components(commands: &mut Commands, entity: Entity, config: &Ph
             Collider::capsule(shaft_height, radius)
         }
         ColliderConfig::Circle { radius } => Collider::circle(*radius),
+        ColliderConfig::Polygon { points } => {
+            let points: Vec<Vec2> = points.iter().copied().map(Vec2::from).collect();
+            let Some(collider) = polygon_collider(&points) else {
+                warn!("Skipping physics for entity with an invalid polygon collider");
+                return;
+            };
+            collider
+        }
     };
 
     // Insert physics components
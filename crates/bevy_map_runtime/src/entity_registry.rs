@@ -3,10 +3,14 @@
 //! This module provides a registry-based system for spawning game entities
 //! from EntityInstance data in map files.
 
+use bevy::asset::AssetEvent;
 use bevy::prelude::*;
-use bevy_map_core::{EntityInstance, Value};
-use std::collections::HashMap;
+use bevy_map_core::{EntityInstance, MapProject, Value, WorldProject};
+use bevy_map_dialogue::DialogueTree;
+use bevy_map_schema::{save_schema, Schema, SchemaError, SchemaMismatch, TypeDef};
+use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
+use std::path::Path;
 use uuid::Uuid;
 
 /// Trait implemented by entities that can be spawned from map data.
@@ -36,6 +40,22 @@ pub trait MapEntityType: Component + Sized + Send + Sync + 'static {
     /// Creates an instance of this component from map entity data
     fn from_instance(instance: &EntityInstance) -> Self;
 
+    /// Converts the current component state back into entity properties
+    ///
+    /// This is the reverse of `from_instance`, used to capture runtime state for
+    /// save games or snapshots. The derive macro writes every `#[map_prop]` field.
+    fn to_properties(&self) -> HashMap<String, Value> {
+        HashMap::new()
+    }
+
+    /// Describes this type for the editor schema
+    ///
+    /// The derive macro lists every `#[map_prop]` field with its type, default and
+    /// range. Enums and embedded types used by the fields are added to `schema`.
+    fn type_def(_schema: &mut Schema) -> TypeDef {
+        TypeDef::default()
+    }
+
     /// Returns the property names for sprite fields (for manual sprite handle injection)
     /// Override this if your entity has fields that should receive sprite handles.
     fn sprite_properties() -> &'static [&'static str] {
@@ -51,15 +71,76 @@ pub trait MapEntityType: Component + Sized + Send + Sync + 'static {
     ) {
         // Default: no-op
     }
+
+    /// Returns the property names for `Handle<DialogueTree>` fields
+    fn dialogue_properties() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Inject a dialogue handle into the component for the given property name.
+    ///
+    /// Called after spawning for each of `dialogue_properties` that references a
+    /// dialogue in the loaded map.
+    fn inject_dialogue_handle(&mut self, _property_name: &str, _handle: Handle<DialogueTree>) {
+        // Default: no-op
+    }
+
+    /// Returns the property names for asset `Handle<T>` fields
+    fn asset_properties() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Load the asset at `path` into the field for the given property name.
+    ///
+    /// Called after spawning for each of `asset_properties` with a non-empty path.
+    /// Returns the loaded handle so failed loads can be reported.
+    fn load_asset_handle(
+        &mut self,
+        _property_name: &str,
+        _path: &str,
+        _asset_server: &AssetServer,
+    ) -> Option<UntypedHandle> {
+        None
+    }
 }
 
 /// Marker component for entities spawned from map data
+///
+/// Use [`MapEntityMarker::new`] to create one outside of map spawning; struct
+/// literals must list every field, including `base_types`.
 #[derive(Component)]
 pub struct MapEntityMarker {
     /// The unique ID of the original EntityInstance
     pub instance_id: Uuid,
     /// The type name from the map editor
     pub type_name: String,
+    /// Types that `type_name` extends in the map schema, nearest first
+    pub base_types: Vec<String>,
+}
+
+impl MapEntityMarker {
+    /// Create a marker for an instance of a type without base types
+    pub fn new(instance_id: Uuid, type_name: impl Into<String>) -> Self {
+        Self {
+            instance_id,
+            type_name: type_name.into(),
+            base_types: Vec::new(),
+        }
+    }
+
+    /// Check if the entity's type is `type_name` or extends it
+    ///
+    /// ```rust,ignore
+    /// fn handle_enemies(query: Query<&MapEntityMarker>) {
+    ///     // Also matches "Boss" if it extends "Enemy"
+    ///     for marker in query.iter().filter(|m| m.is_a("Enemy")) {
+    ///         // ...
+    ///     }
+    /// }
+    /// ```
+    pub fn is_a(&self, type_name: &str) -> bool {
+        self.type_name == type_name || self.base_types.iter().any(|t| t == type_name)
+    }
 }
 
 /// Raw properties from the map editor, accessible by runtime systems
@@ -75,7 +156,7 @@ pub struct MapEntityMarker {
 ///
 /// fn handle_npcs(query: Query<(&MapEntityMarker, &EntityProperties)>) {
 ///     for (marker, props) in &query {
-///         if marker.type_name == "NPC" {
+///         if marker.is_a("NPC") {
 ///             let name = props.get_string("name").unwrap_or("Unknown");
 ///             let health = props.get_int("health").unwrap_or(100);
 ///
@@ -187,7 +268,17 @@ pub struct Dialogue {
 
 /// Trait object for spawning entities
 trait EntitySpawner: Send + Sync {
-    fn spawn(&self, commands: &mut Commands, instance: &EntityInstance, transform: Transform);
+    fn spawn(
+        &self,
+        commands: &mut Commands,
+        instance: &EntityInstance,
+        transform: Transform,
+        marker: MapEntityMarker,
+    );
+
+    fn to_properties(&self, world: &World, entity: Entity) -> Option<HashMap<String, Value>>;
+
+    fn type_def(&self, schema: &mut Schema) -> TypeDef;
 }
 
 /// Generic spawner implementation for any MapEntityType
@@ -196,7 +287,13 @@ struct TypedSpawner<T: MapEntityType> {
 }
 
 impl<T: MapEntityType> EntitySpawner for TypedSpawner<T> {
-    fn spawn(&self, commands: &mut Commands, instance: &EntityInstance, transform: Transform) {
+    fn spawn(
+        &self,
+        commands: &mut Commands,
+        instance: &EntityInstance,
+        transform: Transform,
+        marker: MapEntityMarker,
+    ) {
         let component = T::from_instance(instance);
 
         // Parse entity color from instance if available, otherwise use a default
@@ -208,28 +305,188 @@ impl<T: MapEntityType> EntitySpawner for TypedSpawner<T> {
         // Get marker size from instance or use default
         let marker_size = instance.get_float("_editor_marker_size").unwrap_or(16.0) as f32;
 
-        commands.spawn((
-            component,
-            transform,
-            // Required for visibility
-            Visibility::default(),
-            // Placeholder visual - colored rectangle
-            Sprite {
-                color,
-                custom_size: Some(Vec2::splat(marker_size)),
-                ..default()
-            },
-            MapEntityMarker {
-                instance_id: instance.id,
-                type_name: instance.type_name.clone(),
-            },
-            EntityProperties {
-                properties: instance.properties.clone(),
-            },
-        ));
+        let entity = commands
+            .spawn((
+                component,
+                transform,
+                // Required for visibility
+                Visibility::default(),
+                // Placeholder visual - colored rectangle
+                Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(marker_size)),
+                    ..default()
+                },
+                marker,
+                EntityProperties {
+                    properties: instance.properties.clone(),
+                },
+            ))
+            .id();
+
+        // Dialogue handles need the loaded dialogues, so resolve them once commands apply
+        let dialogues: Vec<(&'static str, String)> = T::dialogue_properties()
+            .iter()
+            .filter_map(|property| {
+                let id = instance.properties.get(*property).and_then(dialogue_id)?;
+                Some((*property, id.to_string()))
+            })
+            .collect();
+        if !dialogues.is_empty() {
+            let instance_id = instance.id;
+            commands.queue(move |world: &mut World| {
+                inject_dialogues::<T>(world, entity, instance_id, dialogues);
+            });
+        }
+
+        // Asset handles need the AssetServer, so load them once commands apply
+        let assets: Vec<(&'static str, String)> = T::asset_properties()
+            .iter()
+            .filter_map(|property| {
+                let path = instance.get_string(property)?;
+                (!path.is_empty()).then(|| (*property, path.to_string()))
+            })
+            .collect();
+        if !assets.is_empty() {
+            commands.queue(move |world: &mut World| {
+                load_assets::<T>(world, entity, assets);
+            });
+        }
+    }
+
+    fn to_properties(&self, world: &World, entity: Entity) -> Option<HashMap<String, Value>> {
+        world.get::<T>(entity).map(T::to_properties)
+    }
+
+    fn type_def(&self, schema: &mut Schema) -> TypeDef {
+        T::type_def(schema)
+    }
+}
+
+/// Look up dialogue trees by ID and inject their handles into the component
+fn inject_dialogues<T: MapEntityType>(
+    world: &mut World,
+    entity: Entity,
+    instance_id: Uuid,
+    dialogues: Vec<(&'static str, String)>,
+) {
+    for (property, id) in dialogues {
+        let tree = world
+            .get_resource::<crate::MapDialogues>()
+            .and_then(|map_dialogues| map_dialogues.get(&id))
+            .cloned();
+        let Some(tree) = tree else {
+            warn!(
+                "Entity {}: property '{}' references unknown dialogue '{}'",
+                instance_id, property, id
+            );
+            continue;
+        };
+        let Some(mut assets) = world.get_resource_mut::<Assets<DialogueTree>>() else {
+            continue;
+        };
+        let handle = assets.add(tree);
+        let _ = world.modify_component::<T, _>(entity, |component| {
+            component.inject_dialogue_handle(property, handle);
+        });
+    }
+}
+
+/// Asset property handles that are still loading, checked by [`report_failed_map_assets`]
+#[derive(Component)]
+pub struct PendingMapAssets {
+    /// (property name, path, handle)
+    pub assets: Vec<(String, String, UntypedHandle)>,
+}
+
+/// Load asset properties into the component and track them until they finish loading
+fn load_assets<T: MapEntityType>(
+    world: &mut World,
+    entity: Entity,
+    assets: Vec<(&'static str, String)>,
+) {
+    let Some(asset_server) = world.get_resource::<AssetServer>().cloned() else {
+        return;
+    };
+    let mut pending = Vec::new();
+    let _ = world.modify_component::<T, _>(entity, |component| {
+        for (property, path) in assets {
+            if let Some(handle) = component.load_asset_handle(property, &path, &asset_server) {
+                pending.push((property.to_string(), path, handle));
+            }
+        }
+    });
+    if !pending.is_empty() {
+        if let Ok(mut entity) = world.get_entity_mut(entity) {
+            entity.insert(PendingMapAssets { assets: pending });
+        }
+    }
+}
+
+/// Emitted when an asset property of a spawned entity could not be loaded
+#[derive(Message, Debug, Clone)]
+pub struct MapAssetFailedEvent {
+    /// The entity the property belongs to
+    pub entity: Entity,
+    /// ID of the entity instance in the map
+    pub instance_id: Uuid,
+    /// Name of the asset property
+    pub property: String,
+    /// Asset path that failed to load
+    pub path: String,
+    /// Why loading failed
+    pub error: String,
+}
+
+/// Warn about asset properties whose files could not be loaded
+///
+/// Runs until every asset property of a spawned entity has finished loading.
+/// Each failure is also emitted as a [`MapAssetFailedEvent`].
+pub fn report_failed_map_assets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut query: Query<(Entity, &MapEntityMarker, &mut PendingMapAssets)>,
+    mut failed: MessageWriter<MapAssetFailedEvent>,
+) {
+    for (entity, marker, mut pending) in query.iter_mut() {
+        pending.assets.retain(|(property, path, handle)| {
+            match asset_server.load_state(handle.id()) {
+                bevy::asset::LoadState::Failed(error) => {
+                    warn!(
+                        "Entity {} ({}): asset property '{}' could not load '{}': {}",
+                        marker.instance_id, marker.type_name, property, path, error
+                    );
+                    failed.write(MapAssetFailedEvent {
+                        entity,
+                        instance_id: marker.instance_id,
+                        property: property.clone(),
+                        path: path.clone(),
+                        error: error.to_string(),
+                    });
+                    false
+                }
+                bevy::asset::LoadState::Loaded => false,
+                _ => true,
+            }
+        });
+        if pending.assets.is_empty() {
+            commands.entity(entity).remove::<PendingMapAssets>();
+        }
     }
 }
 
+/// Extract a dialogue ID from a dialogue property value
+///
+/// Values are either the ID itself or an object with an "id" field.
+fn dialogue_id(value: &Value) -> Option<&str> {
+    match value {
+        Value::String(id) => Some(id.as_str()),
+        Value::Object(obj) => obj.get("id").and_then(|v| v.as_string()),
+        _ => None,
+    }
+    .filter(|id| !id.is_empty())
+}
+
 /// Parse a hex color string like "#ff0000" or "#ff000080" (with alpha)
 fn parse_hex_color(hex: &str) -> Option<Color> {
     let hex = hex.trim_start_matches('#');
@@ -306,13 +563,44 @@ impl EntityRegistry {
         commands: &mut Commands,
         instance: &EntityInstance,
         base_transform: Transform,
+    ) -> bool {
+        self.spawn_with_schema(commands, instance, base_transform, None)
+    }
+
+    /// Spawn an entity, resolving type inheritance from the map's schema
+    ///
+    /// If the instance's type is not registered, the nearest registered type it
+    /// `extends` is spawned instead, so a "Boss" that extends "Enemy" gets the
+    /// `Enemy` component. The marker keeps the original type name and records
+    /// the base types for [`MapEntityMarker::is_a`].
+    pub fn spawn_with_schema(
+        &self,
+        commands: &mut Commands,
+        instance: &EntityInstance,
+        base_transform: Transform,
+        schema: Option<&Schema>,
     ) -> bool {
         // Create transform from instance position + base transform
         let entity_transform =
             base_transform * Transform::from_xyz(instance.position[0], instance.position[1], 0.0);
 
-        if let Some(spawner) = self.spawners.get(&instance.type_name) {
-            spawner.spawn(commands, instance, entity_transform);
+        let base_types: Vec<String> = schema
+            .map(|schema| {
+                schema
+                    .base_types(&instance.type_name)
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let marker = MapEntityMarker {
+            instance_id: instance.id,
+            type_name: instance.type_name.clone(),
+            base_types,
+        };
+
+        if let Some(spawner) = self.spawner_for(&marker) {
+            spawner.spawn(commands, instance, entity_transform, marker);
             true
         } else {
             // Spawn unregistered entities with a placeholder visual (red = unregistered)
@@ -324,10 +612,7 @@ impl EntityRegistry {
                     custom_size: Some(Vec2::splat(16.0)),
                     ..default()
                 },
-                MapEntityMarker {
-                    instance_id: instance.id,
-                    type_name: instance.type_name.clone(),
-                },
+                marker,
                 EntityProperties {
                     properties: instance.properties.clone(),
                 },
@@ -346,10 +631,23 @@ impl EntityRegistry {
         commands: &mut Commands,
         instances: &[EntityInstance],
         base_transform: Transform,
+    ) -> usize {
+        self.spawn_all_with_schema(commands, instances, base_transform, None)
+    }
+
+    /// Spawn all entities, resolving type inheritance from the map's schema
+    ///
+    /// See [`EntityRegistry::spawn_with_schema`].
+    pub fn spawn_all_with_schema(
+        &self,
+        commands: &mut Commands,
+        instances: &[EntityInstance],
+        base_transform: Transform,
+        schema: Option<&Schema>,
     ) -> usize {
         let mut unregistered = 0;
         for instance in instances {
-            if !self.spawn(commands, instance, base_transform) {
+            if !self.spawn_with_schema(commands, instance, base_transform, schema) {
                 warn!(
                     "Entity type '{}' not registered - spawned with red placeholder (use .register_map_entity::<YourType>() to register)",
                     instance.type_name
@@ -359,6 +657,90 @@ impl EntityRegistry {
         }
         unregistered
     }
+
+    /// Convert a live map entity's component state back into properties
+    ///
+    /// Returns `None` if the entity has no `MapEntityMarker`, its type is not
+    /// registered, or it is missing the registered component.
+    pub fn to_properties(&self, world: &World, entity: Entity) -> Option<HashMap<String, Value>> {
+        let marker = world.get::<MapEntityMarker>(entity)?;
+        self.spawner_for(marker)?.to_properties(world, entity)
+    }
+
+    /// The spawner for a marker's type, or for the nearest registered base type
+    fn spawner_for(&self, marker: &MapEntityMarker) -> Option<&dyn EntitySpawner> {
+        std::iter::once(&marker.type_name)
+            .chain(&marker.base_types)
+            .find_map(|type_name| self.spawners.get(type_name))
+            .map(|spawner| spawner.as_ref())
+    }
+
+    /// Re-serialize all live map entities of registered types
+    ///
+    /// Each instance keeps its original ID and type name. Properties start from the
+    /// entity's `EntityProperties` and are overwritten by the component's
+    /// `to_properties`, so editor-only values survive the round trip. Positions are
+    /// taken from the entity's `Transform` translation.
+    pub fn capture_instances(&self, world: &World) -> Vec<EntityInstance> {
+        let Some(mut query) = world.try_query::<(
+            Entity,
+            &MapEntityMarker,
+            Option<&EntityProperties>,
+            Option<&Transform>,
+        )>() else {
+            return Vec::new();
+        };
+
+        let mut instances: Vec<EntityInstance> = query
+            .iter(world)
+            .filter_map(|(entity, marker, properties, transform)| {
+                let spawner = self.spawner_for(marker)?;
+                let mut merged = properties.map(|p| p.properties.clone()).unwrap_or_default();
+                merged.extend(spawner.to_properties(world, entity)?);
+
+                let position = transform
+                    .map(|t| t.translation.truncate())
+                    .unwrap_or_default();
+                Some(EntityInstance {
+                    id: marker.instance_id,
+                    type_name: marker.type_name.clone(),
+                    position: position.into(),
+                    template_id: None,
+                    properties: merged,
+                })
+            })
+            .collect();
+        instances.sort_by_key(|instance| instance.id);
+        instances
+    }
+
+    /// Build a schema describing every registered entity type
+    pub fn schema(&self) -> Schema {
+        let mut schema = Schema {
+            version: 1,
+            ..default()
+        };
+        let mut type_names: Vec<&String> = self.spawners.keys().collect();
+        type_names.sort();
+        for type_name in type_names {
+            let type_def = self.spawners[type_name].type_def(&mut schema);
+            schema.data_types.insert(type_name.clone(), type_def);
+        }
+        schema
+    }
+
+    /// Write the schema of all registered entity types to a JSON file
+    ///
+    /// Import the file in the editor with Project > Import Schema to merge it into
+    /// the project's schema.
+    pub fn export_schema(&self, path: impl AsRef<Path>) -> Result<(), SchemaError> {
+        save_schema(&self.schema(), path.as_ref())
+    }
+
+    /// Find where a map's schema disagrees with the registered entity types
+    pub fn schema_mismatches(&self, map_schema: &Schema) -> Vec<SchemaMismatch> {
+        self.schema().mismatches(map_schema)
+    }
 }
 
 /// Extension trait for registering map entities with the Bevy App
@@ -399,6 +781,60 @@ impl MapEntityExt for App {
     }
 }
 
+/// Read the schema embedded in an exported map, if it has a valid one
+pub(crate) fn embedded_schema(schema: Option<&serde_json::Value>) -> Option<Schema> {
+    serde_json::from_value(schema?.clone()).ok()
+}
+
+/// System that warns when a loaded map's schema disagrees with the registered entity types
+///
+/// Runs when a map or world is loaded or hot-reloaded, so that renamed properties or
+/// changed types show up at startup instead of silently falling back to defaults.
+/// Each distinct schema is checked once: maps sharing a schema (such as the levels
+/// of a world, or per-map copies made by edits and save deltas) warn only once, and
+/// a hot reload only warns again if it changed the schema.
+pub fn check_map_schemas(
+    registry: Res<EntityRegistry>,
+    mut map_events: MessageReader<AssetEvent<MapProject>>,
+    mut world_events: MessageReader<AssetEvent<WorldProject>>,
+    maps: Res<Assets<MapProject>>,
+    worlds: Res<Assets<WorldProject>>,
+    mut checked: Local<HashSet<String>>,
+) {
+    let loaded_maps = map_events.read().filter_map(|event| match event {
+        AssetEvent::Added { id } | AssetEvent::Modified { id } => maps
+            .get(*id)
+            .map(|map| (map.level.name.as_str(), map.schema.as_ref())),
+        _ => None,
+    });
+    let loaded_worlds = world_events.read().filter_map(|event| match event {
+        AssetEvent::Added { id } | AssetEvent::Modified { id } => worlds
+            .get(*id)
+            .map(|world| ("world", world.schema.as_ref())),
+        _ => None,
+    });
+
+    for (name, schema) in loaded_maps.chain(loaded_worlds) {
+        // Maps exported without a schema have nothing to check against
+        let Some(schema) = schema else {
+            continue;
+        };
+        if registry.is_empty() || !checked.insert(schema.to_string()) {
+            continue;
+        }
+        let map_schema = match serde_json::from_value::<Schema>(schema.clone()) {
+            Ok(map_schema) => map_schema,
+            Err(e) => {
+                warn!("Could not read the schema of map '{}': {}", name, e);
+                continue;
+            }
+        };
+        for mismatch in registry.schema_mismatches(&map_schema) {
+            warn!("Map '{}' schema mismatch: {}", name, mismatch);
+        }
+    }
+}
+
 /// System that automatically attaches `Dialogue` components to entities with dialogue properties
 ///
 /// This system runs each frame and looks for entities that have:
@@ -420,25 +856,11 @@ pub fn attach_dialogues(
             }
 
             // Extract dialogue ID from the value
-            let dialogue_id = match value {
-                // Direct string reference to dialogue ID
-                Value::String(id) => Some(id.clone()),
-                // Object with "id" field (embedded dialogue reference)
-                Value::Object(obj) => obj.get("id").and_then(|v| {
-                    if let Value::String(id) = v {
-                        Some(id.clone())
-                    } else {
-                        None
-                    }
-                }),
-                _ => None,
-            };
-
-            if let Some(id) = dialogue_id {
-                if !id.is_empty() {
-                    commands.entity(entity).insert(Dialogue { dialogue_id: id });
-                    break; // Only attach one dialogue per entity
-                }
+            if let Some(id) = dialogue_id(value) {
+                commands.entity(entity).insert(Dialogue {
+                    dialogue_id: id.to_string(),
+                });
+                break; // Only attach one dialogue per entity
             }
         }
     }
@@ -447,6 +869,7 @@ pub fn attach_dialogues(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bevy_map_schema::{PropType, PropertyDef};
 
     #[derive(Component)]
     #[allow(dead_code)]
@@ -466,6 +889,24 @@ mod tests {
                 health: instance.get_int("health").unwrap_or(100) as i32,
             }
         }
+
+        fn to_properties(&self) -> HashMap<String, Value> {
+            HashMap::from([
+                ("name".to_string(), Value::String(self.name.clone())),
+                ("health".to_string(), Value::Int(self.health as i64)),
+            ])
+        }
+
+        fn type_def(_schema: &mut Schema) -> TypeDef {
+            TypeDef {
+                placeable: true,
+                properties: vec![
+                    PropertyDef::new("name", PropType::String),
+                    PropertyDef::new("health", PropType::Int),
+                ],
+                ..default()
+            }
+        }
     }
 
     #[test]
@@ -480,4 +921,250 @@ mod tests {
         assert!(registry.is_registered("TestEntity"));
         assert!(!registry.is_registered("OtherEntity"));
     }
+
+    #[test]
+    fn test_capture_instances() {
+        let mut registry = EntityRegistry::new();
+        registry.register::<TestEntity>();
+
+        let mut instance = EntityInstance::new("TestEntity".to_string(), [10.0, 20.0]);
+        instance.set_string("name", "Guard".to_string());
+        instance.set_string("_editor_color", "#ff0000".to_string());
+
+        let mut world = World::new();
+        let id = instance.id;
+        world.spawn((
+            TestEntity {
+                name: "Guard".to_string(),
+                health: 42,
+            },
+            Transform::from_xyz(30.0, 40.0, 0.0),
+            MapEntityMarker::new(id, "TestEntity"),
+            EntityProperties {
+                properties: instance.properties.clone(),
+            },
+        ));
+        // Unregistered types are skipped
+        world.spawn(MapEntityMarker::new(Uuid::new_v4(), "Unknown"));
+
+        let captured = registry.capture_instances(&world);
+        assert_eq!(captured.len(), 1);
+        assert_eq!(captured[0].id, id);
+        assert_eq!(captured[0].position, [30.0, 40.0]);
+        assert_eq!(captured[0].get_int("health"), Some(42));
+        assert_eq!(captured[0].get_string("name"), Some("Guard"));
+        assert_eq!(captured[0].get_string("_editor_color"), Some("#ff0000"));
+    }
+
+    #[test]
+    fn test_spawn_inherited_type() {
+        let mut registry = EntityRegistry::new();
+        registry.register::<TestEntity>();
+
+        let mut schema = registry.schema();
+        schema.data_types.insert(
+            "Boss".to_string(),
+            TypeDef {
+                extends: Some("TestEntity".to_string()),
+                placeable: true,
+                ..default()
+            },
+        );
+
+        let mut instance = EntityInstance::new("Boss".to_string(), [0.0, 0.0]);
+        instance.set_int("health", 500);
+
+        let mut world = World::new();
+        assert!(registry.spawn_with_schema(
+            &mut world.commands(),
+            &instance,
+            Transform::default(),
+            Some(&schema),
+        ));
+        world.flush();
+
+        let (entity, marker) = world
+            .query::<(Entity, &MapEntityMarker)>()
+            .single(&world)
+            .unwrap();
+        assert_eq!(marker.type_name, "Boss");
+        assert!(marker.is_a("Boss"));
+        assert!(marker.is_a("TestEntity"));
+        assert!(!marker.is_a("Other"));
+        assert_eq!(world.get::<TestEntity>(entity).unwrap().health, 500);
+        assert_eq!(registry.capture_instances(&world).len(), 1);
+
+        // Without the schema, the subtype is unknown
+        assert!(!registry.spawn(&mut world.commands(), &instance, Transform::default()));
+    }
+
+    #[derive(Asset, TypePath)]
+    struct TestAsset;
+
+    #[derive(Component)]
+    struct Speaker {
+        sound: Handle<TestAsset>,
+    }
+
+    impl MapEntityType for Speaker {
+        fn type_name() -> &'static str {
+            "Speaker"
+        }
+
+        fn from_instance(_instance: &EntityInstance) -> Self {
+            Self {
+                sound: Handle::default(),
+            }
+        }
+
+        fn asset_properties() -> &'static [&'static str] {
+            &["sound"]
+        }
+
+        fn load_asset_handle(
+            &mut self,
+            property_name: &str,
+            path: &str,
+            asset_server: &AssetServer,
+        ) -> Option<UntypedHandle> {
+            (property_name == "sound").then(|| {
+                self.sound = asset_server.load(path.to_string());
+                self.sound.clone().untyped()
+            })
+        }
+    }
+
+    #[test]
+    fn test_asset_property_loading() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<TestAsset>()
+            .add_message::<MapAssetFailedEvent>()
+            .add_systems(Update, report_failed_map_assets);
+
+        let mut registry = EntityRegistry::new();
+        registry.register::<Speaker>();
+        let mut instance = EntityInstance::new("Speaker".to_string(), [0.0, 0.0]);
+        instance.set_string("sound", "missing.speaker".to_string());
+        registry.spawn(
+            &mut app.world_mut().commands(),
+            &instance,
+            Transform::default(),
+        );
+        app.world_mut().flush();
+
+        let world = app.world_mut();
+        let (entity, speaker) = world.query::<(Entity, &Speaker)>().single(world).unwrap();
+        assert_eq!(
+            speaker.sound.path().map(|path| path.to_string()),
+            Some("missing.speaker".to_string())
+        );
+        assert!(world.get::<PendingMapAssets>(entity).is_some());
+
+        // No loader handles the extension, so the load fails and is reported
+        let sound = speaker.sound.id();
+        while app
+            .world()
+            .resource::<AssetServer>()
+            .load_state(sound)
+            .is_loading()
+        {
+            app.update();
+        }
+        app.update();
+
+        let failed: Vec<_> = app
+            .world_mut()
+            .resource_mut::<Messages<MapAssetFailedEvent>>()
+            .drain()
+            .collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].entity, entity);
+        assert_eq!(failed[0].property, "sound");
+        assert_eq!(failed[0].path, "missing.speaker");
+        assert!(app.world().get::<PendingMapAssets>(entity).is_none());
+    }
+
+    #[test]
+    fn test_registry_schema() {
+        let mut registry = EntityRegistry::new();
+        registry.register::<TestEntity>();
+
+        let schema = registry.schema();
+        let type_def = schema.get_type("TestEntity").unwrap();
+        assert_eq!(type_def.properties.len(), 2);
+        assert!(registry.schema_mismatches(&schema).is_empty());
+
+        // A map saved before `health` changed type
+        let mut map_schema = schema.clone();
+        map_schema
+            .data_types
+            .get_mut("TestEntity")
+            .unwrap()
+            .properties[1]
+            .prop_type = PropType::String;
+        assert_eq!(
+            registry.schema_mismatches(&map_schema),
+            vec![SchemaMismatch::PropertyType {
+                type_name: "TestEntity".to_string(),
+                property: "health".to_string(),
+                expected: PropType::Int,
+                found: PropType::String,
+            }]
+        );
+    }
+
+    #[derive(Component)]
+    struct Talker {
+        dialogue: Handle<DialogueTree>,
+    }
+
+    impl MapEntityType for Talker {
+        fn type_name() -> &'static str {
+            "Talker"
+        }
+
+        fn from_instance(_instance: &EntityInstance) -> Self {
+            Self {
+                dialogue: Handle::default(),
+            }
+        }
+
+        fn dialogue_properties() -> &'static [&'static str] {
+            &["dialogue"]
+        }
+
+        fn inject_dialogue_handle(&mut self, property_name: &str, handle: Handle<DialogueTree>) {
+            if property_name == "dialogue" {
+                self.dialogue = handle;
+            }
+        }
+    }
+
+    #[test]
+    fn test_dialogue_handle_injection() {
+        let mut registry = EntityRegistry::new();
+        registry.register::<Talker>();
+
+        let mut world = World::new();
+        world.init_resource::<Assets<DialogueTree>>();
+        let mut map_dialogues = crate::MapDialogues::default();
+        map_dialogues.dialogues.insert(
+            "greeting".to_string(),
+            DialogueTree {
+                id: "greeting".to_string(),
+                ..default()
+            },
+        );
+        world.insert_resource(map_dialogues);
+
+        let mut instance = EntityInstance::new("Talker".to_string(), [0.0, 0.0]);
+        instance.set_string("dialogue", "greeting".to_string());
+        registry.spawn(&mut world.commands(), &instance, Transform::default());
+        world.flush();
+
+        let talker = world.query::<&Talker>().single(&world).unwrap();
+        let assets = world.resource::<Assets<DialogueTree>>();
+        assert_eq!(assets.get(&talker.dialogue).unwrap().id, "greeting");
+    }
 }
@@ -92,7 +92,7 @@ fn spawn_entity_sprites(
     // Try to get the first available map project
     let project = map_root_query
         .iter()
-        .find_map(|root| map_assets.get(&root.handle));
+        .find_map(|root| map_assets.get(&root.project));
 
     let Some(project) = project else {
         return;
@@ -186,7 +186,7 @@ fn complete_entity_sprite_loads(
     // Try to get the first available map project
     let project = map_root_query
         .iter()
-        .find_map(|root| map_assets.get(&root.handle));
+        .find_map(|root| map_assets.get(&root.project));
 
     let Some(project) = project else {
         return;
@@ -8,6 +8,7 @@
 //! - bevy_ecs_tilemap-based GPU rendering
 //! - Runtime terrain modification support via autotile integration
 //! - Automatic entity spawning with derive macros
+//! - Typed access to data instances (items, enemies, loot tables) via `MapData`
 //!
 //! # Quick Start (Asset-Based Loading with Hot-Reload)
 //!
@@ -68,7 +69,7 @@ use bevy::asset::AssetEvent;
 use bevy::ecs::message::{Message, MessageReader, MessageWriter};
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
-use bevy_map_core::MapProject;
+use bevy_map_core::{MapProject, TileGridLayout, WorldProject};
 use std::collections::HashMap;
 use std::path::Path;
 use uuid::Uuid;
@@ -128,33 +129,68 @@ pub use bevy_map_dialogue;
 
 pub mod camera;
 pub mod collision;
+pub mod data;
+pub mod delta;
+pub mod edit;
 pub mod entity_input;
 pub mod entity_physics;
 pub mod entity_registry;
 pub mod entity_sprite;
 pub mod loader;
+pub mod navigation;
+#[cfg(feature = "physics")]
+pub mod one_way;
+pub mod property;
 pub mod render;
+pub mod streaming;
+pub mod tile_animation;
+pub mod transition;
+pub mod world;
 
 // Re-export commonly used types
 pub use camera::{clamp_camera_to_bounds, setup_camera_bounds_from_map, CameraBounds};
-pub use collision::{MapCollider, MapCollisionPlugin};
+pub use collision::{merge_cell_rects, MapCollider, MapCollisionPlugin};
+#[cfg(feature = "physics")]
+pub use collision::{
+    polygon_collider, MapCollisionSettings, SensorTile, SensorTileEnterEvent, SensorTileExitEvent,
+    TileColliders, TileCollisionCell,
+};
+pub use data::{read_data_property, DataRef, MapData, MapDataType};
+pub use delta::{MapDeltaConflictEvent, MapDeltaPlugin, MapDeltas};
+pub use edit::{apply_tile_edit, MapEdit, MapEditError, TileChangedEvent, TileEdit};
 pub use entity_input::{
     CustomInput, EntityInputSpawned, MapEntityInputPlugin, PlatformerInput, TopDownInput,
     TwinStickInput,
 };
 pub use entity_physics::{EntityPhysicsSpawned, MapEntityPhysicsPlugin};
 pub use entity_registry::{
-    attach_dialogues, Dialogue, EntityProperties, EntityRegistry, MapEntityExt, MapEntityMarker,
-    MapEntityType,
+    attach_dialogues, check_map_schemas, report_failed_map_assets, Dialogue, EntityProperties,
+    EntityRegistry, MapAssetFailedEvent, MapEntityExt, MapEntityMarker, MapEntityType,
+    PendingMapAssets,
 };
 pub use entity_sprite::{EntitySpriteSetup, EntitySpriteSpawned, MapEntitySpritePlugin};
-pub use loader::{MapLoadError, MapProjectLoader};
+pub use loader::{MapLoadError, MapProjectLoader, WorldProjectLoader};
+pub use navigation::{MapNavigation, MapNavigationPlugin, NavigationSettings};
+#[cfg(feature = "physics")]
+pub use one_way::{DropThrough, DropThroughExt, OneWayPassThrough, OneWayPlatform};
+pub use property::{read_map_property, MapFlag, MapFlags, MapProperty};
 pub use render::{complete_sprite_loads, spawn_sprite_components, SpriteSlot};
+pub use streaming::{
+    LevelLoadedEvent, LevelUnloadedEvent, StreamedLevel, StreamingFocus, WorldStreaming,
+    WorldStreamingPlugin,
+};
+pub use tile_animation::{animate_map_tiles, AnimatedMapTile};
+pub use transition::{
+    ActiveLevelTransition, LevelTransitionEndEvent, LevelTransitionPlugin,
+    LevelTransitionStartEvent, LevelTransitions, LevelTraveler,
+};
+pub use world::{LevelRef, SpawnedWorldLevel, WorldCommandsExt, WorldLevel};
 
 // Re-export key dialogue types for convenience
 pub use bevy_map_dialogue::{
-    DialogueChoice, DialogueChoiceEvent, DialogueEndEvent, DialogueHandle, DialogueNode,
-    DialogueNodeType, DialogueRunner, DialogueTree, StartDialogueEvent,
+    DialogueActionEvent, DialogueChoice, DialogueChoiceEvent, DialogueEndEvent, DialogueEventExt,
+    DialogueEventType, DialogueHandle, DialogueNode, DialogueNodeType, DialogueRunner,
+    DialogueTree, DialogueValue, DialogueVariables, StartDialogueEvent,
 };
 
 // Re-export key animation types for convenience
@@ -173,6 +209,8 @@ pub use bevy_map_animation::{
 /// - Automatic map spawning when `MapHandle` components are added
 /// - Hot-reload support when using Bevy's `file_watcher` feature
 /// - Manual spawning via `SpawnMapEvent` and `SpawnMapProjectEvent`
+/// - Multi-level worlds via `WorldProject` and `WorldLevel`
+/// - Runtime tile editing via `MapEdit`
 pub struct MapRuntimePlugin;
 
 impl Plugin for MapRuntimePlugin {
@@ -183,19 +221,25 @@ impl Plugin for MapRuntimePlugin {
             // Asset loading
             .init_asset::<MapProject>()
             .init_asset_loader::<MapProjectLoader>()
+            .init_asset::<WorldProject>()
+            .init_asset_loader::<WorldProjectLoader>()
             // Resources
             .init_resource::<EntityRegistry>()
             .init_resource::<MapDialogues>()
+            .init_resource::<MapData>()
             // Events
             .add_message::<SpawnMapEvent>()
             .add_message::<SpawnMapProjectEvent>()
             .add_message::<MapSpawnedEvent>()
+            .add_message::<TileChangedEvent>()
             // Systems
             .add_systems(Update, handle_spawn_map_events)
             .add_systems(Update, handle_spawn_map_project_events)
             .add_systems(
                 Update,
                 (
+                    world::handle_world_hot_reload,
+                    world::spawn_world_levels,
                     initialize_map_handles,
                     handle_map_handle_spawning,
                     handle_map_hot_reload,
@@ -205,8 +249,15 @@ impl Plugin for MapRuntimePlugin {
             // Sprite spawning systems
             .add_systems(Update, spawn_sprite_components)
             .add_systems(Update, complete_sprite_loads)
+            // Animated tile playback
+            .add_systems(Update, animate_map_tiles)
             // Dialogue attachment system
             .add_systems(Update, attach_dialogues)
+            // Registered entity types vs. map schema check
+            .add_systems(Update, check_map_schemas)
+            // Asset property load failures
+            .add_message::<MapAssetFailedEvent>()
+            .add_systems(Update, report_failed_map_assets)
             // Camera bounds systems
             .add_systems(Update, setup_camera_bounds_from_map)
             .add_systems(PostUpdate, clamp_camera_to_bounds)
@@ -262,10 +313,41 @@ pub struct MapHandle(pub Handle<MapProject>);
 pub struct MapRoot {
     /// Handle to the source MapProject asset
     pub handle: Handle<MapProject>,
+    /// Handle to the MapProject holding this map's current data
+    ///
+    /// This is the source asset until the map is changed at runtime (by
+    /// `MapEdit` or a save delta), after which it is a copy owned by this map.
+    pub project: Handle<MapProject>,
     /// Cached tileset textures for this map
     pub textures: TilesetTextures,
 }
 
+impl MapRoot {
+    /// Create a root for a map spawned from `handle`
+    pub fn new(handle: Handle<MapProject>, textures: TilesetTextures) -> Self {
+        Self {
+            project: handle.clone(),
+            handle,
+            textures,
+        }
+    }
+}
+
+/// Give a spawned map its own copy of its project and return the copy's handle
+///
+/// Maps that already own a copy keep it.
+pub(crate) fn own_map_project(world: &mut World, map: Entity) -> Option<Handle<MapProject>> {
+    let root = world.get::<MapRoot>(map)?;
+    if root.project != root.handle {
+        return Some(root.project.clone());
+    }
+    let source = root.handle.clone();
+    let copy = world.resource::<Assets<MapProject>>().get(&source)?.clone();
+    let project = world.resource_mut::<Assets<MapProject>>().add(copy);
+    world.get_mut::<MapRoot>(map)?.project = project.clone();
+    Some(project)
+}
+
 /// Internal state tracking for MapHandle entities
 #[derive(Component, Default)]
 struct MapHandleState {
@@ -292,6 +374,8 @@ fn handle_map_handle_spawning(
     mut query: Query<(Entity, &MapHandle, &mut MapHandleState, Option<&Transform>)>,
     entity_registry: Res<EntityRegistry>,
     mut map_dialogues: ResMut<MapDialogues>,
+    mut map_data: ResMut<MapData>,
+    mut images: ResMut<Assets<Image>>,
 ) {
     for (entity, map_handle, mut state, _transform) in query.iter_mut() {
         // Check if asset is loaded
@@ -316,8 +400,13 @@ fn handle_map_handle_spawning(
             state.loading_textures = true;
         }
 
+        // Don't spawn if already spawned
+        if state.spawned {
+            continue;
+        }
+
         // Check if all textures are loaded
-        let Some(textures) = &state.textures else {
+        let Some(textures) = state.textures.as_mut() else {
             continue;
         };
 
@@ -336,10 +425,8 @@ fn handle_map_handle_spawning(
             continue;
         }
 
-        // Don't spawn if already spawned
-        if state.spawned {
-            continue;
-        }
+        // Apply tileset margin/spacing to the loaded atlases
+        textures.prepare_atlases(project, &mut images);
 
         info!(
             "Spawning map '{}' with {} layers, {} tilesets",
@@ -348,8 +435,9 @@ fn handle_map_handle_spawning(
             project.tilesets.len()
         );
 
-        // Load dialogues from the project
+        // Load dialogues and data instances from the project
         map_dialogues.load_from_project(project);
+        map_data.load_from_project(project);
 
         let map_entity = spawn_map_project(
             &mut commands,
@@ -360,10 +448,9 @@ fn handle_map_handle_spawning(
         );
 
         // Add MapRoot marker and make it a child
-        commands.entity(map_entity).insert(MapRoot {
-            handle: map_handle.0.clone(),
-            textures: textures.clone(),
-        });
+        commands
+            .entity(map_entity)
+            .insert(MapRoot::new(map_handle.0.clone(), textures.clone()));
 
         commands.entity(entity).add_child(map_entity);
 
@@ -440,8 +527,13 @@ pub struct TilesetTextures {
     images: HashMap<(Uuid, usize), Handle<Image>>,
     /// Map from sprite_sheet_id to texture handle
     sprite_sheet_images: HashMap<Uuid, Handle<Image>>,
+    /// Tilemap atlases for (tileset_id, image_index) and the layout they were
+    /// prepared for, see `prepare_atlases`
+    atlases: HashMap<(Uuid, usize), (Handle<Image>, TileGridLayout)>,
     /// Tile size from the project (cached for convenience)
     pub tile_size: f32,
+    /// Tile (width, height) of the first tileset, for non-square tiles
+    pub tile_dimensions: Vec2,
 }
 
 impl TilesetTextures {
@@ -474,17 +566,86 @@ impl TilesetTextures {
 
         // Get tile size from the first tileset
         if let Some(tileset) = project.tilesets.values().next() {
+            let (width, height) = tileset.tile_dimensions();
             self.tile_size = tileset.tile_size as f32;
+            self.tile_dimensions = Vec2::new(width as f32, height as f32);
+        }
+    }
+
+    /// Prepare loaded tileset images for use as tilemap atlases
+    ///
+    /// bevy_ecs_tilemap assumes the margin around a tileset image equals the
+    /// spacing between tiles, and counts columns from the image width. Images
+    /// that don't match that layout (see [`TileGridLayout::needs_atlas`]) get a
+    /// re-laid-out copy that [`TilesetTextures::get`] returns instead. Call this
+    /// once all textures are loaded and before `spawn_map_project`; it is done
+    /// automatically for `MapHandle` and `SpawnMapProjectEvent`.
+    pub fn prepare_atlases(&mut self, project: &MapProject, images: &mut Assets<Image>) {
+        for (key, handle) in &self.images {
+            let Some(tileset) = project.get_tileset(key.0) else {
+                continue;
+            };
+            let layout = tileset.grid_layout();
+            if self.atlases.get(key).map(|(_, prepared)| prepared) == Some(&layout) {
+                continue;
+            }
+            let Some(image) = images.get(handle.id()) else {
+                continue;
+            };
+            if !layout.needs_atlas(image.width(), image.height()) {
+                self.atlases.remove(key);
+                continue;
+            }
+
+            let format = image.texture_descriptor.format;
+            let (Some(data), Some(bytes_per_pixel), false) = (
+                image.data.as_ref(),
+                format.block_copy_size(None),
+                format.is_compressed(),
+            ) else {
+                // Maps are often respawned with the same textures, so only warn
+                // the first time an image is seen
+                static WARNED: std::sync::Mutex<Vec<AssetId<Image>>> =
+                    std::sync::Mutex::new(Vec::new());
+                let mut warned = WARNED.lock().unwrap();
+                if !warned.contains(&handle.id()) {
+                    warned.push(handle.id());
+                    warn!(
+                        "Tileset '{}' image {}: cannot apply margin/spacing to this texture format",
+                        tileset.name, key.1
+                    );
+                }
+                continue;
+            };
+
+            let (data, width, height) = layout.atlas_pixels(
+                data,
+                image.width(),
+                image.height(),
+                bytes_per_pixel as usize,
+            );
+            let mut atlas = image.clone();
+            atlas.data = Some(data);
+            atlas.texture_descriptor.size.width = width;
+            atlas.texture_descriptor.size.height = height;
+            self.atlases.insert(*key, (images.add(atlas), layout));
         }
     }
 
     /// Get texture handle for a specific tileset and image index
+    ///
+    /// Returns the prepared atlas if the image needed one.
     pub fn get(&self, tileset_id: Uuid, image_index: usize) -> Option<&Handle<Image>> {
-        self.images.get(&(tileset_id, image_index))
+        let key = (tileset_id, image_index);
+        self.atlases
+            .get(&key)
+            .map(|(handle, _)| handle)
+            .or_else(|| self.images.get(&key))
     }
 
     /// Insert a texture handle manually
     pub fn insert(&mut self, tileset_id: Uuid, image_index: usize, handle: Handle<Image>) {
+        self.atlases.remove(&(tileset_id, image_index));
         self.images.insert((tileset_id, image_index), handle);
     }
 
@@ -935,6 +1096,19 @@ pub struct RuntimeMap {
 #[derive(Component)]
 pub struct MapLayerIndex(pub usize);
 
+/// Component linking a tilemap to the tileset image it renders
+#[derive(Component)]
+pub struct TilemapImageIndex(pub usize);
+
+/// Component on sprites spawned for tiles of collection tilesets
+#[derive(Component, Debug, Clone, Copy)]
+pub struct CollectionTileSprite {
+    /// Grid cell the tile is anchored to
+    pub tile_pos: TilePos,
+    /// Virtual tile index within the tileset
+    pub tile_index: u32,
+}
+
 fn handle_spawn_map_events(
     mut commands: Commands,
     mut spawn_events: MessageReader<SpawnMapEvent>,
@@ -958,6 +1132,9 @@ fn handle_spawn_map_events(
 ///
 /// If an `EntityRegistry` is provided, entities from the level will be
 /// automatically spawned with the appropriate components.
+///
+/// A bare `Level` carries no tileset metadata, so animated tiles are not
+/// played. Use `spawn_map_project` for tile animation support.
 pub fn spawn_map(
     commands: &mut Commands,
     level: &bevy_map_core::Level,
@@ -1060,6 +1237,9 @@ pub fn spawn_map(
 }
 
 /// Update a tile at runtime
+///
+/// This only swaps the rendered tile. Use [`MapEdit`] to also update the map
+/// data, colliders, navigation and autotile neighbors.
 pub fn set_tile(
     commands: &mut Commands,
     tile_storage: &mut TileStorage,
@@ -1114,15 +1294,21 @@ fn handle_spawn_map_project_events(
     mut spawned_events: MessageWriter<MapSpawnedEvent>,
     entity_registry: Res<EntityRegistry>,
     mut map_dialogues: ResMut<MapDialogues>,
+    mut map_data: ResMut<MapData>,
+    mut images: ResMut<Assets<Image>>,
 ) {
     for event in spawn_events.read() {
-        // Load dialogues from the project
+        // Load dialogues and data instances from the project
         map_dialogues.load_from_project(&event.project);
+        map_data.load_from_project(&event.project);
+
+        let mut textures = event.textures.clone();
+        textures.prepare_atlases(&event.project, &mut images);
 
         let map_entity = spawn_map_project(
             &mut commands,
             &event.project,
-            &event.textures,
+            &textures,
             event.transform,
             Some(&entity_registry),
         );
@@ -1134,7 +1320,9 @@ fn handle_spawn_map_project_events(
 ///
 /// This function properly handles:
 /// - Multi-image tilesets (tiles referencing correct image by virtual index)
+/// - Collection tilesets (each tile spawned as a sprite at its image's size)
 /// - Tileset metadata embedded in the project
+/// - Animated tiles (via `AnimatedMapTile`)
 /// - Entity spawning via EntityRegistry
 ///
 /// # Example
@@ -1167,7 +1355,6 @@ pub fn spawn_map_project(
     entity_registry: Option<&EntityRegistry>,
 ) -> Entity {
     let level = &project.level;
-    let tile_size = textures.tile_size;
 
     let map_entity = commands
         .spawn((
@@ -1217,7 +1404,8 @@ pub fn spawn_map_project(
             // For multi-image tilesets, we need to create separate tilemaps per image
             // because bevy_ecs_tilemap uses a single texture per tilemap.
             // Group tiles by which image they belong to.
-            let mut tiles_by_image: HashMap<usize, Vec<(u32, u32, u32)>> = HashMap::new();
+            // Tuple: (x, y, virtual_tile_index, local_tile_index)
+            let mut tiles_by_image: HashMap<usize, Vec<(u32, u32, u32, u32)>> = HashMap::new();
 
             for y in 0..level.height {
                 for x in 0..level.width {
@@ -1226,9 +1414,33 @@ pub fn spawn_map_project(
                         if let Some((image_index, local_tile_index)) =
                             tileset.virtual_to_local(virtual_tile_index)
                         {
+                            // Collection tiles have their own image sizes, so they
+                            // can't share an atlas tilemap
+                            if tileset.is_collection() {
+                                let Some(texture_handle) = textures.get(*tileset_id, image_index)
+                                else {
+                                    warn!(
+                                        "Missing texture for tileset {} image {}",
+                                        tileset_id, image_index
+                                    );
+                                    continue;
+                                };
+                                let sprite_entity = commands
+                                    .spawn(collection_tile_sprite_bundle(
+                                        tileset,
+                                        texture_handle.clone(),
+                                        layer_index,
+                                        TilePos { x, y },
+                                        virtual_tile_index,
+                                    ))
+                                    .id();
+                                commands.entity(map_entity).add_child(sprite_entity);
+                                continue;
+                            }
                             tiles_by_image.entry(image_index).or_default().push((
                                 x,
                                 y,
+                                virtual_tile_index,
                                 local_tile_index,
                             ));
                         }
@@ -1257,46 +1469,37 @@ pub fn spawn_map_project(
                     x: level.width,
                     y: level.height,
                 };
-
-                let tilemap_tile_size = TilemapTileSize {
-                    x: tile_size,
-                    y: tile_size,
-                };
-
-                let grid_size: TilemapGridSize = tilemap_tile_size.into();
                 let mut tile_storage = TileStorage::empty(map_size);
                 let tilemap_entity = commands.spawn_empty().id();
 
                 // Spawn tiles for this image
-                for (x, y, local_tile_index) in image_tiles {
+                for (x, y, virtual_tile_index, local_tile_index) in image_tiles {
                     let tile_pos = TilePos { x, y };
-                    let tile_entity = commands
-                        .spawn(TileBundle {
-                            position: tile_pos,
-                            tilemap_id: TilemapId(tilemap_entity),
-                            texture_index: TileTextureIndex(local_tile_index),
-                            ..default()
-                        })
-                        .id();
+                    let mut tile_commands = commands.spawn(TileBundle {
+                        position: tile_pos,
+                        tilemap_id: TilemapId(tilemap_entity),
+                        texture_index: TileTextureIndex(local_tile_index),
+                        ..default()
+                    });
+
+                    // Attach animation if this tile has animation frames
+                    if let Some(animation) =
+                        AnimatedMapTile::from_tileset(tileset, virtual_tile_index, image_index)
+                    {
+                        tile_commands.insert(animation);
+                    }
+
+                    let tile_entity = tile_commands.id();
                     tile_storage.set(&tile_pos, tile_entity);
                 }
 
-                // Z-offset: layer_index * 0.1 + image_index * 0.01
-                // This ensures proper ordering: all images in layer 0 render before layer 1
-                let layer_z = layer_index as f32 * 0.1 + image_index as f32 * 0.01;
-
-                commands.entity(tilemap_entity).insert((
-                    TilemapBundle {
-                        grid_size,
-                        map_type: TilemapType::Square,
-                        size: map_size,
-                        storage: tile_storage,
-                        texture: TilemapTexture::Single(texture_handle.clone()),
-                        tile_size: tilemap_tile_size,
-                        transform: Transform::from_xyz(0.0, 0.0, layer_z),
-                        ..default()
-                    },
-                    MapLayerIndex(layer_index),
+                commands.entity(tilemap_entity).insert(layer_tilemap_bundle(
+                    tileset,
+                    texture_handle.clone(),
+                    layer_index,
+                    image_index,
+                    map_size,
+                    tile_storage,
                 ));
 
                 commands.entity(map_entity).add_child(tilemap_entity);
@@ -1309,10 +1512,173 @@ pub fn spawn_map_project(
         }
     }
 
-    // Spawn entities if registry is provided
+    // Spawn entities if registry is provided, resolving inherited types
     if let Some(registry) = entity_registry {
-        registry.spawn_all(commands, &level.entities, transform);
+        let schema = entity_registry::embedded_schema(project.schema.as_ref());
+        registry.spawn_all_with_schema(commands, &level.entities, transform, schema.as_ref());
     }
 
     map_entity
 }
+
+/// Tilemap rendering one image of a tileset for a tile layer
+pub(crate) fn layer_tilemap_bundle(
+    tileset: &bevy_map_core::Tileset,
+    texture: Handle<Image>,
+    layer_index: usize,
+    image_index: usize,
+    map_size: TilemapSize,
+    storage: TileStorage,
+) -> impl Bundle {
+    let (tile_width, tile_height) = tileset.tile_dimensions();
+    let tile_size = TilemapTileSize {
+        x: tile_width as f32,
+        y: tile_height as f32,
+    };
+    let spacing = TilemapSpacing {
+        x: tileset.spacing as f32,
+        y: tileset.spacing as f32,
+    };
+
+    // Z-offset: layer_index * 0.1 + image_index * 0.01
+    // This ensures proper ordering: all images in layer 0 render before layer 1
+    let layer_z = layer_index as f32 * 0.1 + image_index as f32 * 0.01;
+
+    // Draw offset is authored Y-down, Bevy is Y-up
+    let [offset_x, offset_y] = tileset.draw_offset;
+
+    (
+        TilemapBundle {
+            grid_size: tile_size.into(),
+            map_type: TilemapType::Square,
+            size: map_size,
+            storage,
+            texture: TilemapTexture::Single(texture),
+            tile_size,
+            spacing,
+            transform: Transform::from_xyz(offset_x as f32, -offset_y as f32, layer_z),
+            ..default()
+        },
+        MapLayerIndex(layer_index),
+        TilemapImageIndex(image_index),
+    )
+}
+
+/// Sprite for a tile of a collection tileset, anchored to its grid cell
+///
+/// The sprite is drawn at the tile image's own size. Its origin (center by
+/// default) is placed relative to the bottom-left corner of the cell, matching
+/// the editor viewport.
+pub(crate) fn collection_tile_sprite_bundle(
+    tileset: &bevy_map_core::Tileset,
+    texture: Handle<Image>,
+    layer_index: usize,
+    tile_pos: TilePos,
+    virtual_tile_index: u32,
+) -> impl Bundle {
+    let (tile_width, tile_height) = tileset.tile_dimensions();
+    let (draw_width, draw_height) = tileset.tile_draw_size(virtual_tile_index);
+    let (origin_x, origin_y) = tileset
+        .get_tile_properties(virtual_tile_index)
+        .cloned()
+        .unwrap_or_default()
+        .get_origin(draw_width, draw_height);
+
+    // Tilemaps center tile (0, 0) on the map origin, so the cell's
+    // bottom-left corner is half a tile down and left of its grid position.
+    // Draw offset is authored Y-down, Bevy is Y-up.
+    let [offset_x, offset_y] = tileset.draw_offset;
+    let x = (tile_pos.x as f32 - 0.5) * tile_width as f32 + origin_x as f32 + offset_x as f32;
+    let y = (tile_pos.y as f32 - 0.5) * tile_height as f32 + origin_y as f32 - offset_y as f32;
+    // Slightly above atlas tiles in the same layer
+    let z = layer_index as f32 * 0.1 + 0.001;
+
+    (
+        Sprite {
+            image: texture,
+            custom_size: Some(Vec2::new(draw_width as f32, draw_height as f32)),
+            ..default()
+        },
+        Transform::from_xyz(x, y, z),
+        Visibility::default(),
+        MapLayerIndex(layer_index),
+        CollectionTileSprite {
+            tile_pos,
+            tile_index: virtual_tile_index,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::asset::RenderAssetUsages;
+    use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+    use bevy_map_core::{Level, Tileset};
+
+    /// Headless app that steps physics by 1/64 s on every update
+    #[cfg(feature = "physics")]
+    pub(crate) fn physics_app(physics: impl PluginGroup) -> App {
+        use bevy::time::TimeUpdateStrategy;
+        use std::time::Duration;
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            AssetPlugin::default(),
+            bevy::scene::ScenePlugin,
+            bevy::mesh::MeshPlugin,
+            physics,
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 64.0,
+        )));
+        app.finish();
+        app
+    }
+
+    #[test]
+    fn test_prepare_atlases_matches_tilemap_layout() {
+        // 4x2 grid of 16px tiles with an 8px margin and no spacing, which
+        // bevy_ecs_tilemap can't read directly (see `TileGridLayout` tests
+        // in bevy_map_core for the pixel layout)
+        let mut tileset = Tileset::new("Tiles".to_string(), "tiles.png".to_string(), 16, 4, 2);
+        tileset.margin = 8;
+        let tileset_id = tileset.id;
+        let layout = tileset.grid_layout();
+        let project = MapProject::new(Level::new("Level".to_string(), 4, 4), vec![tileset]);
+
+        let (width, height) = (80u32, 48u32);
+        let data: Vec<u8> = (0..width * height).map(|i| i as u8).collect();
+        let mut images = Assets::<Image>::default();
+        let source = images.add(Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data.clone(),
+            TextureFormat::R8Unorm,
+            RenderAssetUsages::default(),
+        ));
+
+        let mut textures = TilesetTextures::new();
+        textures.insert(tileset_id, 0, source.clone());
+        textures.prepare_atlases(&project, &mut images);
+
+        // bevy_ecs_tilemap counts round((width - spacing) / (tile + spacing)) columns
+        let atlas = images.get(textures.get(tileset_id, 0).unwrap()).unwrap();
+        assert_eq!((atlas.width(), atlas.height()), (64, 32));
+        assert_eq!(atlas.width() / 16, 4);
+        let (pixels, _, _) = layout.atlas_pixels(&data, width, height, 1);
+        assert_eq!(atlas.data.as_ref(), Some(&pixels));
+
+        // Preparing again reuses the atlas
+        let prepared = textures.get(tileset_id, 0).unwrap().clone();
+        textures.prepare_atlases(&project, &mut images);
+        assert_eq!(textures.get(tileset_id, 0), Some(&prepared));
+        assert_ne!(prepared, source);
+    }
+}
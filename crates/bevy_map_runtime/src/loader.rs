@@ -28,9 +28,11 @@
 use bevy::asset::io::Reader;
 use bevy::asset::{AssetLoader, LoadContext};
 use bevy::reflect::TypePath;
-use bevy_map_core::{EditorProject, MapProject};
+use bevy_map_core::{EditorProject, MapProject, WorldProject};
 use thiserror::Error;
 
+use crate::tile_animation::warn_invalid_animation_frames;
+
 /// Error type for map loading failures
 #[derive(Debug, Error)]
 pub enum MapLoadError {
@@ -67,18 +69,68 @@ impl AssetLoader for MapProjectLoader {
 
         // Try EditorProject format first (what the editor exports)
         // EditorProject uses Vec collections (levels, tilesets arrays)
-        if let Ok(editor_project) = serde_json::from_slice::<EditorProject>(&bytes) {
-            return editor_project
+        let project = if let Ok(editor_project) = serde_json::from_slice::<EditorProject>(&bytes) {
+            editor_project
                 .to_map_project()
-                .ok_or_else(|| MapLoadError::InvalidFormat("No levels in project".to_string()));
-        }
+                .ok_or_else(|| MapLoadError::InvalidFormat("No levels in project".to_string()))?
+        } else {
+            // Fall back to MapProject format (hand-crafted JSON with HashMap collections)
+            let project: MapProject = serde_json::from_slice(&bytes)?;
+
+            // Validate the project
+            project.validate().map_err(MapLoadError::InvalidFormat)?;
+            project
+        };
+
+        warn_invalid_animation_frames(project.tilesets.values());
+        Ok(project)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["map.json"]
+    }
+}
+
+/// Asset loader for WorldProject JSON files
+///
+/// Shares the `.map.json` extension with [`MapProjectLoader`]; Bevy picks the
+/// loader from the requested asset type, so load the file with
+/// `asset_server.load::<WorldProject>(path)` to keep every level.
+#[derive(Default, TypePath)]
+pub struct WorldProjectLoader;
 
-        // Fall back to MapProject format (hand-crafted JSON with HashMap collections)
-        let project: MapProject = serde_json::from_slice(&bytes)?;
+impl AssetLoader for WorldProjectLoader {
+    type Asset = WorldProject;
+    type Settings = ();
+    type Error = MapLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        // Try EditorProject format first (what the editor exports)
+        let project = if let Ok(editor_project) = serde_json::from_slice::<EditorProject>(&bytes) {
+            if editor_project.levels.is_empty() {
+                return Err(MapLoadError::InvalidFormat(
+                    "No levels in project".to_string(),
+                ));
+            }
+            editor_project.to_world_project()
+        } else {
+            // Fall back to WorldProject format (hand-crafted JSON with HashMap collections)
+            let project: WorldProject = serde_json::from_slice(&bytes)?;
 
-        // Validate the project
-        project.validate().map_err(MapLoadError::InvalidFormat)?;
+            // Validate the project
+            project.validate().map_err(MapLoadError::InvalidFormat)?;
+            project
+        };
 
+        warn_invalid_animation_frames(project.tilesets.values());
         Ok(project)
     }
 
@@ -0,0 +1,310 @@
+//! Navigation grids for spawned maps
+//!
+//! [`MapNavigationPlugin`] adds a [`MapNavigation`] component to every spawned
+//! map. It wraps a [`NavGrid`] built from the map's tile collision (and the
+//! optional `nav_cost` custom tile property) and answers A* and flow-field
+//! queries in tile or world coordinates.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use bevy_map_runtime::navigation::{MapNavigation, MapNavigationPlugin};
+//!
+//! app.add_plugins(MapNavigationPlugin);
+//!
+//! fn chase(
+//!     maps: Query<(&MapNavigation, &GlobalTransform)>,
+//!     player: Single<&Transform, With<Player>>,
+//!     mut enemies: Query<&mut Transform, (With<Enemy>, Without<Player>)>,
+//! ) {
+//!     let Ok((nav, map_transform)) = maps.single() else { return };
+//!     for mut enemy in enemies.iter_mut() {
+//!         let from = enemy.translation.truncate();
+//!         let to = player.translation.truncate();
+//!         if let Some(path) = nav.find_path_world(map_transform, from, to) {
+//!             // Walk towards path[1]...
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! When tiles change at runtime, call [`MapNavigation::set_tile`] to update the
+//! affected cell. Flow fields are snapshots; rebuild them after changes.
+
+use bevy::prelude::*;
+use bevy_map_core::{DiagonalMode, FlowField, LayerData, MapProject, NavGrid, NavTile, Tileset};
+use std::collections::HashMap;
+
+/// Plugin that builds a [`MapNavigation`] for every spawned map
+pub struct MapNavigationPlugin;
+
+impl Plugin for MapNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NavigationSettings>()
+            .add_systems(Update, spawn_map_navigation);
+    }
+}
+
+/// Settings used when building navigation grids
+#[derive(Resource, Debug, Clone, Default)]
+pub struct NavigationSettings {
+    /// How diagonal moves are allowed
+    pub diagonal: DiagonalMode,
+}
+
+/// Navigation grid of a spawned map
+///
+/// Tile coordinates match `TilePos` (`y = 0` is the bottom row). World
+/// coordinates are converted through the map entity's `GlobalTransform`.
+#[derive(Component, Debug, Clone)]
+pub struct MapNavigation {
+    grid: NavGrid,
+    /// Navigation data of each tile layer, used to recombine changed cells
+    layers: HashMap<usize, Vec<NavTile>>,
+    tile_size: Vec2,
+}
+
+impl MapNavigation {
+    /// Build the navigation grid of a level
+    ///
+    /// Cells are sized like the first tile layer's tileset.
+    pub fn from_project(project: &MapProject, diagonal: DiagonalMode) -> Self {
+        let level = &project.level;
+        let cell_count = (level.width * level.height) as usize;
+        let mut layers = HashMap::new();
+        let mut tile_size = None;
+
+        for (layer_index, layer) in level.layers.iter().enumerate() {
+            let LayerData::Tiles {
+                tileset_id, tiles, ..
+            } = &layer.data
+            else {
+                continue;
+            };
+            let Some(tileset) = project.get_tileset(*tileset_id) else {
+                continue;
+            };
+            tile_size.get_or_insert_with(|| {
+                let (width, height) = tileset.tile_dimensions();
+                Vec2::new(width as f32, height as f32)
+            });
+            let cells = (0..cell_count)
+                .map(|idx| {
+                    tiles
+                        .get(idx)
+                        .copied()
+                        .flatten()
+                        .map(|tile| NavTile::from_tile(tileset, tile))
+                        .unwrap_or_default()
+                })
+                .collect();
+            layers.insert(layer_index, cells);
+        }
+
+        let mut grid = NavGrid::new(level.width, level.height);
+        grid.diagonal = diagonal;
+        let mut navigation = Self {
+            grid,
+            layers,
+            tile_size: tile_size.unwrap_or(Vec2::splat(16.0)),
+        };
+        for idx in 0..cell_count {
+            navigation.refresh_cell(idx);
+        }
+        navigation
+    }
+
+    /// The underlying grid
+    pub fn grid(&self) -> &NavGrid {
+        &self.grid
+    }
+
+    /// Mutable access to the grid, e.g. to change [`NavGrid::diagonal`] or
+    /// block cells occupied by dynamic obstacles
+    pub fn grid_mut(&mut self) -> &mut NavGrid {
+        &mut self.grid
+    }
+
+    /// Size of a grid cell in pixels
+    pub fn tile_size(&self) -> Vec2 {
+        self.tile_size
+    }
+
+    /// Update a cell after a tile changed (or was cleared with `None`)
+    pub fn set_tile(
+        &mut self,
+        layer_index: usize,
+        x: u32,
+        y: u32,
+        tileset: &Tileset,
+        tile: Option<u32>,
+    ) {
+        if x >= self.grid.width() || y >= self.grid.height() {
+            return;
+        }
+        let idx = (y * self.grid.width() + x) as usize;
+        let cell_count = (self.grid.width() * self.grid.height()) as usize;
+        let cells = self
+            .layers
+            .entry(layer_index)
+            .or_insert_with(|| vec![NavTile::default(); cell_count]);
+        cells[idx] = tile
+            .map(|tile| NavTile::from_tile(tileset, tile))
+            .unwrap_or_default();
+        self.refresh_cell(idx);
+    }
+
+    fn refresh_cell(&mut self, idx: usize) {
+        let cost = NavTile::combine(self.layers.values().map(|cells| cells[idx]));
+        let width = self.grid.width();
+        self.grid
+            .set_cost(idx as u32 % width, idx as u32 / width, cost);
+    }
+
+    /// Center of a tile in the map's local space
+    pub fn tile_to_local(&self, tile: UVec2) -> Vec2 {
+        tile.as_vec2() * self.tile_size
+    }
+
+    /// Tile containing a point in the map's local space
+    pub fn local_to_tile(&self, local: Vec2) -> Option<UVec2> {
+        let cell = (local / self.tile_size).round();
+        let inside = cell.x >= 0.0
+            && cell.y >= 0.0
+            && (cell.x as u32) < self.grid.width()
+            && (cell.y as u32) < self.grid.height();
+        inside.then(|| cell.as_uvec2())
+    }
+
+    /// World position of a tile's center
+    pub fn tile_to_world(&self, map_transform: &GlobalTransform, tile: UVec2) -> Vec2 {
+        map_transform
+            .transform_point(self.tile_to_local(tile).extend(0.0))
+            .truncate()
+    }
+
+    /// Tile containing a world position
+    pub fn world_to_tile(&self, map_transform: &GlobalTransform, world: Vec2) -> Option<UVec2> {
+        let local = map_transform
+            .affine()
+            .inverse()
+            .transform_point3(world.extend(0.0));
+        self.local_to_tile(local.truncate())
+    }
+
+    /// Find the cheapest path between two tiles, including both ends
+    pub fn find_path(&self, start: UVec2, goal: UVec2) -> Option<Vec<UVec2>> {
+        let path = self.grid.find_path(start.into(), goal.into())?;
+        Some(path.into_iter().map(UVec2::from).collect())
+    }
+
+    /// Find a path between two world positions as a list of tile centers
+    pub fn find_path_world(
+        &self,
+        map_transform: &GlobalTransform,
+        start: Vec2,
+        goal: Vec2,
+    ) -> Option<Vec<Vec2>> {
+        let start = self.world_to_tile(map_transform, start)?;
+        let goal = self.world_to_tile(map_transform, goal)?;
+        let path = self.find_path(start, goal)?;
+        Some(
+            path.into_iter()
+                .map(|tile| self.tile_to_world(map_transform, tile))
+                .collect(),
+        )
+    }
+
+    /// Build a flow field leading towards the nearest of the goal tiles
+    pub fn flow_field(&self, goals: impl IntoIterator<Item = UVec2>) -> FlowField {
+        self.grid.flow_field(goals.into_iter().map(Into::into))
+    }
+
+    /// Normalized world-space direction to move in at a world position, following a flow field
+    ///
+    /// Returns `None` at goals, on unreachable tiles and outside the map.
+    pub fn flow_direction_world(
+        &self,
+        field: &FlowField,
+        map_transform: &GlobalTransform,
+        world: Vec2,
+    ) -> Option<Vec2> {
+        let tile = self.world_to_tile(map_transform, world)?;
+        let next = UVec2::from(field.next_cell(tile.x, tile.y)?);
+        (self.tile_to_world(map_transform, next) - world).try_normalize()
+    }
+}
+
+/// System that builds a [`MapNavigation`] when a map is spawned
+pub fn spawn_map_navigation(
+    mut commands: Commands,
+    map_query: Query<(Entity, &super::MapRoot), Added<super::MapRoot>>,
+    map_assets: Res<Assets<MapProject>>,
+    settings: Res<NavigationSettings>,
+) {
+    for (map_entity, map_root) in map_query.iter() {
+        let Some(project) = map_assets.get(&map_root.project) else {
+            continue;
+        };
+        commands
+            .entity(map_entity)
+            .insert(MapNavigation::from_project(project, settings.diagonal));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_map_core::{CollisionData, Layer, Level};
+
+    fn walled_project() -> MapProject {
+        let mut tileset = Tileset::new("Tiles".to_string(), "tiles.png".to_string(), 16, 2, 1);
+        tileset.set_tile_collision(0, CollisionData::full());
+        let mut level = Level::new("Test".to_string(), 5, 3);
+        level.add_layer(Layer::new_tile_layer("Walls".to_string(), tileset.id, 5, 3));
+        // Vertical wall at x = 2, open at the top
+        level.set_tile(0, 2, 0, Some(0));
+        level.set_tile(0, 2, 1, Some(0));
+        MapProject::new(level, vec![tileset])
+    }
+
+    #[test]
+    fn test_world_path_and_incremental_update() {
+        let project = walled_project();
+        let tileset = project.tilesets.values().next().unwrap().clone();
+        let mut nav = MapNavigation::from_project(&project, DiagonalMode::Never);
+        let map_transform = GlobalTransform::from_xyz(100.0, 50.0, 0.0);
+
+        assert_eq!(
+            nav.world_to_tile(&map_transform, Vec2::new(104.0, 45.0)),
+            Some(UVec2::new(0, 0))
+        );
+        assert_eq!(nav.world_to_tile(&map_transform, Vec2::new(0.0, 0.0)), None);
+
+        let path = nav
+            .find_path_world(
+                &map_transform,
+                Vec2::new(100.0, 50.0),
+                Vec2::new(164.0, 50.0),
+            )
+            .unwrap();
+        assert_eq!(path.first(), Some(&Vec2::new(100.0, 50.0)));
+        assert_eq!(path.last(), Some(&Vec2::new(164.0, 50.0)));
+        assert_eq!(path.len(), 9);
+
+        // Closing the gap cuts the map in two
+        nav.set_tile(0, 2, 2, &tileset, Some(0));
+        assert!(nav.find_path(UVec2::new(0, 0), UVec2::new(4, 0)).is_none());
+
+        // Clearing the bottom wall tile opens a shorter route
+        nav.set_tile(0, 2, 0, &tileset, None);
+        let path = nav.find_path(UVec2::new(0, 0), UVec2::new(4, 0)).unwrap();
+        assert_eq!(path.len(), 5);
+
+        let field = nav.flow_field([UVec2::new(4, 0)]);
+        let direction = nav
+            .flow_direction_world(&field, &map_transform, Vec2::new(100.0, 50.0))
+            .unwrap();
+        assert_eq!(direction, Vec2::X);
+    }
+}
@@ -0,0 +1,423 @@
+//! One-way platforms for tile colliders
+//!
+//! Tiles with a `OneWayDirection` in the collision editor get a [`OneWayPlatform`]
+//! component. Bodies approaching from the platform's solid side land on it, while
+//! bodies coming from any other side pass through. This is implemented with
+//! avian2d collision hooks, registered automatically by `MapCollisionPlugin`.
+//!
+//! # Dropping through platforms
+//!
+//! ```rust,ignore
+//! use bevy_map_runtime::one_way::DropThroughExt;
+//!
+//! fn drop_down(mut commands: Commands, player: Single<Entity, With<Player>>, keys: Res<ButtonInput<KeyCode>>) {
+//!     if keys.pressed(KeyCode::KeyS) && keys.just_pressed(KeyCode::Space) {
+//!         commands.entity(*player).drop_through(0.25);
+//!     }
+//! }
+//! ```
+//!
+//! # Custom collision hooks
+//!
+//! avian2d supports a single set of collision hooks. If your game needs its own
+//! hooks, add `PhysicsPlugins` yourself and call [`OneWayPlatformHooks::modify_contacts`]
+//! from your hook implementation.
+
+use avian2d::prelude::*;
+use bevy::ecs::entity::EntityHashSet;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_map_core::OneWayDirection;
+
+/// A collider that only blocks bodies approaching from its solid side
+#[derive(Component, Debug, Clone)]
+#[require(ActiveCollisionHooks = ActiveCollisionHooks::MODIFY_CONTACTS)]
+pub struct OneWayPlatform {
+    /// Solid side of the platform (`Top` is a standard jump-through platform)
+    pub direction: OneWayDirection,
+    /// Bodies currently passing through the platform
+    passing: EntityHashSet,
+}
+
+impl OneWayPlatform {
+    /// Create a one-way platform that is solid on the given side
+    pub fn new(direction: OneWayDirection) -> Self {
+        Self {
+            direction,
+            passing: EntityHashSet::default(),
+        }
+    }
+
+    /// Check if a body is currently passing through this platform
+    pub fn is_passing(&self, entity: Entity) -> bool {
+        self.passing.contains(&entity)
+    }
+
+    /// World-space outward normal of the solid side
+    fn solid_normal(&self, transform: &GlobalTransform) -> Option<Vec2> {
+        let (x, y) = self.direction.solid_normal()?;
+        Some((transform.rotation() * Vec3::new(x, y, 0.0)).truncate())
+    }
+}
+
+/// How a body interacts with one-way platforms
+///
+/// Bodies without this component use [`OneWayPassThrough::ByNormal`].
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OneWayPassThrough {
+    /// Collide only when approaching from the platform's solid side
+    #[default]
+    ByNormal,
+    /// Always pass through one-way platforms
+    Always,
+    /// Always collide, like with a regular collider
+    Never,
+}
+
+/// Temporarily lets a body fall through one-way platforms whose solid side faces up
+///
+/// Insert this on a character to drop down through the platform it stands on.
+/// It is removed automatically once the timer finishes; a body that is still
+/// inside a platform at that point keeps passing through until it leaves it.
+#[derive(Component, Debug, Clone)]
+pub struct DropThrough {
+    pub timer: Timer,
+}
+
+impl DropThrough {
+    /// Drop through platforms for the given number of seconds
+    pub fn new(seconds: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+        }
+    }
+}
+
+impl Default for DropThrough {
+    fn default() -> Self {
+        Self::new(0.25)
+    }
+}
+
+/// Extension trait for making an entity drop through one-way platforms
+pub trait DropThroughExt {
+    /// Let this entity fall through one-way platforms for `seconds`
+    fn drop_through(&mut self, seconds: f32) -> &mut Self;
+}
+
+impl DropThroughExt for EntityCommands<'_> {
+    fn drop_through(&mut self, seconds: f32) -> &mut Self {
+        self.insert(DropThrough::new(seconds))
+    }
+}
+
+/// System that removes finished [`DropThrough`] components
+pub fn tick_drop_through(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut DropThrough)>,
+) {
+    for (entity, mut drop_through) in query.iter_mut() {
+        if drop_through.timer.tick(time.delta()).is_finished() {
+            commands.entity(entity).remove::<DropThrough>();
+        }
+    }
+}
+
+/// Collision hooks implementing [`OneWayPlatform`] pass-through
+#[derive(SystemParam)]
+pub struct OneWayPlatformHooks<'w, 's> {
+    platforms: Query<'w, 's, (&'static OneWayPlatform, &'static GlobalTransform)>,
+    bodies: Query<
+        'w,
+        's,
+        (Option<&'static OneWayPassThrough>, Has<DropThrough>),
+        Without<OneWayPlatform>,
+    >,
+}
+
+impl OneWayPlatformHooks<'_, '_> {
+    /// Pass-through settings of a collider, falling back to its rigid body
+    fn pass_through(&self, collider: Entity, body: Option<Entity>) -> (OneWayPassThrough, bool) {
+        let lookup = |entity: Entity| {
+            self.bodies
+                .get(entity)
+                .ok()
+                .map(|(mode, dropping)| (mode.copied(), dropping))
+        };
+        let (mode, dropping) = lookup(collider).unwrap_or_default();
+        let (body_mode, body_dropping) = body
+            .filter(|body| *body != collider)
+            .and_then(lookup)
+            .unwrap_or_default();
+        (
+            mode.or(body_mode).unwrap_or_default(),
+            dropping || body_dropping,
+        )
+    }
+}
+
+impl CollisionHooks for OneWayPlatformHooks<'_, '_> {
+    fn modify_contacts(&self, contacts: &mut ContactPair, commands: &mut Commands) -> bool {
+        // Figure out which collider is the platform. Manifold normals point from
+        // collider1 to collider2, so flip them when the platform is collider2.
+        let (platform_entity, platform, transform, other, other_body, normal_sign) =
+            if let Ok((platform, transform)) = self.platforms.get(contacts.collider1) {
+                (
+                    contacts.collider1,
+                    platform,
+                    transform,
+                    contacts.collider2,
+                    contacts.body2,
+                    1.0,
+                )
+            } else if let Ok((platform, transform)) = self.platforms.get(contacts.collider2) {
+                (
+                    contacts.collider2,
+                    platform,
+                    transform,
+                    contacts.collider1,
+                    contacts.body1,
+                    -1.0,
+                )
+            } else {
+                return true;
+            };
+
+        let Some(solid_normal) = platform.solid_normal(transform) else {
+            return true;
+        };
+
+        // Bodies already inside the platform keep passing through until they leave it
+        if platform.is_passing(other) {
+            let penetrating = contacts
+                .manifolds
+                .iter()
+                .any(|manifold| manifold.points.iter().any(|point| point.penetration > 0.0));
+            if penetrating {
+                return false;
+            }
+            commands.queue(PassingCommand::Remove {
+                platform: platform_entity,
+                entity: other,
+            });
+        }
+
+        let (mode, dropping) = self.pass_through(other, other_body);
+        let passes = match mode {
+            OneWayPassThrough::Never => false,
+            OneWayPassThrough::Always => true,
+            OneWayPassThrough::ByNormal => {
+                let on_solid_side = contacts.manifolds.iter().all(|manifold| {
+                    let normal = manifold.normal * normal_sign;
+                    normal.length() > f32::EPSILON && normal.dot(solid_normal) >= 0.5
+                });
+                !on_solid_side || (dropping && solid_normal.y >= 0.5)
+            }
+        };
+
+        if passes {
+            commands.queue(PassingCommand::Add {
+                platform: platform_entity,
+                entity: other,
+            });
+        }
+        !passes
+    }
+}
+
+/// Deferred update of the bodies passing through a platform
+enum PassingCommand {
+    Add { platform: Entity, entity: Entity },
+    Remove { platform: Entity, entity: Entity },
+}
+
+impl Command for PassingCommand {
+    fn apply(self, world: &mut World) {
+        match self {
+            PassingCommand::Add { platform, entity } => {
+                if let Some(mut platform) = world.get_mut::<OneWayPlatform>(platform) {
+                    platform.passing.insert(entity);
+                }
+            }
+            PassingCommand::Remove { platform, entity } => {
+                if let Some(mut platform) = world.get_mut::<OneWayPlatform>(platform) {
+                    platform.passing.remove(&entity);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn physics_app() -> App {
+        let mut app = crate::tests::physics_app(
+            PhysicsPlugins::default().with_collision_hooks::<OneWayPlatformHooks>(),
+        );
+        app.add_systems(Update, tick_drop_through);
+        app
+    }
+
+    fn spawn_platform(app: &mut App, direction: OneWayDirection, position: Vec2) -> Entity {
+        app.world_mut()
+            .spawn((
+                RigidBody::Static,
+                Collider::rectangle(64.0, 16.0),
+                Transform::from_translation(position.extend(0.0)),
+                OneWayPlatform::new(direction),
+            ))
+            .id()
+    }
+
+    fn spawn_body(app: &mut App, position: Vec2, velocity: Vec2, gravity: f32) -> Entity {
+        app.world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Collider::rectangle(8.0, 8.0),
+                Transform::from_translation(position.extend(0.0)),
+                LinearVelocity(velocity),
+                GravityScale(gravity),
+                LockedAxes::ROTATION_LOCKED,
+                SleepingDisabled,
+            ))
+            .id()
+    }
+
+    fn position(app: &App, entity: Entity) -> Vec2 {
+        app.world()
+            .get::<Transform>(entity)
+            .unwrap()
+            .translation
+            .truncate()
+    }
+
+    fn run(app: &mut App, frames: usize) {
+        for _ in 0..frames {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn test_body_passes_up_through_top_platform() {
+        let mut app = physics_app();
+        spawn_platform(&mut app, OneWayDirection::Top, Vec2::ZERO);
+        let body = spawn_body(&mut app, Vec2::new(0.0, -40.0), Vec2::new(0.0, 200.0), 0.0);
+
+        run(&mut app, 40);
+        assert!(position(&app, body).y > 20.0);
+    }
+
+    #[test]
+    fn test_body_lands_on_top_platform() {
+        let mut app = physics_app();
+        app.insert_resource(Gravity(Vec2::new(0.0, -500.0)));
+        spawn_platform(&mut app, OneWayDirection::Top, Vec2::ZERO);
+        let body = spawn_body(&mut app, Vec2::new(0.0, 40.0), Vec2::ZERO, 1.0);
+
+        run(&mut app, 120);
+        let y = position(&app, body).y;
+        assert!(
+            y > 8.0 && y < 16.0,
+            "body should rest on the platform, y = {y}"
+        );
+    }
+
+    #[test]
+    fn test_drop_through_falls_through_top_platform() {
+        let mut app = physics_app();
+        app.insert_resource(Gravity(Vec2::new(0.0, -500.0)));
+        spawn_platform(&mut app, OneWayDirection::Top, Vec2::ZERO);
+        let body = spawn_body(&mut app, Vec2::new(0.0, 40.0), Vec2::ZERO, 1.0);
+
+        run(&mut app, 120);
+        assert!(position(&app, body).y > 8.0);
+
+        app.world_mut().commands().entity(body).drop_through(0.25);
+        run(&mut app, 60);
+        assert!(position(&app, body).y < -20.0);
+        assert!(app.world().get::<DropThrough>(body).is_none());
+    }
+
+    #[test]
+    fn test_bottom_platform() {
+        // Ceiling-style platform: solid from below, open from above
+        let mut app = physics_app();
+        app.insert_resource(Gravity(Vec2::new(0.0, 500.0)));
+        spawn_platform(&mut app, OneWayDirection::Bottom, Vec2::ZERO);
+        let landed = spawn_body(&mut app, Vec2::new(0.0, -40.0), Vec2::ZERO, 1.0);
+        spawn_platform(&mut app, OneWayDirection::Bottom, Vec2::new(100.0, 0.0));
+        let through = spawn_body(
+            &mut app,
+            Vec2::new(100.0, 40.0),
+            Vec2::new(0.0, -200.0),
+            0.0,
+        );
+
+        run(&mut app, 120);
+        let y = position(&app, landed).y;
+        assert!(
+            y < -8.0 && y > -16.0,
+            "body should rest against the platform, y = {y}"
+        );
+        assert!(position(&app, through).y < -20.0);
+    }
+
+    #[test]
+    fn test_horizontal_platforms() {
+        let mut app = physics_app();
+        // Solid on the right: bodies moving left pass, bodies moving right from the
+        // left side pass too, while bodies hitting the right face are blocked.
+        spawn_platform(&mut app, OneWayDirection::Right, Vec2::ZERO);
+        spawn_platform(&mut app, OneWayDirection::Left, Vec2::new(0.0, 100.0));
+
+        let through_right = spawn_body(&mut app, Vec2::new(-60.0, 0.0), Vec2::new(200.0, 0.0), 0.0);
+        let blocked_right = spawn_body(&mut app, Vec2::new(60.0, 0.0), Vec2::new(-200.0, 0.0), 0.0);
+        let through_left = spawn_body(
+            &mut app,
+            Vec2::new(60.0, 100.0),
+            Vec2::new(-200.0, 0.0),
+            0.0,
+        );
+        let blocked_left = spawn_body(
+            &mut app,
+            Vec2::new(-60.0, 100.0),
+            Vec2::new(200.0, 0.0),
+            0.0,
+        );
+
+        run(&mut app, 40);
+        assert!(position(&app, through_right).x > 40.0);
+        assert!(position(&app, blocked_right).x >= 35.0);
+        assert!(position(&app, through_left).x < -40.0);
+        assert!(position(&app, blocked_left).x <= -35.0);
+    }
+
+    #[test]
+    fn test_pass_through_modes() {
+        let mut app = physics_app();
+        app.insert_resource(Gravity(Vec2::new(0.0, -500.0)));
+        spawn_platform(&mut app, OneWayDirection::Top, Vec2::ZERO);
+        spawn_platform(&mut app, OneWayDirection::Top, Vec2::new(100.0, 0.0));
+
+        let always = spawn_body(&mut app, Vec2::new(0.0, 40.0), Vec2::ZERO, 1.0);
+        app.world_mut()
+            .entity_mut(always)
+            .insert(OneWayPassThrough::Always);
+        let never = spawn_body(
+            &mut app,
+            Vec2::new(100.0, -40.0),
+            Vec2::new(0.0, 200.0),
+            0.0,
+        );
+        app.world_mut()
+            .entity_mut(never)
+            .insert(OneWayPassThrough::Never);
+
+        run(&mut app, 60);
+        assert!(position(&app, always).y < -20.0);
+        assert!(position(&app, never).y < -8.0);
+    }
+}
@@ -0,0 +1,634 @@
+//! Conversion between typed Rust values and map property values
+//!
+//! `#[derive(MapEntity)]` reads and writes `#[map_prop]` fields through the
+//! [`MapProperty`] trait. It is implemented for primitives, `Vec<T>`, `Option<T>`,
+//! `Vec2` (Point properties), `Color` (hex strings) and `TileRef` (Tile
+//! properties). Derive it with `#[derive(MapProperty)]` for enums backed by
+//! schema enums and for structs backed by embedded types. Flag enums derive
+//! [`MapFlag`] and are stored in [`MapFlags`] fields.
+
+use bevy::prelude::*;
+use bevy_map_core::{EntityInstance, TileRef, Value};
+use bevy_map_schema::{PropType, PropertyDef, Schema};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+
+/// A type that can be converted to and from a map property [`Value`]
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use bevy_map::prelude::*;
+///
+/// // Matches a schema enum: stored as the variant name
+/// #[derive(MapProperty, Default)]
+/// pub enum NpcType {
+///     #[default]
+///     QuestGiver,
+///     Vendor,
+/// }
+///
+/// // Matches an embedded type: stored as an object
+/// #[derive(MapProperty, Default)]
+/// pub struct Stats {
+///     pub strength: i32,
+///     #[map_prop(default = 1.0)]
+///     pub speed: f32,
+/// }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as a map property",
+    note = "derive `MapProperty` for enums and embedded structs, or use `#[map_prop(parse)]` for types implementing `FromStr` and `Display`"
+)]
+pub trait MapProperty: Sized {
+    /// Convert a property value, returning `None` if it has the wrong shape
+    fn from_value(value: &Value) -> Option<Self>;
+
+    /// Convert back into a property value
+    fn to_value(&self) -> Value;
+
+    /// Describe this type as a schema property
+    ///
+    /// Sets the property type and type references on `property`, and adds any
+    /// enums or embedded types it depends on to `schema`. Defaults to a string.
+    fn describe(property: &mut PropertyDef, _schema: &mut Schema) {
+        property.prop_type = PropType::String;
+    }
+}
+
+/// Read a typed property from an entity instance
+///
+/// Returns `None` if the property is missing or null. Values that exist but
+/// cannot be converted are logged with the entity ID and property name.
+pub fn read_map_property<T: MapProperty>(instance: &EntityInstance, name: &str) -> Option<T> {
+    let value = instance.properties.get(name)?;
+    if value.is_null() {
+        return T::from_value(value);
+    }
+
+    let parsed = T::from_value(value);
+    if parsed.is_none() {
+        warn!(
+            "Entity {} ({}): property '{}' value {:?} cannot be converted to {}",
+            instance.id,
+            instance.type_name,
+            name,
+            value,
+            std::any::type_name::<T>()
+        );
+    }
+    parsed
+}
+
+impl MapProperty for String {
+    fn from_value(value: &Value) -> Option<Self> {
+        value.as_string().map(str::to_string)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+}
+
+impl MapProperty for bool {
+    fn from_value(value: &Value) -> Option<Self> {
+        value.as_bool()
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Bool(*self)
+    }
+
+    fn describe(property: &mut PropertyDef, _schema: &mut Schema) {
+        property.prop_type = PropType::Bool;
+    }
+}
+
+macro_rules! impl_int_property {
+    ($($ty:ty),*) => {
+        $(
+            impl MapProperty for $ty {
+                fn from_value(value: &Value) -> Option<Self> {
+                    value.as_int().and_then(|v| <$ty>::try_from(v).ok())
+                }
+
+                fn to_value(&self) -> Value {
+                    Value::Int(*self as i64)
+                }
+
+                fn describe(property: &mut PropertyDef, _schema: &mut Schema) {
+                    property.prop_type = PropType::Int;
+                }
+            }
+        )*
+    };
+}
+
+impl_int_property!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl MapProperty for f32 {
+    fn from_value(value: &Value) -> Option<Self> {
+        value.as_float().map(|v| v as f32)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Float(*self as f64)
+    }
+
+    fn describe(property: &mut PropertyDef, _schema: &mut Schema) {
+        property.prop_type = PropType::Float;
+    }
+}
+
+impl MapProperty for f64 {
+    fn from_value(value: &Value) -> Option<Self> {
+        value.as_float()
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Float(*self)
+    }
+
+    fn describe(property: &mut PropertyDef, _schema: &mut Schema) {
+        property.prop_type = PropType::Float;
+    }
+}
+
+impl<T: MapProperty> MapProperty for Option<T> {
+    fn from_value(value: &Value) -> Option<Self> {
+        if value.is_null() {
+            Some(None)
+        } else {
+            T::from_value(value).map(Some)
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        self.as_ref().map(T::to_value).unwrap_or_default()
+    }
+
+    fn describe(property: &mut PropertyDef, schema: &mut Schema) {
+        T::describe(property, schema);
+    }
+}
+
+impl<T: MapProperty> MapProperty for Vec<T> {
+    fn from_value(value: &Value) -> Option<Self> {
+        value.as_array()?.iter().map(T::from_value).collect()
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Array(self.iter().map(T::to_value).collect())
+    }
+
+    /// Arrays name their item type: a primitive name, or the enum or embedded type
+    fn describe(property: &mut PropertyDef, schema: &mut Schema) {
+        let mut item = PropertyDef::new(property.name.clone(), PropType::String);
+        T::describe(&mut item, schema);
+
+        property.prop_type = PropType::Array;
+        property.item_type = Some(
+            item.embedded_type
+                .or(item.enum_type)
+                .or(item.ref_type)
+                .unwrap_or_else(|| {
+                    match item.prop_type {
+                        PropType::Int => "Int",
+                        PropType::Float => "Float",
+                        PropType::Bool => "Bool",
+                        _ => "String",
+                    }
+                    .to_string()
+                }),
+        );
+    }
+}
+
+/// Point properties are stored as `{"x": .., "y": ..}`; `[x, y]` arrays are also accepted
+impl MapProperty for Vec2 {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Object(obj) => Some(Vec2::new(
+                obj.get("x")?.as_float()? as f32,
+                obj.get("y")?.as_float()? as f32,
+            )),
+            Value::Array(arr) if arr.len() == 2 => Some(Vec2::new(
+                arr[0].as_float()? as f32,
+                arr[1].as_float()? as f32,
+            )),
+            _ => None,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Object(HashMap::from([
+            ("x".to_string(), Value::Float(self.x as f64)),
+            ("y".to_string(), Value::Float(self.y as f64)),
+        ]))
+    }
+
+    fn describe(property: &mut PropertyDef, _schema: &mut Schema) {
+        property.prop_type = PropType::Point;
+    }
+}
+
+/// Color properties are stored as `#rrggbb` or `#rrggbbaa` hex strings
+impl MapProperty for Color {
+    fn from_value(value: &Value) -> Option<Self> {
+        Srgba::hex(value.as_string()?).ok().map(Color::from)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_srgba().to_hex().to_lowercase())
+    }
+
+    fn describe(property: &mut PropertyDef, _schema: &mut Schema) {
+        property.prop_type = PropType::Color;
+    }
+}
+
+/// Tile properties are stored as `{"tileset": "<id>", "tile": <index>}`
+impl MapProperty for TileRef {
+    fn from_value(value: &Value) -> Option<Self> {
+        TileRef::from_value(value)
+    }
+
+    fn to_value(&self) -> Value {
+        TileRef::to_value(self)
+    }
+
+    fn describe(property: &mut PropertyDef, _schema: &mut Schema) {
+        property.prop_type = PropType::Tile;
+    }
+}
+
+/// A variant of a flag enum, usable in [`MapFlags`]
+///
+/// Derived by `#[derive(MapProperty)]` on enums marked `#[map_prop(flags)]`:
+///
+/// ```rust,ignore
+/// #[derive(MapProperty, Clone, Copy, Debug)]
+/// #[map_prop(flags)]
+/// pub enum Ability {
+///     Fly,
+///     Swim,
+///     #[map_prop(value = 8)]
+///     Climb,
+/// }
+///
+/// #[derive(Component, MapEntity)]
+/// #[map_entity(type_name = "Creature")]
+/// pub struct Creature {
+///     #[map_prop(default = Ability::Fly | Ability::Swim)]
+///     pub abilities: MapFlags<Ability>,
+/// }
+/// ```
+pub trait MapFlag: Copy + 'static {
+    /// Every flag, in schema order
+    const ALL: &'static [Self];
+
+    /// The flag's bit mask
+    fn bits(self) -> u64;
+
+    /// The flag's name in map data
+    fn name(self) -> &'static str;
+
+    /// Describe the flag enum as a schema property, adding the enum to `schema`
+    fn describe_flags(property: &mut PropertyDef, schema: &mut Schema);
+}
+
+/// A set of flags from a flag enum, like a `bitflags` type
+///
+/// Stored in map data as an array of flag names. Integer bit masks are also
+/// accepted when reading.
+pub struct MapFlags<F> {
+    bits: u64,
+    marker: PhantomData<F>,
+}
+
+impl<F: MapFlag> MapFlags<F> {
+    /// A set with no flags
+    pub const fn empty() -> Self {
+        Self {
+            bits: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// A set with every flag
+    pub fn all() -> Self {
+        F::ALL.iter().copied().collect()
+    }
+
+    /// Create a set from a bit mask, dropping bits that aren't flags
+    pub fn from_bits(bits: u64) -> Self {
+        Self {
+            bits: bits & Self::all().bits,
+            marker: PhantomData,
+        }
+    }
+
+    /// The set's bit mask
+    pub const fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Check if a flag is set
+    pub fn contains(&self, flag: F) -> bool {
+        self.bits & flag.bits() == flag.bits()
+    }
+
+    pub fn insert(&mut self, flag: F) {
+        self.bits |= flag.bits();
+    }
+
+    pub fn remove(&mut self, flag: F) {
+        self.bits &= !flag.bits();
+    }
+
+    /// Insert or remove a flag
+    pub fn set(&mut self, flag: F, value: bool) {
+        if value {
+            self.insert(flag);
+        } else {
+            self.remove(flag);
+        }
+    }
+
+    /// Iterate over the set flags in schema order
+    pub fn iter(&self) -> impl Iterator<Item = F> + '_ {
+        F::ALL.iter().copied().filter(|flag| self.contains(*flag))
+    }
+}
+
+impl<F> Clone for MapFlags<F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<F> Copy for MapFlags<F> {}
+
+impl<F> Default for MapFlags<F> {
+    fn default() -> Self {
+        Self {
+            bits: 0,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<F> PartialEq for MapFlags<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<F> Eq for MapFlags<F> {}
+
+impl<F> Hash for MapFlags<F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
+    }
+}
+
+impl<F: MapFlag> fmt::Debug for MapFlags<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter().map(F::name)).finish()
+    }
+}
+
+impl<F: MapFlag> From<F> for MapFlags<F> {
+    fn from(flag: F) -> Self {
+        Self {
+            bits: flag.bits(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<F: MapFlag> FromIterator<F> for MapFlags<F> {
+    fn from_iter<I: IntoIterator<Item = F>>(iter: I) -> Self {
+        let mut flags = Self::empty();
+        for flag in iter {
+            flags.insert(flag);
+        }
+        flags
+    }
+}
+
+impl<F: MapFlag> BitOr for MapFlags<F> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self {
+            bits: self.bits | rhs.bits,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<F: MapFlag> BitOr<F> for MapFlags<F> {
+    type Output = Self;
+
+    fn bitor(mut self, rhs: F) -> Self {
+        self.insert(rhs);
+        self
+    }
+}
+
+impl<F: MapFlag> BitOrAssign<F> for MapFlags<F> {
+    fn bitor_assign(&mut self, rhs: F) {
+        self.insert(rhs);
+    }
+}
+
+impl<F: MapFlag> BitAnd for MapFlags<F> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self {
+            bits: self.bits & rhs.bits,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<F: MapFlag> MapProperty for MapFlags<F> {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Array(names) => names
+                .iter()
+                .map(|name| {
+                    let name = name.as_string()?;
+                    F::ALL.iter().copied().find(|flag| flag.name() == name)
+                })
+                .collect(),
+            Value::Int(bits) => u64::try_from(*bits).ok().map(Self::from_bits),
+            _ => None,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Array(
+            self.iter()
+                .map(|flag| Value::String(flag.name().to_string()))
+                .collect(),
+        )
+    }
+
+    fn describe(property: &mut PropertyDef, schema: &mut Schema) {
+        F::describe_flags(property, schema);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_property_round_trip() {
+        let point = Vec2::new(3.0, -4.5);
+        assert_eq!(Vec2::from_value(&point.to_value()), Some(point));
+        assert_eq!(
+            Vec2::from_value(&Value::Array(vec![Value::Int(1), Value::Float(2.0)])),
+            Some(Vec2::new(1.0, 2.0))
+        );
+
+        let color = Color::from_value(&Value::String("#ff8000".to_string())).unwrap();
+        assert_eq!(color.to_value(), Value::String("#ff8000".to_string()));
+
+        let list = vec![Some(1u8), None, Some(3)];
+        assert_eq!(Vec::<Option<u8>>::from_value(&list.to_value()), Some(list));
+
+        // Out-of-range and mismatched values are rejected
+        assert_eq!(u8::from_value(&Value::Int(300)), None);
+        assert_eq!(i32::from_value(&Value::String("1".to_string())), None);
+        assert_eq!(
+            Vec::<i32>::from_value(&Value::Array(vec![Value::Int(1), Value::Bool(true)])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        let mut schema = Schema::default();
+        let mut property = PropertyDef::new("flags", PropType::String);
+        Option::<Vec<bool>>::describe(&mut property, &mut schema);
+        assert_eq!(property.prop_type, PropType::Array);
+        assert_eq!(property.item_type.as_deref(), Some("Bool"));
+
+        let mut property = PropertyDef::new("tint", PropType::String);
+        Color::describe(&mut property, &mut schema);
+        assert_eq!(property.prop_type, PropType::Color);
+
+        let mut property = PropertyDef::new("levels", PropType::String);
+        Vec::<u32>::describe(&mut property, &mut schema);
+        assert_eq!(property.item_type.as_deref(), Some("Int"));
+
+        let mut property = PropertyDef::new("icon", PropType::String);
+        TileRef::describe(&mut property, &mut schema);
+        assert_eq!(property.prop_type, PropType::Tile);
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Ability {
+        Fly,
+        Swim,
+        Climb,
+    }
+
+    impl MapFlag for Ability {
+        const ALL: &'static [Self] = &[Ability::Fly, Ability::Swim, Ability::Climb];
+
+        fn bits(self) -> u64 {
+            match self {
+                Ability::Fly => 1,
+                Ability::Swim => 2,
+                Ability::Climb => 8,
+            }
+        }
+
+        fn name(self) -> &'static str {
+            match self {
+                Ability::Fly => "Fly",
+                Ability::Swim => "Swim",
+                Ability::Climb => "Climb",
+            }
+        }
+
+        fn describe_flags(property: &mut PropertyDef, schema: &mut Schema) {
+            property.prop_type = PropType::Enum;
+            property.enum_type = Some("Ability".to_string());
+            schema.enums.insert(
+                "Ability".to_string(),
+                bevy_map_schema::EnumDef::flags(["Fly", "Swim", "Climb"]),
+            );
+        }
+    }
+
+    #[test]
+    fn test_map_flags() {
+        let mut flags = MapFlags::from(Ability::Fly) | Ability::Climb;
+        assert!(flags.contains(Ability::Climb) && !flags.contains(Ability::Swim));
+        assert_eq!(flags.bits(), 9);
+
+        let value = flags.to_value();
+        assert_eq!(
+            value,
+            Value::Array(vec![
+                Value::String("Fly".to_string()),
+                Value::String("Climb".to_string()),
+            ])
+        );
+        assert_eq!(MapFlags::<Ability>::from_value(&value), Some(flags));
+        assert_eq!(
+            MapFlags::<Ability>::from_value(&Value::Int(2 | 4)),
+            Some(MapFlags::from(Ability::Swim))
+        );
+        assert_eq!(
+            MapFlags::<Ability>::from_value(&Value::Array(vec![Value::String(
+                "Burrow".to_string()
+            )])),
+            None
+        );
+
+        flags.set(Ability::Fly, false);
+        assert_eq!(flags.iter().collect::<Vec<_>>(), vec![Ability::Climb]);
+        assert_eq!(
+            format!("{:?}", MapFlags::<Ability>::all()),
+            r#"{"Fly", "Swim", "Climb"}"#
+        );
+
+        let mut schema = Schema::default();
+        let mut property = PropertyDef::new("abilities", PropType::String);
+        MapFlags::<Ability>::describe(&mut property, &mut schema);
+        assert_eq!(property.prop_type, PropType::Enum);
+        assert!(schema.enums["Ability"].flags);
+    }
+
+    #[test]
+    fn test_read_map_property() {
+        let mut instance = EntityInstance::new("Test".to_string(), [0.0, 0.0]);
+        instance.set_int("health", 10);
+        instance
+            .properties
+            .insert("target".to_string(), Value::Null);
+
+        assert_eq!(read_map_property::<i32>(&instance, "health"), Some(10));
+        assert_eq!(read_map_property::<String>(&instance, "health"), None);
+        assert_eq!(read_map_property::<i32>(&instance, "missing"), None);
+        assert_eq!(read_map_property::<i32>(&instance, "target"), None);
+        assert_eq!(
+            read_map_property::<Option<Vec2>>(&instance, "target"),
+            Some(None)
+        );
+    }
+}
@@ -109,8 +109,7 @@ pub fn spawn_sprite_components(
         let initial_rect = if let Some(anim) = sprite_data.animations.values().next() {
             if let Some(&first_frame) = anim.frames.first() {
                 let columns = sprite_data.columns;
-                if columns > 0 {
-                    let row = first_frame as u32 / columns;
+                if let Some(row) = (first_frame as u32).checked_div(columns) {
                     let col = first_frame as u32 % columns;
                     Some(bevy::math::Rect::new(
                         col as f32 * sprite_data.frame_width as f32,
@@ -171,8 +170,7 @@ pub fn complete_sprite_loads(mut query: Query<(&SpriteSlot, &mut Sprite)>) {
         if let Some(anim) = sprite_data.animations.values().next() {
             if let Some(&first_frame) = anim.frames.first() {
                 let columns = sprite_data.columns;
-                if columns > 0 {
-                    let row = first_frame as u32 / columns;
+                if let Some(row) = (first_frame as u32).checked_div(columns) {
                     let col = first_frame as u32 % columns;
 
                     sprite.rect = Some(Rect {
@@ -0,0 +1,415 @@
+//! Camera-driven level streaming for multi-level worlds
+//!
+//! [`WorldStreamingPlugin`] keeps the levels of a [`WorldProject`] near the
+//! camera spawned and unloads the ones that fall out of range. Levels linked
+//! to the current level by a `LevelConnection` can be kept loaded as well, so
+//! transitions never wait on a spawn.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use bevy_map_runtime::streaming::{WorldStreaming, WorldStreamingPlugin};
+//!
+//! app.add_plugins((MapRuntimePlugin, WorldStreamingPlugin));
+//!
+//! fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     commands.spawn(Camera2d);
+//!     commands.insert_resource(WorldStreaming::new(asset_server.load("maps/world.map.json")));
+//! }
+//!
+//! fn save_level_state(mut unloaded: MessageReader<LevelUnloadedEvent>) {
+//!     for event in unloaded.read() {
+//!         // The level's entities are still alive until the next frame
+//!     }
+//! }
+//! ```
+//!
+//! Distances are measured from the focus point to each level's rectangle.
+//! The focus is every entity with a [`StreamingFocus`] component, or the
+//! first 2D camera if there is none.
+
+use crate::world::SpawnedWorldLevel;
+use crate::{MapRoot, WorldLevel};
+use bevy::ecs::message::{Message, MessageWriter};
+use bevy::prelude::*;
+use bevy_map_core::WorldProject;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Plugin that streams world levels in and out around the camera
+///
+/// Requires `MapRuntimePlugin`.
+pub struct WorldStreamingPlugin;
+
+impl Plugin for WorldStreamingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldStreaming>()
+            .add_message::<LevelLoadedEvent>()
+            .add_message::<LevelUnloadedEvent>()
+            .add_systems(Update, (stream_world_levels, emit_level_loaded_events));
+    }
+}
+
+/// Streaming configuration and state
+#[derive(Resource, Debug, Clone)]
+pub struct WorldStreaming {
+    /// World to stream; nothing is streamed while this is `None`
+    pub world: Option<Handle<WorldProject>>,
+    /// Levels closer than this many pixels to the focus are loaded
+    pub load_radius: f32,
+    /// Streamed levels farther than this are unloaded
+    ///
+    /// Keep it above `load_radius` so levels on the border don't thrash.
+    pub unload_radius: f32,
+    /// Also load levels connected to the current level
+    pub load_connected: bool,
+    /// Level containing the focus, kept while the focus is between levels
+    current_level: Option<Uuid>,
+}
+
+impl Default for WorldStreaming {
+    fn default() -> Self {
+        Self {
+            world: None,
+            load_radius: 256.0,
+            unload_radius: 512.0,
+            load_connected: true,
+            current_level: None,
+        }
+    }
+}
+
+impl WorldStreaming {
+    /// Stream the given world with default radii
+    pub fn new(world: Handle<WorldProject>) -> Self {
+        Self {
+            world: Some(world),
+            ..Default::default()
+        }
+    }
+
+    /// Set the load and unload radii in pixels
+    pub fn with_radius(mut self, load_radius: f32, unload_radius: f32) -> Self {
+        self.load_radius = load_radius;
+        self.unload_radius = unload_radius.max(load_radius);
+        self
+    }
+
+    /// Set whether levels connected to the current level are loaded
+    pub fn with_connected(mut self, load_connected: bool) -> Self {
+        self.load_connected = load_connected;
+        self
+    }
+
+    /// Level the focus is currently in
+    pub fn current_level(&self) -> Option<Uuid> {
+        self.current_level
+    }
+}
+
+/// Marks the entities levels are streamed around (defaults to the 2D camera)
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct StreamingFocus;
+
+/// Component on [`WorldLevel`] entities spawned by the streaming system
+///
+/// Levels spawned manually are never unloaded by streaming.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct StreamedLevel {
+    /// ID of the streamed level
+    pub level_id: Uuid,
+}
+
+/// Marks a streamed level that will be despawned on the next update
+#[derive(Component, Debug, Clone, Copy)]
+pub struct PendingUnload;
+
+/// Emitted when a streamed level's map has been spawned
+#[derive(Message, Debug, Clone)]
+pub struct LevelLoadedEvent {
+    /// ID of the loaded level
+    pub level_id: Uuid,
+    /// The [`WorldLevel`] entity
+    pub level: Entity,
+    /// The level's [`MapRoot`] entity
+    pub map: Entity,
+}
+
+/// Emitted when a streamed level is about to be unloaded
+///
+/// The level's entities stay alive until the next update, so readers can
+/// still save their state.
+#[derive(Message, Debug, Clone)]
+pub struct LevelUnloadedEvent {
+    /// ID of the unloaded level
+    pub level_id: Uuid,
+    /// The [`WorldLevel`] entity
+    pub level: Entity,
+}
+
+/// System that spawns levels near the focus and unloads distant ones
+pub fn stream_world_levels(
+    mut commands: Commands,
+    mut streaming: ResMut<WorldStreaming>,
+    worlds: Res<Assets<WorldProject>>,
+    focus_query: Query<&GlobalTransform, With<StreamingFocus>>,
+    camera_query: Query<&GlobalTransform, With<Camera2d>>,
+    streamed_query: Query<(Entity, &StreamedLevel, Has<PendingUnload>)>,
+    manual_query: Query<(&WorldLevel, Option<&SpawnedWorldLevel>), Without<StreamedLevel>>,
+    mut unloaded_events: MessageWriter<LevelUnloadedEvent>,
+) {
+    // Levels marked last update are despawned now, after readers saw the event
+    let mut loaded = HashSet::new();
+    for (entity, streamed, pending) in streamed_query.iter() {
+        if pending {
+            commands.entity(entity).despawn();
+        } else {
+            loaded.insert(streamed.level_id);
+        }
+    }
+
+    let Some(handle) = streaming.world.clone() else {
+        return;
+    };
+    let Some(world) = worlds.get(&handle) else {
+        return;
+    };
+
+    // Levels spawned manually are never streamed a second time
+    for (requested, spawned) in manual_query.iter() {
+        if requested.world.id() != handle.id() {
+            continue;
+        }
+        let level_id = spawned
+            .map(|s| s.level_id)
+            .or_else(|| requested.level.resolve(world).map(|level| level.id));
+        loaded.extend(level_id);
+    }
+
+    // Focus points in world pixels (Y down, as stored on levels)
+    let mut focus: Vec<Vec2> = focus_query
+        .iter()
+        .map(|transform| transform.translation().truncate())
+        .collect();
+    if focus.is_empty() {
+        focus.extend(
+            camera_query
+                .iter()
+                .next()
+                .map(|transform| transform.translation().truncate()),
+        );
+    }
+    let focus: Vec<(f32, f32)> = focus.into_iter().map(|p| (p.x, -p.y)).collect();
+    if focus.is_empty() {
+        return;
+    }
+
+    if let Some(level) = world.level_at(focus[0].0, focus[0].1) {
+        streaming.current_level = Some(level.id);
+    }
+    let current = streaming
+        .current_level
+        .filter(|id| world.get_level(*id).is_some());
+
+    let mut pinned: HashSet<Uuid> = current.into_iter().collect();
+    if streaming.load_connected {
+        if let Some(current) = current {
+            pinned.extend(world.connections_from(current).iter().map(|c| c.to_level));
+            pinned.extend(
+                world
+                    .world
+                    .connections_to(current)
+                    .iter()
+                    .map(|c| c.from_level),
+            );
+        }
+    }
+
+    let distance = |id: Uuid| {
+        world
+            .get_level(id)
+            .map(|level| {
+                focus
+                    .iter()
+                    .map(|(x, y)| world.distance_to_level(level, *x, *y))
+                    .fold(f32::INFINITY, f32::min)
+            })
+            .unwrap_or(f32::INFINITY)
+    };
+
+    // Unloading levels stay in `loaded` so they are not respawned right away
+    for (entity, streamed, pending) in streamed_query.iter() {
+        if pending || pinned.contains(&streamed.level_id) {
+            continue;
+        }
+        if distance(streamed.level_id) > streaming.unload_radius {
+            commands.entity(entity).insert(PendingUnload);
+            unloaded_events.write(LevelUnloadedEvent {
+                level_id: streamed.level_id,
+                level: entity,
+            });
+        }
+    }
+
+    for level_id in world.level_ids() {
+        if loaded.contains(&level_id) {
+            continue;
+        }
+        if pinned.contains(&level_id) || distance(level_id) <= streaming.load_radius {
+            commands.spawn((
+                WorldLevel::new(handle.clone(), level_id),
+                StreamedLevel { level_id },
+            ));
+        }
+    }
+}
+
+/// System that emits [`LevelLoadedEvent`] once a streamed level's map is spawned
+pub fn emit_level_loaded_events(
+    map_query: Query<(Entity, &ChildOf), Added<MapRoot>>,
+    streamed_query: Query<&StreamedLevel>,
+    mut loaded_events: MessageWriter<LevelLoadedEvent>,
+) {
+    for (map, child_of) in map_query.iter() {
+        let level = child_of.parent();
+        if let Ok(streamed) = streamed_query.get(level) {
+            loaded_events.write(LevelLoadedEvent {
+                level_id: streamed.level_id,
+                level,
+                map,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TilesetTextures;
+    use bevy::ecs::message::Messages;
+    use bevy_map_core::{ConnectionDirection, Level, LevelConnection, MapProject, WorldConfig};
+
+    /// Levels of 320x320 pixels: A and B side by side, C and D far away, A linked to D
+    fn test_world() -> (WorldProject, [Uuid; 4]) {
+        let levels = vec![
+            Level::new_at("A".to_string(), 10, 10, 0, 0),
+            Level::new_at("B".to_string(), 10, 10, 320, 0),
+            Level::new_at("C".to_string(), 10, 10, 2000, 0),
+            Level::new_at("D".to_string(), 10, 10, 4000, 0),
+        ];
+        let ids = [levels[0].id, levels[1].id, levels[2].id, levels[3].id];
+        let mut world_config = WorldConfig::default();
+        world_config.add_connection(LevelConnection::auto_direction(
+            ids[0],
+            ConnectionDirection::South,
+            ids[3],
+        ));
+        let world = WorldProject::new(levels, Vec::new(), world_config);
+        (world, ids)
+    }
+
+    fn streamed_levels(app: &mut App) -> HashSet<Uuid> {
+        let mut query = app
+            .world_mut()
+            .query_filtered::<&StreamedLevel, Without<PendingUnload>>();
+        query
+            .iter(app.world())
+            .map(|streamed| streamed.level_id)
+            .collect()
+    }
+
+    #[test]
+    fn test_levels_stream_around_focus() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            AssetPlugin::default(),
+            WorldStreamingPlugin,
+        ))
+        .init_asset::<WorldProject>()
+        .init_asset::<MapProject>()
+        .add_systems(Update, crate::world::spawn_world_levels);
+
+        let (world, [a, b, c, d]) = test_world();
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<WorldProject>>()
+            .add(world);
+        // A manually spawned level is not streamed a second time
+        let manual = app
+            .world_mut()
+            .spawn(WorldLevel::new(handle.clone(), "B"))
+            .id();
+        app.insert_resource(WorldStreaming::new(handle));
+        let focus = app
+            .world_mut()
+            .spawn((StreamingFocus, Transform::from_xyz(160.0, -160.0, 0.0)))
+            .id();
+
+        app.update();
+        app.update();
+        // B is within the load radius but already spawned, D is connected to A
+        assert_eq!(streamed_levels(&mut app), HashSet::from([a, d]));
+        assert_eq!(
+            app.world()
+                .get::<SpawnedWorldLevel>(manual)
+                .unwrap()
+                .level_id,
+            b
+        );
+        assert_eq!(
+            app.world().resource::<WorldStreaming>().current_level(),
+            Some(a)
+        );
+
+        app.world_mut()
+            .entity_mut(focus)
+            .insert(Transform::from_xyz(2160.0, -160.0, 0.0));
+        app.update();
+        app.update();
+        assert_eq!(streamed_levels(&mut app), HashSet::from([c]));
+        let unloaded: HashSet<Uuid> = app
+            .world_mut()
+            .resource_mut::<Messages<LevelUnloadedEvent>>()
+            .drain()
+            .map(|event| event.level_id)
+            .collect();
+        assert_eq!(unloaded, HashSet::from([a, d]));
+
+        // Unloaded levels are despawned one update after the event
+        app.update();
+        let mut query = app.world_mut().query::<&StreamedLevel>();
+        assert_eq!(query.iter(app.world()).count(), 1);
+    }
+
+    #[test]
+    fn test_loaded_event_for_streamed_maps() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<MapProject>()
+            .add_message::<LevelLoadedEvent>()
+            .add_systems(Update, emit_level_loaded_events);
+
+        let level_id = Uuid::new_v4();
+        let level = app.world_mut().spawn(StreamedLevel { level_id }).id();
+        let map = app
+            .world_mut()
+            .spawn((
+                MapRoot::new(Handle::default(), TilesetTextures::new()),
+                ChildOf(level),
+            ))
+            .id();
+        app.update();
+
+        let events: Vec<LevelLoadedEvent> = app
+            .world_mut()
+            .resource_mut::<Messages<LevelLoadedEvent>>()
+            .drain()
+            .collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].level_id, level_id);
+        assert_eq!(events[0].level, level);
+        assert_eq!(events[0].map, map);
+    }
+}
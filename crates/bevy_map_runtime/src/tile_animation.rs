@@ -0,0 +1,66 @@
+//! Animated tile playback for runtime tilemaps
+//!
+//! Tiles with `animation_frames` in their `TileProperties` get an
+//! `AnimatedMapTile` component when the map is spawned. The
+//! `animate_map_tiles` system then swaps the tile's `TileTextureIndex`
+//! based on the elapsed game time, so all tiles sharing an animation stay
+//! in sync and pausing `Time<Virtual>` pauses tile animation too.
+//!
+//! Animation frames are virtual tile indices. Because a tilemap only holds a
+//! single texture, frames that live in a different image of a multi-image
+//! tileset than the animated tile are skipped. They are reported once per
+//! tile when the map is loaded.
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use bevy_map_core::{TileAnimation, Tileset};
+
+/// Component driving the texture index of an animated tile
+#[derive(Component, Debug, Clone, Deref)]
+pub struct AnimatedMapTile(pub TileAnimation);
+
+impl AnimatedMapTile {
+    /// Build the animation of a tile, with frames local to `image_index`
+    ///
+    /// Returns None if the tile has fewer than two usable frames in `image_index`.
+    pub fn from_tileset(tileset: &Tileset, tile_index: u32, image_index: usize) -> Option<Self> {
+        tileset.tile_animation(tile_index, image_index).map(Self)
+    }
+}
+
+/// Log animation frames that can't be played, once per animated tile
+pub(crate) fn warn_invalid_animation_frames<'a>(tilesets: impl IntoIterator<Item = &'a Tileset>) {
+    for tileset in tilesets {
+        // Pairs are sorted by tile, so each tile's frames are adjacent
+        let mut by_tile: Vec<(u32, Vec<String>)> = Vec::new();
+        for (tile, frame) in tileset.invalid_animation_frames() {
+            match by_tile.last_mut() {
+                Some((last, frames)) if *last == tile => frames.push(frame.to_string()),
+                _ => by_tile.push((tile, vec![frame.to_string()])),
+            }
+        }
+        for (tile, frames) in by_tile {
+            warn!(
+                "Tileset '{}': animation frames [{}] of tile {} are not in the tile's image, skipping",
+                tileset.name,
+                frames.join(", "),
+                tile
+            );
+        }
+    }
+}
+
+/// System that advances all animated tiles
+pub fn animate_map_tiles(
+    time: Res<Time>,
+    mut query: Query<(&AnimatedMapTile, &mut TileTextureIndex)>,
+) {
+    let elapsed = time.elapsed_secs();
+    for (animation, mut texture_index) in query.iter_mut() {
+        if let Some(frame) = animation.frame_at(elapsed) {
+            if texture_index.0 != frame {
+                texture_index.0 = frame;
+            }
+        }
+    }
+}
@@ -0,0 +1,414 @@
+//! Level transitions through world connections
+//!
+//! [`LevelTransitionPlugin`] watches entities with a [`LevelTraveler`]
+//! component. When one leaves its level through an edge that has a
+//! `LevelConnection`, the target level is spawned, the traveler is moved to the
+//! matching point on the target's entry edge (keeping its offset along the
+//! edge) and [`LevelTransitionStartEvent`]/[`LevelTransitionEndEvent`] are
+//! sent.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use bevy_map_runtime::transition::{LevelTransitionPlugin, LevelTransitions, LevelTraveler};
+//!
+//! app.add_plugins((MapRuntimePlugin, LevelTransitionPlugin));
+//!
+//! fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     let world: Handle<WorldProject> = asset_server.load("maps/world.map.json");
+//!     commands.spawn_world_level(world.clone(), "Start");
+//!     // Leave 0.3s for a fade before the player is moved
+//!     commands.insert_resource(LevelTransitions::new(world).with_duration(0.3));
+//!     commands.spawn((Player, LevelTraveler::default(), Transform::from_xyz(48.0, -48.0, 1.0)));
+//! }
+//!
+//! fn fade(transitions: Query<&ActiveLevelTransition>, mut overlay: Single<&mut BackgroundColor, With<Fade>>) {
+//!     if let Ok(transition) = transitions.single() {
+//!         overlay.0.set_alpha(transition.progress());
+//!     }
+//! }
+//! ```
+//!
+//! The traveler's `Transform` is treated as world space, so travelers should
+//! not be parented to a level. With `WorldStreamingPlugin`, streaming decides
+//! which levels stay loaded; without it the previous level is despawned once
+//! the transition ends (see [`LevelTransitions::unload_previous`]).
+
+use crate::streaming::{StreamedLevel, WorldStreaming};
+use crate::world::{LevelRef, SpawnedWorldLevel, WorldLevel};
+use bevy::ecs::message::{Message, MessageWriter};
+use bevy::prelude::*;
+use bevy_map_core::{ConnectionDirection, WorldProject};
+use uuid::Uuid;
+
+/// Plugin that moves travelers between connected levels
+///
+/// Requires `MapRuntimePlugin`. Works with or without `WorldStreamingPlugin`.
+pub struct LevelTransitionPlugin;
+
+impl Plugin for LevelTransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelTransitions>()
+            .add_message::<LevelTransitionStartEvent>()
+            .add_message::<LevelTransitionEndEvent>()
+            .add_systems(
+                Update,
+                (advance_level_transitions, detect_level_exits)
+                    .chain()
+                    // Streaming spawns first so a target level is never spawned twice
+                    .after(crate::streaming::stream_world_levels),
+            );
+    }
+}
+
+/// Transition configuration
+#[derive(Resource, Debug, Clone)]
+pub struct LevelTransitions {
+    /// World to transition in; falls back to the streamed world when `None`
+    pub world: Option<Handle<WorldProject>>,
+    /// Seconds between leaving a level and being moved into the next one
+    ///
+    /// Use it to play a fade or slide; `0` moves the traveler at once.
+    pub duration: f32,
+    /// Distance in pixels from the entry edge the traveler is placed at
+    pub entry_inset: f32,
+    /// Despawn the previous level when a transition ends
+    ///
+    /// Ignored while `WorldStreaming` streams the same world.
+    pub unload_previous: bool,
+}
+
+impl Default for LevelTransitions {
+    fn default() -> Self {
+        Self {
+            world: None,
+            duration: 0.0,
+            entry_inset: 16.0,
+            unload_previous: true,
+        }
+    }
+}
+
+impl LevelTransitions {
+    /// Transition in the given world
+    pub fn new(world: Handle<WorldProject>) -> Self {
+        Self {
+            world: Some(world),
+            ..Default::default()
+        }
+    }
+
+    /// Set the transition duration in seconds
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = duration.max(0.0);
+        self
+    }
+
+    /// Set the distance from the entry edge
+    pub fn with_entry_inset(mut self, entry_inset: f32) -> Self {
+        self.entry_inset = entry_inset;
+        self
+    }
+}
+
+/// Marks an entity that moves between levels through connections
+#[derive(Component, Debug, Clone, Default)]
+pub struct LevelTraveler {
+    /// Level the traveler is in, detected from its position when `None`
+    pub level: Option<Uuid>,
+}
+
+impl LevelTraveler {
+    /// Start a traveler in a known level
+    pub fn in_level(level: Uuid) -> Self {
+        Self { level: Some(level) }
+    }
+}
+
+/// Present on a traveler while it moves between two levels
+///
+/// Pause player control while this component exists, and read
+/// [`ActiveLevelTransition::progress`] to drive a fade or slide.
+#[derive(Component, Debug, Clone)]
+pub struct ActiveLevelTransition {
+    /// Level being left
+    pub from_level: Uuid,
+    /// Level being entered
+    pub to_level: Uuid,
+    /// Edge of the previous level the traveler left through
+    pub exit_edge: ConnectionDirection,
+    /// Edge of the next level the traveler enters through
+    pub entry_edge: ConnectionDirection,
+    /// World position where the traveler left
+    pub exit_position: Vec2,
+    /// World position the traveler is moved to
+    pub entry_position: Vec2,
+    timer: Timer,
+}
+
+impl ActiveLevelTransition {
+    /// Fraction of the transition that has elapsed, from 0 to 1
+    pub fn progress(&self) -> f32 {
+        self.timer.fraction()
+    }
+}
+
+/// Sent when a traveler leaves a level through a connected edge
+#[derive(Message, Debug, Clone)]
+pub struct LevelTransitionStartEvent {
+    /// The traveling entity
+    pub traveler: Entity,
+    /// Level being left
+    pub from_level: Uuid,
+    /// Level being entered
+    pub to_level: Uuid,
+    /// Edge of the previous level the traveler left through
+    pub exit_edge: ConnectionDirection,
+    /// Edge of the next level the traveler enters through
+    pub entry_edge: ConnectionDirection,
+    /// World position where the traveler left
+    pub exit_position: Vec2,
+    /// World position the traveler will be moved to
+    pub entry_position: Vec2,
+}
+
+/// Sent when a traveler has been moved into the next level
+#[derive(Message, Debug, Clone)]
+pub struct LevelTransitionEndEvent {
+    /// The traveling entity
+    pub traveler: Entity,
+    /// Level that was left
+    pub from_level: Uuid,
+    /// Level the traveler is now in
+    pub to_level: Uuid,
+}
+
+/// System that starts transitions when travelers leave through a connected edge
+pub fn detect_level_exits(
+    mut commands: Commands,
+    settings: Res<LevelTransitions>,
+    streaming: Option<Res<WorldStreaming>>,
+    worlds: Res<Assets<WorldProject>>,
+    mut travelers: Query<(Entity, &mut LevelTraveler, &Transform), Without<ActiveLevelTransition>>,
+    levels: Query<(&WorldLevel, Option<&SpawnedWorldLevel>)>,
+    mut start_events: MessageWriter<LevelTransitionStartEvent>,
+) {
+    let streamed_world = streaming.as_ref().and_then(|s| s.world.clone());
+    let Some(handle) = settings.world.clone().or(streamed_world.clone()) else {
+        return;
+    };
+    let Some(world) = worlds.get(&handle) else {
+        return;
+    };
+    let streamed = streamed_world.is_some_and(|s| s.id() == handle.id());
+
+    for (entity, mut traveler, transform) in travelers.iter_mut() {
+        let position = transform.translation.truncate();
+        let (x, y) = (position.x, -position.y);
+
+        let Some(current) = traveler.level.and_then(|id| world.get_level(id)) else {
+            traveler.level = world.level_at(x, y).map(|level| level.id);
+            continue;
+        };
+        let Some(exit_edge) = world.exit_edge(current, x, y) else {
+            continue;
+        };
+        let Some((to_level, entry_edge)) = world.exit_through(current.id, exit_edge) else {
+            // Walking into an unconnected neighbor still updates the level
+            if let Some(level) = world.level_at(x, y) {
+                traveler.level = Some(level.id);
+            }
+            continue;
+        };
+        let Some(target) = world.get_level(to_level) else {
+            continue;
+        };
+
+        let (entry_x, entry_y) = world.transition_entry(
+            current,
+            exit_edge,
+            target,
+            entry_edge,
+            (x, y),
+            settings.entry_inset,
+        );
+        let entry_position = Vec2::new(entry_x, -entry_y);
+
+        let target_spawned = levels.iter().any(|(requested, spawned)| {
+            requested.world.id() == handle.id()
+                && (requested.level == LevelRef::Id(to_level)
+                    || spawned.is_some_and(|s| s.level_id == to_level))
+        });
+        if !target_spawned {
+            let mut level = commands.spawn(WorldLevel::new(handle.clone(), to_level));
+            if streamed {
+                level.insert(StreamedLevel { level_id: to_level });
+            }
+        }
+
+        start_events.write(LevelTransitionStartEvent {
+            traveler: entity,
+            from_level: current.id,
+            to_level,
+            exit_edge,
+            entry_edge,
+            exit_position: position,
+            entry_position,
+        });
+        commands.entity(entity).insert(ActiveLevelTransition {
+            from_level: current.id,
+            to_level,
+            exit_edge,
+            entry_edge,
+            exit_position: position,
+            entry_position,
+            timer: Timer::from_seconds(settings.duration, TimerMode::Once),
+        });
+    }
+}
+
+/// System that finishes transitions once their duration has elapsed
+pub fn advance_level_transitions(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<LevelTransitions>,
+    streaming: Option<Res<WorldStreaming>>,
+    mut travelers: Query<(
+        Entity,
+        &mut LevelTraveler,
+        &mut Transform,
+        &mut ActiveLevelTransition,
+    )>,
+    levels: Query<(Entity, &WorldLevel, &SpawnedWorldLevel)>,
+    mut end_events: MessageWriter<LevelTransitionEndEvent>,
+) {
+    let streamed_world = streaming.as_ref().and_then(|s| s.world.clone());
+    let handle = settings.world.clone().or(streamed_world.clone());
+    let streamed = match (&handle, &streamed_world) {
+        (Some(handle), Some(streamed_world)) => handle.id() == streamed_world.id(),
+        _ => false,
+    };
+
+    for (entity, mut traveler, mut transform, mut transition) in travelers.iter_mut() {
+        transition.timer.tick(time.delta());
+        if !transition.timer.is_finished() {
+            continue;
+        }
+
+        transform.translation.x = transition.entry_position.x;
+        transform.translation.y = transition.entry_position.y;
+        traveler.level = Some(transition.to_level);
+
+        if settings.unload_previous && !streamed {
+            for (level_entity, requested, spawned) in levels.iter() {
+                let same_world = handle
+                    .as_ref()
+                    .is_some_and(|h| h.id() == requested.world.id());
+                if same_world && spawned.level_id == transition.from_level {
+                    commands.entity(level_entity).despawn();
+                }
+            }
+        }
+
+        end_events.write(LevelTransitionEndEvent {
+            traveler: entity,
+            from_level: transition.from_level,
+            to_level: transition.to_level,
+        });
+        commands.entity(entity).remove::<ActiveLevelTransition>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::spawn_world_levels;
+    use bevy::ecs::message::Messages;
+    use bevy_map_core::{Level, LevelConnection, MapProject, WorldConfig};
+
+    #[test]
+    fn test_traveler_moves_through_connection() {
+        // A covers 0..320 x 0..320, B covers 320..640 x 64..384 (world pixels, Y down)
+        let a = Level::new_at("A".to_string(), 10, 10, 0, 0);
+        let b = Level::new_at("B".to_string(), 10, 10, 320, 64);
+        let (a_id, b_id) = (a.id, b.id);
+        let mut world_config = WorldConfig::default();
+        world_config.add_connection(LevelConnection::auto_direction(
+            a_id,
+            ConnectionDirection::East,
+            b_id,
+        ));
+        let world = WorldProject::new(vec![a, b], Vec::new(), world_config);
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            LevelTransitionPlugin,
+        ))
+        .init_asset::<WorldProject>()
+        .init_asset::<MapProject>()
+        .add_systems(Update, spawn_world_levels);
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<WorldProject>>()
+            .add(world);
+        app.insert_resource(LevelTransitions::new(handle.clone()));
+        let level_a = app
+            .world_mut()
+            .spawn(WorldLevel::new(handle.clone(), a_id))
+            .id();
+        let traveler = app
+            .world_mut()
+            .spawn((
+                LevelTraveler::default(),
+                Transform::from_xyz(100.0, -100.0, 1.0),
+            ))
+            .id();
+
+        app.update();
+        assert_eq!(
+            app.world().get::<LevelTraveler>(traveler).unwrap().level,
+            Some(a_id)
+        );
+
+        // Step over A's east edge, 100 pixels below its top
+        app.world_mut()
+            .get_mut::<Transform>(traveler)
+            .unwrap()
+            .translation
+            .x = 330.0;
+        app.update();
+        let started: Vec<LevelTransitionStartEvent> = app
+            .world_mut()
+            .resource_mut::<Messages<LevelTransitionStartEvent>>()
+            .drain()
+            .collect();
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0].to_level, b_id);
+        assert_eq!(started[0].entry_edge, ConnectionDirection::West);
+        assert_eq!(started[0].entry_position, Vec2::new(336.0, -164.0));
+        assert!(app.world().get::<ActiveLevelTransition>(traveler).is_some());
+
+        app.update();
+        let transform = app.world().get::<Transform>(traveler).unwrap();
+        assert_eq!(transform.translation, Vec3::new(336.0, -164.0, 1.0));
+        assert_eq!(
+            app.world().get::<LevelTraveler>(traveler).unwrap().level,
+            Some(b_id)
+        );
+        assert!(app.world().get::<ActiveLevelTransition>(traveler).is_none());
+        let ended = app
+            .world_mut()
+            .resource_mut::<Messages<LevelTransitionEndEvent>>()
+            .drain()
+            .count();
+        assert_eq!(ended, 1);
+
+        // B was spawned for the transition and A unloaded afterwards
+        assert!(app.world().get_entity(level_a).is_err());
+        let mut query = app.world_mut().query::<&SpawnedWorldLevel>();
+        let spawned: Vec<Uuid> = query.iter(app.world()).map(|s| s.level_id).collect();
+        assert_eq!(spawned, vec![b_id]);
+    }
+}
@@ -0,0 +1,320 @@
+//! Spawning individual levels of a multi-level world
+//!
+//! A [`WorldProject`] asset keeps every level of an editor project together
+//! with the world layout and level connections. Load it with
+//! `asset_server.load::<WorldProject>(path)` and spawn levels by name or UUID;
+//! each level is placed at its `world_x`/`world_y` position.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use bevy_map_runtime::{WorldCommandsExt, WorldProject};
+//!
+//! #[derive(Resource)]
+//! struct GameWorld(Handle<WorldProject>);
+//!
+//! fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     let world: Handle<WorldProject> = asset_server.load("maps/world.map.json");
+//!     commands.spawn_world_level(world.clone(), "Start");
+//!     commands.insert_resource(GameWorld(world));
+//! }
+//!
+//! fn leave_start(mut commands: Commands, world: Res<GameWorld>) {
+//!     commands.despawn_world_level(world.0.clone(), "Start");
+//! }
+//! ```
+//!
+//! Spawned levels go through the regular [`MapHandle`] pipeline, so
+//! collision, navigation, entities and hot-reload work as for single maps.
+
+use crate::MapHandle;
+use bevy::asset::AssetEvent;
+use bevy::ecs::message::MessageReader;
+use bevy::prelude::*;
+use bevy_map_core::{Level, MapProject, WorldProject};
+use uuid::Uuid;
+
+/// Identifies a level of a [`WorldProject`] by UUID or by name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LevelRef {
+    /// Level with this ID
+    Id(Uuid),
+    /// First level with this name
+    Name(String),
+}
+
+impl LevelRef {
+    /// Find the referenced level in a world
+    pub fn resolve<'a>(&self, world: &'a WorldProject) -> Option<&'a Level> {
+        match self {
+            LevelRef::Id(id) => world.get_level(*id),
+            LevelRef::Name(name) => world.level_by_name(name),
+        }
+    }
+}
+
+impl From<Uuid> for LevelRef {
+    fn from(id: Uuid) -> Self {
+        LevelRef::Id(id)
+    }
+}
+
+impl From<&str> for LevelRef {
+    fn from(name: &str) -> Self {
+        LevelRef::Name(name.to_string())
+    }
+}
+
+impl From<String> for LevelRef {
+    fn from(name: String) -> Self {
+        LevelRef::Name(name)
+    }
+}
+
+/// Component requesting one level of a [`WorldProject`]
+///
+/// Once the world asset is loaded, the level is placed at its world position
+/// and spawned as a child map. Despawn the entity to unload the level.
+#[derive(Component, Debug, Clone)]
+#[require(Transform, Visibility)]
+pub struct WorldLevel {
+    /// Handle to the world containing the level
+    pub world: Handle<WorldProject>,
+    /// Which level to spawn
+    pub level: LevelRef,
+}
+
+impl WorldLevel {
+    /// Create a new WorldLevel request
+    pub fn new(world: Handle<WorldProject>, level: impl Into<LevelRef>) -> Self {
+        Self {
+            world,
+            level: level.into(),
+        }
+    }
+}
+
+/// Added to a [`WorldLevel`] entity once its level has been resolved
+#[derive(Component, Debug, Clone)]
+pub struct SpawnedWorldLevel {
+    /// ID of the spawned level
+    pub level_id: Uuid,
+    /// Single-level project generated for the level
+    pub map: Handle<MapProject>,
+}
+
+/// Extension trait for spawning and despawning world levels via commands
+pub trait WorldCommandsExt {
+    /// Spawn a level of a world at its world position
+    ///
+    /// Returns the entity that will contain the level once loaded.
+    fn spawn_world_level(
+        &mut self,
+        world: Handle<WorldProject>,
+        level: impl Into<LevelRef>,
+    ) -> Entity;
+
+    /// Despawn every spawned instance of a level of a world
+    fn despawn_world_level(&mut self, world: Handle<WorldProject>, level: impl Into<LevelRef>);
+}
+
+impl WorldCommandsExt for Commands<'_, '_> {
+    fn spawn_world_level(
+        &mut self,
+        world: Handle<WorldProject>,
+        level: impl Into<LevelRef>,
+    ) -> Entity {
+        self.spawn(WorldLevel::new(world, level)).id()
+    }
+
+    fn despawn_world_level(&mut self, world: Handle<WorldProject>, level: impl Into<LevelRef>) {
+        let level = level.into();
+        self.queue(move |ecs: &mut World| {
+            let level_id = ecs
+                .get_resource::<Assets<WorldProject>>()
+                .and_then(|worlds| worlds.get(&world))
+                .and_then(|project| level.resolve(project))
+                .map(|l| l.id);
+
+            let mut query = ecs.query::<(Entity, &WorldLevel, Option<&SpawnedWorldLevel>)>();
+            let targets: Vec<Entity> = query
+                .iter(ecs)
+                .filter(|(_, requested, spawned)| {
+                    requested.world.id() == world.id()
+                        && (requested.level == level
+                            || spawned.is_some_and(|s| Some(s.level_id) == level_id))
+                })
+                .map(|(entity, _, _)| entity)
+                .collect();
+
+            for entity in targets {
+                ecs.despawn(entity);
+            }
+        });
+    }
+}
+
+/// Translation that places a level at its world position
+fn level_translation(world: &WorldProject, level: &Level, z: f32) -> Vec3 {
+    let (x, y) = world.level_origin(level);
+    Vec3::new(x, y, z)
+}
+
+/// System that resolves [`WorldLevel`] requests and hands them to the map pipeline
+pub fn spawn_world_levels(
+    mut commands: Commands,
+    worlds: Res<Assets<WorldProject>>,
+    mut maps: ResMut<Assets<MapProject>>,
+    mut query: Query<(Entity, &WorldLevel, &mut Transform), Without<SpawnedWorldLevel>>,
+) {
+    for (entity, request, mut transform) in query.iter_mut() {
+        let Some(world) = worlds.get(&request.world) else {
+            continue;
+        };
+
+        let Some(level) = request.level.resolve(world) else {
+            warn!("Level {:?} not found in world project", request.level);
+            commands.entity(entity).remove::<WorldLevel>();
+            continue;
+        };
+        let Some(project) = world.level_project(level.id) else {
+            continue;
+        };
+
+        transform.translation = level_translation(world, level, transform.translation.z);
+        let map = maps.add(project);
+        commands.entity(entity).insert((
+            MapHandle(map.clone()),
+            SpawnedWorldLevel {
+                level_id: level.id,
+                map,
+            },
+        ));
+        info!("Spawning world level '{}'", level.name);
+    }
+}
+
+/// System that refreshes spawned levels when their world asset changes
+///
+/// Replacing the generated `MapProject` triggers the regular map hot-reload.
+pub fn handle_world_hot_reload(
+    mut asset_events: MessageReader<AssetEvent<WorldProject>>,
+    worlds: Res<Assets<WorldProject>>,
+    mut maps: ResMut<Assets<MapProject>>,
+    mut query: Query<(&WorldLevel, &SpawnedWorldLevel, &mut Transform)>,
+) {
+    for event in asset_events.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+        let Some(world) = worlds.get(*id) else {
+            continue;
+        };
+
+        for (request, spawned, mut transform) in query.iter_mut() {
+            if request.world.id() != *id {
+                continue;
+            }
+            let (Some(level), Some(project)) = (
+                world.get_level(spawned.level_id),
+                world.level_project(spawned.level_id),
+            ) else {
+                continue;
+            };
+
+            transform.translation = level_translation(world, level, transform.translation.z);
+            if let Err(err) = maps.insert(spawned.map.id(), project) {
+                warn!("Failed to reload level '{}': {}", level.name, err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_map_core::WorldConfig;
+
+    fn test_world() -> WorldProject {
+        WorldProject::new(
+            vec![
+                Level::new_at("Start".to_string(), 4, 4, 0, 0),
+                Level::new_at("Cave".to_string(), 4, 2, 128, 32),
+            ],
+            Vec::new(),
+            WorldConfig::default(),
+        )
+    }
+
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<WorldProject>()
+            .init_asset::<MapProject>()
+            .add_systems(Update, (spawn_world_levels, handle_world_hot_reload));
+        app
+    }
+
+    #[test]
+    fn test_spawn_and_despawn_world_level() {
+        let mut app = test_app();
+        let world = test_world();
+        let cave_id = world.level_by_name("Cave").unwrap().id;
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<WorldProject>>()
+            .add(world);
+
+        let start = app
+            .world_mut()
+            .commands()
+            .spawn_world_level(handle.clone(), "Start");
+        let cave = app
+            .world_mut()
+            .commands()
+            .spawn_world_level(handle.clone(), cave_id);
+        app.update();
+        app.update();
+
+        let spawned = app.world().get::<SpawnedWorldLevel>(cave).unwrap();
+        assert_eq!(spawned.level_id, cave_id);
+        let project = app
+            .world()
+            .resource::<Assets<MapProject>>()
+            .get(&spawned.map)
+            .unwrap();
+        assert_eq!(project.level.name, "Cave");
+        assert!(app.world().get::<MapHandle>(cave).is_some());
+
+        // Cave's top-left corner is at (128, -32); its bottom-left tile center
+        // is half a 32px tile inside
+        let translation = app.world().get::<Transform>(cave).unwrap().translation;
+        assert_eq!(translation, Vec3::new(144.0, -80.0, 0.0));
+
+        // Despawning by name also matches levels requested by UUID
+        app.world_mut()
+            .commands()
+            .despawn_world_level(handle.clone(), "Cave");
+        app.update();
+        assert!(app.world().get_entity(cave).is_err());
+        assert!(app.world().get_entity(start).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_level_is_dropped() {
+        let mut app = test_app();
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<WorldProject>>()
+            .add(test_world());
+        let entity = app
+            .world_mut()
+            .commands()
+            .spawn_world_level(handle, "Missing");
+        app.update();
+        app.update();
+
+        assert!(app.world().get::<WorldLevel>(entity).is_none());
+        assert!(app.world().get::<MapHandle>(entity).is_none());
+    }
+}
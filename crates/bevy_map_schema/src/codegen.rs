@@ -0,0 +1,564 @@
+//! Rust code generation from schemas
+//!
+//! Generates Rust types for a schema so game code stays in sync with the types
+//! designers define in the editor. Intended to be called from a `build.rs`:
+//!
+//! ```rust,ignore
+//! // build.rs
+//! fn main() {
+//!     let out_dir = std::env::var("OUT_DIR").unwrap();
+//!     bevy_map_schema::generate_rust_file(
+//!         "assets/maps/game.map.json",
+//!         std::path::Path::new(&out_dir).join("map_types.rs"),
+//!     )
+//!     .unwrap();
+//! }
+//!
+//! // src/main.rs
+//! include!(concat!(env!("OUT_DIR"), "/map_types.rs"));
+//! ```
+//!
+//! The generated code uses `bevy` and `bevy_map`, which must be dependencies of
+//! the crate that includes it.
+
+use crate::{parse_schema, EnumDef, PropType, PropertyDef, Schema, SchemaError, TypeDef};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Generate Rust source for every enum and type in a schema
+///
+/// - `enums` become enums deriving `MapProperty`; flag enums are used through
+///   `MapFlags`, a bitset of their variants
+/// - `embedded_types` become structs deriving `MapProperty`
+/// - `data_types` become components deriving `MapEntity`, keeping their
+///   `placeable` setting
+///
+/// Inherited properties are included in each struct. Items are sorted by name and
+/// properties keep their schema order, so the output only changes when the schema
+/// does. Names that are not valid Rust identifiers are converted, with
+/// `#[map_prop(name = "...")]` keeping the original name.
+///
+/// Asset properties of data types become `Handle<Image>` or `Handle<AudioSource>`
+/// when their extensions are all image or all audio formats, and paths otherwise.
+pub fn generate_rust(schema: &Schema) -> String {
+    let mut out = String::new();
+    out.push_str("// @generated by bevy_map_schema. Do not edit; change the schema instead.\n");
+
+    let mut enum_names: Vec<&String> = schema
+        .enums
+        .keys()
+        .filter(|name| has_variants(schema, name))
+        .collect();
+    enum_names.sort();
+    for name in enum_names {
+        out.push('\n');
+        write_enum(&mut out, name, &schema.enums[name]);
+    }
+
+    let mut embedded_names: Vec<&String> = schema.embedded_types.keys().collect();
+    embedded_names.sort();
+    for name in embedded_names {
+        out.push('\n');
+        if let Some(type_def) = schema.resolved_type(name) {
+            write_embedded_type(&mut out, schema, name, &type_def);
+        }
+    }
+
+    for name in schema.data_type_names() {
+        out.push('\n');
+        if let Some(type_def) = schema.resolved_type(name) {
+            write_data_type(&mut out, schema, name, &type_def);
+        }
+    }
+
+    out
+}
+
+/// Generate Rust source for a schema file and write it to `out_path`
+///
+/// `schema_path` can be a schema JSON file or a `.map.json` project with an
+/// embedded `schema`. Prints `cargo:rerun-if-changed` for the schema file so a
+/// `build.rs` re-runs when it changes, and only writes the output when its
+/// contents differ to avoid needless rebuilds.
+pub fn generate_rust_file(
+    schema_path: impl AsRef<Path>,
+    out_path: impl AsRef<Path>,
+) -> Result<(), SchemaError> {
+    let schema_path = schema_path.as_ref();
+    println!("cargo:rerun-if-changed={}", schema_path.display());
+
+    let content =
+        std::fs::read_to_string(schema_path).map_err(|e| SchemaError::IoError(e.to_string()))?;
+    let schema = parse_project_schema(&content)?;
+    let code = generate_rust(&schema);
+
+    let out_path = out_path.as_ref();
+    if std::fs::read_to_string(out_path).ok().as_deref() != Some(code.as_str()) {
+        std::fs::write(out_path, code).map_err(|e| SchemaError::IoError(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Parse a schema file, or the `schema` section of a map project file
+fn parse_project_schema(json: &str) -> Result<Schema, SchemaError> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| SchemaError::ParseError(e.to_string()))?;
+    match value.get("schema") {
+        Some(schema) => parse_schema(&schema.to_string()),
+        None => parse_schema(json),
+    }
+}
+
+fn write_enum(out: &mut String, name: &str, enum_def: &EnumDef) {
+    let type_name = type_ident(name);
+    // Flag sets default to empty, so flag enums have no default variant
+    let default = if enum_def.flags { "" } else { "Default, " };
+    let _ = writeln!(
+        out,
+        "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, {}bevy_map::MapProperty)]",
+        default
+    );
+    let mut args = Vec::new();
+    if type_name != name {
+        args.push(format!("name = {:?}", name));
+    }
+    if enum_def.flags {
+        args.push("flags".to_string());
+    }
+    if !args.is_empty() {
+        let _ = writeln!(out, "#[map_prop({})]", args.join(", "));
+    }
+    let _ = writeln!(out, "pub enum {} {{", type_name);
+    for (index, value) in enum_def.variants.iter().enumerate() {
+        let variant = type_ident(&value.name);
+        if index == 0 && !enum_def.flags {
+            let _ = writeln!(out, "    #[default]");
+        }
+        let mut args = Vec::new();
+        if variant != value.name {
+            args.push(format!("name = {:?}", value.name));
+        }
+        if let Some(explicit) = value.value {
+            args.push(format!("value = {}", explicit));
+        }
+        if !args.is_empty() {
+            let _ = writeln!(out, "    #[map_prop({})]", args.join(", "));
+        }
+        let _ = writeln!(out, "    {},", variant);
+    }
+    let _ = writeln!(out, "}}");
+}
+
+fn write_embedded_type(out: &mut String, schema: &Schema, name: &str, type_def: &TypeDef) {
+    let type_name = type_ident(name);
+    let _ = writeln!(
+        out,
+        "#[derive(Debug, Clone, Default, bevy_map::MapProperty)]"
+    );
+    if type_name != name {
+        let _ = writeln!(out, "#[map_prop(name = {:?})]", name);
+    }
+    let _ = writeln!(out, "pub struct {} {{", type_name);
+    write_fields(out, schema, name, type_def, false);
+    let _ = writeln!(out, "}}");
+}
+
+fn write_data_type(out: &mut String, schema: &Schema, name: &str, type_def: &TypeDef) {
+    let _ = writeln!(
+        out,
+        "#[derive(bevy::prelude::Component, Debug, Clone, bevy_map::MapEntity)]"
+    );
+    if type_def.placeable {
+        let _ = writeln!(out, "#[map_entity(type_name = {:?})]", name);
+    } else {
+        let _ = writeln!(
+            out,
+            "#[map_entity(type_name = {:?}, placeable = false)]",
+            name
+        );
+    }
+    let _ = writeln!(out, "pub struct {} {{", type_ident(name));
+    write_fields(out, schema, name, type_def, true);
+    let _ = writeln!(out, "}}");
+}
+
+fn write_fields(
+    out: &mut String,
+    schema: &Schema,
+    type_name: &str,
+    type_def: &TypeDef,
+    is_entity: bool,
+) {
+    for prop in &type_def.properties {
+        let Some(rust_type) = rust_type(schema, type_name, prop, is_entity) else {
+            let _ = writeln!(
+                out,
+                "    // Skipped '{}': {:?} property has no Rust equivalent",
+                prop.name, prop.prop_type
+            );
+            continue;
+        };
+
+        let field = field_ident(&prop.name);
+        let mut args = Vec::new();
+        if field != prop.name {
+            args.push(format!("name = {:?}", prop.name));
+        }
+        if let Some(default) = prop
+            .default
+            .as_ref()
+            .and_then(|default| rust_default(schema, prop, default))
+        {
+            args.push(format!("default = {}", default));
+        }
+        if prop.prop_type == PropType::Asset {
+            if !prop.extensions.is_empty() {
+                args.push(format!("extensions = {:?}", prop.extensions.join(", ")));
+            } else if rust_type == "String" {
+                args.push("asset".to_string());
+            }
+        }
+        if matches!(prop.prop_type, PropType::Int | PropType::Float) {
+            if let Some(min) = prop.min {
+                args.push(format!("min = {:?}", min));
+            }
+            if let Some(max) = prop.max {
+                args.push(format!("max = {:?}", max));
+            }
+        }
+
+        if args.is_empty() {
+            // Entity fields need the attribute; embedded fields are all read
+            if is_entity {
+                let _ = writeln!(out, "    #[map_prop]");
+            }
+        } else {
+            let _ = writeln!(out, "    #[map_prop({})]", args.join(", "));
+        }
+        let _ = writeln!(out, "    pub {}: {},", field, rust_type);
+    }
+}
+
+/// The Rust type for a property, or `None` if it has no equivalent
+#[allow(deprecated)]
+fn rust_type(
+    schema: &Schema,
+    type_name: &str,
+    prop: &PropertyDef,
+    is_entity: bool,
+) -> Option<String> {
+    let rust_type = match prop.prop_type {
+        PropType::String | PropType::Multiline | PropType::Ref => "String".to_string(),
+        PropType::Int => "i32".to_string(),
+        PropType::Float => "f32".to_string(),
+        PropType::Bool => "bool".to_string(),
+        PropType::Point => "bevy::math::Vec2".to_string(),
+        PropType::Color => "bevy::color::Color".to_string(),
+        PropType::Enum => match &prop.enum_type {
+            Some(enum_type) if is_flag_enum(schema, enum_type) => {
+                format!("bevy_map::runtime::MapFlags<{}>", type_ident(enum_type))
+            }
+            Some(enum_type) if has_variants(schema, enum_type) => type_ident(enum_type),
+            _ => "String".to_string(),
+        },
+        // A struct cannot contain itself; nest it in an array instead
+        PropType::Embedded => match prop.embedded_type.as_deref()? {
+            embedded_type if embedded_type == type_name => return None,
+            embedded_type => type_ident(embedded_type),
+        },
+        PropType::Array => {
+            let item = prop.item_type.as_deref().unwrap_or("String");
+            let item_type = match item {
+                "Int" => "i32".to_string(),
+                "Float" => "f32".to_string(),
+                "Bool" => "bool".to_string(),
+                _ if (has_variants(schema, item) && !is_flag_enum(schema, item))
+                    || schema.embedded_types.contains_key(item) =>
+                {
+                    type_ident(item)
+                }
+                // Strings and references to data type instances
+                _ => "String".to_string(),
+            };
+            format!("Vec<{}>", item_type)
+        }
+        // Dialogue handles can only be injected into spawned entities
+        PropType::Dialogue if is_entity => {
+            "bevy::asset::Handle<bevy_map::DialogueTree>".to_string()
+        }
+        PropType::Dialogue => "String".to_string(),
+        PropType::Asset => match asset_handle_type(&prop.extensions) {
+            Some(asset) if is_entity => format!("bevy::asset::Handle<{}>", asset),
+            _ => "String".to_string(),
+        },
+        PropType::Tile => "bevy_map::core::TileRef".to_string(),
+        PropType::Sprite => return None,
+    };
+    Some(rust_type)
+}
+
+/// The Bevy asset type loaded from files with these extensions, if known
+fn asset_handle_type(extensions: &[String]) -> Option<&'static str> {
+    const IMAGES: &[&str] = &["png", "jpg", "jpeg", "bmp", "gif", "webp"];
+    const AUDIO: &[&str] = &["ogg", "wav", "mp3", "flac"];
+    let all_in = |known: &[&str]| {
+        !extensions.is_empty()
+            && extensions.iter().all(|extension| {
+                let extension = extension.trim_start_matches('.').to_ascii_lowercase();
+                known.contains(&extension.as_str())
+            })
+    };
+    if all_in(IMAGES) {
+        Some("bevy::image::Image")
+    } else if all_in(AUDIO) {
+        Some("bevy::audio::AudioSource")
+    } else {
+        None
+    }
+}
+
+/// Enums without variants are not generated; their properties are plain strings
+fn has_variants(schema: &Schema, enum_type: &str) -> bool {
+    schema
+        .get_enum(enum_type)
+        .is_some_and(|values| !values.is_empty())
+}
+
+fn is_flag_enum(schema: &Schema, enum_type: &str) -> bool {
+    has_variants(schema, enum_type) && schema.enums[enum_type].flags
+}
+
+/// A Rust expression for a schema default value, if it can be expressed
+fn rust_default(
+    schema: &Schema,
+    prop: &PropertyDef,
+    default: &serde_json::Value,
+) -> Option<String> {
+    match prop.prop_type {
+        PropType::String | PropType::Multiline => default.as_str().map(|s| format!("{:?}", s)),
+        PropType::Int => default.as_i64().map(|i| i.to_string()),
+        PropType::Float => default.as_f64().map(|f| format!("{:?}", f)),
+        PropType::Bool => default.as_bool().map(|b| b.to_string()),
+        PropType::Enum => {
+            let enum_type = prop.enum_type.as_deref()?;
+            let enum_def = schema.get_enum(enum_type)?;
+            let variant = |value: &str| {
+                enum_def
+                    .contains(value)
+                    .then(|| format!("{}::{}", type_ident(enum_type), type_ident(value)))
+            };
+            if enum_def.flags {
+                let variants = default
+                    .as_array()?
+                    .iter()
+                    .map(|value| variant(value.as_str()?))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(format!(
+                    "bevy_map::runtime::MapFlags::from_iter([{}])",
+                    variants.join(", ")
+                ))
+            } else {
+                variant(default.as_str()?)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Convert a name to an UpperCamelCase type or variant identifier
+fn type_ident(name: &str) -> String {
+    let mut ident = String::new();
+    for word in name.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            ident.push(first.to_ascii_uppercase());
+            // Acronyms such as "NPC" become "Npc"
+            if word.chars().all(|c| !c.is_ascii_lowercase()) {
+                ident.extend(chars.map(|c| c.to_ascii_lowercase()));
+            } else {
+                ident.extend(chars);
+            }
+        }
+    }
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if is_keyword(&ident) {
+        ident.push('_');
+    }
+    ident
+}
+
+/// Convert a name to a snake_case field identifier
+fn field_ident(name: &str) -> String {
+    let mut ident = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if prev_lower {
+                ident.push('_');
+            }
+            ident.push(c.to_ascii_lowercase());
+            prev_lower = false;
+        } else if c.is_ascii_alphanumeric() {
+            ident.push(c);
+            prev_lower = true;
+        } else {
+            if !ident.is_empty() && !ident.ends_with('_') {
+                ident.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if is_keyword(&ident) {
+        ident.push('_');
+    }
+    ident
+}
+
+fn is_keyword(ident: &str) -> bool {
+    matches!(
+        ident,
+        "as" | "async"
+            | "await"
+            | "break"
+            | "const"
+            | "continue"
+            | "crate"
+            | "dyn"
+            | "else"
+            | "enum"
+            | "extern"
+            | "false"
+            | "fn"
+            | "for"
+            | "if"
+            | "impl"
+            | "in"
+            | "let"
+            | "loop"
+            | "match"
+            | "mod"
+            | "move"
+            | "mut"
+            | "pub"
+            | "ref"
+            | "return"
+            | "self"
+            | "Self"
+            | "static"
+            | "struct"
+            | "super"
+            | "trait"
+            | "true"
+            | "type"
+            | "unsafe"
+            | "use"
+            | "where"
+            | "while"
+            | "abstract"
+            | "become"
+            | "box"
+            | "do"
+            | "final"
+            | "gen"
+            | "macro"
+            | "override"
+            | "priv"
+            | "try"
+            | "typeof"
+            | "unsized"
+            | "virtual"
+            | "yield"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r##"{
+        "version": 1,
+        "project": { "name": "Test" },
+        "enums": {
+            "NpcType": ["QuestGiver", "vendor"],
+            "Abilities": {
+                "flags": true,
+                "variants": ["Fly", { "name": "swim", "color": "#0000ff", "value": 8 }]
+            }
+        },
+        "data_types": {
+            "NPC": {
+                "placeable": true,
+                "properties": [
+                    { "name": "name", "type": "string", "default": "Bob" },
+                    { "name": "npcType", "type": "enum", "enumType": "NpcType", "default": "vendor" },
+                    { "name": "type", "type": "int", "min": 0, "max": 10 },
+                    { "name": "stats", "type": "embedded", "embeddedType": "Stats" },
+                    { "name": "dialogue", "type": "dialogue" },
+                    { "name": "voice", "type": "asset", "extensions": ["ogg", "wav"] },
+                    { "name": "scene", "type": "asset", "extensions": ["scn.ron"] },
+                    { "name": "icon", "type": "tile" },
+                    { "name": "abilities", "type": "enum", "enumType": "Abilities", "default": ["Fly", "swim"] }
+                ]
+            },
+            "Item": {
+                "properties": [{ "name": "value", "type": "int" }]
+            }
+        },
+        "embedded_types": {
+            "Stats": {
+                "properties": [
+                    { "name": "speed", "type": "float", "default": 1.5 },
+                    { "name": "waypoints", "type": "array", "itemType": "Float" }
+                ]
+            }
+        }
+    }"##;
+
+    #[test]
+    fn test_generate_rust() {
+        let schema = parse_schema(SCHEMA).unwrap();
+        let code = generate_rust(&schema);
+
+        assert!(code.contains("pub enum NpcType {\n    #[default]\n    QuestGiver,\n    #[map_prop(name = \"vendor\")]\n    Vendor,\n}"));
+        assert!(code.contains("#[map_entity(type_name = \"NPC\")]\npub struct Npc {"));
+        assert!(code
+            .contains("#[map_entity(type_name = \"Item\", placeable = false)]\npub struct Item {"));
+        assert!(code.contains("    #[map_prop(default = \"Bob\")]\n    pub name: String,"));
+        assert!(code.contains(
+            "    #[map_prop(name = \"npcType\", default = NpcType::Vendor)]\n    pub npc_type: NpcType,"
+        ));
+        assert!(code.contains(
+            "    #[map_prop(name = \"type\", min = 0.0, max = 10.0)]\n    pub type_: i32,"
+        ));
+        assert!(code.contains("    #[map_prop]\n    pub stats: Stats,"));
+        assert!(code.contains("    pub dialogue: bevy::asset::Handle<bevy_map::DialogueTree>,"));
+        assert!(code.contains(
+            "    #[map_prop(extensions = \"ogg, wav\")]\n    pub voice: bevy::asset::Handle<bevy::audio::AudioSource>,"
+        ));
+        assert!(code.contains("    #[map_prop(extensions = \"scn.ron\")]\n    pub scene: String,"));
+        assert!(code.contains("    #[map_prop]\n    pub icon: bevy_map::core::TileRef,"));
+        assert!(code.contains("#[map_prop(flags)]\npub enum Abilities {\n    Fly,\n    #[map_prop(name = \"swim\", value = 8)]\n    Swim,\n}"));
+        assert!(code.contains(
+            "    #[map_prop(default = bevy_map::runtime::MapFlags::from_iter([Abilities::Fly, Abilities::Swim]))]\n    pub abilities: bevy_map::runtime::MapFlags<Abilities>,"
+        ));
+        assert!(code.contains(
+            "    #[map_prop(default = 1.5)]\n    pub speed: f32,\n    pub waypoints: Vec<f32>,"
+        ));
+
+        // Output is stable across runs
+        assert_eq!(code, generate_rust(&parse_schema(SCHEMA).unwrap()));
+    }
+
+    #[test]
+    fn test_parse_project_schema() {
+        let project = format!(r#"{{ "version": 1, "schema": {} }}"#, SCHEMA);
+        let schema = parse_project_schema(&project).unwrap();
+        assert!(schema.data_types.contains_key("NPC"));
+        assert!(parse_project_schema(SCHEMA).is_ok());
+    }
+}
@@ -0,0 +1,631 @@
+//! CSV export and import of data instances
+//!
+//! Each data type is exported as its own table: an `id` column followed by one
+//! column per property of the resolved type. Scalars are written as plain cells;
+//! arrays, embedded values, points and tiles are written as JSON.
+//!
+//! Importing a table builds a [`CsvImport`] without modifying anything. Rows are
+//! matched to existing instances by `id`, then by the `name` column, and every
+//! value is validated against the schema, so the changes can be previewed before
+//! they are applied.
+
+use crate::{validate_instance, PropType, PropertyDef, Schema, SchemaError};
+use bevy_map_core::{DataInstance, DataStore, Value};
+use std::collections::HashMap;
+use std::fmt;
+use uuid::Uuid;
+
+/// Name of the column holding instance IDs
+pub const CSV_ID_COLUMN: &str = "id";
+
+/// Export every instance of a data type as CSV
+pub fn export_csv(
+    schema: &Schema,
+    type_name: &str,
+    instances: &[DataInstance],
+) -> Result<String, SchemaError> {
+    let columns = csv_columns(schema, type_name)?;
+
+    let mut out = String::new();
+    let header: Vec<&str> = std::iter::once(CSV_ID_COLUMN)
+        .chain(columns.iter().map(|column| column.name.as_str()))
+        .collect();
+    write_record(&mut out, &header);
+
+    for instance in instances {
+        let mut cells = vec![instance.id.to_string()];
+        cells.extend(columns.iter().map(|column| {
+            instance
+                .properties
+                .get(&column.name)
+                .map(format_cell)
+                .unwrap_or_default()
+        }));
+        write_record(&mut out, &cells);
+    }
+    Ok(out)
+}
+
+/// The property columns of a data type's table, in schema order
+fn csv_columns(schema: &Schema, type_name: &str) -> Result<Vec<PropertyDef>, SchemaError> {
+    let type_def = schema
+        .resolved_type(type_name)
+        .ok_or_else(|| SchemaError::ValidationError(format!("Unknown type: {}", type_name)))?;
+    Ok(type_def.properties)
+}
+
+/// How an imported row changes the project
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvChange {
+    /// The row creates a new instance
+    Added,
+    /// The row changes an existing instance
+    Updated,
+    /// The row matches an existing instance exactly
+    Unchanged,
+}
+
+/// A property changed by an imported row (`None` means unset)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvPropertyChange {
+    pub property: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// An imported row, matched against the existing instances
+#[derive(Debug, Clone)]
+pub struct CsvRow {
+    /// Line of the row in the CSV file (1-based, the header is line 1)
+    pub line: usize,
+    pub change: CsvChange,
+    /// The existing instance, for updated and unchanged rows
+    pub before: Option<DataInstance>,
+    /// The instance after the import
+    pub after: DataInstance,
+    /// Changed properties, in column order
+    pub changes: Vec<CsvPropertyChange>,
+}
+
+impl CsvRow {
+    /// Display label: the instance's `name` property, or its ID
+    pub fn label(&self) -> String {
+        match self.after.name() {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => self.after.id.to_string(),
+        }
+    }
+}
+
+/// A row that cannot be imported
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRowError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for CsvRowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// The result of reading a CSV table, ready to be previewed and applied
+#[derive(Debug, Clone)]
+pub struct CsvImport {
+    pub type_name: String,
+    /// Rows that passed validation
+    pub rows: Vec<CsvRow>,
+    /// Rows that failed validation; the import should not be applied if any exist
+    pub errors: Vec<CsvRowError>,
+}
+
+impl CsvImport {
+    /// Rows that add or update an instance
+    pub fn changed_rows(&self) -> impl Iterator<Item = &CsvRow> {
+        self.rows
+            .iter()
+            .filter(|row| row.change != CsvChange::Unchanged)
+    }
+
+    /// Number of rows with the given change
+    pub fn count(&self, change: CsvChange) -> usize {
+        self.rows.iter().filter(|row| row.change == change).count()
+    }
+
+    /// Check if every row passed validation
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Apply the changed rows to a data store
+    pub fn apply(&self, store: &mut DataStore) {
+        for row in self.changed_rows() {
+            match store.get_mut(row.after.id) {
+                Some(instance) => instance.properties = row.after.properties.clone(),
+                None => store.add(row.after.clone()),
+            }
+        }
+    }
+}
+
+/// Read a CSV table of a data type, matching rows to `existing` instances
+///
+/// Rows are matched by the `id` column, then by the `name` column. Columns that
+/// are missing from the file keep their existing values, and empty cells unset
+/// the property. Unknown columns and malformed files are errors; rows with
+/// values that don't fit the schema are reported in [`CsvImport::errors`].
+pub fn import_csv(
+    schema: &Schema,
+    type_name: &str,
+    csv: &str,
+    existing: &[DataInstance],
+) -> Result<CsvImport, SchemaError> {
+    let columns = csv_columns(schema, type_name)?;
+    let mut records = parse_records(csv)?.into_iter();
+    let Some((_, header)) = records.next() else {
+        return Err(SchemaError::ParseError("CSV file is empty".to_string()));
+    };
+
+    // Map each header cell to a property, or to the ID column
+    let mut id_column = None;
+    let mut header_props: Vec<Option<&PropertyDef>> = Vec::new();
+    for (index, name) in header.iter().enumerate() {
+        let name = name.trim();
+        if name == CSV_ID_COLUMN {
+            id_column = Some(index);
+            header_props.push(None);
+            continue;
+        }
+        let Some(prop) = columns.iter().find(|prop| prop.name == name) else {
+            return Err(SchemaError::ValidationError(format!(
+                "Column '{}' is not a property of '{}'",
+                name, type_name
+            )));
+        };
+        if header_props.iter().flatten().any(|p| p.name == name) {
+            return Err(SchemaError::ValidationError(format!(
+                "Column '{}' appears more than once",
+                name
+            )));
+        }
+        header_props.push(Some(prop));
+    }
+    let name_column = header_props
+        .iter()
+        .position(|prop| prop.is_some_and(|prop| prop.name == "name"));
+
+    let mut import = CsvImport {
+        type_name: type_name.to_string(),
+        rows: Vec::new(),
+        errors: Vec::new(),
+    };
+    // Which line matched each existing instance, to catch duplicate rows
+    let mut matched: HashMap<Uuid, usize> = HashMap::new();
+
+    for (line, cells) in records {
+        if cells.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        if cells.len() != header.len() {
+            import.errors.push(CsvRowError {
+                line,
+                message: format!("expected {} cells, found {}", header.len(), cells.len()),
+            });
+            continue;
+        }
+
+        match read_row(
+            schema,
+            type_name,
+            &header_props,
+            &cells,
+            id_column,
+            name_column,
+            existing,
+        ) {
+            Ok((before, after)) => {
+                if let Some(&first) = matched.get(&after.id) {
+                    import.errors.push(CsvRowError {
+                        line,
+                        message: format!("matches the same instance as line {}", first),
+                    });
+                    continue;
+                }
+                matched.insert(after.id, line);
+
+                let changes = diff_properties(&columns, before.as_ref(), &after);
+                let change = match &before {
+                    None => CsvChange::Added,
+                    Some(_) if changes.is_empty() => CsvChange::Unchanged,
+                    Some(_) => CsvChange::Updated,
+                };
+                import.rows.push(CsvRow {
+                    line,
+                    change,
+                    before,
+                    after,
+                    changes,
+                });
+            }
+            Err(message) => import.errors.push(CsvRowError { line, message }),
+        }
+    }
+
+    Ok(import)
+}
+
+/// Build the instance for one row, returning the instance it replaces
+fn read_row(
+    schema: &Schema,
+    type_name: &str,
+    header_props: &[Option<&PropertyDef>],
+    cells: &[String],
+    id_column: Option<usize>,
+    name_column: Option<usize>,
+    existing: &[DataInstance],
+) -> Result<(Option<DataInstance>, DataInstance), String> {
+    let id = match id_column.map(|index| cells[index].trim()) {
+        Some(id) if !id.is_empty() => {
+            Some(Uuid::parse_str(id).map_err(|_| format!("'{}' is not a valid instance ID", id))?)
+        }
+        _ => None,
+    };
+    let name = name_column.map(|index| cells[index].as_str());
+    let before = match id {
+        Some(id) => existing.iter().find(|instance| instance.id == id),
+        None => name.filter(|name| !name.is_empty()).and_then(|name| {
+            existing
+                .iter()
+                .find(|instance| instance.name() == Some(name))
+        }),
+    };
+
+    let mut after = match before {
+        Some(instance) => instance.clone(),
+        None => {
+            let mut instance = DataInstance::new(type_name.to_string());
+            if let Some(id) = id {
+                instance.id = id;
+            }
+            instance
+        }
+    };
+
+    for (prop, cell) in header_props.iter().zip(cells) {
+        let Some(prop) = prop else {
+            continue;
+        };
+        match parse_cell(schema, prop, cell)? {
+            Some(value) => {
+                after.properties.insert(prop.name.clone(), value);
+            }
+            // Empty cells unset the property, but empty strings are kept as they are
+            None if after.properties.get(&prop.name) == Some(&Value::String(String::new())) => {}
+            None => {
+                after.properties.remove(&prop.name);
+            }
+        }
+    }
+
+    let json: HashMap<String, serde_json::Value> = after
+        .properties
+        .iter()
+        .map(|(key, value)| (key.clone(), value.to_json()))
+        .collect();
+    validate_instance(schema, type_name, &json).map_err(|err| match err {
+        SchemaError::ValidationError(message) => message,
+        other => other.to_string(),
+    })?;
+
+    Ok((before.cloned(), after))
+}
+
+/// Properties that differ between the existing instance and the imported one
+fn diff_properties(
+    columns: &[PropertyDef],
+    before: Option<&DataInstance>,
+    after: &DataInstance,
+) -> Vec<CsvPropertyChange> {
+    columns
+        .iter()
+        .filter_map(|column| {
+            let old = before.and_then(|before| before.properties.get(&column.name));
+            let new = after.properties.get(&column.name);
+            (old != new).then(|| CsvPropertyChange {
+                property: column.name.clone(),
+                before: old.cloned(),
+                after: new.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Format a value as a cell: scalars as text, structured values as JSON
+pub fn format_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Int(n) => n.to_string(),
+        Value::Float(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Array(_) | Value::Object(_) => value.to_json().to_string(),
+    }
+}
+
+/// Parse a cell for a property, returning `None` for an empty cell
+pub fn parse_cell(
+    schema: &Schema,
+    prop: &PropertyDef,
+    cell: &str,
+) -> Result<Option<Value>, String> {
+    if cell.is_empty() {
+        return Ok(None);
+    }
+
+    let flags = prop.prop_type == PropType::Enum
+        && prop
+            .enum_type
+            .as_ref()
+            .and_then(|enum_type| schema.get_enum(enum_type))
+            .is_some_and(|enum_def| enum_def.flags);
+
+    #[allow(deprecated)]
+    let value = match prop.prop_type {
+        PropType::Int => Value::Int(
+            cell.trim()
+                .parse()
+                .map_err(|_| format!("'{}': '{}' is not an integer", prop.name, cell))?,
+        ),
+        PropType::Float => Value::Float(
+            cell.trim()
+                .parse()
+                .map_err(|_| format!("'{}': '{}' is not a number", prop.name, cell))?,
+        ),
+        PropType::Bool => match cell.trim().to_ascii_lowercase().as_str() {
+            "true" | "yes" | "1" => Value::Bool(true),
+            "false" | "no" | "0" => Value::Bool(false),
+            _ => return Err(format!("'{}': '{}' is not true or false", prop.name, cell)),
+        },
+        PropType::Array | PropType::Embedded | PropType::Point | PropType::Tile => {
+            parse_json_cell(prop, cell)?
+        }
+        PropType::Enum if flags => parse_json_cell(prop, cell)?,
+        PropType::String
+        | PropType::Multiline
+        | PropType::Enum
+        | PropType::Ref
+        | PropType::Color
+        | PropType::Sprite
+        | PropType::Dialogue
+        | PropType::Asset => Value::String(cell.to_string()),
+    };
+    Ok(Some(value))
+}
+
+fn parse_json_cell(prop: &PropertyDef, cell: &str) -> Result<Value, String> {
+    serde_json::from_str(cell)
+        .map(Value::from_json)
+        .map_err(|err| format!("'{}': invalid JSON ({})", prop.name, err))
+}
+
+/// Write one CSV record, quoting cells that need it
+fn write_record(out: &mut String, cells: &[impl AsRef<str>]) {
+    for (index, cell) in cells.iter().enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+        let cell = cell.as_ref();
+        if cell.contains([',', '"', '\n', '\r']) || cell.trim() != cell {
+            out.push('"');
+            out.push_str(&cell.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(cell);
+        }
+    }
+    out.push('\n');
+}
+
+/// Split CSV text into records, each with the line it starts on
+///
+/// Follows RFC 4180: quoted cells may contain commas, newlines and doubled quotes.
+fn parse_records(csv: &str) -> Result<Vec<(usize, Vec<String>)>, SchemaError> {
+    let csv = csv.strip_prefix('\u{feff}').unwrap_or(csv);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    cell.push(c);
+                }
+                _ => cell.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if cell.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut cell)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                record.push(std::mem::take(&mut cell));
+                records.push((record_line, std::mem::take(&mut record)));
+                line += 1;
+                record_line = line;
+            }
+            _ => cell.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(SchemaError::ParseError(format!(
+            "Unterminated quoted cell starting on line {}",
+            record_line
+        )));
+    }
+    if !cell.is_empty() || !record.is_empty() {
+        record.push(cell);
+        records.push((record_line, record));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EnumDef, TypeDef};
+
+    fn item_schema() -> Schema {
+        let mut schema = Schema::default();
+        schema
+            .enums
+            .insert("Rarity".to_string(), EnumDef::new(["Common", "Rare"]));
+        let mut rarity = PropertyDef::new("rarity", PropType::Enum);
+        rarity.enum_type = Some("Rarity".to_string());
+        let mut value = PropertyDef::new("value", PropType::Int);
+        value.min = Some(0.0);
+        schema.data_types.insert(
+            "Item".to_string(),
+            TypeDef {
+                properties: vec![
+                    PropertyDef::new("name", PropType::String),
+                    value,
+                    rarity,
+                    PropertyDef::new("tags", PropType::Array),
+                ],
+                ..Default::default()
+            },
+        );
+        schema
+    }
+
+    fn item(name: &str, value: i64) -> DataInstance {
+        let mut instance = DataInstance::new("Item".to_string());
+        instance
+            .properties
+            .insert("name".to_string(), Value::String(name.to_string()));
+        instance
+            .properties
+            .insert("value".to_string(), Value::Int(value));
+        instance
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let schema = item_schema();
+        let mut sword = item("Sword, Long", 10);
+        sword.properties.insert(
+            "tags".to_string(),
+            Value::Array(vec![Value::String("melee".to_string())]),
+        );
+        let instances = vec![sword, item("Shield", 5)];
+
+        let csv = export_csv(&schema, "Item", &instances).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("id,name,value,rarity,tags"));
+        assert!(lines
+            .next()
+            .unwrap()
+            .ends_with(r#","Sword, Long",10,,"[""melee""]""#));
+
+        let import = import_csv(&schema, "Item", &csv, &instances).unwrap();
+        assert!(import.is_valid());
+        assert_eq!(import.count(CsvChange::Unchanged), 2);
+        assert_eq!(import.changed_rows().count(), 0);
+    }
+
+    #[test]
+    fn test_csv_import_matches_and_validates() {
+        let schema = item_schema();
+        let shield = item("Shield", 5);
+        let shield_id = shield.id;
+        let existing = vec![shield];
+
+        let csv = "name,value,rarity\n\
+                   Shield,7,Rare\n\
+                   Potion,2,\n\
+                   Bomb,-1,Common\n\
+                   Axe,3,Legendary\n\
+                   Bow,x,Common\n";
+        let import = import_csv(&schema, "Item", csv, &existing).unwrap();
+
+        // Shield is matched by name and updated
+        let updated = &import.rows[0];
+        assert_eq!(updated.change, CsvChange::Updated);
+        assert_eq!(updated.after.id, shield_id);
+        assert_eq!(updated.changes.len(), 2);
+        assert_eq!(updated.changes[0].before, Some(Value::Int(5)));
+        assert_eq!(updated.changes[0].after, Some(Value::Int(7)));
+
+        assert_eq!(import.rows[1].change, CsvChange::Added);
+        assert!(!import.rows[1].after.properties.contains_key("rarity"));
+
+        // Out of range, unknown enum value and a bad integer
+        let lines: Vec<usize> = import.errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![4, 5, 6]);
+        assert!(!import.is_valid());
+
+        let mut store = DataStore::default();
+        store.add(existing[0].clone());
+        import.apply(&mut store);
+        assert_eq!(store.len(), 2);
+        assert_eq!(
+            store.get(shield_id).unwrap().properties.get("value"),
+            Some(&Value::Int(7))
+        );
+    }
+
+    #[test]
+    fn test_csv_import_errors() {
+        let schema = item_schema();
+        let shield = item("Shield", 5);
+        let id = shield.id;
+        let existing = vec![shield];
+
+        assert!(import_csv(&schema, "Item", "name,damage\nSword,1\n", &[]).is_err());
+        assert!(import_csv(&schema, "Quest", "name\n", &[]).is_err());
+        assert!(import_csv(&schema, "Item", "name\n\"Sword\n", &[]).is_err());
+
+        // Two rows for the same instance, a bad ID and a short row
+        let csv = format!("id,name\n{id},Shield\n,Shield\nnot-an-id,Axe\n{id}\n");
+        let import = import_csv(&schema, "Item", &csv, &existing).unwrap();
+        assert_eq!(import.rows.len(), 1);
+        let messages: Vec<String> = import.errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "line 3: matches the same instance as line 2",
+                "line 4: 'not-an-id' is not a valid instance ID",
+                "line 5: expected 2 cells, found 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_records() {
+        let records = parse_records("a,\"b\nc\",\"d\"\"e\"\r\n1,2,3").unwrap();
+        assert_eq!(
+            records,
+            vec![
+                (
+                    1,
+                    vec!["a".to_string(), "b\nc".to_string(), "d\"e".to_string()]
+                ),
+                (3, vec!["1".to_string(), "2".to_string(), "3".to_string()]),
+            ]
+        );
+    }
+}
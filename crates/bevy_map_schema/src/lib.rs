@@ -18,9 +18,17 @@
 //! schema.validate_entity(&entity)?;
 //! ```
 
+mod codegen;
+mod csv;
+mod merge;
+mod refactor;
 mod types;
 mod validate;
 
+pub use codegen::*;
+pub use csv::*;
+pub use merge::*;
+pub use refactor::*;
 pub use types::*;
 pub use validate::*;
 
@@ -78,6 +86,7 @@ pub fn load_schema_from_bytes(bytes: &[u8]) -> Result<Schema, SchemaError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
 
     #[test]
     fn test_parse_minimal_schema() {
@@ -122,6 +131,99 @@ mod tests {
         assert_eq!(item_type.properties.len(), 2);
     }
 
+    #[test]
+    fn test_type_inheritance() {
+        let json = r#"{
+            "version": 1,
+            "project": { "name": "Test" },
+            "data_types": {
+                "Enemy": {
+                    "properties": [
+                        { "name": "health", "type": "int", "default": 10, "min": 0 },
+                        { "name": "faction", "type": "string" }
+                    ]
+                },
+                "Boss": {
+                    "extends": "Enemy",
+                    "properties": [
+                        { "name": "health", "type": "int", "default": 500 },
+                        { "name": "phases", "type": "int" }
+                    ]
+                }
+            }
+        }"#;
+
+        let schema = parse_schema(json).unwrap();
+        let boss = schema.resolved_type("Boss").unwrap();
+        let names: Vec<&str> = boss.properties.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["health", "faction", "phases"]);
+        assert_eq!(boss.properties[0].default, Some(serde_json::json!(500)));
+        assert_eq!(boss.properties[0].min, Some(0.0));
+        assert!(schema.is_subtype("Boss", "Enemy"));
+        assert!(!schema.is_subtype("Enemy", "Boss"));
+        assert_eq!(schema.subtypes("Enemy"), vec!["Boss"]);
+
+        let cycle = json.replace(r#""Enemy": {"#, r#""Enemy": { "extends": "Boss","#);
+        let result = parse_schema(&cycle);
+        assert!(matches!(result, Err(SchemaError::ValidationError(msg)) if msg.contains("cycle")));
+
+        let conflict = json.replace(
+            r#"{ "name": "health", "type": "int", "default": 500 }"#,
+            r#"{ "name": "health", "type": "string" }"#,
+        );
+        assert!(parse_schema(&conflict).is_err());
+    }
+
+    #[test]
+    fn test_enum_definitions() {
+        let json = r##"{
+            "version": 1,
+            "project": { "name": "Test" },
+            "enums": {
+                "Faction": ["Friendly", { "name": "Hostile", "color": "#ff0000", "value": 10 }],
+                "Abilities": { "flags": true, "variants": ["Fly", "Swim", { "name": "Climb", "value": 16 }] }
+            },
+            "data_types": {
+                "NPC": {
+                    "color": "#808080",
+                    "color_by": "faction",
+                    "properties": [
+                        { "name": "faction", "type": "enum", "enumType": "Faction", "default": "Hostile" },
+                        { "name": "abilities", "type": "enum", "enumType": "Abilities" }
+                    ]
+                }
+            }
+        }"##;
+
+        let schema = parse_schema(json).unwrap();
+        let faction = schema.get_enum("Faction").unwrap();
+        assert!(!faction.flags);
+        assert_eq!(faction.value_of("Friendly"), Some(0));
+        assert_eq!(faction.value_of("Hostile"), Some(10));
+        let abilities = schema.get_enum("Abilities").unwrap();
+        assert!(abilities.flags);
+        assert_eq!(abilities.value_of("Swim"), Some(2));
+        assert_eq!(abilities.value_of("Climb"), Some(16));
+
+        // Plain variants are written as names, so old schemas round-trip unchanged
+        let saved = serde_json::to_value(&schema.enums).unwrap();
+        assert_eq!(saved["Faction"][0], serde_json::json!("Friendly"));
+        assert_eq!(saved["Faction"][1]["color"], serde_json::json!("#ff0000"));
+        assert_eq!(saved["Abilities"]["flags"], serde_json::json!(true));
+
+        // Markers take the variant color, falling back to the default and type color
+        let mut props = HashMap::new();
+        assert_eq!(schema.marker_color("NPC", &props), Some("#ff0000"));
+        props.insert(
+            "faction".to_string(),
+            bevy_map_core::Value::String("Friendly".to_string()),
+        );
+        assert_eq!(schema.marker_color("NPC", &props), Some("#808080"));
+
+        let invalid = json.replace(r#""color_by": "faction""#, r#""color_by": "abilitie""#);
+        assert!(parse_schema(&invalid).is_err());
+    }
+
     #[test]
     fn test_invalid_enum_reference() {
         let json = r#"{
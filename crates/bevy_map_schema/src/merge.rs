@@ -0,0 +1,374 @@
+//! Merging and comparing schemas
+//!
+//! Used for code-first workflows, where a schema generated from Rust types is
+//! imported into the editor or checked against the schema a map was saved with.
+
+use crate::{PropType, PropertyDef, Schema, TypeDef};
+use std::fmt;
+
+/// A difference between a schema generated from code and a map's schema
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaMismatch {
+    /// The type does not exist in the map's schema
+    MissingType { type_name: String },
+    /// The property does not exist on the map's type
+    MissingProperty { type_name: String, property: String },
+    /// The property has a different type in the map's schema
+    PropertyType {
+        type_name: String,
+        property: String,
+        expected: PropType,
+        found: PropType,
+    },
+    /// The property references a different enum, embedded or item type
+    TypeReference {
+        type_name: String,
+        property: String,
+        expected: Option<String>,
+        found: Option<String>,
+    },
+    /// The enum does not exist in the map's schema
+    MissingEnum { enum_name: String },
+    /// The enum value does not exist in the map's enum
+    MissingEnumValue { enum_name: String, value: String },
+    /// The enum is a flag enum in one schema and a plain enum in the other
+    EnumFlags { enum_name: String, expected: bool },
+}
+
+impl fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaMismatch::MissingType { type_name } => {
+                write!(f, "type '{}' is missing from the map schema", type_name)
+            }
+            SchemaMismatch::MissingProperty {
+                type_name,
+                property,
+            } => write!(
+                f,
+                "property '{}.{}' is missing from the map schema",
+                type_name, property
+            ),
+            SchemaMismatch::PropertyType {
+                type_name,
+                property,
+                expected,
+                found,
+            } => write!(
+                f,
+                "property '{}.{}' is {} in code but {} in the map schema",
+                type_name,
+                property,
+                expected.display_name(),
+                found.display_name()
+            ),
+            SchemaMismatch::TypeReference {
+                type_name,
+                property,
+                expected,
+                found,
+            } => write!(
+                f,
+                "property '{}.{}' references {} in code but {} in the map schema",
+                type_name,
+                property,
+                expected.as_deref().unwrap_or("nothing"),
+                found.as_deref().unwrap_or("nothing")
+            ),
+            SchemaMismatch::MissingEnum { enum_name } => {
+                write!(f, "enum '{}' is missing from the map schema", enum_name)
+            }
+            SchemaMismatch::MissingEnumValue { enum_name, value } => write!(
+                f,
+                "enum value '{}::{}' is missing from the map schema",
+                enum_name, value
+            ),
+            SchemaMismatch::EnumFlags {
+                enum_name,
+                expected,
+            } => write!(
+                f,
+                "enum '{}' is {} in code but {} in the map schema",
+                enum_name,
+                if *expected {
+                    "a flag enum"
+                } else {
+                    "a plain enum"
+                },
+                if *expected {
+                    "a plain enum"
+                } else {
+                    "a flag enum"
+                }
+            ),
+        }
+    }
+}
+
+impl Schema {
+    /// Merge another schema into this one
+    ///
+    /// Missing enums, types and properties are added, and missing enum values are
+    /// appended. Properties that exist in both take their type, default, range and
+    /// type references from `other`, and enums take their flags and explicit
+    /// values; editor-only settings such as colors, icons and `showIf` conditions
+    /// are kept.
+    pub fn merge(&mut self, other: &Schema) {
+        for (name, enum_def) in &other.enums {
+            let existing = self.enums.entry(name.clone()).or_default();
+            existing.flags = enum_def.flags;
+            for variant in &enum_def.variants {
+                match existing.variant_mut(&variant.name) {
+                    Some(existing_variant) => {
+                        if variant.value.is_some() {
+                            existing_variant.value = variant.value;
+                        }
+                    }
+                    None => existing.variants.push(variant.clone()),
+                }
+            }
+        }
+
+        for (name, type_def) in &other.data_types {
+            merge_type(&mut self.data_types, name, type_def);
+        }
+        for (name, type_def) in &other.embedded_types {
+            merge_type(&mut self.embedded_types, name, type_def);
+        }
+    }
+
+    /// Find where a map's schema disagrees with this schema
+    ///
+    /// `self` is treated as the source of truth, typically a schema generated from
+    /// registered Rust types. Types, properties and enum values that only exist in
+    /// `map` are not reported.
+    pub fn mismatches(&self, map: &Schema) -> Vec<SchemaMismatch> {
+        let mut mismatches = Vec::new();
+
+        let mut enum_names: Vec<&String> = self.enums.keys().collect();
+        enum_names.sort();
+        for enum_name in enum_names {
+            let Some(map_enum) = map.get_enum(enum_name) else {
+                mismatches.push(SchemaMismatch::MissingEnum {
+                    enum_name: enum_name.clone(),
+                });
+                continue;
+            };
+            let code_enum = &self.enums[enum_name];
+            if code_enum.flags != map_enum.flags {
+                mismatches.push(SchemaMismatch::EnumFlags {
+                    enum_name: enum_name.clone(),
+                    expected: code_enum.flags,
+                });
+            }
+            for value in code_enum.names() {
+                if !map_enum.contains(value) {
+                    mismatches.push(SchemaMismatch::MissingEnumValue {
+                        enum_name: enum_name.clone(),
+                        value: value.to_string(),
+                    });
+                }
+            }
+        }
+
+        let mut types: Vec<(&String, &TypeDef)> = self
+            .data_types
+            .iter()
+            .chain(self.embedded_types.iter())
+            .collect();
+        types.sort_by_key(|(name, _)| *name);
+        for (type_name, type_def) in types {
+            let Some(map_type) = map.resolved_type(type_name) else {
+                mismatches.push(SchemaMismatch::MissingType {
+                    type_name: type_name.clone(),
+                });
+                continue;
+            };
+
+            for prop in &type_def.properties {
+                let Some(map_prop) = map_type.properties.iter().find(|p| p.name == prop.name)
+                else {
+                    mismatches.push(SchemaMismatch::MissingProperty {
+                        type_name: type_name.clone(),
+                        property: prop.name.clone(),
+                    });
+                    continue;
+                };
+
+                if !same_prop_type(prop.prop_type, map_prop.prop_type) {
+                    mismatches.push(SchemaMismatch::PropertyType {
+                        type_name: type_name.clone(),
+                        property: prop.name.clone(),
+                        expected: prop.prop_type,
+                        found: map_prop.prop_type,
+                    });
+                } else if type_reference(prop) != type_reference(map_prop) {
+                    mismatches.push(SchemaMismatch::TypeReference {
+                        type_name: type_name.clone(),
+                        property: prop.name.clone(),
+                        expected: type_reference(prop).map(str::to_string),
+                        found: type_reference(map_prop).map(str::to_string),
+                    });
+                }
+            }
+        }
+
+        mismatches
+    }
+}
+
+fn merge_type(
+    types: &mut std::collections::HashMap<String, TypeDef>,
+    name: &str,
+    type_def: &TypeDef,
+) {
+    let Some(existing) = types.get_mut(name) else {
+        types.insert(name.to_string(), type_def.clone());
+        return;
+    };
+
+    for prop in &type_def.properties {
+        match existing.properties.iter_mut().find(|p| p.name == prop.name) {
+            Some(existing_prop) => {
+                existing_prop.prop_type = prop.prop_type;
+                existing_prop.default = prop.default.clone();
+                existing_prop.min = prop.min;
+                existing_prop.max = prop.max;
+                existing_prop.enum_type = prop.enum_type.clone();
+                existing_prop.ref_type = prop.ref_type.clone();
+                existing_prop.item_type = prop.item_type.clone();
+                existing_prop.embedded_type = prop.embedded_type.clone();
+                existing_prop.extensions = prop.extensions.clone();
+            }
+            None => existing.properties.push(prop.clone()),
+        }
+    }
+}
+
+/// Strings are interchangeable with multiline strings
+fn same_prop_type(a: PropType, b: PropType) -> bool {
+    let normalize = |t| match t {
+        PropType::Multiline => PropType::String,
+        t => t,
+    };
+    normalize(a) == normalize(b)
+}
+
+/// The enum, embedded, ref or item type a property points at, if any
+fn type_reference(prop: &PropertyDef) -> Option<&str> {
+    prop.enum_type
+        .as_deref()
+        .or(prop.embedded_type.as_deref())
+        .or(prop.ref_type.as_deref())
+        .or(prop.item_type.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EnumDef;
+
+    fn schema_with(type_name: &str, properties: Vec<PropertyDef>) -> Schema {
+        let mut schema = Schema::default();
+        schema.data_types.insert(
+            type_name.to_string(),
+            TypeDef {
+                properties,
+                ..Default::default()
+            },
+        );
+        schema
+    }
+
+    #[test]
+    fn test_merge_keeps_editor_settings() {
+        let mut shown = PropertyDef::new("health", PropType::Float);
+        shown.show_if = Some("alive".to_string());
+        let mut editor = schema_with("NPC", vec![shown]);
+        editor.data_types.get_mut("NPC").unwrap().color = "#ff0000".to_string();
+        let mut faction = EnumDef::new(["Friendly"]);
+        faction.variants[0].color = Some("#00ff00".to_string());
+        editor.enums.insert("Faction".to_string(), faction);
+
+        let mut code = schema_with(
+            "NPC",
+            vec![
+                PropertyDef::new("health", PropType::Int),
+                PropertyDef::new("name", PropType::String),
+            ],
+        );
+        code.enums
+            .insert("Faction".to_string(), EnumDef::new(["Friendly", "Hostile"]));
+        code.enums
+            .insert("Abilities".to_string(), EnumDef::flags(["Fly", "Swim"]));
+
+        editor.merge(&code);
+
+        let npc = editor.get_type("NPC").unwrap();
+        assert_eq!(npc.color, "#ff0000");
+        assert_eq!(npc.properties.len(), 2);
+        assert_eq!(npc.properties[0].prop_type, PropType::Int);
+        assert_eq!(npc.properties[0].show_if.as_deref(), Some("alive"));
+        let faction = &editor.enums["Faction"];
+        assert_eq!(
+            faction.names().collect::<Vec<_>>(),
+            vec!["Friendly", "Hostile"]
+        );
+        assert_eq!(faction.variants[0].color.as_deref(), Some("#00ff00"));
+        assert!(editor.enums["Abilities"].flags);
+        assert!(code.mismatches(&editor).is_empty());
+    }
+
+    #[test]
+    fn test_mismatches() {
+        let mut faction = PropertyDef::new("faction", PropType::Enum);
+        faction.enum_type = Some("Faction".to_string());
+        let mut code = schema_with(
+            "NPC",
+            vec![
+                PropertyDef::new("health", PropType::Int),
+                PropertyDef::new("name", PropType::String),
+                faction.clone(),
+            ],
+        );
+        code.enums
+            .insert("Faction".to_string(), EnumDef::new(["Friendly", "Hostile"]));
+        code.enums
+            .insert("Abilities".to_string(), EnumDef::flags(["Fly"]));
+        code.data_types
+            .insert("Chest".to_string(), TypeDef::default());
+
+        let mut map = schema_with(
+            "NPC",
+            vec![
+                PropertyDef::new("health", PropType::Float),
+                PropertyDef::new("name", PropType::Multiline),
+                faction,
+            ],
+        );
+        map.enums
+            .insert("Faction".to_string(), EnumDef::new(["Friendly"]));
+        map.enums
+            .insert("Abilities".to_string(), EnumDef::new(["Fly"]));
+
+        let mismatches = code.mismatches(&map);
+        assert_eq!(mismatches.len(), 4);
+        assert!(mismatches.contains(&SchemaMismatch::EnumFlags {
+            enum_name: "Abilities".to_string(),
+            expected: true,
+        }));
+        assert!(mismatches.contains(&SchemaMismatch::MissingEnumValue {
+            enum_name: "Faction".to_string(),
+            value: "Hostile".to_string(),
+        }));
+        assert!(mismatches.contains(&SchemaMismatch::MissingType {
+            type_name: "Chest".to_string(),
+        }));
+        assert!(mismatches.contains(&SchemaMismatch::PropertyType {
+            type_name: "NPC".to_string(),
+            property: "health".to_string(),
+            expected: PropType::Int,
+            found: PropType::Float,
+        }));
+    }
+}
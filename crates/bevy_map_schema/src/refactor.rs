@@ -0,0 +1,847 @@
+//! Schema refactoring operations
+//!
+//! Schema edits that also update the property values stored in entity and data
+//! instances, so renames, type changes and deletions don't leave stale keys
+//! behind. A [`SchemaRefactor`] is applied to the schema with
+//! [`SchemaRefactor::apply_to_schema`] and to each instance's properties with
+//! [`SchemaRefactor::apply_to_properties`], which also walks embedded values.
+
+use crate::{PropType, PropertyDef, Schema, TypeDef};
+use bevy_map_core::Value;
+use std::collections::HashMap;
+
+/// A schema change that is propagated to stored property values
+#[derive(Debug, Clone)]
+pub enum SchemaRefactor {
+    /// Replace a property definition
+    ///
+    /// Values move to the new name and are converted if the type or enum changes.
+    EditProperty {
+        type_name: String,
+        property: String,
+        def: Box<PropertyDef>,
+    },
+    /// Delete a property and its values
+    DeleteProperty { type_name: String, property: String },
+    /// Rename an enum value and every stored use of it
+    RenameEnumValue {
+        enum_name: String,
+        from: String,
+        to: String,
+    },
+    /// Delete an enum value; properties holding it are cleared
+    DeleteEnumValue { enum_name: String, value: String },
+    /// Switch an enum between single values and flag sets
+    ///
+    /// Single values become one-flag sets. Flag sets with more than one flag
+    /// cannot become a single value and are cleared.
+    SetEnumFlags { enum_name: String, flags: bool },
+    /// Delete a type, its instances and every property that references it
+    DeleteType { type_name: String },
+}
+
+/// A stored value that could not be converted by a refactor
+///
+/// The value is removed from the instance, so the property falls back to its default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefactorFailure {
+    /// Property path, with embedded properties joined by `.`
+    pub property: String,
+    /// The value that was removed
+    pub value: Value,
+    /// Why it could not be converted
+    pub reason: String,
+}
+
+impl SchemaRefactor {
+    /// Short description for undo history and previews
+    pub fn description(&self) -> String {
+        match self {
+            SchemaRefactor::EditProperty {
+                type_name,
+                property,
+                def,
+            } if def.name != *property => {
+                format!("Rename Property {}.{} to {}", type_name, property, def.name)
+            }
+            SchemaRefactor::EditProperty {
+                type_name,
+                property,
+                ..
+            } => format!("Edit Property {}.{}", type_name, property),
+            SchemaRefactor::DeleteProperty {
+                type_name,
+                property,
+            } => format!("Delete Property {}.{}", type_name, property),
+            SchemaRefactor::RenameEnumValue {
+                enum_name,
+                from,
+                to,
+            } => format!("Rename Enum Value {}::{} to {}", enum_name, from, to),
+            SchemaRefactor::DeleteEnumValue { enum_name, value } => {
+                format!("Delete Enum Value {}::{}", enum_name, value)
+            }
+            SchemaRefactor::SetEnumFlags {
+                enum_name,
+                flags: true,
+            } => format!("Make {} a Flag Enum", enum_name),
+            SchemaRefactor::SetEnumFlags { enum_name, .. } => {
+                format!("Make {} a Plain Enum", enum_name)
+            }
+            SchemaRefactor::DeleteType { type_name } => format!("Delete Type {}", type_name),
+        }
+    }
+
+    /// Whether instances of a type are deleted by this refactor
+    pub fn deletes_instances_of(&self, type_name: &str) -> bool {
+        matches!(self, SchemaRefactor::DeleteType { type_name: deleted } if deleted == type_name)
+    }
+
+    /// Apply the change to a schema
+    pub fn apply_to_schema(&self, schema: &mut Schema) {
+        match self {
+            SchemaRefactor::EditProperty {
+                type_name,
+                property,
+                def,
+            } => {
+                if let Some(prop) = type_def_mut(schema, type_name)
+                    .and_then(|t| t.properties.iter_mut().find(|p| p.name == *property))
+                {
+                    *prop = (**def).clone();
+                }
+                // Overrides in derived types follow the renamed property
+                for subtype in subtype_names(schema, type_name) {
+                    let Some(prop) = type_def_mut(schema, &subtype)
+                        .and_then(|t| t.properties.iter_mut().find(|p| p.name == *property))
+                    else {
+                        continue;
+                    };
+                    if prop.prop_type != def.prop_type {
+                        prop.default = None;
+                    }
+                    prop.name = def.name.clone();
+                    prop.prop_type = def.prop_type;
+                }
+                // Marker colors follow the property, or stop if it is no longer an enum
+                for name in
+                    std::iter::once(type_name.clone()).chain(subtype_names(schema, type_name))
+                {
+                    if let Some(type_def) = type_def_mut(schema, &name) {
+                        if type_def.color_by.as_ref() == Some(property) {
+                            type_def.color_by =
+                                (def.prop_type == PropType::Enum).then(|| def.name.clone());
+                        }
+                    }
+                }
+            }
+            SchemaRefactor::DeleteProperty {
+                type_name,
+                property,
+            } => {
+                for name in
+                    std::iter::once(type_name.clone()).chain(subtype_names(schema, type_name))
+                {
+                    if let Some(type_def) = type_def_mut(schema, &name) {
+                        type_def.properties.retain(|p| p.name != *property);
+                        if type_def.color_by.as_ref() == Some(property) {
+                            type_def.color_by = None;
+                        }
+                    }
+                }
+            }
+            SchemaRefactor::RenameEnumValue {
+                enum_name,
+                from,
+                to,
+            } => {
+                if let Some(variant) = schema
+                    .enums
+                    .get_mut(enum_name)
+                    .and_then(|enum_def| enum_def.variant_mut(from))
+                {
+                    variant.name = to.clone();
+                }
+                for prop in all_properties_mut(schema) {
+                    if prop.enum_type.as_ref() == Some(enum_name) {
+                        rename_default(prop, from, to);
+                    }
+                }
+            }
+            SchemaRefactor::DeleteEnumValue { enum_name, value } => {
+                if let Some(enum_def) = schema.enums.get_mut(enum_name) {
+                    enum_def.variants.retain(|v| v.name != *value);
+                }
+                for prop in all_properties_mut(schema) {
+                    if prop.enum_type.as_ref() != Some(enum_name) {
+                        continue;
+                    }
+                    match &mut prop.default {
+                        Some(serde_json::Value::String(default)) if default == value => {
+                            prop.default = None;
+                        }
+                        Some(serde_json::Value::Array(flags)) => {
+                            flags.retain(|flag| flag.as_str() != Some(value));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            SchemaRefactor::SetEnumFlags { enum_name, flags } => {
+                if let Some(enum_def) = schema.enums.get_mut(enum_name) {
+                    enum_def.flags = *flags;
+                }
+                for prop in all_properties_mut(schema) {
+                    if prop.prop_type != PropType::Enum
+                        || prop.enum_type.as_ref() != Some(enum_name)
+                    {
+                        continue;
+                    }
+                    prop.default = match prop.default.take() {
+                        Some(serde_json::Value::String(default)) if *flags => {
+                            Some(serde_json::json!([default]))
+                        }
+                        Some(serde_json::Value::Array(mut set)) if !*flags && set.len() == 1 => {
+                            set.pop()
+                        }
+                        Some(default) if default.is_array() == *flags => Some(default),
+                        _ => None,
+                    };
+                }
+            }
+            SchemaRefactor::DeleteType { type_name } => {
+                let Some(deleted) = schema
+                    .data_types
+                    .remove(type_name)
+                    .or_else(|| schema.embedded_types.remove(type_name))
+                else {
+                    return;
+                };
+                // Derived types take over the deleted type's properties and base type
+                for type_def in schema
+                    .data_types
+                    .values_mut()
+                    .chain(schema.embedded_types.values_mut())
+                    .filter(|t| t.extends.as_ref() == Some(type_name))
+                {
+                    type_def.extends = deleted.extends.clone();
+                    for (index, prop) in deleted.properties.iter().enumerate() {
+                        if !type_def.properties.iter().any(|p| p.name == prop.name) {
+                            type_def
+                                .properties
+                                .insert(index.min(type_def.properties.len()), prop.clone());
+                        }
+                    }
+                }
+                for type_def in schema
+                    .data_types
+                    .values_mut()
+                    .chain(schema.embedded_types.values_mut())
+                {
+                    type_def
+                        .properties
+                        .retain(|p| !references_type(p, type_name));
+                }
+            }
+        }
+    }
+
+    /// Apply the change to the properties of an instance of `type_name`
+    ///
+    /// `schema` must be the schema from before the refactor. Embedded values are
+    /// updated too. Returns the values that could not be converted; they are
+    /// removed from `properties`.
+    pub fn apply_to_properties(
+        &self,
+        schema: &Schema,
+        type_name: &str,
+        properties: &mut HashMap<String, Value>,
+    ) -> Vec<RefactorFailure> {
+        let mut failures = Vec::new();
+        self.apply_to_object(schema, type_name, properties, "", &mut failures);
+        failures
+    }
+
+    fn apply_to_object(
+        &self,
+        schema: &Schema,
+        type_name: &str,
+        object: &mut HashMap<String, Value>,
+        path: &str,
+        failures: &mut Vec<RefactorFailure>,
+    ) {
+        let Some(type_def) = schema.resolved_type(type_name) else {
+            return;
+        };
+
+        // Update nested objects first, while keys still have their old names
+        for prop in &type_def.properties {
+            let Some(value) = object.get_mut(&prop.name) else {
+                continue;
+            };
+            let prop_path = join_path(path, &prop.name);
+            match (prop.prop_type, value) {
+                (PropType::Embedded, Value::Object(nested)) => {
+                    if let Some(embedded_type) = &prop.embedded_type {
+                        self.apply_to_object(schema, embedded_type, nested, &prop_path, failures);
+                    }
+                }
+                (PropType::Array, Value::Array(items)) => {
+                    let Some(item_type) = prop.item_type.as_ref() else {
+                        continue;
+                    };
+                    if !schema.embedded_types.contains_key(item_type) {
+                        continue;
+                    }
+                    for (index, item) in items.iter_mut().enumerate() {
+                        if let Value::Object(nested) = item {
+                            let item_path = format!("{}[{}]", prop_path, index);
+                            self.apply_to_object(schema, item_type, nested, &item_path, failures);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        match self {
+            SchemaRefactor::EditProperty {
+                type_name: target,
+                property,
+                def,
+            } if schema.is_subtype(type_name, target) => {
+                let Some(value) = object.remove(property) else {
+                    return;
+                };
+                let old_def = type_def.properties.iter().find(|p| p.name == *property);
+                match old_def.map_or(Ok(value.clone()), |old| {
+                    convert_value(&value, old, def, schema)
+                }) {
+                    Ok(converted) => {
+                        object.insert(def.name.clone(), converted);
+                    }
+                    Err(reason) => failures.push(RefactorFailure {
+                        property: join_path(path, property),
+                        value,
+                        reason,
+                    }),
+                }
+            }
+            SchemaRefactor::DeleteProperty {
+                type_name: target,
+                property,
+            } if schema.is_subtype(type_name, target) => {
+                object.remove(property);
+            }
+            SchemaRefactor::RenameEnumValue {
+                enum_name,
+                from,
+                to,
+            } => {
+                for prop in &type_def.properties {
+                    let Some(value) = object.get_mut(&prop.name) else {
+                        continue;
+                    };
+                    for_each_enum_value(prop, enum_name, value, |v| {
+                        if v == from {
+                            *v = to.clone();
+                        }
+                    });
+                }
+            }
+            SchemaRefactor::DeleteEnumValue {
+                enum_name,
+                value: deleted,
+            } => {
+                for prop in &type_def.properties {
+                    match object.get_mut(&prop.name) {
+                        Some(Value::String(value))
+                            if prop.prop_type == PropType::Enum
+                                && prop.enum_type.as_ref() == Some(enum_name)
+                                && value == deleted =>
+                        {
+                            object.remove(&prop.name);
+                        }
+                        Some(Value::Array(items))
+                            if prop.item_type.as_ref() == Some(enum_name)
+                                || (prop.prop_type == PropType::Enum
+                                    && prop.enum_type.as_ref() == Some(enum_name)) =>
+                        {
+                            items.retain(|item| item.as_string() != Some(deleted.as_str()));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            SchemaRefactor::SetEnumFlags { enum_name, flags } => {
+                for prop in &type_def.properties {
+                    if prop.prop_type != PropType::Enum
+                        || prop.enum_type.as_ref() != Some(enum_name)
+                    {
+                        continue;
+                    }
+                    let Some(value) = object.remove(&prop.name) else {
+                        continue;
+                    };
+                    match (value, *flags) {
+                        (Value::String(name), true) => {
+                            object
+                                .insert(prop.name.clone(), Value::Array(vec![Value::String(name)]));
+                        }
+                        (Value::Array(mut set), false) if set.len() <= 1 => {
+                            if let Some(name) = set.pop() {
+                                object.insert(prop.name.clone(), name);
+                            }
+                        }
+                        (value @ Value::Array(_), false) => failures.push(RefactorFailure {
+                            property: join_path(path, &prop.name),
+                            value,
+                            reason: "more than one flag is set".to_string(),
+                        }),
+                        (value, _) => {
+                            object.insert(prop.name.clone(), value);
+                        }
+                    }
+                }
+            }
+            SchemaRefactor::DeleteType { type_name: deleted } => {
+                for prop in &type_def.properties {
+                    if references_type(prop, deleted) {
+                        object.remove(&prop.name);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Convert a stored value from one property definition to another
+///
+/// Values are unchanged if the type and enum are the same. Lossless conversions
+/// between numbers, strings and booleans are supported; anything else fails.
+pub fn convert_value(
+    value: &Value,
+    from: &PropertyDef,
+    to: &PropertyDef,
+    schema: &Schema,
+) -> Result<Value, String> {
+    if value.is_null() {
+        return Ok(Value::Null);
+    }
+    let same_type = from.prop_type == to.prop_type
+        || matches!(
+            (from.prop_type, to.prop_type),
+            (PropType::String, PropType::Multiline) | (PropType::Multiline, PropType::String)
+        );
+    if same_type
+        && from.enum_type == to.enum_type
+        && from.item_type == to.item_type
+        && from.extensions == to.extensions
+    {
+        return Ok(value.clone());
+    }
+
+    let converted = match (to.prop_type, value) {
+        (PropType::String | PropType::Multiline, Value::String(s)) => {
+            Some(Value::String(s.clone()))
+        }
+        (PropType::String | PropType::Multiline, Value::Int(i)) => {
+            Some(Value::String(i.to_string()))
+        }
+        (PropType::String | PropType::Multiline, Value::Float(f)) => {
+            Some(Value::String(f.to_string()))
+        }
+        (PropType::String | PropType::Multiline, Value::Bool(b)) => {
+            Some(Value::String(b.to_string()))
+        }
+        (PropType::Int, Value::Int(i)) => Some(Value::Int(*i)),
+        (PropType::Int, Value::Float(f)) if f.fract() == 0.0 => Some(Value::Int(*f as i64)),
+        (PropType::Int, Value::Bool(b)) => Some(Value::Int(*b as i64)),
+        (PropType::Int, Value::String(s)) => s.trim().parse().ok().map(Value::Int),
+        (PropType::Float, Value::Int(i)) => Some(Value::Float(*i as f64)),
+        (PropType::Float, Value::Float(f)) => Some(Value::Float(*f)),
+        (PropType::Float, Value::String(s)) => s.trim().parse().ok().map(Value::Float),
+        (PropType::Bool, Value::Bool(b)) => Some(Value::Bool(*b)),
+        (PropType::Bool, Value::Int(i @ (0 | 1))) => Some(Value::Bool(*i == 1)),
+        (PropType::Bool, Value::String(s)) => s.trim().parse().ok().map(Value::Bool),
+        (PropType::Enum, Value::String(_) | Value::Array(_)) => {
+            let enum_def = to
+                .enum_type
+                .as_ref()
+                .and_then(|enum_type| schema.get_enum(enum_type));
+            // Flag sets hold any number of variants, plain enums exactly one
+            let names: Option<Vec<&str>> = match value {
+                Value::String(s) => Some(vec![s.as_str()]),
+                Value::Array(items) => items.iter().map(Value::as_string).collect(),
+                _ => None,
+            };
+            match (enum_def, names) {
+                (Some(enum_def), Some(names))
+                    if names.iter().all(|name| enum_def.contains(name)) =>
+                {
+                    match names.as_slice() {
+                        _ if enum_def.flags => Some(Value::Array(
+                            names
+                                .iter()
+                                .map(|name| Value::String(name.to_string()))
+                                .collect(),
+                        )),
+                        [name] => Some(Value::String(name.to_string())),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+        }
+        (PropType::Color, Value::String(s)) if s.starts_with('#') => Some(Value::String(s.clone())),
+        (PropType::Asset, Value::String(s)) if to.accepts_extension(s) => {
+            Some(Value::String(s.clone()))
+        }
+        (PropType::Array, Value::Array(items)) => {
+            let item_def = |item_type: Option<&String>| {
+                let mut def = PropertyDef::new("", PropType::String);
+                match item_type.map(String::as_str) {
+                    Some("Int") => def.prop_type = PropType::Int,
+                    Some("Float") => def.prop_type = PropType::Float,
+                    Some("Bool") => def.prop_type = PropType::Bool,
+                    Some(enum_type) if schema.enums.contains_key(enum_type) => {
+                        def.prop_type = PropType::Enum;
+                        def.enum_type = Some(enum_type.to_string());
+                    }
+                    Some(other) if other != "String" => def.item_type = Some(other.to_string()),
+                    _ => {}
+                }
+                def
+            };
+            let (from_item, to_item) = (
+                item_def(from.item_type.as_ref()),
+                item_def(to.item_type.as_ref()),
+            );
+            if from.prop_type != PropType::Array || from_item.item_type != to_item.item_type {
+                None
+            } else {
+                items
+                    .iter()
+                    .map(|item| convert_value(item, &from_item, &to_item, schema))
+                    .collect::<Result<Vec<_>, _>>()
+                    .ok()
+                    .map(Value::Array)
+            }
+        }
+        _ => None,
+    };
+
+    converted.ok_or_else(|| {
+        format!(
+            "cannot convert {} to {}",
+            value_description(value),
+            to.prop_type.display_name()
+        )
+    })
+}
+
+fn value_description(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("'{}'", s),
+        Value::Int(i) => i.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Array(_) => "an array".to_string(),
+        Value::Object(_) => "an object".to_string(),
+        Value::Null => "null".to_string(),
+    }
+}
+
+fn type_def_mut<'a>(schema: &'a mut Schema, type_name: &str) -> Option<&'a mut TypeDef> {
+    match schema.data_types.get_mut(type_name) {
+        Some(type_def) => Some(type_def),
+        None => schema.embedded_types.get_mut(type_name),
+    }
+}
+
+/// Names of all types that extend `type_name`, directly or indirectly
+fn subtype_names(schema: &Schema, type_name: &str) -> Vec<String> {
+    schema
+        .data_types
+        .keys()
+        .chain(schema.embedded_types.keys())
+        .filter(|name| *name != type_name && schema.is_subtype(name, type_name))
+        .cloned()
+        .collect()
+}
+
+fn all_properties_mut(schema: &mut Schema) -> impl Iterator<Item = &mut PropertyDef> {
+    schema
+        .data_types
+        .values_mut()
+        .chain(schema.embedded_types.values_mut())
+        .flat_map(|type_def| type_def.properties.iter_mut())
+}
+
+fn rename_default(prop: &mut PropertyDef, from: &str, to: &str) {
+    match &mut prop.default {
+        Some(serde_json::Value::String(default)) if default == from => {
+            *default = to.to_string();
+        }
+        Some(serde_json::Value::Array(flags)) => {
+            for flag in flags.iter_mut().filter(|flag| flag.as_str() == Some(from)) {
+                *flag = serde_json::Value::String(to.to_string());
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether a property holds values of the given type
+fn references_type(prop: &PropertyDef, type_name: &str) -> bool {
+    [&prop.ref_type, &prop.embedded_type, &prop.item_type]
+        .into_iter()
+        .any(|t| t.as_deref() == Some(type_name))
+}
+
+/// Call `f` on each enum value of `enum_name` held by a property
+fn for_each_enum_value(
+    prop: &PropertyDef,
+    enum_name: &str,
+    value: &mut Value,
+    mut f: impl FnMut(&mut String),
+) {
+    match value {
+        Value::String(s)
+            if prop.prop_type == PropType::Enum && prop.enum_type.as_deref() == Some(enum_name) =>
+        {
+            f(s)
+        }
+        Value::Array(items)
+            if prop.item_type.as_deref() == Some(enum_name)
+                || (prop.prop_type == PropType::Enum
+                    && prop.enum_type.as_deref() == Some(enum_name)) =>
+        {
+            for item in items {
+                if let Value::String(s) = item {
+                    f(s);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn join_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EnumDef;
+
+    fn test_schema() -> Schema {
+        let mut schema = Schema::default();
+        schema
+            .enums
+            .insert("Faction".to_string(), EnumDef::new(["Friendly", "Hostile"]));
+
+        let mut faction = PropertyDef::new("faction", PropType::Enum);
+        faction.enum_type = Some("Faction".to_string());
+        let mut stats = PropertyDef::new("stats", PropType::Embedded);
+        stats.embedded_type = Some("Stats".to_string());
+        schema.data_types.insert(
+            "NPC".to_string(),
+            TypeDef {
+                properties: vec![PropertyDef::new("hp", PropType::String), faction, stats],
+                ..Default::default()
+            },
+        );
+        schema.embedded_types.insert(
+            "Stats".to_string(),
+            TypeDef {
+                properties: vec![PropertyDef::new("speed", PropType::Float)],
+                ..Default::default()
+            },
+        );
+        schema
+    }
+
+    fn npc(hp: &str, faction: &str) -> HashMap<String, Value> {
+        HashMap::from([
+            ("hp".to_string(), Value::String(hp.to_string())),
+            ("faction".to_string(), Value::String(faction.to_string())),
+            (
+                "stats".to_string(),
+                Value::Object(HashMap::from([("speed".to_string(), Value::Float(2.0))])),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_edit_property_converts_values() {
+        let schema = test_schema();
+        let refactor = SchemaRefactor::EditProperty {
+            type_name: "NPC".to_string(),
+            property: "hp".to_string(),
+            def: Box::new(PropertyDef::new("health", PropType::Int)),
+        };
+
+        let mut good = npc("10", "Friendly");
+        assert!(refactor
+            .apply_to_properties(&schema, "NPC", &mut good)
+            .is_empty());
+        assert_eq!(good.get("health"), Some(&Value::Int(10)));
+        assert!(!good.contains_key("hp"));
+
+        let mut bad = npc("lots", "Friendly");
+        let failures = refactor.apply_to_properties(&schema, "NPC", &mut bad);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].property, "hp");
+        assert!(!bad.contains_key("hp") && !bad.contains_key("health"));
+
+        let mut updated = schema.clone();
+        refactor.apply_to_schema(&mut updated);
+        assert_eq!(updated.data_types["NPC"].properties[0].name, "health");
+    }
+
+    #[test]
+    fn test_embedded_and_enum_refactors() {
+        let schema = test_schema();
+        let mut props = npc("10", "Hostile");
+
+        let rename_speed = SchemaRefactor::EditProperty {
+            type_name: "Stats".to_string(),
+            property: "speed".to_string(),
+            def: Box::new(PropertyDef::new("moveSpeed", PropType::Float)),
+        };
+        rename_speed.apply_to_properties(&schema, "NPC", &mut props);
+        let stats = props["stats"].as_object().unwrap();
+        assert_eq!(stats.get("moveSpeed"), Some(&Value::Float(2.0)));
+
+        let rename_value = SchemaRefactor::RenameEnumValue {
+            enum_name: "Faction".to_string(),
+            from: "Hostile".to_string(),
+            to: "Enemy".to_string(),
+        };
+        rename_value.apply_to_properties(&schema, "NPC", &mut props);
+        assert_eq!(props["faction"], Value::String("Enemy".to_string()));
+
+        let delete_stats = SchemaRefactor::DeleteType {
+            type_name: "Stats".to_string(),
+        };
+        delete_stats.apply_to_properties(&schema, "NPC", &mut props);
+        assert!(!props.contains_key("stats"));
+
+        let mut updated = schema.clone();
+        delete_stats.apply_to_schema(&mut updated);
+        assert!(crate::validate_schema(&updated).is_ok());
+        assert_eq!(updated.data_types["NPC"].properties.len(), 2);
+    }
+
+    #[test]
+    fn test_flag_enum_refactors() {
+        let mut schema = test_schema();
+        schema.enums.insert(
+            "Abilities".to_string(),
+            EnumDef::flags(["Fly", "Swim", "Climb"]),
+        );
+        let mut abilities = PropertyDef::new("abilities", PropType::Enum);
+        abilities.enum_type = Some("Abilities".to_string());
+        abilities.default = Some(serde_json::json!(["Swim"]));
+        let npc_type = schema.data_types.get_mut("NPC").unwrap();
+        npc_type.properties.push(abilities);
+        npc_type.color_by = Some("abilities".to_string());
+
+        let mut props = npc("10", "Friendly");
+        props.insert(
+            "abilities".to_string(),
+            Value::Array(vec![
+                Value::String("Fly".to_string()),
+                Value::String("Swim".to_string()),
+            ]),
+        );
+
+        let rename = SchemaRefactor::RenameEnumValue {
+            enum_name: "Abilities".to_string(),
+            from: "Swim".to_string(),
+            to: "Dive".to_string(),
+        };
+        rename.apply_to_properties(&schema, "NPC", &mut props);
+        rename.apply_to_schema(&mut schema);
+        assert_eq!(
+            props["abilities"],
+            Value::Array(vec![
+                Value::String("Fly".to_string()),
+                Value::String("Dive".to_string()),
+            ])
+        );
+        let abilities = &schema.data_types["NPC"].properties[3];
+        assert_eq!(abilities.default, Some(serde_json::json!(["Dive"])));
+
+        let delete = SchemaRefactor::DeleteEnumValue {
+            enum_name: "Abilities".to_string(),
+            value: "Fly".to_string(),
+        };
+        delete.apply_to_properties(&schema, "NPC", &mut props);
+        delete.apply_to_schema(&mut schema);
+        assert_eq!(
+            props["abilities"],
+            Value::Array(vec![Value::String("Dive".to_string())])
+        );
+        assert_eq!(
+            schema.enums["Abilities"].names().collect::<Vec<_>>(),
+            vec!["Dive", "Climb"]
+        );
+
+        let plain = SchemaRefactor::SetEnumFlags {
+            enum_name: "Abilities".to_string(),
+            flags: false,
+        };
+        let mut two = props.clone();
+        two.insert(
+            "abilities".to_string(),
+            Value::Array(vec![
+                Value::String("Dive".to_string()),
+                Value::String("Climb".to_string()),
+            ]),
+        );
+        assert_eq!(plain.apply_to_properties(&schema, "NPC", &mut two).len(), 1);
+        assert!(!two.contains_key("abilities"));
+        assert!(plain
+            .apply_to_properties(&schema, "NPC", &mut props)
+            .is_empty());
+        assert_eq!(props["abilities"], Value::String("Dive".to_string()));
+        let mut plain_schema = schema.clone();
+        plain.apply_to_schema(&mut plain_schema);
+        assert!(!plain_schema.enums["Abilities"].flags);
+        assert_eq!(
+            plain_schema.data_types["NPC"].properties[3].default,
+            Some(serde_json::json!("Dive"))
+        );
+
+        // A single variant converts into a flag set
+        let mut faction_flags = PropertyDef::new("faction", PropType::Enum);
+        faction_flags.enum_type = Some("Abilities".to_string());
+        let faction = &schema.data_types["NPC"].properties[1];
+        assert_eq!(
+            convert_value(
+                &Value::String("Climb".to_string()),
+                faction,
+                &faction_flags,
+                &schema
+            ),
+            Ok(Value::Array(vec![Value::String("Climb".to_string())]))
+        );
+
+        // Markers stop coloring by a deleted property
+        SchemaRefactor::DeleteProperty {
+            type_name: "NPC".to_string(),
+            property: "abilities".to_string(),
+        }
+        .apply_to_schema(&mut schema);
+        assert_eq!(schema.data_types["NPC"].color_by, None);
+        assert!(crate::validate_schema(&schema).is_ok());
+    }
+}
@@ -9,7 +9,7 @@ pub struct Schema {
     pub version: u32,
     pub project: ProjectConfig,
     #[serde(default)]
-    pub enums: HashMap<String, Vec<String>>,
+    pub enums: HashMap<String, EnumDef>,
     #[serde(default)]
     pub data_types: HashMap<String, TypeDef>,
     #[serde(default)]
@@ -24,11 +24,56 @@ impl Schema {
             .or_else(|| self.embedded_types.get(name))
     }
 
-    /// Get enum values by name
-    pub fn get_enum(&self, name: &str) -> Option<&Vec<String>> {
+    /// Get an enum definition by name
+    pub fn get_enum(&self, name: &str) -> Option<&EnumDef> {
         self.enums.get(name)
     }
 
+    /// Get the marker color for an entity of the given type
+    ///
+    /// If the type (or a base type) sets `color_by`, the color of the selected
+    /// variant of that enum property is used, falling back to the property's
+    /// default. For flag enums the first set variant with a color wins. Returns
+    /// the type's own color otherwise.
+    pub fn marker_color<'a>(
+        &'a self,
+        type_name: &str,
+        properties: &'a HashMap<String, bevy_map_core::Value>,
+    ) -> Option<&'a str> {
+        use bevy_map_core::Value;
+
+        let type_def = self.get_type(type_name)?;
+        let mut chain = self.base_types(type_name);
+        chain.insert(0, type_name);
+        let types = || chain.iter().filter_map(|name| self.get_type(name));
+
+        let variant_color = types()
+            .find_map(|t| t.color_by.as_deref())
+            .and_then(|property| {
+                let prop =
+                    types().find_map(|t| t.properties.iter().find(|p| p.name == property))?;
+                let enum_def = self.get_enum(prop.enum_type.as_deref()?)?;
+                let selected: Vec<&str> = match properties.get(property) {
+                    Some(Value::String(name)) => vec![name.as_str()],
+                    Some(Value::Array(items)) => {
+                        items.iter().filter_map(Value::as_string).collect()
+                    }
+                    _ => match &prop.default {
+                        Some(serde_json::Value::String(name)) => vec![name.as_str()],
+                        Some(serde_json::Value::Array(items)) => {
+                            items.iter().filter_map(|item| item.as_str()).collect()
+                        }
+                        _ => Vec::new(),
+                    },
+                };
+                selected
+                    .into_iter()
+                    .find_map(|name| enum_def.variant(name)?.color.as_deref())
+            });
+
+        Some(variant_color.unwrap_or(&type_def.color))
+    }
+
     /// Get all type names sorted alphabetically
     pub fn all_type_names(&self) -> Vec<&str> {
         let mut names: Vec<&str> = self.data_types.keys().map(|s| s.as_str()).collect();
@@ -43,6 +88,75 @@ impl Schema {
         names
     }
 
+    /// Get a type definition with inherited properties resolved
+    ///
+    /// Properties from base types come first, in order from the root type down.
+    /// A property redeclared by a derived type overrides the inherited default,
+    /// min and max.
+    pub fn resolved_type(&self, name: &str) -> Option<TypeDef> {
+        let mut resolved = self.get_type(name)?.clone();
+        let mut properties: Vec<PropertyDef> = Vec::new();
+        let mut chain = self.base_types(name);
+        chain.insert(0, name);
+        for type_name in chain.iter().rev() {
+            let Some(type_def) = self.get_type(type_name) else {
+                continue;
+            };
+            for prop in &type_def.properties {
+                match properties.iter_mut().find(|p| p.name == prop.name) {
+                    Some(inherited) => {
+                        if prop.default.is_some() {
+                            inherited.default = prop.default.clone();
+                        }
+                        if prop.min.is_some() {
+                            inherited.min = prop.min;
+                        }
+                        if prop.max.is_some() {
+                            inherited.max = prop.max;
+                        }
+                    }
+                    None => properties.push(prop.clone()),
+                }
+            }
+        }
+        resolved.properties = properties;
+        Some(resolved)
+    }
+
+    /// Get the types a type extends, nearest first
+    ///
+    /// Stops at unknown types and cycles, which `validate_schema` reports.
+    pub fn base_types(&self, name: &str) -> Vec<&str> {
+        let mut bases: Vec<&str> = Vec::new();
+        let mut current = self.get_type(name);
+        while let Some(base) = current.and_then(|t| t.extends.as_deref()) {
+            if base == name || bases.contains(&base) {
+                break;
+            }
+            bases.push(base);
+            current = self.get_type(base);
+        }
+        bases
+    }
+
+    /// Check if a type is `base` or extends it
+    pub fn is_subtype(&self, name: &str, base: &str) -> bool {
+        name == base || self.base_types(name).contains(&base)
+    }
+
+    /// Get the types that directly extend a type, sorted alphabetically
+    pub fn subtypes(&self, name: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .data_types
+            .iter()
+            .chain(self.embedded_types.iter())
+            .filter(|(_, def)| def.extends.as_deref() == Some(name))
+            .map(|(name, _)| name.as_str())
+            .collect();
+        names.sort();
+        names
+    }
+
     /// Get all placeable type names (types that can be placed in levels)
     pub fn placeable_type_names(&self) -> Vec<&str> {
         let mut names: Vec<&str> = self
@@ -81,6 +195,12 @@ pub struct TypeDef {
     /// Marker size in pixels for rendering on canvas (default: 16)
     #[serde(default)]
     pub marker_size: Option<u32>,
+    /// Base type whose properties this type inherits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    /// Enum property whose variant color is used for the editor marker
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_by: Option<String>,
     #[serde(default)]
     pub properties: Vec<PropertyDef>,
 }
@@ -96,6 +216,8 @@ impl Default for TypeDef {
             icon: None,
             placeable: false,
             marker_size: None,
+            extends: None,
+            color_by: None,
             properties: Vec::new(),
         }
     }
@@ -122,6 +244,238 @@ pub struct PropertyDef {
     pub item_type: Option<String>,
     #[serde(rename = "embeddedType")]
     pub embedded_type: Option<String>,
+    /// File extensions accepted by an asset property, without the dot (empty = any)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<String>,
+}
+
+impl PropertyDef {
+    /// Create an optional property with no default, range or type references
+    pub fn new(name: impl Into<String>, prop_type: PropType) -> Self {
+        Self {
+            name: name.into(),
+            prop_type,
+            required: false,
+            default: None,
+            min: None,
+            max: None,
+            show_if: None,
+            enum_type: None,
+            ref_type: None,
+            item_type: None,
+            embedded_type: None,
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Check if an asset path has one of the accepted extensions
+    pub fn accepts_extension(&self, path: &str) -> bool {
+        if self.extensions.is_empty() {
+            return true;
+        }
+        // Compared as suffixes so multi-part extensions like "scn.ron" work
+        let path = path.to_ascii_lowercase();
+        self.extensions.iter().any(|accepted| {
+            let accepted = accepted.trim_start_matches('.').to_ascii_lowercase();
+            path.strip_suffix(&accepted)
+                .is_some_and(|stem| stem.ends_with('.'))
+        })
+    }
+}
+
+/// Definition of an enum (from schema)
+///
+/// Plain enums store a single variant name. Flag enums store a set of variants
+/// as an array of names. Enums without flags or variant settings are written as
+/// a plain list of names.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "EnumDefRepr", into = "EnumDefRepr")]
+pub struct EnumDef {
+    pub variants: Vec<EnumVariant>,
+    /// Values are sets of variants (bitsets) rather than a single variant
+    pub flags: bool,
+}
+
+impl EnumDef {
+    /// Create a plain enum from variant names
+    pub fn new(names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            variants: names.into_iter().map(EnumVariant::new).collect(),
+            flags: false,
+        }
+    }
+
+    /// Create a flag enum from variant names
+    pub fn flags(names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            flags: true,
+            ..Self::new(names)
+        }
+    }
+
+    /// Variant names in order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.variants.iter().map(|v| v.name.as_str())
+    }
+
+    /// Check if the enum has a variant with this name
+    pub fn contains(&self, name: &str) -> bool {
+        self.variant(name).is_some()
+    }
+
+    /// Get a variant by name
+    pub fn variant(&self, name: &str) -> Option<&EnumVariant> {
+        self.variants.iter().find(|v| v.name == name)
+    }
+
+    /// Get a variant by name for editing
+    pub fn variant_mut(&mut self, name: &str) -> Option<&mut EnumVariant> {
+        self.variants.iter_mut().find(|v| v.name == name)
+    }
+
+    /// Add a variant if no variant has this name, returning whether it was added
+    pub fn push(&mut self, name: impl Into<String>) -> bool {
+        let name = name.into();
+        if self.contains(&name) {
+            return false;
+        }
+        self.variants.push(EnumVariant::new(name));
+        true
+    }
+
+    /// Integer value of a variant
+    ///
+    /// Defaults to the variant's index, or `1 << index` for flag enums.
+    pub fn value_of(&self, name: &str) -> Option<i64> {
+        let index = self.variants.iter().position(|v| v.name == name)?;
+        Some(self.variants[index].value.unwrap_or(if self.flags {
+            1 << index.min(62)
+        } else {
+            index as i64
+        }))
+    }
+
+    pub fn len(&self) -> usize {
+        self.variants.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variants.is_empty()
+    }
+}
+
+/// A variant of an [`EnumDef`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "EnumVariantRepr", into = "EnumVariantRepr")]
+pub struct EnumVariant {
+    pub name: String,
+    /// Hex color shown in the editor, e.g. on entity markers
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    /// Explicit integer value (or bit mask for flag enums)
+    pub value: Option<i64>,
+}
+
+impl EnumVariant {
+    /// Create a variant with no color, icon or explicit value
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    fn is_plain(&self) -> bool {
+        self.color.is_none() && self.icon.is_none() && self.value.is_none()
+    }
+}
+
+/// Serialized form of [`EnumDef`]: a list of names, or an object with flags
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum EnumDefRepr {
+    Variants(Vec<EnumVariant>),
+    Full {
+        #[serde(default)]
+        flags: bool,
+        #[serde(default)]
+        variants: Vec<EnumVariant>,
+    },
+}
+
+impl From<EnumDefRepr> for EnumDef {
+    fn from(repr: EnumDefRepr) -> Self {
+        match repr {
+            EnumDefRepr::Variants(variants) => Self {
+                variants,
+                flags: false,
+            },
+            EnumDefRepr::Full { flags, variants } => Self { variants, flags },
+        }
+    }
+}
+
+impl From<EnumDef> for EnumDefRepr {
+    fn from(def: EnumDef) -> Self {
+        if def.flags {
+            EnumDefRepr::Full {
+                flags: true,
+                variants: def.variants,
+            }
+        } else {
+            EnumDefRepr::Variants(def.variants)
+        }
+    }
+}
+
+/// Serialized form of [`EnumVariant`]: a name, or an object with settings
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum EnumVariantRepr {
+    Name(String),
+    Full {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        color: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        icon: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        value: Option<i64>,
+    },
+}
+
+impl From<EnumVariantRepr> for EnumVariant {
+    fn from(repr: EnumVariantRepr) -> Self {
+        match repr {
+            EnumVariantRepr::Name(name) => Self::new(name),
+            EnumVariantRepr::Full {
+                name,
+                color,
+                icon,
+                value,
+            } => Self {
+                name,
+                color,
+                icon,
+                value,
+            },
+        }
+    }
+}
+
+impl From<EnumVariant> for EnumVariantRepr {
+    fn from(variant: EnumVariant) -> Self {
+        if variant.is_plain() {
+            EnumVariantRepr::Name(variant.name)
+        } else {
+            EnumVariantRepr::Full {
+                name: variant.name,
+                color: variant.color,
+                icon: variant.icon,
+                value: variant.value,
+            }
+        }
+    }
 }
 
 /// Property types supported by the schema
@@ -148,6 +502,10 @@ pub enum PropType {
     )]
     Sprite,
     Dialogue,
+    /// Path to an asset file, relative to the assets folder
+    Asset,
+    /// A tile in a tileset: `{"tileset": "<id>", "tile": <index>}`
+    Tile,
 }
 
 impl PropType {
@@ -167,6 +525,8 @@ impl PropType {
             PropType::Color => "Color",
             PropType::Sprite => "Sprite (Deprecated)",
             PropType::Dialogue => "Dialogue Tree",
+            PropType::Asset => "Asset",
+            PropType::Tile => "Tile",
         }
     }
 
@@ -190,6 +550,8 @@ impl PropType {
             PropType::Point,
             PropType::Color,
             PropType::Dialogue,
+            PropType::Asset,
+            PropType::Tile,
         ]
     }
 }
@@ -6,6 +6,23 @@ use crate::{Schema, SchemaError};
 pub fn validate_schema(schema: &Schema) -> Result<(), SchemaError> {
     // Check that all enum references point to valid enums
     for (type_name, type_def) in schema.data_types.iter().chain(schema.embedded_types.iter()) {
+        validate_extends(schema, type_name, type_def)?;
+
+        if let Some(color_by) = &type_def.color_by {
+            let is_enum = schema.resolved_type(type_name).is_some_and(|resolved| {
+                resolved
+                    .properties
+                    .iter()
+                    .any(|p| p.name == *color_by && p.prop_type == crate::PropType::Enum)
+            });
+            if !is_enum {
+                return Err(SchemaError::ValidationError(format!(
+                    "Type '{}' colors markers by '{}', which is not an enum property",
+                    type_name, color_by
+                )));
+            }
+        }
+
         for prop in &type_def.properties {
             if let Some(enum_type) = &prop.enum_type {
                 if !schema.enums.contains_key(enum_type) {
@@ -39,6 +56,63 @@ pub fn validate_schema(schema: &Schema) -> Result<(), SchemaError> {
     Ok(())
 }
 
+/// Check that a type's base type exists, doesn't form a cycle and isn't
+/// contradicted by the type's own properties
+fn validate_extends(
+    schema: &Schema,
+    type_name: &str,
+    type_def: &crate::TypeDef,
+) -> Result<(), SchemaError> {
+    let Some(base) = &type_def.extends else {
+        return Ok(());
+    };
+
+    let mut visited = vec![type_name];
+    let mut current = base.as_str();
+    loop {
+        if visited.contains(&current) {
+            visited.push(current);
+            return Err(SchemaError::ValidationError(format!(
+                "Type inheritance cycle: {}",
+                visited.join(" -> ")
+            )));
+        }
+        let Some(current_def) = schema.get_type(current) else {
+            return Err(SchemaError::ValidationError(format!(
+                "Type '{}' extends unknown type '{}'",
+                visited.last().unwrap(),
+                current
+            )));
+        };
+        visited.push(current);
+        match &current_def.extends {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+
+    // Redeclared properties may only override defaults and ranges
+    let Some(inherited) = schema.resolved_type(base) else {
+        return Ok(());
+    };
+    for prop in &type_def.properties {
+        if let Some(base_prop) = inherited.properties.iter().find(|p| p.name == prop.name) {
+            if base_prop.prop_type != prop.prop_type {
+                return Err(SchemaError::ValidationError(format!(
+                    "Type '{}' property '{}' is {} but '{}' declares it as {}",
+                    type_name,
+                    prop.name,
+                    prop.prop_type.display_name(),
+                    base,
+                    base_prop.prop_type.display_name()
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Validate an entity instance against the schema
 pub fn validate_instance(
     schema: &Schema,
@@ -46,7 +120,7 @@ pub fn validate_instance(
     properties: &std::collections::HashMap<String, serde_json::Value>,
 ) -> Result<(), SchemaError> {
     let type_def = schema
-        .get_type(type_name)
+        .resolved_type(type_name)
         .ok_or_else(|| SchemaError::ValidationError(format!("Unknown type: {}", type_name)))?;
 
     // Check required properties are present
@@ -78,13 +152,11 @@ fn validate_property_value(
     use crate::PropType;
 
     match prop_def.prop_type {
-        PropType::String | PropType::Multiline => {
-            if !value.is_string() && !value.is_null() {
-                return Err(SchemaError::ValidationError(format!(
-                    "Property '{}' must be a string",
-                    prop_def.name
-                )));
-            }
+        PropType::String | PropType::Multiline if !value.is_string() && !value.is_null() => {
+            return Err(SchemaError::ValidationError(format!(
+                "Property '{}' must be a string",
+                prop_def.name
+            )));
         }
         PropType::Int => {
             if let Some(n) = value.as_i64() {
@@ -136,45 +208,88 @@ fn validate_property_value(
                 )));
             }
         }
-        PropType::Bool => {
-            if !value.is_boolean() && !value.is_null() {
-                return Err(SchemaError::ValidationError(format!(
-                    "Property '{}' must be a boolean",
-                    prop_def.name
-                )));
-            }
+        PropType::Bool if !value.is_boolean() && !value.is_null() => {
+            return Err(SchemaError::ValidationError(format!(
+                "Property '{}' must be a boolean",
+                prop_def.name
+            )));
         }
         PropType::Enum => {
-            if let Some(s) = value.as_str() {
-                if let Some(enum_type) = &prop_def.enum_type {
-                    if let Some(enum_values) = schema.get_enum(enum_type) {
-                        if !enum_values.contains(&s.to_string()) {
-                            return Err(SchemaError::ValidationError(format!(
-                                "Property '{}' must be one of: {:?}",
-                                prop_def.name, enum_values
-                            )));
-                        }
+            let enum_def = prop_def
+                .enum_type
+                .as_ref()
+                .and_then(|enum_type| schema.get_enum(enum_type));
+            let check_variant = |name: &str| match enum_def {
+                Some(enum_def) if !enum_def.contains(name) => {
+                    Err(SchemaError::ValidationError(format!(
+                        "Property '{}' must be one of: {:?}",
+                        prop_def.name,
+                        enum_def.names().collect::<Vec<_>>()
+                    )))
+                }
+                _ => Ok(()),
+            };
+            let flags = enum_def.is_some_and(|enum_def| enum_def.flags);
+            match value {
+                serde_json::Value::Null => {}
+                serde_json::Value::String(s) if !flags => check_variant(s)?,
+                serde_json::Value::Array(items)
+                    if flags && items.iter().all(|item| item.is_string()) =>
+                {
+                    for item in items.iter().filter_map(|item| item.as_str()) {
+                        check_variant(item)?;
                     }
                 }
-            } else if !value.is_null() {
-                return Err(SchemaError::ValidationError(format!(
-                    "Property '{}' must be a string enum value",
-                    prop_def.name
-                )));
+                _ if flags => {
+                    return Err(SchemaError::ValidationError(format!(
+                        "Property '{}' must be an array of flag names",
+                        prop_def.name
+                    )));
+                }
+                _ => {
+                    return Err(SchemaError::ValidationError(format!(
+                        "Property '{}' must be a string enum value",
+                        prop_def.name
+                    )));
+                }
             }
         }
-        PropType::Array => {
-            if !value.is_array() && !value.is_null() {
+        PropType::Array if !value.is_array() && !value.is_null() => {
+            return Err(SchemaError::ValidationError(format!(
+                "Property '{}' must be an array",
+                prop_def.name
+            )));
+        }
+        PropType::Ref if !value.is_string() && !value.is_null() => {
+            return Err(SchemaError::ValidationError(format!(
+                "Property '{}' must be a reference string",
+                prop_def.name
+            )));
+        }
+        PropType::Asset => {
+            if let Some(path) = value.as_str() {
+                if !path.is_empty() && !prop_def.accepts_extension(path) {
+                    return Err(SchemaError::ValidationError(format!(
+                        "Property '{}' must be a {} file",
+                        prop_def.name,
+                        prop_def.extensions.join("/")
+                    )));
+                }
+            } else if !value.is_null() {
                 return Err(SchemaError::ValidationError(format!(
-                    "Property '{}' must be an array",
+                    "Property '{}' must be an asset path",
                     prop_def.name
                 )));
             }
         }
-        PropType::Ref => {
-            if !value.is_string() && !value.is_null() {
+        PropType::Tile => {
+            let is_tile = value.as_object().is_some_and(|tile| {
+                tile.get("tileset").is_some_and(|id| id.is_string())
+                    && tile.get("tile").is_some_and(|index| index.is_u64())
+            });
+            if !is_tile && !value.is_null() {
                 return Err(SchemaError::ValidationError(format!(
-                    "Property '{}' must be a reference string",
+                    "Property '{}' must be a tile with a tileset and tile index",
                     prop_def.name
                 )));
             }
@@ -291,4 +406,80 @@ mod tests {
         let result = validate_instance(&schema, "Item", &props);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_validate_flag_enum() {
+        let schema = parse_schema(
+            r#"{
+            "version": 1,
+            "project": { "name": "Test" },
+            "enums": {
+                "Abilities": { "flags": true, "variants": ["Fly", "Swim", "Climb"] }
+            },
+            "data_types": {
+                "Creature": {
+                    "properties": [
+                        { "name": "abilities", "type": "enum", "enumType": "Abilities" }
+                    ]
+                }
+            }
+        }"#,
+        )
+        .unwrap();
+
+        let mut props = std::collections::HashMap::new();
+        props.insert("abilities".to_string(), serde_json::json!(["Fly", "Swim"]));
+        assert!(validate_instance(&schema, "Creature", &props).is_ok());
+
+        props.insert("abilities".to_string(), serde_json::json!([]));
+        assert!(validate_instance(&schema, "Creature", &props).is_ok());
+
+        props.insert(
+            "abilities".to_string(),
+            serde_json::json!(["Fly", "Burrow"]),
+        );
+        assert!(validate_instance(&schema, "Creature", &props).is_err());
+
+        // A single name is not a flag set
+        props.insert("abilities".to_string(), serde_json::json!("Fly"));
+        assert!(validate_instance(&schema, "Creature", &props).is_err());
+    }
+
+    #[test]
+    fn test_validate_asset_and_tile() {
+        let schema = parse_schema(
+            r#"{
+            "version": 1,
+            "project": { "name": "Test" },
+            "enums": {},
+            "data_types": {
+                "Speaker": {
+                    "properties": [
+                        { "name": "sound", "type": "asset", "extensions": ["ogg", "wav"] },
+                        { "name": "icon", "type": "tile" }
+                    ]
+                }
+            },
+            "embedded_types": {}
+        }"#,
+        )
+        .unwrap();
+
+        let mut props = std::collections::HashMap::new();
+        props.insert("sound".to_string(), serde_json::json!("audio/hum.OGG"));
+        props.insert(
+            "icon".to_string(),
+            serde_json::json!({ "tileset": "5e0c8a4e-4f4b-4c1e-9d3a-1a2b3c4d5e6f", "tile": 12 }),
+        );
+        assert!(validate_instance(&schema, "Speaker", &props).is_ok());
+
+        // Wrong extension
+        props.insert("sound".to_string(), serde_json::json!("audio/hum.mp3"));
+        assert!(validate_instance(&schema, "Speaker", &props).is_err());
+
+        // Tile without an index
+        props.insert("sound".to_string(), serde_json::json!("audio/hum.wav"));
+        props.insert("icon".to_string(), serde_json::json!({ "tileset": "a" }));
+        assert!(validate_instance(&schema, "Speaker", &props).is_err());
+    }
 }
@@ -0,0 +1,10 @@
+//! Generates Rust types for `schema.json`, the way a game's build script would
+
+fn main() {
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    bevy_map_schema::generate_rust_file(
+        "schema.json",
+        std::path::Path::new(&out_dir).join("map_types.rs"),
+    )
+    .unwrap();
+}
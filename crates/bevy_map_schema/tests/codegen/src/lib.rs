@@ -0,0 +1,47 @@
+//! Compile test for code generated by `bevy_map_schema::generate_rust`
+//!
+//! `build.rs` generates Rust types for `schema.json`, so any generated code
+//! that doesn't compile fails this crate's build.
+
+include!(concat!(env!("OUT_DIR"), "/map_types.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_map::core::{EntityInstance, Value};
+    use bevy_map::runtime::MapEntityType;
+    use bevy_map::schema::Schema;
+
+    #[test]
+    fn test_generated_entity_reads_properties() {
+        let mut instance = EntityInstance::new("NPC".to_string(), [0.0, 0.0]);
+        instance
+            .properties
+            .insert("name".to_string(), Value::String("Alice".to_string()));
+        instance.properties.insert(
+            "abilities".to_string(),
+            Value::Array(vec![Value::String("swim".to_string())]),
+        );
+
+        let npc = Npc::from_instance(&instance);
+        assert_eq!(npc.name, "Alice");
+        assert_eq!(npc.health, 50);
+        assert_eq!(npc.npc_type, NpcType::Vendor);
+        assert!(npc.abilities.contains(Abilities::Swim));
+        assert!(!npc.abilities.contains(Abilities::Fly));
+        assert_eq!(npc.type_, "");
+
+        let item = Item::from_instance(&EntityInstance::new("Item".to_string(), [0.0, 0.0]));
+        assert_eq!(item.value, 1);
+    }
+
+    #[test]
+    fn test_generated_type_defs() {
+        let mut schema = Schema::default();
+        assert!(Npc::type_def(&mut schema).placeable);
+        assert!(!Item::type_def(&mut schema).placeable);
+        assert!(!Character::type_def(&mut schema).placeable);
+        assert!(schema.embedded_types.contains_key("Stats"));
+    }
+}
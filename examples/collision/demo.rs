@@ -13,7 +13,7 @@ use avian2d::prelude::*;
 use bevy::asset::{AssetPlugin, UnapprovedPathMode};
 use bevy::prelude::*;
 use bevy_map::prelude::*;
-use bevy_map::runtime::MapCollisionPlugin;
+use bevy_map::runtime::{MapCollisionPlugin, MapCollisionSettings};
 use bevy_map::AnimatedSprite;
 
 fn main() {
@@ -35,6 +35,10 @@ fn main() {
         )
         .add_plugins(MapRuntimePlugin)
         .add_plugins(MapCollisionPlugin)
+        // Merge solid ground tiles into large colliders so the player doesn't snag on seams
+        .insert_resource(MapCollisionSettings {
+            merge_full_tiles: true,
+        })
         // Platformer gravity
         .insert_resource(Gravity(Vec2::new(0.0, -800.0)))
         // Register Player entity type
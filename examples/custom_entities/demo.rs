@@ -44,14 +44,25 @@ fn main() {
         .run();
 }
 
+/// NPC kind - matches the NpcType enum in example_project.map.json
+#[derive(MapProperty, Debug, Clone, Copy)]
+pub enum NpcType {
+    QuestGiver,
+    LoreBased,
+    Vendor,
+}
+
 /// NPC entity - matches example_project.map.json schema
 #[derive(Component, MapEntity, Debug, Clone)]
 #[map_entity(type_name = "NPC")]
 pub struct Npc {
     #[map_prop]
     pub name: String,
-    #[map_prop(default = "")]
-    pub npc_type: String,
+    #[map_prop]
+    pub npc_type: Option<NpcType>,
+    /// Filled in from the map's dialogues after spawning
+    #[map_prop]
+    pub dialogue: Handle<DialogueTree>,
 }
 
 /// Enemy entity - matches example_project.map.json schema
@@ -113,7 +124,7 @@ fn handle_input(
         info!("=== Entity List ===");
         for (e, npc, t) in npcs.iter() {
             info!(
-                "{:?} at {:?}: NPC '{}' type:{}",
+                "{:?} at {:?}: NPC '{}' type:{:?}",
                 e,
                 t.translation.xy(),
                 npc.name,
@@ -165,11 +176,9 @@ fn update_display(
     if filter.current == 0 || filter.current == 1 {
         display.push_str(&format!("NPCs ({})\n", npcs.iter().count()));
         for npc in npcs.iter() {
-            let npc_type = if npc.npc_type.is_empty() {
-                "generic"
-            } else {
-                &npc.npc_type
-            };
+            let npc_type = npc
+                .npc_type
+                .map_or("generic".to_string(), |t| format!("{:?}", t));
             display.push_str(&format!("  {} [{}]\n", npc.name, npc_type));
         }
     }